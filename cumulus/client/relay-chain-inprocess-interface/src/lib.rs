@@ -430,8 +430,11 @@ fn build_polkadot_full_node(
 		hwbench,
 		execute_workers_max_num: None,
 		prepare_workers_hard_max_num: None,
+		persistent_pvf_artifacts: false,
+		pvf_sandbox: Default::default(),
 		prepare_workers_soft_max_num: None,
 		keep_finalized_for: None,
+		archive_available_data: false,
 		invulnerable_ah_collators: HashSet::new(),
 		collator_protocol_hold_off: None,
 		experimental_collator_protocol: false,
@@ -139,19 +139,28 @@ impl RecoveryHandle for FailingRecoveryHandle {
 		message: AvailabilityRecoveryMessage,
 		origin: &'static str,
 	) {
-		let AvailabilityRecoveryMessage::RecoverAvailableData(ref receipt, _, _, _, _) = message;
-		let candidate_hash = receipt.hash();
+		let candidate_hash = match message {
+			AvailabilityRecoveryMessage::RecoverAvailableData(ref receipt, ..) |
+			AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(ref receipt, ..) => receipt.hash(),
+		};
 
 		// For every 3rd block we immediately signal unavailability to trigger
 		// a retry. The same candidate is never failed multiple times to ensure progress.
 		if self.counter.is_multiple_of(3) && self.failed_hashes.insert(candidate_hash) {
 			tracing::info!(target: LOG_TARGET, ?candidate_hash, "Failing pov recovery.");
 
-			let AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, back_sender) =
-				message;
-			back_sender
-				.send(Err(RecoveryError::Unavailable))
-				.expect("Return channel should work here.");
+			let sent = match message {
+				AvailabilityRecoveryMessage::RecoverAvailableData(_, _, _, _, back_sender) => {
+					back_sender.send(Err(RecoveryError::Unavailable)).is_ok()
+				},
+				AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(
+					_,
+					_,
+					_,
+					back_sender,
+				) => back_sender.send(Err(RecoveryError::Unavailable)).is_ok(),
+			};
+			assert!(sent, "Return channel should work here.");
 		} else {
 			self.overseer_handle.send_msg(message, origin).await;
 		}
@@ -64,6 +64,12 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Export the available data of candidates from the availability store.
+	ExportAvailability(ExportAvailabilityCmd),
+
+	/// Import available data written by `export-availability` into the availability store.
+	ImportAvailability(ImportAvailabilityCmd),
 }
 
 #[allow(missing_docs)]
@@ -150,16 +156,51 @@ pub struct RunCmd {
 	///  **Dangerous!** Do not touch unless explicitly advised to.
 	#[arg(long)]
 	pub prepare_workers_hard_max_num: Option<usize>,
+	/// Keep prepared pvf artifacts across restarts.
+	///
+	/// Persisted artifacts are only reused if they were prepared by the same node version and
+	/// workers, so that PVFs don't have to be prepared again after every restart. Their integrity
+	/// is checked before every execution.
+	#[arg(long)]
+	pub persistent_pvf_artifacts: bool,
+	/// Path to a seccomp profile that further restricts the pvf jobs, in the JSON format of
+	/// `seccompiler`.
+	///
+	/// The filters named `prepare` and `execute` are applied to the respective jobs, on top of
+	/// the default filter of the workers. Only supported on Linux x86-64.
+	#[arg(long, value_name = "PATH")]
+	pub pvf_seccomp_profile: Option<PathBuf>,
+	/// A cgroup v2 directory under which each pvf worker is put into its own cgroup.
+	///
+	/// The `memory` and `pids` controllers, and `cpu` if a CPU limit is set, must be enabled in
+	/// its `cgroup.subtree_control`. The usage of each job is reported in the metrics. Only
+	/// supported on Linux.
+	#[arg(long, value_name = "PATH")]
+	pub pvf_cgroup_root: Option<PathBuf>,
+	/// The maximum memory of each pvf job, in MiB.
+	#[arg(long, value_name = "MIB", requires = "pvf_cgroup_root")]
+	pub pvf_job_memory_max: Option<u64>,
+	/// The maximum CPU bandwidth of each pvf job, in percent of a single CPU.
+	#[arg(long, value_name = "PERCENT", requires = "pvf_cgroup_root")]
+	pub pvf_job_cpu_max_percent: Option<u32>,
+	/// The maximum number of processes and threads of each pvf job.
+	#[arg(long, value_name = "COUNT", requires = "pvf_cgroup_root")]
+	pub pvf_job_pids_max: Option<u64>,
 	/// TESTING ONLY: disable the version check between nodes and workers.
 	#[arg(long, hide = true)]
 	pub disable_worker_version_check: bool,
 
 	/// How long finalized data should be kept in the availability store (in hours).
-	/// Only used for testnets. If not specified, set to 1 hour. Always set to 25 hours for live
-	/// networks.
+	/// If not specified, set to 1 hour for testnets and to 25 hours for live networks. Live
+	/// networks never keep finalized data for less than 25 hours.
 	#[arg(long)]
 	pub keep_finalized_for: Option<u32>,
 
+	/// Keep the available data (PoVs) of finalized candidates in the availability store forever.
+	/// Erasure chunks are still pruned after `--keep-finalized-for`.
+	#[arg(long)]
+	pub archive_available_data: bool,
+
 	/// Overrides `HOLD_OFF_DURATION` in collator_protocol/validator_side. The value is in
 	/// milliseconds.
 	///
@@ -179,6 +220,70 @@ pub struct RunCmd {
 	pub collator_reputation_persist_interval: Option<u64>,
 }
 
+/// The `export-availability` command.
+#[derive(Debug, Parser)]
+pub struct ExportAvailabilityCmd {
+	/// The file to write the exported data to.
+	#[arg(value_name = "FILE")]
+	pub output: PathBuf,
+
+	/// Only export candidates first seen, or finalized, at or after this unix time (in seconds).
+	#[arg(long, conflicts_with = "candidates")]
+	pub since: Option<u64>,
+
+	/// Only export candidates first seen, or finalized, before this unix time (in seconds).
+	#[arg(long, conflicts_with = "candidates")]
+	pub until: Option<u64>,
+
+	/// Only export the candidate with the given hash. Can be passed multiple times.
+	#[arg(long = "candidate", value_name = "HASH")]
+	pub candidates: Vec<sp_core::H256>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+/// The `import-availability` command.
+#[derive(Debug, Parser)]
+pub struct ImportAvailabilityCmd {
+	/// The file written by `export-availability`.
+	#[arg(value_name = "FILE")]
+	pub input: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for ExportAvailabilityCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+impl sc_cli::CliConfiguration for ImportAvailabilityCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
 #[allow(missing_docs)]
 #[derive(Debug, Parser)]
 pub struct Cli {
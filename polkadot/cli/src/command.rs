@@ -259,6 +259,18 @@ where
 	let invulnerable_ah_collators = get_invulnerable_ah_collators(&chain_spec);
 	let experimental_collator_protocol = cli.run.experimental_collator_protocol;
 
+	let pvf_sandbox = polkadot_service::PvfSandboxConfig {
+		seccomp_profile_path: cli.run.pvf_seccomp_profile.clone(),
+		cgroup_limits: cli.run.pvf_cgroup_root.clone().map(|root| {
+			polkadot_service::PvfCgroupLimits {
+				root,
+				memory_max: cli.run.pvf_job_memory_max.map(|mib| mib * 1024 * 1024),
+				cpu_max_percent: cli.run.pvf_job_cpu_max_percent,
+				pids_max: cli.run.pvf_job_pids_max,
+			}
+		}),
+	};
+
 	runner.run_node_until_exit(move |config| async move {
 		let hwbench = (!cli.run.no_hardware_benchmarks)
 			.then(|| {
@@ -290,7 +302,10 @@ where
 				execute_workers_max_num: cli.run.execute_workers_max_num,
 				prepare_workers_hard_max_num: cli.run.prepare_workers_hard_max_num,
 				prepare_workers_soft_max_num: cli.run.prepare_workers_soft_max_num,
+				persistent_pvf_artifacts: cli.run.persistent_pvf_artifacts,
+				pvf_sandbox,
 				keep_finalized_for: cli.run.keep_finalized_for,
+				archive_available_data: cli.run.archive_available_data,
 				invulnerable_ah_collators,
 				collator_protocol_hold_off,
 				experimental_collator_protocol,
@@ -531,6 +546,38 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			Ok(runner.sync_run(|config| cmd.run::<polkadot_service::Block>(&config))?)
 		},
+		Some(Subcommand::ExportAvailability(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let selection = if cmd.candidates.is_empty() {
+				let since = Duration::from_secs(cmd.since.unwrap_or_default());
+				let until = cmd.until.map_or(Duration::MAX, Duration::from_secs);
+				polkadot_service::AvailabilityExportSelection::Time(since..until)
+			} else {
+				polkadot_service::AvailabilityExportSelection::Candidates(
+					cmd.candidates.iter().copied().map(polkadot_service::CandidateHash).collect(),
+				)
+			};
+
+			runner.sync_run(|config| {
+				let file =
+					std::fs::File::create(&cmd.output).map_err(polkadot_service::Error::Io)?;
+				let mut writer = std::io::BufWriter::new(file);
+				let exported =
+					polkadot_service::export_availability(&config, selection, &mut writer)?;
+				info!("Exported {} candidates to {}", exported, cmd.output.display());
+				Ok(())
+			})
+		},
+		Some(Subcommand::ImportAvailability(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let file = std::fs::File::open(&cmd.input).map_err(polkadot_service::Error::Io)?;
+				let mut reader = std::io::BufReader::new(file);
+				let imported = polkadot_service::import_availability(&config, &mut reader)?;
+				info!("Imported {} candidates from {}", imported, cmd.input.display());
+				Ok(())
+			})
+		},
 	}?;
 
 	#[cfg(feature = "pyroscope")]
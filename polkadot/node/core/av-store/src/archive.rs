@@ -0,0 +1,194 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Export and import of the available data of candidates.
+//!
+//! Exported data can be imported into the availability store of another node, e.g. an archive
+//! node serving historical PoVs long after validators have pruned them.
+//!
+//! Every candidate is exported along with its erasure root, and the data is only imported if it
+//! re-encodes to that root.
+
+use std::{io, ops::Range, sync::Arc, time::Duration};
+
+use bitvec::{bitvec, order::Lsb0 as BitOrderLsb0};
+use codec::{Decode, Encode, IoReader};
+use polkadot_node_primitives::AvailableData;
+use polkadot_node_subsystem_util::database::{DBTransaction, Database};
+use polkadot_primitives::{CandidateHash, Hash};
+
+use crate::{
+	load_available_data, load_meta, write_available_data, write_meta, write_pruning_key,
+	CandidateMeta, Config, Error, State, LOG_TARGET, META_PREFIX,
+};
+
+/// Version of the export format, written at the start of every export.
+const EXPORT_VERSION: u8 = 1;
+
+/// The candidates to export.
+#[derive(Debug, Clone)]
+pub enum ExportSelection {
+	/// All candidates whose state last changed in the given range of unix time, i.e. candidates
+	/// first seen in that range or, for finalized ones, finalized in that range.
+	Time(Range<Duration>),
+	/// The given candidates.
+	Candidates(Vec<CandidateHash>),
+}
+
+/// A candidate as written by [`export_candidates`].
+#[derive(Debug, Encode, Decode)]
+struct ExportedCandidate {
+	candidate_hash: CandidateHash,
+	/// The number of validators the available data is erasure coded for.
+	n_validators: u32,
+	/// The erasure root of the candidate, as found in its receipt.
+	erasure_root: Hash,
+	available_data: AvailableData,
+}
+
+/// The erasure root of `available_data` when erasure coded for `n_validators`.
+fn erasure_root(n_validators: usize, available_data: &AvailableData) -> Result<Hash, Error> {
+	let chunks = polkadot_erasure_coding::obtain_chunks_v1(n_validators, available_data)?;
+	Ok(polkadot_erasure_coding::branches(chunks.as_ref()).root())
+}
+
+/// Writes the available data of the selected candidates to `writer`.
+///
+/// The erasure root of each candidate is computed from its data, which was checked against the
+/// erasure root of the candidate receipt when it was stored. Candidates without available data are
+/// skipped. Returns the number of exported candidates.
+pub fn export_candidates(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	selection: ExportSelection,
+	writer: &mut impl io::Write,
+) -> Result<usize, Error> {
+	let candidates = match selection {
+		ExportSelection::Candidates(candidates) => candidates,
+		ExportSelection::Time(range) => candidates_in_range(db, config, range)?,
+	};
+
+	writer.write_all(&EXPORT_VERSION.encode())?;
+
+	let mut exported = 0;
+	for candidate_hash in candidates {
+		// The chunks of a candidate are pruned before its available data, but the length of
+		// `chunks_stored` is kept.
+		let n_validators = match load_meta(db, config, &candidate_hash)? {
+			Some(meta) if meta.data_available => meta.chunks_stored.len(),
+			_ => 0,
+		};
+		let available_data = match load_available_data(db, config, &candidate_hash)? {
+			Some(available_data) if n_validators > 0 => available_data,
+			_ => {
+				gum::debug!(target: LOG_TARGET, ?candidate_hash, "No available data to export");
+				continue;
+			},
+		};
+
+		let erasure_root = erasure_root(n_validators, &available_data)?;
+		let candidate = ExportedCandidate {
+			candidate_hash,
+			n_validators: n_validators as u32,
+			erasure_root,
+			available_data,
+		};
+		writer.write_all(&Some(candidate).encode())?;
+		exported += 1;
+	}
+	writer.write_all(&None::<ExportedCandidate>.encode())?;
+	writer.flush()?;
+
+	Ok(exported)
+}
+
+/// Stores the candidates exported by [`export_candidates`] and read from `reader`.
+///
+/// Imported candidates are treated as finalized at `now`, so they are pruned like any other
+/// finalized candidate unless `config.archive_available_data` is set. Candidates whose data is
+/// already stored are skipped. Returns the number of imported candidates.
+///
+/// The import stops with [`Error::InvalidErasureRoot`] at the first candidate whose data doesn't
+/// match its erasure root; the candidates read before it stay imported.
+pub fn import_candidates(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	reader: &mut impl io::Read,
+	now: Duration,
+) -> Result<usize, Error> {
+	let mut input = IoReader(reader);
+	if u8::decode(&mut input)? != EXPORT_VERSION {
+		return Err(Error::Codec("Unsupported export version".into()));
+	}
+
+	let keep_finalized_for = Duration::from_secs(config.keep_finalized_for as u64 * 3600);
+	let mut imported = 0;
+	while let Some(ExportedCandidate {
+		candidate_hash,
+		n_validators,
+		erasure_root: root,
+		available_data,
+	}) = Option::<ExportedCandidate>::decode(&mut input)?
+	{
+		if erasure_root(n_validators as usize, &available_data)? != root {
+			gum::warn!(target: LOG_TARGET, ?candidate_hash, "Imported data doesn't match its erasure root");
+			return Err(Error::InvalidErasureRoot);
+		}
+
+		let mut tx = DBTransaction::new();
+		let meta = match load_meta(db, config, &candidate_hash)? {
+			Some(meta) if meta.data_available => continue,
+			Some(meta) => CandidateMeta { data_available: true, ..meta },
+			None => {
+				write_pruning_key(&mut tx, config, now + keep_finalized_for, &candidate_hash);
+				CandidateMeta {
+					state: State::Finalized(now.into()),
+					data_available: true,
+					chunks_stored: bitvec![u8, BitOrderLsb0; 0; n_validators as usize],
+				}
+			},
+		};
+
+		write_meta(&mut tx, config, &candidate_hash, &meta);
+		write_available_data(&mut tx, config, &candidate_hash, &available_data);
+		db.write(tx)?;
+		imported += 1;
+	}
+
+	Ok(imported)
+}
+
+fn candidates_in_range(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	range: Range<Duration>,
+) -> Result<Vec<CandidateHash>, Error> {
+	let mut candidates = Vec::new();
+	for r in db.iter_with_prefix(config.col_meta, META_PREFIX) {
+		let (k, v) = r?;
+		let candidate_hash = CandidateHash::decode(&mut &k[META_PREFIX.len()..])?;
+		let meta = CandidateMeta::decode(&mut &v[..])?;
+		let at: Duration = match meta.state {
+			State::Unavailable(at) | State::Unfinalized(at, _) | State::Finalized(at) => at.into(),
+		};
+
+		if meta.data_available && range.contains(&at) {
+			candidates.push(candidate_hash);
+		}
+	}
+
+	Ok(candidates)
+}
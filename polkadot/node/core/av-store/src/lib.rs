@@ -52,8 +52,12 @@ use polkadot_primitives::{
 };
 use util::availability_chunks::availability_chunk_indices;
 
+mod archive;
 mod metrics;
-pub use self::metrics::*;
+pub use self::{
+	archive::{export_candidates, import_candidates, ExportSelection},
+	metrics::*,
+};
 
 #[cfg(test)]
 mod tests;
@@ -429,6 +433,9 @@ pub struct Config {
 	pub col_meta: u32,
 	/// How long finalized data should be kept (in hours).
 	pub keep_finalized_for: u32,
+	/// Whether the available data of finalized candidates should be kept forever. Their chunks
+	/// are still pruned after `keep_finalized_for`.
+	pub archive_available_data: bool,
 }
 
 trait Clock: Send + Sync {
@@ -1353,6 +1360,13 @@ fn store_available_data(
 	Ok(())
 }
 
+// Whether the available data of the candidate is kept when it gets pruned.
+fn is_archived(config: &Config, meta: &CandidateMeta) -> bool {
+	config.archive_available_data &&
+		meta.data_available &&
+		matches!(meta.state, State::Finalized(_))
+}
+
 fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(), Error> {
 	let (range_start, range_end) = pruning_range(now);
 
@@ -1370,12 +1384,16 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 			Err(_) => continue, // sanity
 		};
 
-		delete_meta(&mut tx, config, &candidate_hash);
+		let meta = load_meta(db, config, &candidate_hash)?;
+		let archived = meta.as_ref().map_or(false, |meta| is_archived(config, meta));
+		if !archived {
+			delete_meta(&mut tx, config, &candidate_hash);
+		}
 
 		// Clean up all attached data of the candidate.
-		if let Some(meta) = load_meta(db, config, &candidate_hash)? {
+		if let Some(mut meta) = meta {
 			// delete available data.
-			if meta.data_available {
+			if meta.data_available && !archived {
 				delete_available_data(&mut tx, config, &candidate_hash)
 			}
 
@@ -1386,6 +1404,12 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 				}
 			}
 
+			// archived candidates only keep their available data.
+			if archived {
+				meta.chunks_stored.fill(false);
+				write_meta(&mut tx, config, &candidate_hash, &meta);
+			}
+
 			// delete unfinalized block references. Pruning references don't need to be
 			// manually taken care of as we are deleting them as we go in the outer loop.
 			if let State::Unfinalized(_, blocks) = meta.state {
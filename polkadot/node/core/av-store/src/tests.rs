@@ -43,8 +43,12 @@ mod columns {
 	pub const NUM_COLUMNS: u32 = 2;
 }
 
-const TEST_CONFIG: Config =
-	Config { col_data: columns::DATA, col_meta: columns::META, keep_finalized_for: 1 };
+const TEST_CONFIG: Config = Config {
+	col_data: columns::DATA,
+	col_meta: columns::META,
+	keep_finalized_for: 1,
+	archive_available_data: false,
+};
 
 type VirtualOverseer =
 	polkadot_node_subsystem_test_helpers::TestSubsystemContextHandle<AvailabilityStoreMessage>;
@@ -1384,3 +1388,177 @@ fn query_chunk_size_works() {
 		virtual_overseer
 	});
 }
+
+fn write_finalized_candidate(
+	store: &Arc<dyn Database>,
+	config: &Config,
+	candidate_hash: &CandidateHash,
+	available_data: &AvailableData,
+	finalized_at: Duration,
+) {
+	let chunk = ErasureChunk {
+		chunk: vec![1, 2, 3],
+		index: ChunkIndex(0),
+		proof: Proof::try_from(vec![vec![3, 4, 5]]).unwrap(),
+	};
+
+	with_tx(store, |tx| {
+		write_meta(
+			tx,
+			config,
+			candidate_hash,
+			&CandidateMeta {
+				data_available: true,
+				chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 1, 0],
+				state: State::Finalized(finalized_at.into()),
+			},
+		);
+		write_available_data(tx, config, candidate_hash, available_data);
+		write_chunk(tx, config, candidate_hash, ValidatorIndex(0), &chunk);
+		write_pruning_key(tx, config, finalized_at, candidate_hash);
+	});
+}
+
+#[test]
+fn archive_keeps_available_data_of_finalized_candidates() {
+	let store = test_store();
+	let config = Config { archive_available_data: true, ..TEST_CONFIG };
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let available_data = AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(vec![4, 5, 6]) }),
+		validation_data: TestState::default().persisted_validation_data,
+	};
+
+	write_finalized_candidate(
+		&store,
+		&config,
+		&candidate_hash,
+		&available_data,
+		Duration::from_secs(10),
+	);
+	prune_all(&store, &config, Duration::from_secs(20)).unwrap();
+
+	assert_eq!(
+		load_available_data(&store, &config, &candidate_hash).unwrap(),
+		Some(available_data)
+	);
+	assert!(load_chunk(&store, &config, &candidate_hash, ValidatorIndex(0))
+		.unwrap()
+		.is_none());
+	let meta = load_meta(&store, &config, &candidate_hash).unwrap().unwrap();
+	assert!(meta.data_available);
+	assert!(meta.chunks_stored.not_any());
+
+	// Nothing is left to prune.
+	let (range_start, _) = pruning_range(Duration::from_secs(u32::MAX as u64));
+	assert_eq!(store.iter_with_prefix(config.col_meta, &range_start[..]).count(), 0);
+
+	// Without archive mode, everything is pruned.
+	write_finalized_candidate(
+		&store,
+		&TEST_CONFIG,
+		&candidate_hash,
+		&AvailableData {
+			pov: Arc::new(PoV { block_data: BlockData(vec![4, 5, 6]) }),
+			validation_data: TestState::default().persisted_validation_data,
+		},
+		Duration::from_secs(30),
+	);
+	prune_all(&store, &TEST_CONFIG, Duration::from_secs(40)).unwrap();
+
+	assert!(load_available_data(&store, &TEST_CONFIG, &candidate_hash).unwrap().is_none());
+	assert!(load_meta(&store, &TEST_CONFIG, &candidate_hash).unwrap().is_none());
+}
+
+#[test]
+fn export_and_import_available_data() {
+	let source = test_store();
+	let candidate_a = CandidateHash(Hash::repeat_byte(1));
+	let candidate_b = CandidateHash(Hash::repeat_byte(2));
+	let available_data = |byte| AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(vec![byte; 3]) }),
+		validation_data: TestState::default().persisted_validation_data,
+	};
+
+	write_finalized_candidate(
+		&source,
+		&TEST_CONFIG,
+		&candidate_a,
+		&available_data(1),
+		Duration::from_secs(10),
+	);
+	write_finalized_candidate(
+		&source,
+		&TEST_CONFIG,
+		&candidate_b,
+		&available_data(2),
+		Duration::from_secs(20),
+	);
+
+	let mut exported = Vec::new();
+	let selection = ExportSelection::Time(Duration::from_secs(15)..Duration::from_secs(25));
+	assert_eq!(export_candidates(&source, &TEST_CONFIG, selection, &mut exported).unwrap(), 1);
+
+	let target = test_store();
+	let now = Duration::from_secs(100);
+	assert_eq!(import_candidates(&target, &TEST_CONFIG, &mut &exported[..], now).unwrap(), 1);
+	assert!(load_meta(&target, &TEST_CONFIG, &candidate_a).unwrap().is_none());
+	assert_eq!(
+		load_available_data(&target, &TEST_CONFIG, &candidate_b).unwrap(),
+		Some(available_data(2))
+	);
+	assert_matches!(
+		load_meta(&target, &TEST_CONFIG, &candidate_b).unwrap(),
+		Some(CandidateMeta { state: State::Finalized(at), data_available: true, .. }) => {
+			assert_eq!(at, now.into());
+		}
+	);
+
+	// Imported candidates are pruned like finalized ones.
+	prune_all(&target, &TEST_CONFIG, now + Duration::from_secs(3600)).unwrap();
+	assert!(load_available_data(&target, &TEST_CONFIG, &candidate_b).unwrap().is_none());
+
+	// Importing again is a no-op for candidates already stored.
+	let mut exported = Vec::new();
+	let selection = ExportSelection::Candidates(vec![candidate_a, candidate_b]);
+	assert_eq!(export_candidates(&source, &TEST_CONFIG, selection, &mut exported).unwrap(), 2);
+	assert_eq!(import_candidates(&source, &TEST_CONFIG, &mut &exported[..], now).unwrap(), 0);
+}
+
+#[test]
+fn import_rejects_tampered_available_data() {
+	let source = test_store();
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let available_data = AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(vec![1, 2, 3, 4]) }),
+		validation_data: TestState::default().persisted_validation_data,
+	};
+	write_finalized_candidate(
+		&source,
+		&TEST_CONFIG,
+		&candidate_hash,
+		&available_data,
+		Duration::from_secs(10),
+	);
+
+	let mut exported = Vec::new();
+	let selection = ExportSelection::Candidates(vec![candidate_hash]);
+	assert_eq!(export_candidates(&source, &TEST_CONFIG, selection, &mut exported).unwrap(), 1);
+
+	// Replace the block data in the export.
+	let block_data = available_data.pov.block_data.encode();
+	let position = exported
+		.windows(block_data.len())
+		.position(|window| window == &block_data[..])
+		.unwrap();
+	exported[position + block_data.len() - 1] = 5;
+
+	let target = test_store();
+	let now = Duration::from_secs(100);
+	assert_matches!(
+		import_candidates(&target, &TEST_CONFIG, &mut &exported[..], now),
+		Err(Error::InvalidErasureRoot)
+	);
+	assert!(load_meta(&target, &TEST_CONFIG, &candidate_hash).unwrap().is_none());
+	assert!(load_available_data(&target, &TEST_CONFIG, &candidate_hash).unwrap().is_none());
+}
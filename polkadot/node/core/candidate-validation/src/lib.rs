@@ -114,6 +114,10 @@ pub struct Config {
 	pub pvf_prepare_workers_soft_max_num: usize,
 	/// The absolute number of pvf workers that can be spawned in the pvf prepare pool.
 	pub pvf_prepare_workers_hard_max_num: usize,
+	/// Whether prepared pvf artifacts should be kept across restarts.
+	pub pvf_persistent_artifacts: bool,
+	/// Restrictions applied to the pvf jobs on top of the default security features.
+	pub pvf_sandbox: polkadot_node_core_pvf::SandboxConfig,
 }
 
 /// The candidate validation subsystem.
@@ -377,22 +381,24 @@ async fn run<Context>(
 		pvf_execute_workers_max_num,
 		pvf_prepare_workers_soft_max_num,
 		pvf_prepare_workers_hard_max_num,
+		pvf_persistent_artifacts,
+		pvf_sandbox,
 	}: Config,
 ) -> SubsystemResult<()> {
-	let (mut validation_host, task) = polkadot_node_core_pvf::start(
-		polkadot_node_core_pvf::Config::new(
-			artifacts_cache_path,
-			node_version,
-			secure_validator_mode,
-			prep_worker_path,
-			exec_worker_path,
-			pvf_execute_workers_max_num,
-			pvf_prepare_workers_soft_max_num,
-			pvf_prepare_workers_hard_max_num,
-		),
-		pvf_metrics,
-	)
-	.await?;
+	let mut pvf_config = polkadot_node_core_pvf::Config::new(
+		artifacts_cache_path,
+		node_version,
+		secure_validator_mode,
+		prep_worker_path,
+		exec_worker_path,
+		pvf_execute_workers_max_num,
+		pvf_prepare_workers_soft_max_num,
+		pvf_prepare_workers_hard_max_num,
+	);
+	pvf_config.persistent_artifacts = pvf_persistent_artifacts;
+	pvf_config.sandbox = pvf_sandbox;
+	let (mut validation_host, task) =
+		polkadot_node_core_pvf::start(pvf_config, pvf_metrics).await?;
 	ctx.spawn_blocking("pvf-validation-host", task.boxed())?;
 
 	let mut tasks = FuturesUnordered::new();
@@ -63,10 +63,17 @@ pub struct SecurityStatus {
 	pub can_do_secure_clone: bool,
 }
 
+/// A seccomp filter compiled by the host from the operator's profile. It is applied to each job
+/// process, on top of the filter of the worker. Each instruction is a BPF `(code, jt, jf, k)`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct JobSeccompFilter(pub Vec<(u16, u8, u8, u32)>);
+
 /// A handshake with information for the worker.
 #[derive(Debug, Encode, Decode)]
 pub struct WorkerHandshake {
 	pub security_status: SecurityStatus,
+	/// The seccomp filter to apply to each job, if the operator configured one.
+	pub job_seccomp_filter: Option<JobSeccompFilter>,
 }
 
 /// Write some data prefixed by its length into `w`. Sync version of `framed_send` to avoid
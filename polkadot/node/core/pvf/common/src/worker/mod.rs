@@ -19,7 +19,8 @@
 pub mod security;
 
 use crate::{
-	framed_recv_blocking, framed_send_blocking, SecurityStatus, WorkerHandshake, LOG_TARGET,
+	framed_recv_blocking, framed_send_blocking, JobSeccompFilter, SecurityStatus, WorkerHandshake,
+	LOG_TARGET,
 };
 use codec::{Decode, Encode};
 use cpu_time::ProcessTime;
@@ -315,7 +316,12 @@ pub fn run_worker<F>(
 	worker_version: Option<&str>,
 	mut event_loop: F,
 ) where
-	F: FnMut(UnixStream, &WorkerInfo, SecurityStatus) -> io::Result<Never>,
+	F: FnMut(
+		UnixStream,
+		&WorkerInfo,
+		SecurityStatus,
+		Option<JobSeccompFilter>,
+	) -> io::Result<Never>,
 {
 	#[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
 	let mut worker_info = WorkerInfo {
@@ -370,10 +376,11 @@ pub fn run_worker<F>(
 		Err(err) => worker_shutdown_error(worker_info, &err.to_string()),
 	};
 
-	let WorkerHandshake { security_status } = match recv_worker_handshake(&mut stream) {
-		Ok(ok) => ok,
-		Err(err) => worker_shutdown_error(worker_info, &err.to_string()),
-	};
+	let WorkerHandshake { security_status, job_seccomp_filter } =
+		match recv_worker_handshake(&mut stream) {
+			Ok(ok) => ok,
+			Err(err) => worker_shutdown_error(worker_info, &err.to_string()),
+		};
 
 	// Enable some security features.
 	{
@@ -451,7 +458,7 @@ pub fn run_worker<F>(
 	}
 
 	// Run the main worker loop.
-	let err = event_loop(stream, &worker_info, security_status)
+	let err = event_loop(stream, &worker_info, security_status, job_seccomp_filter)
 		// It's never `Ok` because it's `Ok(Never)`.
 		.unwrap_err();
 
@@ -25,6 +25,7 @@
 //!   - Unshare the user and mount namespaces.
 //!   - Change the root directory to a worker-specific temporary directory.
 //! - Restrict networking by blocking socket creation and io_uring.
+//! - Optionally restrict the jobs further with the operator's seccomp profile.
 //! - Remove env vars
 
 #[cfg(target_os = "linux")]
@@ -36,7 +37,7 @@ pub mod landlock;
 #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
 pub mod seccomp;
 
-use crate::{worker::WorkerInfo, LOG_TARGET};
+use crate::{worker::WorkerInfo, JobSeccompFilter, LOG_TARGET};
 
 /// Require env vars to have been removed when spawning the process, to prevent malicious code from
 /// accessing them.
@@ -75,3 +76,18 @@ pub fn check_env_vars_were_cleared(worker_info: &WorkerInfo) -> bool {
 
 	ok
 }
+
+/// Applies the seccomp filter of the operator's profile, if any, to the calling job process.
+pub fn enable_job_seccomp_filter(filter: Option<&JobSeccompFilter>) -> Result<(), String> {
+	let Some(filter) = filter else { return Ok(()) };
+
+	#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+	return seccomp::enable_for_job(filter)
+		.map_err(|err| format!("could not enable the seccomp profile: {}", err));
+
+	#[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+	{
+		let _ = filter;
+		Err("seccomp profiles are only supported on Linux x86-64".into())
+	}
+}
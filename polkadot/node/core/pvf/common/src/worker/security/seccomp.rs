@@ -70,10 +70,17 @@
 //! When a forbidden syscall is attempted we immediately kill the process in order to prevent the
 //! attacker from doing anything else. In execution, this will result in voting against the
 //! candidate.
+//!
+//! # Job profiles
+//!
+//! Operators can further restrict the jobs with their own profile, e.g. an allowlist of the
+//! syscalls wasmtime needs. The host compiles it and hands it over in the worker handshake, and
+//! each job process applies it on top of the filter above. Whatever the profile says, the
+//! blocklist above stays in place.
 
 use crate::{
 	worker::{stringify_panic_payload, WorkerInfo},
-	LOG_TARGET,
+	JobSeccompFilter, LOG_TARGET,
 };
 use seccompiler::*;
 use std::collections::BTreeMap;
@@ -108,6 +115,22 @@ pub fn enable_for_worker(worker_info: &WorkerInfo) -> Result<()> {
 	try_restrict()
 }
 
+/// Applies the seccomp filter of the operator's profile to the calling job process.
+///
+/// Must be called before the job spawns any threads, as the filter only applies to the calling
+/// thread and the threads it spawns afterwards.
+pub fn enable_for_job(filter: &JobSeccompFilter) -> Result<()> {
+	let bpf_prog: BpfProgram = filter
+		.0
+		.iter()
+		.map(|&(code, jt, jf, k)| sock_filter { code, jt, jf, k })
+		.collect();
+
+	seccompiler::apply_filter(&bpf_prog)?;
+
+	Ok(())
+}
+
 /// Runs a check for seccomp in its own thread, and returns an error indicating whether seccomp with
 /// our rules is fully enabled on the current Linux environment.
 pub fn check_can_fully_enable() -> Result<()> {
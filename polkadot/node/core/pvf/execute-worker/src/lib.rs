@@ -48,11 +48,11 @@ use polkadot_node_core_pvf_common::{
 	framed_recv_blocking, framed_send_blocking,
 	worker::{
 		cpu_time_monitor_loop, get_total_cpu_usage, pipe2_cloexec, recv_child_response, run_worker,
-		send_result, stringify_errno, stringify_panic_payload,
+		security, send_result, stringify_errno, stringify_panic_payload,
 		thread::{self, WaitOutcome},
 		PipeFd, WorkerInfo, WorkerKind,
 	},
-	worker_dir,
+	worker_dir, JobSeccompFilter,
 };
 use polkadot_node_primitives::{BlockData, POV_BOMB_LIMIT};
 use polkadot_parachain_primitives::primitives::{
@@ -139,7 +139,7 @@ pub fn worker_entrypoint(
 		worker_dir_path,
 		node_version,
 		worker_version,
-		|mut stream, worker_info, security_status| {
+		|mut stream, worker_info, security_status, job_seccomp_filter| {
 			let artifact_path = worker_dir::execute_artifact(&worker_info.worker_dir_path);
 
 			let Handshake { executor_params } =
@@ -288,6 +288,7 @@ pub fn worker_entrypoint(
 								&params,
 								execution_timeout,
 								execute_thread_stack_size,
+								job_seccomp_filter.as_ref(),
 								worker_info,
 								security_status.can_unshare_user_namespace_and_change_root,
 								usage_before,
@@ -304,6 +305,7 @@ pub fn worker_entrypoint(
 								&params,
 								execution_timeout,
 								execute_thread_stack_size,
+								job_seccomp_filter.as_ref(),
 								worker_info,
 								usage_before,
 								pov_size,
@@ -319,6 +321,7 @@ pub fn worker_entrypoint(
 							&params,
 							execution_timeout,
 							execute_thread_stack_size,
+							job_seccomp_filter.as_ref(),
 							worker_info,
 							usage_before,
 							pov_size,
@@ -379,13 +382,12 @@ fn handle_clone(
 	params: &Arc<Vec<u8>>,
 	execution_timeout: Duration,
 	execute_stack_size: usize,
+	job_seccomp_filter: Option<&JobSeccompFilter>,
 	worker_info: &WorkerInfo,
 	have_unshare_newuser: bool,
 	usage_before: Usage,
 	pov_size: u32,
 ) -> io::Result<Result<WorkerResponse, WorkerError>> {
-	use polkadot_node_core_pvf_common::worker::security;
-
 	// SAFETY: new process is spawned within a single threaded process. This invariant
 	// is enforced by tests. Stack size being specified to ensure child doesn't overflow
 	match unsafe {
@@ -402,6 +404,7 @@ fn handle_clone(
 					Arc::clone(params),
 					execution_timeout,
 					execute_stack_size,
+					job_seccomp_filter,
 				)
 			}),
 		)
@@ -430,6 +433,7 @@ fn handle_fork(
 	params: &Arc<Vec<u8>>,
 	execution_timeout: Duration,
 	execute_worker_stack_size: usize,
+	job_seccomp_filter: Option<&JobSeccompFilter>,
 	worker_info: &WorkerInfo,
 	usage_before: Usage,
 	pov_size: u32,
@@ -446,6 +450,7 @@ fn handle_fork(
 			Arc::clone(params),
 			execution_timeout,
 			execute_worker_stack_size,
+			job_seccomp_filter,
 		),
 		Ok(ForkResult::Parent { child }) => handle_parent_process(
 			pipe_read_fd,
@@ -475,6 +480,7 @@ fn handle_child_process(
 	params: Arc<Vec<u8>>,
 	execution_timeout: Duration,
 	execute_thread_stack_size: usize,
+	job_seccomp_filter: Option<&JobSeccompFilter>,
 ) -> ! {
 	// SAFETY: this is an open and owned file descriptor at this point.
 	let mut pipe_write = unsafe { PipeFd::from_raw_fd(pipe_write_fd) };
@@ -492,6 +498,11 @@ fn handle_child_process(
 		send_child_response(&mut pipe_write, job_error_from_errno("closing stream", errno));
 	}
 
+	// Restrict the job further before it spawns any threads.
+	if let Err(err) = security::enable_job_seccomp_filter(job_seccomp_filter) {
+		send_child_response(&mut pipe_write, Err(JobError::Kernel(err)));
+	}
+
 	gum::debug!(
 		target: LOG_TARGET,
 		worker_job_pid = %process::id(),
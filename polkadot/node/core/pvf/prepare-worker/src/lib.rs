@@ -44,11 +44,11 @@ use polkadot_node_core_pvf_common::{
 	pvf::PvfPrepData,
 	worker::{
 		cpu_time_monitor_loop, get_total_cpu_usage, pipe2_cloexec, recv_child_response, run_worker,
-		send_result, stringify_errno, stringify_panic_payload,
+		security, send_result, stringify_errno, stringify_panic_payload,
 		thread::{self, spawn_worker_thread, WaitOutcome},
 		PipeFd, WorkerInfo, WorkerKind,
 	},
-	worker_dir, ProcessTime,
+	worker_dir, JobSeccompFilter, ProcessTime,
 };
 use polkadot_primitives::ExecutorParams;
 use std::{
@@ -207,7 +207,7 @@ pub fn worker_entrypoint(
 		worker_dir_path,
 		node_version,
 		worker_version,
-		|mut stream, worker_info, security_status| {
+		|mut stream, worker_info, security_status, job_seccomp_filter| {
 			let temp_artifact_dest = worker_dir::prepare_tmp_artifact(&worker_info.worker_dir_path);
 
 			loop {
@@ -248,6 +248,7 @@ pub fn worker_entrypoint(
 								preparation_timeout,
 								prepare_job_kind,
 								&executor_params,
+								job_seccomp_filter.as_ref(),
 								worker_info,
 								security_status.can_unshare_user_namespace_and_change_root,
 								&temp_artifact_dest,
@@ -263,6 +264,7 @@ pub fn worker_entrypoint(
 								preparation_timeout,
 								prepare_job_kind,
 								&executor_params,
+								job_seccomp_filter.as_ref(),
 								worker_info,
 								&temp_artifact_dest,
 								usage_before,
@@ -277,6 +279,7 @@ pub fn worker_entrypoint(
 							preparation_timeout,
 							prepare_job_kind,
 							&executor_params,
+							job_seccomp_filter.as_ref(),
 							worker_info,
 							&temp_artifact_dest,
 							usage_before,
@@ -347,13 +350,12 @@ fn handle_clone(
 	preparation_timeout: Duration,
 	prepare_job_kind: PrepareJobKind,
 	executor_params: &Arc<ExecutorParams>,
+	job_seccomp_filter: Option<&JobSeccompFilter>,
 	worker_info: &WorkerInfo,
 	have_unshare_newuser: bool,
 	temp_artifact_dest: &Path,
 	usage_before: Usage,
 ) -> Result<PrepareWorkerSuccess, PrepareError> {
-	use polkadot_node_core_pvf_common::worker::security;
-
 	// SAFETY: new process is spawned within a single threaded process. This invariant
 	// is enforced by tests. Stack size being specified to ensure child doesn't overflow
 	match unsafe {
@@ -369,6 +371,7 @@ fn handle_clone(
 					preparation_timeout,
 					prepare_job_kind,
 					Arc::clone(&executor_params),
+					job_seccomp_filter,
 				)
 			}),
 		)
@@ -394,6 +397,7 @@ fn handle_fork(
 	preparation_timeout: Duration,
 	prepare_job_kind: PrepareJobKind,
 	executor_params: &Arc<ExecutorParams>,
+	job_seccomp_filter: Option<&JobSeccompFilter>,
 	worker_info: &WorkerInfo,
 	temp_artifact_dest: &Path,
 	usage_before: Usage,
@@ -409,6 +413,7 @@ fn handle_fork(
 			preparation_timeout,
 			prepare_job_kind,
 			Arc::clone(executor_params),
+			job_seccomp_filter,
 		),
 		Ok(ForkResult::Parent { child }) => handle_parent_process(
 			pipe_read_fd,
@@ -440,6 +445,7 @@ fn handle_child_process(
 	preparation_timeout: Duration,
 	prepare_job_kind: PrepareJobKind,
 	executor_params: Arc<ExecutorParams>,
+	job_seccomp_filter: Option<&JobSeccompFilter>,
 ) -> ! {
 	// SAFETY: pipe_writer is an open and owned file descriptor at this point.
 	let mut pipe_write = unsafe { PipeFd::from_raw_fd(pipe_write_fd) };
@@ -463,6 +469,11 @@ fn handle_child_process(
 		);
 	}
 
+	// Restrict the job further before it spawns any threads.
+	if let Err(err) = security::enable_job_seccomp_filter(job_seccomp_filter) {
+		send_child_response(&mut pipe_write, JobResult::Err(PrepareError::Kernel(err)));
+	}
+
 	let worker_job_pid = process::id();
 	gum::debug!(
 		target: LOG_TARGET,
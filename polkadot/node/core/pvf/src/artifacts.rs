@@ -18,7 +18,10 @@
 //!
 //! # Lifecycle of an artifact
 //!
-//! 1. During node start-up, we prune all the cached artifacts, if any.
+//! 1. During node start-up, we prune all the cached artifacts, if any. If the persistent artifact
+//!    cache is enabled, artifacts that were compiled by the same workers and have the recorded
+//!    size are loaded as [`ArtifactState::Prepared`] instead, see [`ArtifactFingerprint`]. Their
+//!    contents are checked against the recorded checksum by the execute worker.
 //!
 //! 2. In order to be executed, a PVF should be prepared first. This means that artifacts should
 //!    have an [`ArtifactState::Prepared`] entry for that artifact in the table. If not, the
@@ -35,7 +38,8 @@
 //! 3. The pool gets an available worker and instructs it to work on the given PVF. The worker
 //!    starts compilation. When the worker finishes successfully, it writes the serialized artifact
 //!    into a temporary file and notifies the host that it's done. The host atomically moves
-//!    (renames) the temporary file to the destination filename of the artifact.
+//!    (renames) the temporary file to the destination filename of the artifact. If the persistent
+//!    artifact cache is enabled, the host also writes the artifact metadata next to it.
 //!
 //! 4. If the worker concluded successfully or returned an error, then the pool notifies the queue.
 //!    In both cases, the queue reports to the host that the result is ready.
@@ -54,14 +58,15 @@
 //!    older by a predefined parameter. This process is run very rarely (say, once a day). Once the
 //!    artifact is expired it is removed from disk eagerly atomically.
 
-use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX};
+use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX, LOG_TARGET};
 use always_assert::always;
+use codec::{Decode, Encode};
 use polkadot_node_core_pvf_common::{error::PrepareError, pvf::PvfPrepData, ArtifactChecksum};
 use polkadot_parachain_primitives::primitives::ValidationCodeHash;
 use polkadot_primitives::ExecutorParamsPrepHash;
 use std::{
 	collections::HashMap,
-	fs,
+	fs, io,
 	path::{Path, PathBuf},
 	time::{Duration, SystemTime},
 };
@@ -69,6 +74,12 @@ use std::{
 /// The extension to use for cached artifacts.
 const ARTIFACT_EXTENSION: &str = "pvf";
 
+/// The extension to use for the metadata of persisted artifacts.
+const ARTIFACT_METADATA_EXTENSION: &str = "pvfmeta";
+
+/// The extension to use for metadata that is still being written.
+const ARTIFACT_METADATA_TMP_EXTENSION: &str = "pvfmeta-tmp";
+
 /// The prefix that artifacts used to start with under the old naming scheme.
 const ARTIFACT_OLD_PREFIX: &str = "wasmtime_";
 
@@ -85,6 +96,38 @@ pub fn generate_artifact_path(cache_path: &Path) -> PathBuf {
 	artifact_path
 }
 
+/// Returns the path of the metadata file that belongs to the artifact at the given path.
+pub fn artifact_metadata_path(artifact_path: &Path) -> PathBuf {
+	artifact_path.with_extension(ARTIFACT_METADATA_EXTENSION)
+}
+
+/// Identifies the workers that compile and execute artifacts.
+///
+/// An artifact is only valid for the exact wasmtime version and compilation settings it was
+/// prepared with, so a persisted artifact is only loaded at start-up if it was produced under the
+/// same fingerprint. The fingerprint covers the node version and the contents of both worker
+/// binaries, so upgrading or rebuilding either of them invalidates the persisted artifacts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct ArtifactFingerprint([u8; 32]);
+
+impl ArtifactFingerprint {
+	/// Computes the fingerprint from the node version and the worker binaries.
+	pub fn new(node_version: Option<&str>, prepare_worker: &[u8], execute_worker: &[u8]) -> Self {
+		let preimage = (node_version, prepare_worker, execute_worker).encode();
+		Self(sp_core::hashing::blake2_256(&preimage))
+	}
+}
+
+/// Metadata stored alongside a persisted artifact.
+#[derive(Debug, Encode, Decode)]
+struct ArtifactMetadata {
+	fingerprint: ArtifactFingerprint,
+	code_hash: ValidationCodeHash,
+	executor_params_prep_hash: ExecutorParamsPrepHash,
+	checksum: ArtifactChecksum,
+	size: u64,
+}
+
 /// Identifier of an artifact. Encodes a code hash of the PVF and a hash of preparation-related
 ///  executor parameter set.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -170,6 +213,9 @@ pub enum ArtifactState {
 /// A container of all known artifact ids and their states.
 pub struct Artifacts {
 	inner: HashMap<ArtifactId, ArtifactState>,
+	/// The fingerprint that prepared artifacts are persisted with, if the persistent artifact
+	/// cache is enabled.
+	fingerprint: Option<ArtifactFingerprint>,
 }
 
 /// Parameters we use to cleanup artifacts
@@ -202,7 +248,7 @@ impl ArtifactsCleanupConfig {
 impl Artifacts {
 	#[cfg(test)]
 	pub(crate) fn empty() -> Self {
-		Self { inner: HashMap::new() }
+		Self { inner: HashMap::new(), fingerprint: None }
 	}
 
 	#[cfg(test)]
@@ -231,10 +277,16 @@ impl Artifacts {
 	}
 
 	/// Create an empty table and the cache directory on-disk if it doesn't exist.
-	pub async fn new(cache_path: &Path) -> Self {
+	///
+	/// If a `fingerprint` is given, the persistent artifact cache is enabled: artifacts persisted
+	/// by a previous run under the same fingerprint are loaded into the table, after checking
+	/// their size. Everything else is removed.
+	pub async fn new(cache_path: &Path, fingerprint: Option<ArtifactFingerprint>) -> Self {
 		// Make sure that the cache path directory and all its parents are created.
 		let _ = tokio::fs::create_dir_all(cache_path).await;
 
+		let mut inner = HashMap::new();
+
 		// Delete any leftover artifacts and worker dirs from previous runs. We don't delete the
 		// entire cache directory in case the user made a mistake and set it to e.g. their home
 		// directory. This is a best-effort to do clean-up, so ignore any errors.
@@ -243,14 +295,77 @@ impl Artifacts {
 			let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else { continue };
 			if path.is_dir() && file_name.starts_with(WORKER_DIR_PREFIX) {
 				let _ = fs::remove_dir_all(path);
-			} else if path.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION) ||
-				file_name.starts_with(ARTIFACT_OLD_PREFIX)
+			} else if path.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION) {
+				let loaded = fingerprint.and_then(|fingerprint| load_persisted(&path, fingerprint));
+				match loaded {
+					Some((artifact_id, state)) if !inner.contains_key(&artifact_id) => {
+						gum::debug!(
+							target: LOG_TARGET,
+							?artifact_id,
+							"loaded persisted artifact {}",
+							path.display(),
+						);
+						inner.insert(artifact_id, state);
+					},
+					_ => {
+						let _ = fs::remove_file(artifact_metadata_path(&path));
+						let _ = fs::remove_file(path);
+					},
+				}
+			} else if file_name.starts_with(ARTIFACT_OLD_PREFIX) {
+				let _ = fs::remove_file(path);
+			}
+		}
+
+		// Remove the metadata left behind by artifacts that no longer exist, or that was never
+		// completely written.
+		for entry in fs::read_dir(cache_path).into_iter().flatten().flatten() {
+			let path = entry.path();
+			let Some(ext) = path.extension() else { continue };
+			if ext == ARTIFACT_METADATA_TMP_EXTENSION ||
+				(ext == ARTIFACT_METADATA_EXTENSION &&
+					!path.with_extension(ARTIFACT_EXTENSION).exists())
 			{
 				let _ = fs::remove_file(path);
 			}
 		}
 
-		Self { inner: HashMap::new() }
+		if fingerprint.is_some() {
+			gum::info!(
+				target: LOG_TARGET,
+				"loaded {} persisted PVF artifacts from {}",
+				inner.len(),
+				cache_path.display(),
+			);
+		}
+
+		Self { inner, fingerprint }
+	}
+
+	/// Persist the metadata of a freshly prepared artifact, if the persistent artifact cache is
+	/// enabled, so that it can be loaded again after a restart.
+	pub fn persist(
+		&self,
+		artifact_id: &ArtifactId,
+		path: &Path,
+		checksum: ArtifactChecksum,
+		size: u64,
+	) -> io::Result<()> {
+		let Some(fingerprint) = self.fingerprint else { return Ok(()) };
+		let metadata = ArtifactMetadata {
+			fingerprint,
+			code_hash: artifact_id.code_hash,
+			executor_params_prep_hash: artifact_id.executor_params_prep_hash,
+			checksum,
+			size,
+		};
+
+		// Write to a temporary file first and rename it, so that a crash never leaves behind a
+		// partially written metadata file.
+		let metadata_path = artifact_metadata_path(path);
+		let tmp_path = metadata_path.with_extension(ARTIFACT_METADATA_TMP_EXTENSION);
+		fs::write(&tmp_path, metadata.encode())?;
+		fs::rename(&tmp_path, &metadata_path)
 	}
 
 	/// Returns the state of the given artifact by its ID.
@@ -342,9 +457,54 @@ impl Artifacts {
 	}
 }
 
+/// Load the persisted artifact at the given path, if its metadata was written under the given
+/// fingerprint and its size matches the recorded one.
+///
+/// The artifact is not read here, so that the start-up does not depend on the size of the cache.
+/// Its contents are checked against the recorded checksum by the execute worker before every
+/// execution, and an artifact that fails the check is removed and prepared again.
+fn load_persisted(
+	path: &Path,
+	fingerprint: ArtifactFingerprint,
+) -> Option<(ArtifactId, ArtifactState)> {
+	let metadata = fs::read(artifact_metadata_path(path)).ok()?;
+	let Ok(metadata) = ArtifactMetadata::decode(&mut &metadata[..]) else {
+		gum::warn!(target: LOG_TARGET, "corrupted metadata of persisted artifact {}", path.display());
+		return None;
+	};
+	if metadata.fingerprint != fingerprint {
+		gum::debug!(
+			target: LOG_TARGET,
+			"persisted artifact {} was prepared by different workers",
+			path.display(),
+		);
+		return None;
+	}
+
+	if fs::metadata(path).ok()?.len() != metadata.size {
+		gum::warn!(
+			target: LOG_TARGET,
+			"persisted artifact {} failed the integrity check",
+			path.display(),
+		);
+		return None;
+	}
+
+	let artifact_id = ArtifactId::new(metadata.code_hash, metadata.executor_params_prep_hash);
+	let state = ArtifactState::Prepared {
+		checksum: metadata.checksum,
+		path: path.to_owned(),
+		last_time_needed: SystemTime::now(),
+		size: metadata.size,
+	};
+	Some((artifact_id, state))
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::testing::artifact_id;
+	use assert_matches::assert_matches;
+	use polkadot_node_core_pvf_common::compute_checksum;
 
 	use super::*;
 
@@ -355,6 +515,8 @@ mod tests {
 
 		// These should be cleared.
 		fs::write(cache_path.join("abcd.pvf"), "test").unwrap();
+		fs::write(cache_path.join("abcd.pvfmeta"), "test").unwrap();
+		fs::write(cache_path.join("efgh.pvfmeta"), "test").unwrap();
 		fs::write(cache_path.join("wasmtime_..."), "test").unwrap();
 		fs::create_dir(cache_path.join("worker-dir-prepare-test")).unwrap();
 
@@ -363,7 +525,7 @@ mod tests {
 		fs::write(cache_path.join("polkadot_..."), "test").unwrap();
 		fs::create_dir(cache_path.join("worker-prepare-test")).unwrap();
 
-		let artifacts = Artifacts::new(cache_path).await;
+		let artifacts = Artifacts::new(cache_path, None).await;
 
 		let entries: Vec<String> = fs::read_dir(&cache_path)
 			.unwrap()
@@ -389,7 +551,7 @@ mod tests {
 		let artifact_id2 = artifact_id(2);
 		let artifact_id3 = artifact_id(3);
 
-		let mut artifacts = Artifacts::new(cache_path).await;
+		let mut artifacts = Artifacts::new(cache_path, None).await;
 		let cleanup_config = ArtifactsCleanupConfig::new(1500, Duration::from_secs(0));
 
 		artifacts.insert_prepared(
@@ -437,7 +599,7 @@ mod tests {
 		let artifact_id2 = artifact_id(2);
 		let artifact_id3 = artifact_id(3);
 
-		let mut artifacts = Artifacts::new(cache_path).await;
+		let mut artifacts = Artifacts::new(cache_path, None).await;
 		let cleanup_config = ArtifactsCleanupConfig::new(1500, Duration::from_secs(12));
 
 		artifacts.insert_prepared(
@@ -471,4 +633,122 @@ mod tests {
 		assert!(!artifacts.artifact_ids().contains(&artifact_id3));
 		assert!(pruned.contains(&(artifact_id3, path3)));
 	}
+
+	fn fingerprint(discriminator: u8) -> ArtifactFingerprint {
+		ArtifactFingerprint::new(Some("1.0.0"), &[discriminator], &[discriminator])
+	}
+
+	/// Write an artifact with the given contents and persist it under the given fingerprint.
+	fn write_persisted(
+		cache_path: &Path,
+		artifact_id: &ArtifactId,
+		contents: &[u8],
+		fingerprint: ArtifactFingerprint,
+	) -> PathBuf {
+		let path = generate_artifact_path(cache_path);
+		fs::write(&path, contents).unwrap();
+		let artifacts = Artifacts { inner: HashMap::new(), fingerprint: Some(fingerprint) };
+		artifacts
+			.persist(artifact_id, &path, compute_checksum(contents), contents.len() as u64)
+			.unwrap();
+		path
+	}
+
+	#[test]
+	fn fingerprint_covers_node_version_and_workers() {
+		let fingerprint = ArtifactFingerprint::new(Some("1.0.0"), b"prepare", b"execute");
+		assert_eq!(fingerprint, ArtifactFingerprint::new(Some("1.0.0"), b"prepare", b"execute"));
+		assert_ne!(fingerprint, ArtifactFingerprint::new(Some("1.0.1"), b"prepare", b"execute"));
+		assert_ne!(fingerprint, ArtifactFingerprint::new(None, b"prepare", b"execute"));
+		assert_ne!(fingerprint, ArtifactFingerprint::new(Some("1.0.0"), b"prepare2", b"execute"));
+		assert_ne!(fingerprint, ArtifactFingerprint::new(Some("1.0.0"), b"prepare", b"execute2"));
+	}
+
+	#[tokio::test]
+	async fn persisted_artifacts_loaded_on_startup() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+
+		let path1 = write_persisted(cache_path, &artifact_id(1), b"artifact1", fingerprint(1));
+		let path2 = write_persisted(cache_path, &artifact_id(2), b"artifact2", fingerprint(1));
+		// Not persisted, e.g. the node crashed before the metadata was written.
+		let path3 = generate_artifact_path(cache_path);
+		fs::write(&path3, b"artifact3").unwrap();
+		fs::write(cache_path.join("abcd.pvfmeta-tmp"), "test").unwrap();
+
+		let mut artifacts = Artifacts::new(cache_path, Some(fingerprint(1))).await;
+
+		assert_eq!(artifacts.len(), 2);
+		assert_matches!(
+			artifacts.artifact_state_mut(&artifact_id(1)),
+			Some(ArtifactState::Prepared { path, checksum, size: 9, .. })
+				if *path == path1 && *checksum == compute_checksum(b"artifact1")
+		);
+		assert_matches!(
+			artifacts.artifact_state_mut(&artifact_id(2)),
+			Some(ArtifactState::Prepared { path, .. }) if *path == path2
+		);
+
+		let mut entries: Vec<PathBuf> =
+			fs::read_dir(&cache_path).unwrap().map(|entry| entry.unwrap().path()).collect();
+		entries.sort();
+		let mut expected = vec![
+			path1.clone(),
+			artifact_metadata_path(&path1),
+			path2.clone(),
+			artifact_metadata_path(&path2),
+		];
+		expected.sort();
+		assert_eq!(entries, expected);
+	}
+
+	#[tokio::test]
+	async fn stale_and_corrupted_artifacts_dropped_on_startup() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+
+		// Prepared by different workers.
+		write_persisted(cache_path, &artifact_id(1), b"artifact1", fingerprint(2));
+		// Modified after it was persisted, but of the same size: only caught by the execute worker.
+		let path2 = write_persisted(cache_path, &artifact_id(2), b"artifact2", fingerprint(1));
+		fs::write(&path2, b"artifact3").unwrap();
+		// Truncated.
+		let path3 = write_persisted(cache_path, &artifact_id(3), b"artifact3", fingerprint(1));
+		fs::write(&path3, b"art").unwrap();
+		// Corrupted metadata.
+		let path4 = write_persisted(cache_path, &artifact_id(4), b"artifact4", fingerprint(1));
+		fs::write(artifact_metadata_path(&path4), b"garbage").unwrap();
+		// A valid one.
+		let path5 = write_persisted(cache_path, &artifact_id(5), b"artifact5", fingerprint(1));
+
+		let artifacts = Artifacts::new(cache_path, Some(fingerprint(1))).await;
+
+		let mut artifact_ids = artifacts.artifact_ids();
+		artifact_ids.sort();
+		assert_eq!(artifact_ids, vec![artifact_id(2), artifact_id(5)]);
+		let mut entries: Vec<PathBuf> =
+			fs::read_dir(&cache_path).unwrap().map(|entry| entry.unwrap().path()).collect();
+		entries.sort();
+		let mut expected = vec![
+			path2.clone(),
+			artifact_metadata_path(&path2),
+			path5.clone(),
+			artifact_metadata_path(&path5),
+		];
+		expected.sort();
+		assert_eq!(entries, expected);
+	}
+
+	#[tokio::test]
+	async fn persisted_artifacts_cleared_when_disabled() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+
+		write_persisted(cache_path, &artifact_id(1), b"artifact1", fingerprint(1));
+
+		let artifacts = Artifacts::new(cache_path, None).await;
+
+		assert_eq!(artifacts.len(), 0);
+		assert_eq!(fs::read_dir(&cache_path).unwrap().count(), 0);
+	}
 }
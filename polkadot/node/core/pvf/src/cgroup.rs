@@ -0,0 +1,264 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Optional cgroup v2 limits for the workers.
+//!
+//! Right after a worker is spawned, the host moves it into its own cgroup under the root configured
+//! by the operator. The job processes that the worker clones or forks inherit the cgroup, so the
+//! limits apply to the worker together with the job it is running. A worker runs a single job at a
+//! time, so in practice the limits apply to each job.
+//!
+//! The root must be a cgroup v2 directory that the node can write to, with the controllers needed
+//! by the limits enabled for its children (see `cgroup.subtree_control`). The `memory` and `pids`
+//! controllers are always needed, as their usage is reported in the metrics.
+
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+/// The prefix of the per-worker cgroups, so we can recognize the ones left behind.
+const CGROUP_PREFIX: &str = "pvf-";
+
+/// The period of `cpu.max`, in microseconds.
+const CPU_MAX_PERIOD_US: u64 = 100_000;
+
+/// cgroup v2 limits applied to each worker, and so to each job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CgroupLimits {
+	/// The cgroup v2 directory under which the per-worker cgroups are created.
+	pub root: PathBuf,
+	/// The maximum memory of a worker and its job, in bytes (`memory.max`).
+	pub memory_max: Option<u64>,
+	/// The maximum CPU bandwidth of a worker and its job, in percent of a single CPU (`cpu.max`).
+	pub cpu_max_percent: Option<u32>,
+	/// The maximum number of processes and threads of a worker and its job (`pids.max`).
+	pub pids_max: Option<u64>,
+}
+
+impl CgroupLimits {
+	/// Checks that the root exists and has the needed controllers enabled for its children.
+	pub(crate) fn check(&self) -> Result<(), String> {
+		if self.cpu_max_percent == Some(0) {
+			return Err("the CPU limit must be at least 1%".into());
+		}
+
+		let subtree_control = fs::read_to_string(self.root.join("cgroup.subtree_control"))
+			.map_err(|err| {
+				format!("{} is not a cgroup v2 directory: {}", self.root.display(), err)
+			})?;
+		let enabled: Vec<&str> = subtree_control.split_whitespace().collect();
+
+		let mut needed = vec!["memory", "pids"];
+		if self.cpu_max_percent.is_some() {
+			needed.push("cpu");
+		}
+		for controller in needed {
+			if !enabled.contains(&controller) {
+				return Err(format!(
+					"the {} controller is not enabled in {}",
+					controller,
+					self.root.join("cgroup.subtree_control").display()
+				));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Resource usage of a worker cgroup, observed after each job.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CgroupUsage {
+	/// The peak memory usage of the worker so far, in bytes.
+	pub memory_peak: Option<u64>,
+	/// The CPU time used since the previous observation.
+	pub cpu_time: Option<Duration>,
+	/// The peak number of processes and threads of the worker so far.
+	pub pids_peak: Option<u64>,
+	/// The number of processes killed by the OOM killer since the previous observation.
+	pub oom_kills: u64,
+}
+
+/// The cgroup of a single worker. It is removed when dropped, if the worker is gone by then;
+/// otherwise it is pruned the next time a worker cgroup is created.
+#[derive(Debug)]
+pub(crate) struct WorkerCgroup {
+	path: PathBuf,
+	last_cpu_usage: Duration,
+	last_oom_kills: u64,
+}
+
+impl WorkerCgroup {
+	/// Creates a cgroup with the given limits and moves the process with the given pid into it.
+	pub(crate) fn create(limits: &CgroupLimits, debug_id: &str, pid: u32) -> io::Result<Self> {
+		prune_stale(&limits.root);
+
+		let path = limits.root.join(format!("{}{}-{}", CGROUP_PREFIX, debug_id, pid));
+		fs::create_dir(&path)?;
+		// From now on the cgroup is removed on drop, including on errors below.
+		let cgroup = Self { path, last_cpu_usage: Duration::ZERO, last_oom_kills: 0 };
+
+		if let Some(memory_max) = limits.memory_max {
+			cgroup.write("memory.max", &memory_max.to_string())?;
+		}
+		if let Some(percent) = limits.cpu_max_percent {
+			let quota = CPU_MAX_PERIOD_US * percent as u64 / 100;
+			cgroup.write("cpu.max", &format!("{} {}", quota, CPU_MAX_PERIOD_US))?;
+		}
+		if let Some(pids_max) = limits.pids_max {
+			cgroup.write("pids.max", &pids_max.to_string())?;
+		}
+		cgroup.write("cgroup.procs", &pid.to_string())?;
+
+		Ok(cgroup)
+	}
+
+	/// Returns the usage of the cgroup. CPU time and OOM kills are counted since the previous
+	/// call. Values that the kernel does not provide are `None`.
+	pub(crate) fn usage(&mut self) -> CgroupUsage {
+		// `memory.peak` and `pids.peak` are only available on recent kernels.
+		let memory_peak = self.read_u64("memory.peak").or_else(|| self.read_u64("memory.current"));
+		let pids_peak = self.read_u64("pids.peak").or_else(|| self.read_u64("pids.current"));
+
+		let cpu_time = self.read_keyed("cpu.stat", "usage_usec").map(|usage_usec| {
+			let total = Duration::from_micros(usage_usec);
+			let delta = total.saturating_sub(self.last_cpu_usage);
+			self.last_cpu_usage = total;
+			delta
+		});
+		let oom_kills = self
+			.read_keyed("memory.events", "oom_kill")
+			.map(|total| {
+				let delta = total.saturating_sub(self.last_oom_kills);
+				self.last_oom_kills = total;
+				delta
+			})
+			.unwrap_or(0);
+
+		CgroupUsage { memory_peak, cpu_time, pids_peak, oom_kills }
+	}
+
+	fn write(&self, file: &str, value: &str) -> io::Result<()> {
+		fs::write(self.path.join(file), value)
+	}
+
+	fn read_u64(&self, file: &str) -> Option<u64> {
+		fs::read_to_string(self.path.join(file)).ok()?.trim().parse().ok()
+	}
+
+	fn read_keyed(&self, file: &str, key: &str) -> Option<u64> {
+		parse_keyed(&fs::read_to_string(self.path.join(file)).ok()?, key)
+	}
+}
+
+impl Drop for WorkerCgroup {
+	fn drop(&mut self) {
+		// Fails if the killed worker has not been reaped yet. It is pruned later in that case.
+		let _ = fs::remove_dir(&self.path);
+	}
+}
+
+/// Removes the empty worker cgroups left behind, e.g. by workers that were still exiting when
+/// their cgroup was dropped, or by a previous run of the node.
+fn prune_stale(root: &Path) {
+	let Ok(entries) = fs::read_dir(root) else { return };
+	for entry in entries.flatten() {
+		if entry.file_name().to_string_lossy().starts_with(CGROUP_PREFIX) {
+			// A cgroup with processes in it can't be removed, so live workers are left alone.
+			let _ = fs::remove_dir(entry.path());
+		}
+	}
+}
+
+/// Parses a value out of a flat keyed file such as `cpu.stat` or `memory.events`.
+fn parse_keyed(contents: &str, key: &str) -> Option<u64> {
+	contents.lines().find_map(|line| {
+		let (k, v) = line.split_once(' ')?;
+		(k == key).then(|| v.trim().parse().ok()).flatten()
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn limits(root: &Path) -> CgroupLimits {
+		CgroupLimits {
+			root: root.to_owned(),
+			memory_max: Some(1 << 30),
+			cpu_max_percent: Some(150),
+			pids_max: Some(64),
+		}
+	}
+
+	#[test]
+	fn parses_keyed_files() {
+		let cpu_stat = "usage_usec 1500\nuser_usec 1000\nsystem_usec 500\n";
+		assert_eq!(parse_keyed(cpu_stat, "usage_usec"), Some(1500));
+		assert_eq!(parse_keyed(cpu_stat, "system_usec"), Some(500));
+		assert_eq!(parse_keyed(cpu_stat, "nr_periods"), None);
+	}
+
+	#[test]
+	fn check_requires_enabled_controllers() {
+		let root = tempfile::tempdir().unwrap();
+		let limits = limits(root.path());
+		assert!(limits.check().is_err());
+
+		fs::write(root.path().join("cgroup.subtree_control"), "memory pids\n").unwrap();
+		assert!(limits.check().unwrap_err().contains("cpu controller"));
+
+		fs::write(root.path().join("cgroup.subtree_control"), "cpu memory pids\n").unwrap();
+		assert_eq!(limits.check(), Ok(()));
+		assert!(CgroupLimits { cpu_max_percent: Some(0), ..limits }.check().is_err());
+	}
+
+	#[test]
+	fn create_writes_limits_and_usage_reports_deltas() {
+		let root = tempfile::tempdir().unwrap();
+		let stale = root.path().join("pvf-execute-1");
+		fs::create_dir(&stale).unwrap();
+
+		let mut cgroup = WorkerCgroup::create(&limits(root.path()), "execute", 42).unwrap();
+		let path = root.path().join("pvf-execute-42");
+		assert!(!stale.exists());
+		assert_eq!(fs::read_to_string(path.join("memory.max")).unwrap(), "1073741824");
+		assert_eq!(fs::read_to_string(path.join("cpu.max")).unwrap(), "150000 100000");
+		assert_eq!(fs::read_to_string(path.join("pids.max")).unwrap(), "64");
+		assert_eq!(fs::read_to_string(path.join("cgroup.procs")).unwrap(), "42");
+
+		fs::write(path.join("memory.current"), "2048\n").unwrap();
+		fs::write(path.join("pids.peak"), "7\n").unwrap();
+		fs::write(path.join("cpu.stat"), "usage_usec 3000\nuser_usec 2000\n").unwrap();
+		fs::write(path.join("memory.events"), "low 0\noom 1\noom_kill 1\n").unwrap();
+		assert_eq!(
+			cgroup.usage(),
+			CgroupUsage {
+				memory_peak: Some(2048),
+				cpu_time: Some(Duration::from_millis(3)),
+				pids_peak: Some(7),
+				oom_kills: 1,
+			}
+		);
+
+		fs::write(path.join("cpu.stat"), "usage_usec 5000\nuser_usec 4000\n").unwrap();
+		let usage = cgroup.usage();
+		assert_eq!(usage.cpu_time, Some(Duration::from_millis(2)));
+		assert_eq!(usage.oom_kills, 0);
+	}
+}
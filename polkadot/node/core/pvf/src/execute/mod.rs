@@ -21,6 +21,6 @@
 //! `polkadot_node_core_pvf_worker::execute_worker_entrypoint`.
 
 mod queue;
-mod worker_interface;
+pub(crate) mod worker_interface;
 
 pub use queue::{start, FromQueue, PendingExecutionRequest, ToQueue};
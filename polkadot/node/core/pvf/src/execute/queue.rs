@@ -21,6 +21,7 @@ use crate::{
 	artifacts::{ArtifactId, ArtifactPathId},
 	host::ResultSender,
 	metrics::Metrics,
+	sandbox::JobSandbox,
 	worker_interface::{IdleWorker, WorkerHandle},
 	InvalidCandidate, PossiblyInvalidError, ValidationError, LOG_TARGET,
 };
@@ -162,6 +163,7 @@ struct Queue {
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 
 	/// The queue of jobs that are waiting for a worker to pick up.
 	unscheduled: Unscheduled,
@@ -181,6 +183,7 @@ impl Queue {
 		spawn_timeout: Duration,
 		node_version: Option<String>,
 		security_status: SecurityStatus,
+		sandbox: JobSandbox,
 		to_queue_rx: mpsc::Receiver<ToQueue>,
 		from_queue_tx: mpsc::UnboundedSender<FromQueue>,
 	) -> Self {
@@ -191,6 +194,7 @@ impl Queue {
 			spawn_timeout,
 			node_version,
 			security_status,
+			sandbox,
 			to_queue_rx,
 			from_queue_tx,
 			unscheduled: Unscheduled::new(),
@@ -435,6 +439,16 @@ async fn handle_job_finish(
 	artifact_id: ArtifactId,
 	result_tx: ResultSender,
 ) {
+	// Observe the usage before the worker is possibly retired below.
+	let usage = queue
+		.workers
+		.running
+		.get_mut(worker)
+		.and_then(|data| data.handle.cgroup_usage());
+	if let Some(usage) = usage {
+		queue.metrics.execute_worker().on_cgroup_usage(usage);
+	}
+
 	let (idle_worker, result, duration, sync_channel, pov_size) = match worker_result {
 		Ok(WorkerInterfaceResponse {
 			worker_response:
@@ -618,6 +632,7 @@ fn spawn_extra_worker(queue: &mut Queue, job: ExecuteJob) {
 			queue.spawn_timeout,
 			queue.node_version.clone(),
 			queue.security_status.clone(),
+			queue.sandbox.clone(),
 		)
 		.boxed(),
 	);
@@ -638,6 +653,7 @@ async fn spawn_worker_task(
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 ) -> QueueEvent {
 	use futures_timer::Delay;
 
@@ -649,6 +665,7 @@ async fn spawn_worker_task(
 			spawn_timeout,
 			node_version.as_deref(),
 			security_status.clone(),
+			sandbox.clone(),
 		)
 		.await
 		{
@@ -717,6 +734,7 @@ pub fn start(
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 ) -> (mpsc::Sender<ToQueue>, mpsc::UnboundedReceiver<FromQueue>, impl Future<Output = ()>) {
 	let (to_queue_tx, to_queue_rx) = mpsc::channel(20);
 	let (from_queue_tx, from_queue_rx) = mpsc::unbounded();
@@ -729,6 +747,7 @@ pub fn start(
 		spawn_timeout,
 		node_version,
 		security_status,
+		sandbox,
 		to_queue_rx,
 		from_queue_tx,
 	)
@@ -1088,6 +1107,7 @@ mod tests {
 			Duration::from_secs(1),
 			None,
 			SecurityStatus::default(),
+			JobSandbox::default(),
 			to_queue_rx,
 			from_queue_tx,
 		);
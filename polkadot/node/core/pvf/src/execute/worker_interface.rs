@@ -18,6 +18,7 @@
 
 use crate::{
 	artifacts::ArtifactPathId,
+	sandbox::JobSandbox,
 	worker_interface::{
 		clear_worker_dir_path, framed_recv, framed_send, spawn_with_program_path, IdleWorker,
 		SpawnErr, WorkerDir, WorkerHandle, JOB_TIMEOUT_WALL_CLOCK_FACTOR,
@@ -46,6 +47,7 @@ pub async fn spawn(
 	spawn_timeout: Duration,
 	node_version: Option<&str>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
 	let mut extra_args = vec!["execute-worker"];
 	if let Some(node_version) = node_version {
@@ -59,6 +61,7 @@ pub async fn spawn(
 		&extra_args,
 		spawn_timeout,
 		security_status,
+		sandbox,
 	)
 	.await?;
 	send_execute_handshake(&mut idle_worker.stream, Handshake { executor_params })
@@ -21,10 +21,13 @@
 //! [`ValidationHost`], that allows communication with that event-loop.
 
 use crate::{
-	artifacts::{ArtifactId, ArtifactPathId, ArtifactState, Artifacts, ArtifactsCleanupConfig},
+	artifacts::{
+		self, ArtifactFingerprint, ArtifactId, ArtifactPathId, ArtifactState, Artifacts,
+		ArtifactsCleanupConfig,
+	},
 	execute::{self, PendingExecutionRequest},
 	metrics::Metrics,
-	prepare, Priority, SecurityStatus, ValidationError, LOG_TARGET,
+	prepare, Priority, SandboxConfig, SecurityStatus, ValidationError, LOG_TARGET,
 };
 use always_assert::never;
 use futures::{
@@ -45,7 +48,7 @@ use polkadot_parachain_primitives::primitives::ValidationResult;
 use polkadot_primitives::Hash;
 use std::{
 	collections::HashMap,
-	path::PathBuf,
+	path::{Path, PathBuf},
 	time::{Duration, SystemTime},
 };
 
@@ -226,6 +229,14 @@ pub struct Config {
 	pub execute_worker_spawn_timeout: Duration,
 	/// The maximum number of execute workers that can run at the same time.
 	pub execute_workers_max_num: usize,
+
+	/// Whether prepared artifacts should be kept across restarts. Persisted artifacts are only
+	/// reused if they were prepared by the same node version and worker binaries.
+	pub persistent_artifacts: bool,
+
+	/// Restrictions applied to the prepare and execute jobs on top of the default security
+	/// features.
+	pub sandbox: SandboxConfig,
 }
 
 impl Config {
@@ -253,6 +264,10 @@ impl Config {
 			execute_worker_program_path,
 			execute_worker_spawn_timeout: Duration::from_secs(3),
 			execute_workers_max_num,
+
+			persistent_artifacts: false,
+
+			sandbox: SandboxConfig::default(),
 		}
 	}
 }
@@ -272,7 +287,9 @@ pub async fn start(
 	gum::debug!(target: LOG_TARGET, ?config, "starting PVF validation host");
 
 	// Make sure the cache is initialized before doing anything else.
-	let artifacts = Artifacts::new(&config.cache_path).await;
+	let fingerprint =
+		if config.persistent_artifacts { artifact_fingerprint(&config).await } else { None };
+	let artifacts = Artifacts::new(&config.cache_path, fingerprint).await;
 
 	// Run checks for supported security features once per host startup. If some checks fail, warn
 	// if Secure Validator Mode is disabled and return an error otherwise.
@@ -303,6 +320,11 @@ pub async fn start(
 		SecurityStatus::default()
 	};
 
+	let (prepare_sandbox, execute_sandbox) = config
+		.sandbox
+		.build()
+		.map_err(|err| SubsystemError::Context(format!("invalid PVF sandbox config: {}", err)))?;
+
 	let (to_host_tx, to_host_rx) = mpsc::channel(HOST_MESSAGE_QUEUE_SIZE);
 
 	let validation_host = ValidationHost { to_host_tx, security_status: security_status.clone() };
@@ -314,6 +336,7 @@ pub async fn start(
 		config.prepare_worker_spawn_timeout,
 		config.node_version.clone(),
 		security_status.clone(),
+		prepare_sandbox,
 	);
 
 	let (to_prepare_queue_tx, from_prepare_queue_rx, run_prepare_queue) = prepare::start_queue(
@@ -333,6 +356,7 @@ pub async fn start(
 		config.execute_worker_spawn_timeout,
 		config.node_version,
 		security_status,
+		execute_sandbox,
 	);
 
 	let (to_sweeper_tx, to_sweeper_rx) = mpsc::channel(100);
@@ -368,6 +392,32 @@ pub async fn start(
 	Ok((validation_host, task))
 }
 
+/// Computes the fingerprint that persisted artifacts are checked against, see
+/// [`ArtifactFingerprint`].
+///
+/// Returns `None` if the worker binaries can't be read, in which case the persistent artifact
+/// cache is disabled for this run.
+async fn artifact_fingerprint(config: &Config) -> Option<ArtifactFingerprint> {
+	async fn read(path: &Path) -> Option<Vec<u8>> {
+		tokio::fs::read(path)
+			.await
+			.map_err(|err| {
+				gum::warn!(
+					target: LOG_TARGET,
+					?err,
+					"could not read worker binary {}, persistent PVF artifacts are disabled",
+					path.display(),
+				);
+			})
+			.ok()
+	}
+
+	let prepare_worker = read(&config.prepare_worker_program_path).await?;
+	let execute_worker = read(&config.execute_worker_program_path).await?;
+
+	Some(ArtifactFingerprint::new(config.node_version.as_deref(), &prepare_worker, &execute_worker))
+}
+
 /// A mapping from an artifact ID which is in preparation state to the list of pending execution
 /// requests that should be executed once the artifact's preparation is finished.
 #[derive(Default)]
@@ -787,6 +837,15 @@ async fn handle_prepare_done(
 ) -> Result<(), Fatal> {
 	let prepare::FromQueue { artifact_id, result } = from_queue;
 
+	if let Ok(success) = &result {
+		if let Err(err) =
+			artifacts.persist(&artifact_id, &success.path, success.checksum, success.size)
+		{
+			// The artifact is still usable, it just won't survive a restart.
+			gum::warn!(target: LOG_TARGET, ?artifact_id, ?err, "failed to persist artifact");
+		}
+	}
+
 	// Make some sanity checks and extract the current state.
 	let state = match artifacts.artifact_state_mut(&artifact_id) {
 		None => {
@@ -987,6 +1046,9 @@ async fn sweeper_task(mut sweeper_rx: mpsc::Receiver<PathBuf>) {
 		match sweeper_rx.next().await {
 			None => break,
 			Some(condemned) => {
+				// Remove the metadata first, so that the artifact is never loaded again even if
+				// its removal fails.
+				let _ = tokio::fs::remove_file(artifacts::artifact_metadata_path(&condemned)).await;
 				let result = tokio::fs::remove_file(&condemned).await;
 				gum::trace!(
 					target: LOG_TARGET,
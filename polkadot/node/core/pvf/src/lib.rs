@@ -91,12 +91,15 @@
 //! [`params`][`polkadot_parachain_primitives::primitives::ValidationParams`].
 
 mod artifacts;
+mod cgroup;
 mod error;
 mod execute;
 mod host;
 mod metrics;
+pub mod offline;
 mod prepare;
 mod priority;
+mod sandbox;
 #[cfg(target_os = "linux")]
 mod security;
 mod worker_interface;
@@ -104,6 +107,7 @@ mod worker_interface;
 #[cfg(feature = "test-utils")]
 pub mod testing;
 
+pub use cgroup::CgroupLimits;
 pub use error::{InvalidCandidate, PossiblyInvalidError, ValidationError};
 pub use host::{
 	start, Config, ValidationHost, EXECUTE_BINARY_NAME, HOST_MESSAGE_QUEUE_SIZE,
@@ -111,6 +115,7 @@ pub use host::{
 };
 pub use metrics::Metrics;
 pub use priority::Priority;
+pub use sandbox::SandboxConfig;
 pub use worker_interface::{framed_recv, framed_send, JOB_TIMEOUT_WALL_CLOCK_FACTOR};
 
 // Re-export some common types.
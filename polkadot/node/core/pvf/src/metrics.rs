@@ -16,6 +16,7 @@
 
 //! Prometheus metrics related to the validation host.
 
+use crate::cgroup::CgroupUsage;
 use polkadot_node_core_pvf_common::prepare::MemoryStats;
 use polkadot_node_metrics::metrics::{self, prometheus};
 use polkadot_node_subsystem::messages::PvfExecKind;
@@ -155,6 +156,11 @@ struct MetricsInner {
 	pov_size: prometheus::HistogramVec,
 	code_size: prometheus::Histogram,
 	exec_kind_selected: prometheus::CounterVec<prometheus::U64>,
+	// Resource usage of the worker cgroups, if cgroup limits are configured
+	cgroup_memory_peak: prometheus::HistogramVec,
+	cgroup_cpu_time: prometheus::HistogramVec,
+	cgroup_pids_peak: prometheus::HistogramVec,
+	cgroup_oom_kills: prometheus::CounterVec<prometheus::U64>,
 }
 
 impl metrics::Metrics for Metrics {
@@ -388,6 +394,65 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			cgroup_memory_peak: prometheus::register(
+				prometheus::HistogramVec::new(
+					prometheus::HistogramOpts::new(
+						"polkadot_pvf_cgroup_memory_peak",
+						"Peak memory usage of the worker cgroup observed after a job (in kilobytes)",
+					).buckets(
+						prometheus::exponential_buckets(8192.0, 2.0, 10)
+							.expect("arguments are always valid; qed"),
+					),
+					&["flavor"],
+				)?,
+				registry,
+			)?,
+			cgroup_cpu_time: prometheus::register(
+				prometheus::HistogramVec::new(
+					prometheus::HistogramOpts::new(
+						"polkadot_pvf_cgroup_cpu_time",
+						"CPU time used by the worker cgroup during a job in seconds",
+					).buckets(vec![
+						0.01,
+						0.05,
+						0.1,
+						0.5,
+						1.0,
+						2.0,
+						5.0,
+						10.0,
+						30.0,
+						60.0,
+						120.0,
+						360.0,
+					]),
+					&["flavor"],
+				)?,
+				registry,
+			)?,
+			cgroup_pids_peak: prometheus::register(
+				prometheus::HistogramVec::new(
+					prometheus::HistogramOpts::new(
+						"polkadot_pvf_cgroup_pids_peak",
+						"Peak number of processes and threads in the worker cgroup observed after a job",
+					).buckets(
+						prometheus::exponential_buckets(1.0, 2.0, 10)
+							.expect("arguments are always valid; qed"),
+					),
+					&["flavor"],
+				)?,
+				registry,
+			)?,
+			cgroup_oom_kills: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_pvf_cgroup_oom_kills",
+						"The total number of processes killed in worker cgroups for exceeding the memory limit",
+					),
+					&["flavor"],
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(inner)))
 	}
@@ -433,4 +498,27 @@ impl<'a> WorkerRelatedMetrics<'a> {
 			metrics.worker_retired.with_label_values(&[self.flavor.as_label()]).inc();
 		}
 	}
+
+	/// When the resource usage of the worker's cgroup was observed after a job.
+	pub(crate) fn on_cgroup_usage(&self, usage: CgroupUsage) {
+		if let Some(metrics) = &self.metrics.0 {
+			let label = self.flavor.as_label();
+			if let Some(memory_peak) = usage.memory_peak {
+				metrics
+					.cgroup_memory_peak
+					.with_label_values(&[label])
+					.observe((memory_peak / 1024) as f64);
+			}
+			if let Some(cpu_time) = usage.cpu_time {
+				metrics
+					.cgroup_cpu_time
+					.with_label_values(&[label])
+					.observe(cpu_time.as_secs_f64());
+			}
+			if let Some(pids_peak) = usage.pids_peak {
+				metrics.cgroup_pids_peak.with_label_values(&[label]).observe(pids_peak as f64);
+			}
+			metrics.cgroup_oom_kills.with_label_values(&[label]).inc_by(usage.oom_kills);
+		}
+	}
 }
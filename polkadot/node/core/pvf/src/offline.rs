@@ -0,0 +1,195 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Preparation and execution of PVFs outside of a running node, e.g. so that parachain teams can
+//! check a runtime upgrade locally before submitting it.
+//!
+//! The jobs run in the same prepare and execute workers, with the same executor parameters, as on
+//! a validator. Unlike in the validation host, there are no queues, retries or artifact cache:
+//! every call spawns a fresh worker and kills it afterwards. The workers are started without the
+//! security features of Secure Validator Mode, as only trusted code is expected to be checked.
+
+use crate::{
+	artifacts::{ArtifactId, ArtifactPathId},
+	execute::worker_interface as execute_worker,
+	metrics::Metrics,
+	prepare::worker_interface::{self as prepare_worker, Outcome},
+	sandbox::JobSandbox,
+	InvalidCandidate, PossiblyInvalidError, ValidationError,
+};
+use polkadot_node_core_pvf_common::{
+	error::PrepareError,
+	execute::{JobResponse, ValidationContext, WorkerError, WorkerResponse},
+	prepare::PrepareSuccess,
+	pvf::PvfPrepData,
+	SecurityStatus,
+};
+use polkadot_parachain_primitives::primitives::ValidationResult;
+use std::{path::PathBuf, time::Duration};
+
+/// Configuration for preparing and executing PVFs outside of a node.
+#[derive(Debug, Clone)]
+pub struct OfflineConfig {
+	/// The directory where the artifacts and the worker directories are created.
+	pub cache_path: PathBuf,
+	/// The version of the node the workers must have. `None` skips the version check.
+	pub node_version: Option<String>,
+	/// The path to the prepare worker binary.
+	pub prepare_worker_program_path: PathBuf,
+	/// The path to the execute worker binary.
+	pub execute_worker_program_path: PathBuf,
+	/// The time allotted for a worker to spawn and report to us.
+	pub worker_spawn_timeout: Duration,
+}
+
+/// An error that happened while preparing or executing a PVF outside of a node.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+	/// The worker could not be spawned.
+	#[error("could not spawn the {kind} worker: {err}")]
+	Spawn { kind: &'static str, err: String },
+	/// The preparation failed.
+	#[error(transparent)]
+	Prepare(#[from] PrepareError),
+	/// The execution failed.
+	#[error(transparent)]
+	Execute(#[from] ValidationError),
+}
+
+/// The outcome of a successful execution.
+#[derive(Debug, Clone)]
+pub struct ExecuteSuccess {
+	/// The result of the validation.
+	pub result: ValidationResult,
+	/// The CPU time taken by the execute job.
+	pub cpu_time: Duration,
+	/// The uncompressed PoV size.
+	pub pov_size: u32,
+}
+
+/// Prepares the given PVF in a fresh prepare worker.
+///
+/// On success, the artifact is left in the cache directory so that it can be passed to
+/// [`execute_pvf`]; the returned statistics are the ones reported by the worker.
+pub async fn prepare_pvf(
+	config: &OfflineConfig,
+	pvf: PvfPrepData,
+) -> Result<PrepareSuccess, Error> {
+	let (idle, _handle) = prepare_worker::spawn(
+		&config.prepare_worker_program_path,
+		&config.cache_path,
+		config.worker_spawn_timeout,
+		config.node_version.as_deref(),
+		SecurityStatus::default(),
+		JobSandbox::default(),
+	)
+	.await
+	.map_err(|err| Error::Spawn { kind: "prepare", err: err.to_string() })?;
+
+	let outcome =
+		prepare_worker::start_work(&Metrics::default(), idle, pvf, config.cache_path.clone()).await;
+
+	// Mirrors how the prepare pool turns the outcome into a result.
+	let result = match outcome {
+		Outcome::Concluded { worker: _, result } => result,
+		Outcome::CreateTmpFileErr { worker: _, err } => Err(PrepareError::CreateTmpFile(err)),
+		Outcome::RenameTmpFile { worker: _, err, src, dest } => {
+			Err(PrepareError::RenameTmpFile { err, src, dest })
+		},
+		Outcome::ClearWorkerDir { err } => Err(PrepareError::ClearWorkerDir(err)),
+		Outcome::Unreachable => Err(PrepareError::IoErr("the worker is unreachable".into())),
+		Outcome::IoErr(err) => Err(PrepareError::IoErr(err)),
+		Outcome::JobDied { err, job_pid } => Err(PrepareError::JobDied { err, job_pid }),
+		Outcome::TimedOut => Err(PrepareError::TimedOut),
+		Outcome::OutOfMemory => Err(PrepareError::OutOfMemory),
+	};
+
+	result.map_err(Error::Prepare)
+}
+
+/// Executes an artifact prepared by [`prepare_pvf`] for the given PVF in a fresh execute worker.
+///
+/// The executor parameters of the validation context must be the ones the artifact was prepared
+/// with.
+pub async fn execute_pvf(
+	config: &OfflineConfig,
+	pvf: &PvfPrepData,
+	artifact: &PrepareSuccess,
+	validation_context: ValidationContext,
+) -> Result<ExecuteSuccess, Error> {
+	let (idle, _handle) = execute_worker::spawn(
+		&config.execute_worker_program_path,
+		&config.cache_path,
+		validation_context.executor_params.clone(),
+		config.worker_spawn_timeout,
+		config.node_version.as_deref(),
+		SecurityStatus::default(),
+		JobSandbox::default(),
+	)
+	.await
+	.map_err(|err| Error::Spawn { kind: "execute", err: err.to_string() })?;
+
+	let artifact =
+		ArtifactPathId::new(ArtifactId::from_pvf_prep_data(pvf), &artifact.path, artifact.checksum);
+	let worker_result = execute_worker::start_work(idle, artifact, validation_context).await;
+
+	// Mirrors how the execute queue turns the worker result into a validation result.
+	let err = match worker_result {
+		Ok(execute_worker::Response {
+			worker_response: WorkerResponse { job_response, duration, pov_size },
+			..
+		}) => match job_response {
+			JobResponse::Ok { result_descriptor } => {
+				return Ok(ExecuteSuccess {
+					result: result_descriptor,
+					cpu_time: duration,
+					pov_size,
+				})
+			},
+			JobResponse::InvalidCandidate(err) => {
+				ValidationError::Invalid(InvalidCandidate::WorkerReportedInvalid(err))
+			},
+			JobResponse::PoVDecompressionFailure => {
+				ValidationError::Invalid(InvalidCandidate::PoVDecompressionFailure)
+			},
+			JobResponse::RuntimeConstruction(err) => {
+				ValidationError::PossiblyInvalid(PossiblyInvalidError::RuntimeConstruction(err))
+			},
+			JobResponse::CorruptedArtifact => {
+				ValidationError::PossiblyInvalid(PossiblyInvalidError::CorruptedArtifact)
+			},
+		},
+		Err(execute_worker::Error::InternalError(err)) |
+		Err(execute_worker::Error::WorkerError(WorkerError::InternalError(err))) => {
+			ValidationError::Internal(err)
+		},
+		Err(execute_worker::Error::HardTimeout) |
+		Err(execute_worker::Error::WorkerError(WorkerError::JobTimedOut)) => {
+			ValidationError::Invalid(InvalidCandidate::HardTimeout)
+		},
+		Err(execute_worker::Error::CommunicationErr(_)) => {
+			ValidationError::PossiblyInvalid(PossiblyInvalidError::AmbiguousWorkerDeath)
+		},
+		Err(execute_worker::Error::WorkerError(WorkerError::JobDied { err, .. })) => {
+			ValidationError::PossiblyInvalid(PossiblyInvalidError::AmbiguousJobDeath(err))
+		},
+		Err(execute_worker::Error::WorkerError(WorkerError::JobError(err))) => {
+			ValidationError::PossiblyInvalid(PossiblyInvalidError::JobError(err.to_string()))
+		},
+	};
+
+	Err(err.into())
+}
@@ -17,6 +17,7 @@
 use super::worker_interface::{self, Outcome};
 use crate::{
 	metrics::Metrics,
+	sandbox::JobSandbox,
 	worker_interface::{IdleWorker, WorkerHandle},
 	LOG_TARGET,
 };
@@ -117,6 +118,7 @@ struct Pool {
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 
 	to_pool: mpsc::Receiver<ToPool>,
 	from_pool: mpsc::UnboundedSender<FromPool>,
@@ -136,6 +138,7 @@ async fn run(
 		spawn_timeout,
 		node_version,
 		security_status,
+		sandbox,
 		to_pool,
 		mut from_pool,
 		mut spawned,
@@ -165,6 +168,7 @@ async fn run(
 					spawn_timeout,
 					node_version.clone(),
 					security_status.clone(),
+					&sandbox,
 					&mut spawned,
 					&mut mux,
 					to_pool,
@@ -213,6 +217,7 @@ fn handle_to_pool(
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	sandbox: &JobSandbox,
 	spawned: &mut HopSlotMap<Worker, WorkerData>,
 	mux: &mut Mux,
 	to_pool: ToPool,
@@ -228,6 +233,7 @@ fn handle_to_pool(
 					spawn_timeout,
 					node_version,
 					security_status,
+					sandbox.clone(),
 				)
 				.boxed(),
 			);
@@ -274,6 +280,7 @@ async fn spawn_worker_task(
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 ) -> PoolEvent {
 	use futures_timer::Delay;
 
@@ -284,6 +291,7 @@ async fn spawn_worker_task(
 			spawn_timeout,
 			node_version.as_deref(),
 			security_status.clone(),
+			sandbox.clone(),
 		)
 		.await
 		{
@@ -327,6 +335,12 @@ fn handle_mux(
 			Ok(())
 		},
 		PoolEvent::StartWork(worker, outcome) => {
+			// Observe the usage before the worker is possibly retired below.
+			let usage = spawned.get_mut(worker).and_then(|data| data.handle.cgroup_usage());
+			if let Some(usage) = usage {
+				metrics.prepare_worker().on_cgroup_usage(usage);
+			}
+
 			// If we receive an outcome that the worker is unreachable or that an error occurred on
 			// the worker, we attempt to kill the worker process.
 			match outcome {
@@ -500,6 +514,7 @@ pub fn start(
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 ) -> (mpsc::Sender<ToPool>, mpsc::UnboundedReceiver<FromPool>, impl Future<Output = ()>) {
 	let (to_pool_tx, to_pool_rx) = mpsc::channel(10);
 	let (from_pool_tx, from_pool_rx) = mpsc::unbounded();
@@ -511,6 +526,7 @@ pub fn start(
 		spawn_timeout,
 		node_version,
 		security_status,
+		sandbox,
 		to_pool: to_pool_rx,
 		from_pool: from_pool_tx,
 		spawned: HopSlotMap::with_capacity_and_key(20),
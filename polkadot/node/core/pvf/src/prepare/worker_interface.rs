@@ -19,6 +19,7 @@
 use crate::{
 	artifacts::generate_artifact_path,
 	metrics::Metrics,
+	sandbox::JobSandbox,
 	worker_interface::{
 		clear_worker_dir_path, framed_recv, framed_send, spawn_with_program_path, IdleWorker,
 		SpawnErr, WorkerDir, WorkerHandle, JOB_TIMEOUT_WALL_CLOCK_FACTOR,
@@ -49,6 +50,7 @@ pub async fn spawn(
 	spawn_timeout: Duration,
 	node_version: Option<&str>,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
 	let mut extra_args = vec!["prepare-worker"];
 	if let Some(node_version) = node_version {
@@ -62,6 +64,7 @@ pub async fn spawn(
 		&extra_args,
 		spawn_timeout,
 		security_status,
+		sandbox,
 	)
 	.await
 }
@@ -236,12 +239,12 @@ async fn handle_response(
 		return Outcome::TimedOut;
 	}
 
-	let size = match tokio::fs::metadata(cache_path).await {
+	let size = match tokio::fs::metadata(&tmp_file).await {
 		Ok(metadata) => metadata.len(),
 		Err(err) => {
 			gum::warn!(
 				target: LOG_TARGET,
-				?cache_path,
+				?tmp_file,
 				"failed to read size of the artifact: {}",
 				err,
 			);
@@ -0,0 +1,123 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Restrictions configured by the operator for the prepare and execute jobs. They are applied on
+//! top of the security features that the workers always enable, see
+//! [`polkadot_node_core_pvf_common::worker::security`].
+
+use crate::cgroup::CgroupLimits;
+use polkadot_node_core_pvf_common::JobSeccompFilter;
+use std::path::{Path, PathBuf};
+
+/// The name of the filter applied to prepare jobs in a seccomp profile.
+const PREPARE_FILTER_NAME: &str = "prepare";
+/// The name of the filter applied to execute jobs in a seccomp profile.
+const EXECUTE_FILTER_NAME: &str = "execute";
+
+/// Sandbox configuration for the prepare and execute jobs.
+#[derive(Debug, Clone, Default)]
+pub struct SandboxConfig {
+	/// The path to a seccomp profile, in the JSON format of `seccompiler`. The filters named
+	/// `prepare` and `execute` are applied to the respective jobs; a missing filter leaves those
+	/// jobs unrestricted beyond the default. Only supported on Linux x86-64.
+	pub seccomp_profile_path: Option<PathBuf>,
+	/// The cgroup v2 limits applied to each worker and its jobs. Only supported on Linux.
+	pub cgroup_limits: Option<CgroupLimits>,
+}
+
+/// This is publicly exposed only for integration tests.
+///
+/// The restrictions that a pool applies to each of its workers.
+#[derive(Debug, Clone, Default)]
+#[doc(hidden)]
+pub struct JobSandbox {
+	/// The seccomp filter applied to each job.
+	pub seccomp_filter: Option<JobSeccompFilter>,
+	/// The cgroup limits applied to each worker.
+	pub cgroup_limits: Option<CgroupLimits>,
+}
+
+impl SandboxConfig {
+	/// Compiles the seccomp profile and checks the cgroup root, returning the sandboxes for the
+	/// prepare and execute workers respectively.
+	pub(crate) fn build(&self) -> Result<(JobSandbox, JobSandbox), String> {
+		let (prepare_filter, execute_filter) = match &self.seccomp_profile_path {
+			Some(path) => compile_seccomp_profile(path)?,
+			None => (None, None),
+		};
+
+		if let Some(limits) = &self.cgroup_limits {
+			if cfg!(not(target_os = "linux")) {
+				return Err("cgroup limits are only supported on Linux".into());
+			}
+			limits.check()?;
+		}
+
+		Ok((
+			JobSandbox {
+				seccomp_filter: prepare_filter,
+				cgroup_limits: self.cgroup_limits.clone(),
+			},
+			JobSandbox {
+				seccomp_filter: execute_filter,
+				cgroup_limits: self.cgroup_limits.clone(),
+			},
+		))
+	}
+}
+
+/// Compiles the seccomp profile at the given path into the filters for prepare and execute jobs.
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+fn compile_seccomp_profile(
+	path: &Path,
+) -> Result<(Option<JobSeccompFilter>, Option<JobSeccompFilter>), String> {
+	let file = std::fs::File::open(path)
+		.map_err(|err| format!("could not open seccomp profile {}: {}", path.display(), err))?;
+	let filters = seccompiler::compile_from_json(
+		std::io::BufReader::new(file),
+		seccompiler::TargetArch::x86_64,
+	)
+	.map_err(|err| format!("invalid seccomp profile {}: {}", path.display(), err))?;
+
+	let (mut prepare_filter, mut execute_filter) = (None, None);
+	for (name, program) in filters {
+		let filter = JobSeccompFilter(
+			program.into_iter().map(|insn| (insn.code, insn.jt, insn.jf, insn.k)).collect(),
+		);
+		match name.as_str() {
+			PREPARE_FILTER_NAME => prepare_filter = Some(filter),
+			EXECUTE_FILTER_NAME => execute_filter = Some(filter),
+			_ => {
+				return Err(format!(
+					"unknown filter `{}` in seccomp profile {}; expected `{}` or `{}`",
+					name,
+					path.display(),
+					PREPARE_FILTER_NAME,
+					EXECUTE_FILTER_NAME
+				))
+			},
+		}
+	}
+
+	Ok((prepare_filter, execute_filter))
+}
+
+#[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+fn compile_seccomp_profile(
+	_path: &Path,
+) -> Result<(Option<JobSeccompFilter>, Option<JobSeccompFilter>), String> {
+	Err("seccomp profiles are only supported on Linux x86-64".into())
+}
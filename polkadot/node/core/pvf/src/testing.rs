@@ -18,6 +18,7 @@
 
 pub use crate::{
 	host::{EXECUTE_BINARY_NAME, PREPARE_BINARY_NAME},
+	sandbox::JobSandbox,
 	worker_interface::{spawn_with_program_path, SpawnErr},
 };
 
@@ -16,7 +16,11 @@
 
 //! Common logic for implementation of worker processes.
 
-use crate::LOG_TARGET;
+use crate::{
+	cgroup::{CgroupUsage, WorkerCgroup},
+	sandbox::JobSandbox,
+	LOG_TARGET,
+};
 use codec::Encode;
 use futures::FutureExt as _;
 use futures_timer::Delay;
@@ -57,6 +61,8 @@ pub const JOB_TIMEOUT_WALL_CLOCK_FACTOR: u32 = 4;
 /// - `spawn_timeout`: The amount of time to wait for the child process to spawn.
 ///
 /// - `security_status`: contains the detected status of security features.
+///
+/// - `sandbox`: the restrictions configured by the operator for the worker and its jobs.
 #[doc(hidden)]
 pub async fn spawn_with_program_path(
 	debug_id: &'static str,
@@ -65,6 +71,7 @@ pub async fn spawn_with_program_path(
 	extra_args: &[&str],
 	spawn_timeout: Duration,
 	security_status: SecurityStatus,
+	sandbox: JobSandbox,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
 	let program_path = program_path.into();
 	let worker_dir = WorkerDir::new(debug_id, cache_path).await?;
@@ -83,15 +90,27 @@ pub async fn spawn_with_program_path(
 				Err(err) => return Err(SpawnErr::Bind { socket_path, err: err.to_string() }),
 			};
 
-			let handle =
+			let mut handle =
 				WorkerHandle::spawn(&program_path, &extra_args, &socket_path, &worker_dir.path())
 					.map_err(|err| SpawnErr::ProcessSpawn { program_path, err: err.to_string() })?;
 
+			// Move the worker into its cgroup before it gets any job. Dropping the handle on error
+			// kills the worker.
+			if let Some(limits) = &sandbox.cgroup_limits {
+				let cgroup = WorkerCgroup::create(limits, debug_id, handle.id())
+					.map_err(|err| SpawnErr::Cgroup { err: err.to_string() })?;
+				handle.cgroup = Some(cgroup);
+			}
+
 			futures::select! {
 				accept_result = listener.accept().fuse() => {
 					let (mut stream, _) = accept_result
 						.map_err(|err| SpawnErr::Accept { socket_path, err: err.to_string() })?;
-					send_worker_handshake(&mut stream, WorkerHandshake { security_status })
+					let handshake = WorkerHandshake {
+						security_status,
+						job_seccomp_filter: sandbox.seccomp_filter,
+					};
+					send_worker_handshake(&mut stream, handshake)
 						.await
 						.map_err(|err| SpawnErr::Handshake { err: err.to_string() })?;
 					Ok((IdleWorker { stream, pid: handle.id(), worker_dir }, handle))
@@ -206,6 +225,8 @@ pub enum SpawnErr {
 	AcceptTimeout { spawn_timeout: Duration },
 	#[error("failed to send handshake after successful spawning was signaled: {err}")]
 	Handshake { err: String },
+	#[error("could not move the spawned process into its cgroup: {err}")]
+	Cgroup { err: String },
 }
 
 /// This is a representation of a potentially running worker. Drop it and the process will be
@@ -225,6 +246,8 @@ pub struct WorkerHandle {
 	stdout: process::ChildStdout,
 	program: PathBuf,
 	drop_box: Box<[u8]>,
+	// Declared after `child`, so the worker is killed before we try to remove its cgroup.
+	cgroup: Option<WorkerCgroup>,
 }
 
 impl WorkerHandle {
@@ -273,6 +296,7 @@ impl WorkerHandle {
 			// OTOH, we also don't want to be super smart here and we could just afford to allocate
 			// a buffer for that here.
 			drop_box: vec![0; 8192].into_boxed_slice(),
+			cgroup: None,
 		})
 	}
 
@@ -280,6 +304,11 @@ impl WorkerHandle {
 	pub fn id(&self) -> u32 {
 		self.child_id
 	}
+
+	/// Returns the resource usage of the worker's cgroup, if it has one.
+	pub(crate) fn cgroup_usage(&mut self) -> Option<CgroupUsage> {
+		self.cgroup.as_mut().map(WorkerCgroup::usage)
+	}
 }
 
 impl futures::Future for WorkerHandle {
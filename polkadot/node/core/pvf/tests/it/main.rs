@@ -42,6 +42,7 @@ use std::{io::Write, sync::Arc, time::Duration};
 use tokio::sync::Mutex;
 
 mod adder;
+mod offline;
 #[cfg(target_os = "linux")]
 mod process;
 mod worker_common;
@@ -603,6 +604,49 @@ async fn cache_cleared_on_startup() {
 	assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 0);
 }
 
+#[tokio::test]
+async fn persisted_artifacts_reused_after_restart() {
+	// Don't drop this host, it owns the `TempDir` which gets cleared on drop.
+	let host = TestHost::new_with_config(|cfg| {
+		cfg.persistent_artifacts = true;
+	})
+	.await;
+
+	let _stats = host
+		.precheck_pvf(test_parachain_halt::wasm_binary_unwrap(), Default::default())
+		.await
+		.unwrap();
+
+	// The cache dir should contain one artifact, its metadata and one worker dir.
+	let cache_dir = host.cache_dir.path().to_owned();
+	let artifacts = || {
+		std::fs::read_dir(&cache_dir)
+			.unwrap()
+			.map(|entry| entry.unwrap().path())
+			.filter(|path| path.extension().map_or(false, |ext| ext == "pvf"))
+			.map(|path| (path.clone(), std::fs::metadata(path).unwrap().modified().unwrap()))
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 3);
+	let before = artifacts();
+	assert_eq!(before.len(), 1);
+
+	// Start a new host, only the worker dir should be cleared.
+	let host = TestHost::new_with_config(|cfg| {
+		cfg.cache_path = cache_dir.clone();
+		cfg.persistent_artifacts = true;
+	})
+	.await;
+	assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 2);
+
+	// The persisted artifact is used instead of preparing the PVF again.
+	let _stats = host
+		.precheck_pvf(test_parachain_halt::wasm_binary_unwrap(), Default::default())
+		.await
+		.unwrap();
+	assert_eq!(artifacts(), before);
+}
+
 // This test checks if the adder parachain runtime can be prepared with 10Mb preparation memory
 // limit enforced. At the moment of writing, the limit if far enough to prepare the PVF. If it
 // starts failing, either Wasmtime version has changed, or the PVF code itself has changed, and
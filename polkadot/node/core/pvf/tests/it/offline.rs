@@ -0,0 +1,124 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Integration tests of the preparation and execution of PVFs outside of the validation host.
+
+use super::{TEST_EXECUTION_TIMEOUT, TEST_PREPARATION_TIMEOUT, VALIDATION_CODE_BOMB_LIMIT};
+use assert_matches::assert_matches;
+use codec::{Decode, Encode};
+use polkadot_node_core_pvf::{
+	offline::{self, Error, OfflineConfig},
+	testing::build_workers_and_get_paths,
+	InvalidCandidate, PrepareError, PrepareJobKind, PvfPrepData, ValidationError,
+};
+use polkadot_node_core_pvf_common::execute::ValidationContext;
+use polkadot_node_primitives::PoV;
+use polkadot_parachain_primitives::primitives::{
+	BlockData as GenericBlockData, HeadData as GenericHeadData,
+};
+use polkadot_primitives::PersistedValidationData;
+use polkadot_primitives_test_helpers::dummy_candidate_receipt;
+use sp_core::H256;
+use std::{sync::Arc, time::Duration};
+use test_parachain_adder::{hash_state, BlockData, HeadData};
+
+fn config(cache_dir: &tempfile::TempDir) -> OfflineConfig {
+	let (prepare_worker_program_path, execute_worker_program_path) = build_workers_and_get_paths();
+	OfflineConfig {
+		cache_path: cache_dir.path().to_owned(),
+		node_version: None,
+		prepare_worker_program_path,
+		execute_worker_program_path,
+		worker_spawn_timeout: Duration::from_secs(3),
+	}
+}
+
+fn pvf(code: &[u8], kind: PrepareJobKind) -> PvfPrepData {
+	PvfPrepData::from_code(
+		code.into(),
+		Default::default(),
+		TEST_PREPARATION_TIMEOUT,
+		kind,
+		VALIDATION_CODE_BOMB_LIMIT,
+	)
+}
+
+fn validation_context(parent_head: &HeadData, block_data: BlockData) -> ValidationContext {
+	ValidationContext {
+		candidate_receipt: dummy_candidate_receipt(H256::default()).into(),
+		pvd: Arc::new(PersistedValidationData {
+			parent_head: GenericHeadData(parent_head.encode()),
+			relay_parent_number: 1u32,
+			relay_parent_storage_root: H256::default(),
+			max_pov_size: 4096 * 1024,
+		}),
+		pov: Arc::new(PoV { block_data: GenericBlockData(block_data.encode()) }),
+		executor_params: Default::default(),
+		exec_timeout: TEST_EXECUTION_TIMEOUT,
+		v3_enabled: false,
+	}
+}
+
+#[tokio::test]
+async fn prepare_and_execute_adder() {
+	let cache_dir = tempfile::tempdir().unwrap();
+	let config = config(&cache_dir);
+	let pvf = pvf(test_parachain_adder::wasm_binary_unwrap(), PrepareJobKind::Compilation);
+
+	let artifact = offline::prepare_pvf(&config, pvf.clone()).await.unwrap();
+	assert!(artifact.path.starts_with(cache_dir.path()));
+	assert_eq!(std::fs::metadata(&artifact.path).unwrap().len(), artifact.size);
+
+	let parent_head = HeadData { number: 0, parent_hash: [0; 32], post_state: hash_state(0) };
+	let outcome = offline::execute_pvf(
+		&config,
+		&pvf,
+		&artifact,
+		validation_context(&parent_head, BlockData { state: 0, add: 512 }),
+	)
+	.await
+	.unwrap();
+
+	let new_head = HeadData::decode(&mut &outcome.result.head_data.0[..]).unwrap();
+	assert_eq!(new_head.number, 1);
+	assert_eq!(new_head.parent_hash, parent_head.hash());
+	assert_eq!(new_head.post_state, hash_state(512));
+
+	// The same artifact can be executed again, and reports invalid blocks.
+	let result = offline::execute_pvf(
+		&config,
+		&pvf,
+		&artifact,
+		validation_context(&parent_head, BlockData { state: 256, add: 256 }),
+	)
+	.await;
+	assert_matches!(
+		result,
+		Err(Error::Execute(ValidationError::Invalid(InvalidCandidate::WorkerReportedInvalid(_))))
+	);
+}
+
+#[tokio::test]
+async fn precheck_rejects_invalid_pvf() {
+	let cache_dir = tempfile::tempdir().unwrap();
+	let config = config(&cache_dir);
+
+	let result =
+		offline::prepare_pvf(&config, pvf(b"definitely not wasm", PrepareJobKind::Prechecking))
+			.await;
+
+	assert_matches!(result, Err(Error::Prepare(PrepareError::Prevalidation(_))));
+}
@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use polkadot_node_core_pvf::{
-	testing::{build_workers_and_get_paths, spawn_with_program_path, SpawnErr},
+	testing::{build_workers_and_get_paths, spawn_with_program_path, JobSandbox, SpawnErr},
 	SecurityStatus,
 };
 use std::{env, time::Duration};
@@ -35,6 +35,7 @@ async fn spawn_immediate_exit() {
 		&["exit"],
 		Duration::from_secs(2),
 		SecurityStatus::default(),
+		JobSandbox::default(),
 	)
 	.await;
 	assert!(
@@ -54,6 +55,7 @@ async fn spawn_timeout() {
 		&["test-sleep"],
 		spawn_timeout,
 		SecurityStatus::default(),
+		JobSandbox::default(),
 	)
 	.await;
 	assert!(
@@ -72,6 +74,7 @@ async fn should_connect() {
 		&["prepare-worker"],
 		Duration::from_secs(2),
 		SecurityStatus::default(),
+		JobSandbox::default(),
 	)
 	.await
 	.unwrap();
@@ -42,6 +42,8 @@ enum NemesisVariant {
 	DisputeFinalizedCandidates(DisputeFinalizedCandidatesOptions),
 	/// Spam many request statements instead of sending a single one.
 	SpamStatementRequests(SpamStatementRequestsOptions),
+	/// Back candidates, but withhold their erasure chunks from other validators.
+	WithholdAvailabilityChunks(WithholdAvailabilityChunksOptions),
 }
 
 #[derive(Debug, Parser)]
@@ -105,6 +107,15 @@ impl MalusCli {
 
 				polkadot_cli::run_node(cli, SpamStatementRequests { spam_factor }, finality_delay)?
 			},
+			NemesisVariant::WithholdAvailabilityChunks(opts) => {
+				let WithholdAvailabilityChunksOptions { percentage, cli } = opts;
+
+				polkadot_cli::run_node(
+					cli,
+					WithholdAvailabilityChunks { percentage },
+					finality_delay,
+				)?
+			},
 		}
 		Ok(())
 	}
@@ -244,4 +255,23 @@ mod tests {
 			assert!(opts.cli.run.base.bob);
 		});
 	}
+
+	#[test]
+	fn withhold_availability_chunks_percentage_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"withhold-availability-chunks",
+			"--percentage",
+			"50",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::WithholdAvailabilityChunks(opts),
+			..
+		} => {
+			assert_eq!(opts.percentage, 50);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
 }
@@ -0,0 +1,149 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that backs candidates but withholds their erasure chunks.
+//!
+//! This malus variant behaves honestly in backing, bitfield signing and approval voting, so it
+//! stores the erasure chunks and signs availability for them. However, whenever a peer asks for a
+//! chunk or for the full available data of a candidate, the availability store pretends not to
+//! have it. Other validators then have to recover the data from the remaining chunks.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::CandidateHash;
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::sync::Arc;
+
+/// Wraps around the availability store and hides the chunks of some candidates.
+#[derive(Clone)]
+struct ChunkWithholder {
+	/// Percentage of candidates for which chunks are withheld.
+	percentage: u8,
+}
+
+impl ChunkWithholder {
+	/// The decision only depends on the candidate hash, so either all queries for the chunks of a
+	/// candidate are answered or none.
+	fn withholds(&self, candidate_hash: &CandidateHash) -> bool {
+		let sample = u16::from_le_bytes([candidate_hash.0[0], candidate_hash.0[1]]) as u32;
+		sample * 100 < self.percentage as u32 * (u16::MAX as u32 + 1)
+	}
+}
+
+impl<Sender> MessageInterceptor<Sender> for ChunkWithholder
+where
+	Sender: overseer::AvailabilityStoreSenderTrait + Clone + Send + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	/// Intercept the queries used to answer chunk and available data requests of other peers.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, tx),
+			} if self.withholds(&candidate_hash) => {
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					?validator_index,
+					"😈 Withholding erasure chunk.",
+				);
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryAvailableData(candidate_hash, tx),
+			} if self.withholds(&candidate_hash) => {
+				gum::info!(target: MALUS, ?candidate_hash, "😈 Withholding available data.");
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication { msg } => Some(FromOrchestra::Communication { msg }),
+			FromOrchestra::Signal(signal) => Some(FromOrchestra::Signal(signal)),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct WithholdAvailabilityChunksOptions {
+	/// Determines the percentage of candidates for which chunks are withheld.
+	/// Defaults to 100% of candidates.
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// WithholdAvailabilityChunks implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct WithholdAvailabilityChunks {
+	/// Percentage of candidates for which chunks are withheld.
+	pub percentage: u8,
+}
+
+impl OverseerGen for WithholdAvailabilityChunks {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that withholds erasure chunks of {}% of the candidates.",
+			&self.percentage,
+		);
+
+		let chunk_withholder = ChunkWithholder { percentage: self.percentage };
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_availability_store(move |av_store| {
+			InterceptedSubsystem::new(av_store, chunk_withholder)
+		})
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
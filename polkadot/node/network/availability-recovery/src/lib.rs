@@ -57,7 +57,10 @@ use polkadot_node_network_protocol::{
 use polkadot_node_primitives::AvailableData;
 use polkadot_node_subsystem::{
 	errors::RecoveryError,
-	messages::{AvailabilityRecoveryMessage, AvailabilityStoreMessage},
+	messages::{
+		AvailabilityRecoveryMessage, AvailabilityRecoveryStrategy, AvailabilityStoreMessage,
+		RecoveryOptions, RecoveryOutcome,
+	},
 	overseer, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, SpawnedSubsystem,
 	SubsystemContext, SubsystemError,
 };
@@ -67,7 +70,7 @@ use polkadot_node_subsystem_util::{
 };
 use polkadot_primitives::{
 	node_features, BlockNumber, CandidateHash, CandidateReceiptV2 as CandidateReceipt, ChunkIndex,
-	CoreIndex, GroupIndex, Hash, SessionIndex, ValidatorIndex,
+	Hash, SessionIndex, ValidatorIndex,
 };
 
 mod error;
@@ -80,6 +83,7 @@ pub use metrics::Metrics;
 mod tests;
 
 type RecoveryResult = std::result::Result<AvailableData, RecoveryError>;
+type OutcomeResult = std::result::Result<RecoveryOutcome, RecoveryError>;
 
 const LOG_TARGET: &str = "parachain::availability-recovery";
 
@@ -202,15 +206,39 @@ fn reconstructed_data_matches_root(
 	branches.root() == *expected_root
 }
 
+/// The sending side of a recovery request.
+enum ResponseSender {
+	/// Requested with `RecoverAvailableData`.
+	Data(oneshot::Sender<RecoveryResult>),
+	/// Requested with `RecoverAvailableDataWithOptions`.
+	Outcome(oneshot::Sender<OutcomeResult>),
+}
+
+impl ResponseSender {
+	fn poll_canceled(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+		match self {
+			Self::Data(tx) => tx.poll_canceled(cx),
+			Self::Outcome(tx) => tx.poll_canceled(cx),
+		}
+	}
+
+	fn send(self, result: OutcomeResult) -> std::result::Result<(), ()> {
+		match self {
+			Self::Data(tx) => tx.send(result.map(|outcome| outcome.available_data)).map_err(|_| ()),
+			Self::Outcome(tx) => tx.send(result).map_err(|_| ()),
+		}
+	}
+}
+
 /// Accumulate all awaiting sides for some particular `AvailableData`.
 struct RecoveryHandle {
 	candidate_hash: CandidateHash,
-	remote: RemoteHandle<RecoveryResult>,
-	awaiting: Vec<oneshot::Sender<RecoveryResult>>,
+	remote: RemoteHandle<OutcomeResult>,
+	awaiting: Vec<ResponseSender>,
 }
 
 impl Future for RecoveryHandle {
-	type Output = Option<(CandidateHash, RecoveryResult)>;
+	type Output = Option<(CandidateHash, OutcomeResult)>;
 
 	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
 		let mut indices_to_remove = Vec::new();
@@ -256,27 +284,29 @@ impl Future for RecoveryHandle {
 /// Cached result of an availability recovery operation.
 #[derive(Debug, Clone)]
 enum CachedRecovery {
-	/// Availability was successfully retrieved before.
-	Valid(AvailableData),
+	/// Availability was successfully retrieved before, using the given strategy.
+	Valid(AvailableData, AvailabilityRecoveryStrategy),
 	/// Availability was successfully retrieved before, but was found to be invalid.
 	Invalid,
 }
 
 impl CachedRecovery {
 	/// Convert back to	`Result` to deliver responses.
-	fn into_result(self) -> RecoveryResult {
+	fn into_result(self) -> OutcomeResult {
 		match self {
-			Self::Valid(d) => Ok(d),
+			Self::Valid(available_data, strategy) => {
+				Ok(RecoveryOutcome { available_data, strategy, stats: Vec::new() })
+			},
 			Self::Invalid => Err(RecoveryError::Invalid),
 		}
 	}
 }
 
-impl TryFrom<RecoveryResult> for CachedRecovery {
+impl TryFrom<OutcomeResult> for CachedRecovery {
 	type Error = ();
-	fn try_from(o: RecoveryResult) -> std::result::Result<CachedRecovery, Self::Error> {
+	fn try_from(o: OutcomeResult) -> std::result::Result<CachedRecovery, Self::Error> {
 		match o {
-			Ok(d) => Ok(Self::Valid(d)),
+			Ok(outcome) => Ok(Self::Valid(outcome.available_data, outcome.strategy)),
 			Err(RecoveryError::Invalid) => Ok(Self::Invalid),
 			// We don't want to cache unavailable state, as that state might change, so if
 			// requested again we want to try again!
@@ -347,7 +377,7 @@ async fn handle_signal(state: &mut State, signal: OverseerSignal) -> bool {
 async fn launch_recovery_task<Context>(
 	state: &mut State,
 	ctx: &mut Context,
-	response_sender: oneshot::Sender<RecoveryResult>,
+	response_sender: ResponseSender,
 	recovery_strategies: VecDeque<Box<dyn RecoveryStrategy<<Context as SubsystemContext>::Sender>>>,
 	params: RecoveryParams,
 ) -> Result<()> {
@@ -373,14 +403,13 @@ async fn handle_recover<Context>(
 	ctx: &mut Context,
 	receipt: CandidateReceipt,
 	session_index: SessionIndex,
-	backing_group: Option<GroupIndex>,
-	response_sender: oneshot::Sender<RecoveryResult>,
+	options: RecoveryOptions,
+	response_sender: ResponseSender,
 	metrics: &Metrics,
 	erasure_task_tx: futures::channel::mpsc::Sender<ErasureTask>,
 	recovery_strategy_kind: RecoveryStrategyKind,
 	bypass_availability_store: bool,
 	post_recovery_check: PostRecoveryCheck,
-	maybe_core_index: Option<CoreIndex>,
 	req_v1_protocol_name: ProtocolName,
 	req_v2_protocol_name: ProtocolName,
 ) -> Result<()> {
@@ -406,15 +435,81 @@ async fn handle_recover<Context>(
 
 	match session_info_res {
 		Ok(ExtendedSessionInfo { session_info, node_features, .. }) => {
-			let mut backer_group = None;
 			let n_validators = session_info.validators.len();
 			let systematic_threshold = systematic_recovery_threshold(n_validators)?;
+
+			let chunk_mapping_enabled = if let Some(&true) = node_features
+				.get(usize::from(node_features::FeatureIndex::AvailabilityChunkMapping as u8))
+				.as_deref()
+			{
+				true
+			} else {
+				false
+			};
+
+			// Preferred validators first, then the backing group.
+			let mut backers = Vec::new();
+			for validator in options.preferred_validators.iter().chain(
+				options
+					.backing_group
+					.and_then(|group| session_info.validator_groups.get(group))
+					.into_iter()
+					.flatten(),
+			) {
+				if (validator.0 as usize) < n_validators && !backers.contains(validator) {
+					backers.push(*validator);
+				}
+			}
+
+			let fetch_full = || {
+				FetchFull::new(FetchFullParams {
+					validators: backers.clone(),
+					// Out of range validators were not added to the backers.
+					preferred_validators: options
+						.preferred_validators
+						.iter()
+						.filter(|validator| backers.contains(validator))
+						.copied()
+						.collect(),
+				})
+			};
+
+			let systematic_chunks = |core_index| -> Result<FetchSystematicChunks> {
+				let chunk_indices =
+					availability_chunk_indices(node_features, n_validators, core_index)?;
+
+				// Only get the validators according to the threshold.
+				let validators = chunk_indices
+					.iter()
+					.enumerate()
+					.map(|(v_index, c_index)| {
+						(
+							*c_index,
+							ValidatorIndex(
+								u32::try_from(v_index)
+									.expect("validator count should not exceed u32"),
+							),
+						)
+					})
+					.filter(|(c_index, _)| {
+						usize::try_from(c_index.0)
+							.expect("usize is at least u32 bytes on all modern targets.") <
+							systematic_threshold
+					})
+					.collect();
+
+				Ok(FetchSystematicChunks::new(FetchSystematicChunksParams {
+					validators,
+					backers: backers.clone(),
+				}))
+			};
+
 			let mut recovery_strategies: VecDeque<
 				Box<dyn RecoveryStrategy<<Context as SubsystemContext>::Sender>>,
 			> = VecDeque::with_capacity(3);
 
-			if let Some(backing_group) = backing_group {
-				if let Some(backing_validators) = session_info.validator_groups.get(backing_group) {
+			if options.strategies.is_empty() {
+				if !backers.is_empty() {
 					let mut small_pov_size = true;
 
 					match recovery_strategy_kind {
@@ -455,80 +550,63 @@ async fn handle_recover<Context>(
 							true,
 						) |
 						(RecoveryStrategyKind::BackersThenSystematicChunks, _) => {
-							recovery_strategies.push_back(Box::new(FetchFull::new(
-								FetchFullParams { validators: backing_validators.to_vec() },
-							)))
+							recovery_strategies.push_back(Box::new(fetch_full()))
 						},
 						_ => {},
 					};
+				}
 
-					backer_group = Some(backing_validators);
+				// We can only attempt systematic recovery if we received the core index of the
+				// candidate and chunk mapping is enabled.
+				if let Some(core_index) = options.core_index {
+					if matches!(
+						recovery_strategy_kind,
+						RecoveryStrategyKind::BackersThenSystematicChunks |
+							RecoveryStrategyKind::SystematicChunks |
+							RecoveryStrategyKind::BackersFirstIfSizeLowerThenSystematicChunks(_)
+					) && chunk_mapping_enabled
+					{
+						recovery_strategies.push_back(Box::new(systematic_chunks(core_index)?));
+					}
 				}
-			}
 
-			let chunk_mapping_enabled = if let Some(&true) = node_features
-				.get(usize::from(node_features::FeatureIndex::AvailabilityChunkMapping as u8))
-				.as_deref()
-			{
-				true
+				recovery_strategies
+					.push_back(Box::new(FetchChunks::new(FetchChunksParams { n_validators })));
 			} else {
-				false
-			};
+				let mut requested = Vec::with_capacity(options.strategies.len());
+				for strategy in options.strategies.iter().copied() {
+					if requested.contains(&strategy) {
+						continue;
+					}
+					requested.push(strategy);
 
-			// We can only attempt systematic recovery if we received the core index of the
-			// candidate and chunk mapping is enabled.
-			if let Some(core_index) = maybe_core_index {
-				if matches!(
-					recovery_strategy_kind,
-					RecoveryStrategyKind::BackersThenSystematicChunks |
-						RecoveryStrategyKind::SystematicChunks |
-						RecoveryStrategyKind::BackersFirstIfSizeLowerThenSystematicChunks(_)
-				) && chunk_mapping_enabled
-				{
-					let chunk_indices =
-						availability_chunk_indices(node_features, n_validators, core_index)?;
-
-					let chunk_indices: VecDeque<_> = chunk_indices
-						.iter()
-						.enumerate()
-						.map(|(v_index, c_index)| {
-							(
-								*c_index,
-								ValidatorIndex(
-									u32::try_from(v_index)
-										.expect("validator count should not exceed u32"),
-								),
-							)
-						})
-						.collect();
-
-					// Only get the validators according to the threshold.
-					let validators = chunk_indices
-						.clone()
-						.into_iter()
-						.filter(|(c_index, _)| {
-							usize::try_from(c_index.0)
-								.expect("usize is at least u32 bytes on all modern targets.") <
-								systematic_threshold
-						})
-						.collect();
-
-					recovery_strategies.push_back(Box::new(FetchSystematicChunks::new(
-						FetchSystematicChunksParams {
-							validators,
-							backers: backer_group.map(|v| v.to_vec()).unwrap_or_else(|| vec![]),
+					match (strategy, options.core_index) {
+						(AvailabilityRecoveryStrategy::FullFromBackers, _)
+							if !backers.is_empty() =>
+						{
+							recovery_strategies.push_back(Box::new(fetch_full()))
+						},
+						(AvailabilityRecoveryStrategy::SystematicChunks, Some(core_index))
+							if chunk_mapping_enabled =>
+						{
+							recovery_strategies.push_back(Box::new(systematic_chunks(core_index)?))
 						},
-					)));
+						(AvailabilityRecoveryStrategy::Chunks, _) => recovery_strategies.push_back(
+							Box::new(FetchChunks::new(FetchChunksParams { n_validators })),
+						),
+						_ => {
+							gum::debug!(
+								target: LOG_TARGET,
+								?candidate_hash,
+								?strategy,
+								"Requested recovery strategy can't be used for the candidate, skipping",
+							);
+						},
+					}
 				}
 			}
 
-			recovery_strategies.push_back(Box::new(FetchChunks::new(FetchChunksParams {
-				n_validators: session_info.validators.len(),
-			})));
-
-			let session_info = session_info.clone();
-
-			let n_validators = session_info.validators.len();
+			let validator_authority_keys = session_info.discovery_keys.clone();
 
 			launch_recovery_task(
 				state,
@@ -536,7 +614,7 @@ async fn handle_recover<Context>(
 				response_sender,
 				recovery_strategies,
 				RecoveryParams {
-					validator_authority_keys: session_info.discovery_keys.clone(),
+					validator_authority_keys,
 					n_validators,
 					threshold: recovery_threshold(n_validators)?,
 					systematic_threshold,
@@ -756,17 +834,42 @@ impl AvailabilityRecoverySubsystem {
 										&mut ctx,
 										receipt,
 										session_index,
-										maybe_backing_group,
+										RecoveryOptions {
+											backing_group: maybe_backing_group,
+											core_index: maybe_core_index,
+											..Default::default()
+										},
+										ResponseSender::Data(response_sender),
+										&metrics,
+										erasure_task_tx.clone(),
+										recovery_strategy_kind.clone(),
+										bypass_availability_store,
+										post_recovery_check.clone(),
+										req_v1_protocol_name.clone(),
+										req_v2_protocol_name.clone(),
+									).await,
+								FromOrchestra::Communication {
+									msg: AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(
+										receipt,
+										session_index,
+										options,
 										response_sender,
+									)
+								} => handle_recover(
+										&mut state,
+										&mut ctx,
+										receipt,
+										session_index,
+										options,
+										ResponseSender::Outcome(response_sender),
 										&metrics,
 										erasure_task_tx.clone(),
 										recovery_strategy_kind.clone(),
 										bypass_availability_store,
 										post_recovery_check.clone(),
-										maybe_core_index,
 										req_v1_protocol_name.clone(),
 										req_v2_protocol_name.clone(),
-									).await
+									).await,
 							}
 						},
 						Err(e) => Err(Error::SubsystemReceive(e))
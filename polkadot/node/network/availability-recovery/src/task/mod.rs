@@ -32,12 +32,18 @@ use crate::{metrics::Metrics, ErasureTask, PostRecoveryCheck, LOG_TARGET};
 
 use codec::Encode;
 use polkadot_node_primitives::AvailableData;
-use polkadot_node_subsystem::{messages::AvailabilityStoreMessage, overseer, RecoveryError};
+use polkadot_node_subsystem::{
+	messages::{
+		AvailabilityRecoveryStrategy, AvailabilityStoreMessage, RecoveryOutcome,
+		RecoveryStrategyStats,
+	},
+	overseer, RecoveryError,
+};
 use polkadot_primitives::{AuthorityDiscoveryId, CandidateHash, Hash};
 use sc_network::ProtocolName;
 
 use futures::channel::{mpsc, oneshot};
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Instant};
 
 /// Recovery parameters common to all strategies in a `RecoveryTask`.
 #[derive(Clone)]
@@ -108,36 +114,49 @@ where
 	}
 
 	async fn in_availability_store(&mut self) -> Option<AvailableData> {
-		if !self.params.bypass_availability_store {
-			let (tx, rx) = oneshot::channel();
-			self.sender
-				.send_message(AvailabilityStoreMessage::QueryAvailableData(
-					self.params.candidate_hash,
-					tx,
-				))
-				.await;
-
-			match rx.await {
-				Ok(Some(data)) => return Some(data),
-				Ok(None) => {},
-				Err(oneshot::Canceled) => {
-					gum::warn!(
-						target: LOG_TARGET,
-						candidate_hash = ?self.params.candidate_hash,
-						"Failed to reach the availability store",
-					)
-				},
-			}
+		let (tx, rx) = oneshot::channel();
+		self.sender
+			.send_message(AvailabilityStoreMessage::QueryAvailableData(
+				self.params.candidate_hash,
+				tx,
+			))
+			.await;
+
+		match rx.await {
+			Ok(maybe_data) => maybe_data,
+			Err(oneshot::Canceled) => {
+				gum::warn!(
+					target: LOG_TARGET,
+					candidate_hash = ?self.params.candidate_hash,
+					"Failed to reach the availability store",
+				);
+				None
+			},
 		}
-
-		None
 	}
 
 	/// Run this recovery task to completion. It will loop through the configured strategies
-	/// in-order and return whenever the first one recovers the full `AvailableData`.
-	pub async fn run(mut self) -> Result<AvailableData, RecoveryError> {
-		if let Some(data) = self.in_availability_store().await {
-			return Ok(data);
+	/// in-order and return whenever the first one recovers the full `AvailableData`, along with
+	/// the strategy that recovered it and the time spent in each strategy.
+	pub async fn run(mut self) -> Result<RecoveryOutcome, RecoveryError> {
+		let mut stats = Vec::with_capacity(self.strategies.len() + 1);
+
+		if !self.params.bypass_availability_store {
+			let started = Instant::now();
+			let maybe_data = self.in_availability_store().await;
+			stats.push(RecoveryStrategyStats {
+				strategy: AvailabilityRecoveryStrategy::AvailabilityStore,
+				duration: started.elapsed(),
+				succeeded: maybe_data.is_some(),
+			});
+
+			if let Some(available_data) = maybe_data {
+				return Ok(RecoveryOutcome {
+					available_data,
+					strategy: AvailabilityRecoveryStrategy::AvailabilityStore,
+					stats,
+				});
+			}
 		}
 
 		self.params.metrics.on_recovery_started();
@@ -147,6 +166,7 @@ where
 		while let Some(current_strategy) = self.strategies.pop_front() {
 			let display_name = current_strategy.display_name();
 			let strategy_type = current_strategy.strategy_type();
+			let kind = current_strategy.kind();
 
 			gum::debug!(
 				target: LOG_TARGET,
@@ -155,7 +175,13 @@ where
 				display_name
 			);
 
+			let started = Instant::now();
 			let res = current_strategy.run(&mut self.state, &mut self.sender, &self.params).await;
+			stats.push(RecoveryStrategyStats {
+				strategy: kind,
+				duration: started.elapsed(),
+				succeeded: res.is_ok(),
+			});
 
 			match res {
 				Err(RecoveryError::Unavailable) => {
@@ -178,9 +204,11 @@ where
 					}
 					return Err(err);
 				},
-				Ok(data) => {
-					self.params.metrics.on_recovery_succeeded(strategy_type, data.encoded_size());
-					return Ok(data);
+				Ok(available_data) => {
+					self.params
+						.metrics
+						.on_recovery_succeeded(strategy_type, available_data.encoded_size());
+					return Ok(RecoveryOutcome { available_data, strategy: kind, stats });
 				},
 			}
 		}
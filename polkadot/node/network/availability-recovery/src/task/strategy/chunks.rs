@@ -27,7 +27,7 @@ use crate::{
 };
 
 use polkadot_node_primitives::AvailableData;
-use polkadot_node_subsystem::{overseer, RecoveryError};
+use polkadot_node_subsystem::{messages::AvailabilityRecoveryStrategy, overseer, RecoveryError};
 use polkadot_primitives::ValidatorIndex;
 
 use futures::{channel::oneshot, SinkExt};
@@ -174,6 +174,10 @@ impl<Sender: overseer::AvailabilityRecoverySenderTrait> RecoveryStrategy<Sender>
 		"regular_chunks"
 	}
 
+	fn kind(&self) -> AvailabilityRecoveryStrategy {
+		AvailabilityRecoveryStrategy::Chunks
+	}
+
 	async fn run(
 		mut self: Box<Self>,
 		state: &mut State,
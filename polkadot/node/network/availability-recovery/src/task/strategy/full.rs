@@ -23,7 +23,10 @@ use polkadot_node_network_protocol::request_response::{
 	self as req_res, outgoing::RequestError, OutgoingRequest, Recipient, Requests,
 };
 use polkadot_node_primitives::AvailableData;
-use polkadot_node_subsystem::{messages::NetworkBridgeTxMessage, overseer, RecoveryError};
+use polkadot_node_subsystem::{
+	messages::{AvailabilityRecoveryStrategy, NetworkBridgeTxMessage},
+	overseer, RecoveryError,
+};
 use polkadot_primitives::ValidatorIndex;
 use sc_network::{IfDisconnected, OutboundFailure, RequestFailure};
 
@@ -34,6 +37,8 @@ use rand::seq::SliceRandom;
 pub struct FetchFullParams {
 	/// Validators that will be used for fetching the data.
 	pub validators: Vec<ValidatorIndex>,
+	/// Validators to try first, in order, before the (shuffled) `validators`.
+	pub preferred_validators: Vec<ValidatorIndex>,
 }
 
 /// `RecoveryStrategy` that sequentially tries to fetch the full `AvailableData` from
@@ -45,7 +50,11 @@ pub struct FetchFull {
 impl FetchFull {
 	/// Create a new `FetchFull` recovery strategy.
 	pub fn new(mut params: FetchFullParams) -> Self {
+		let preferred = std::mem::take(&mut params.preferred_validators);
+		params.validators.retain(|v| !preferred.contains(v));
 		params.validators.shuffle(&mut rand::thread_rng());
+		// Validators are popped from the back.
+		params.validators.extend(preferred.into_iter().rev());
 		Self { params }
 	}
 }
@@ -60,6 +69,10 @@ impl<Sender: overseer::AvailabilityRecoverySenderTrait> RecoveryStrategy<Sender>
 		"full_from_backers"
 	}
 
+	fn kind(&self) -> AvailabilityRecoveryStrategy {
+		AvailabilityRecoveryStrategy::FullFromBackers
+	}
+
 	async fn run(
 		mut self: Box<Self>,
 		_: &mut State,
@@ -39,7 +39,7 @@ use polkadot_node_network_protocol::request_response::{
 };
 use polkadot_node_primitives::{AvailableData, ErasureChunk};
 use polkadot_node_subsystem::{
-	messages::{AvailabilityStoreMessage, NetworkBridgeTxMessage},
+	messages::{AvailabilityRecoveryStrategy, AvailabilityStoreMessage, NetworkBridgeTxMessage},
 	overseer, RecoveryError,
 };
 use polkadot_primitives::{AuthorityDiscoveryId, BlakeTwo256, ChunkIndex, HashT, ValidatorIndex};
@@ -182,6 +182,9 @@ pub trait RecoveryStrategy<Sender: overseer::AvailabilityRecoverySenderTrait>: S
 
 	/// Return the strategy type for use as a metric label.
 	fn strategy_type(&self) -> &'static str;
+
+	/// Return the strategy as reported to the requester.
+	fn kind(&self) -> AvailabilityRecoveryStrategy;
 }
 
 /// Utility type used for recording the result of requesting a chunk from a validator.
@@ -1371,6 +1374,10 @@ mod tests {
 				"good_strategy"
 			}
 
+			fn kind(&self) -> AvailabilityRecoveryStrategy {
+				AvailabilityRecoveryStrategy::Chunks
+			}
+
 			async fn run(
 				mut self: Box<Self>,
 				_state: &mut State,
@@ -1394,6 +1401,10 @@ mod tests {
 				"unavailable_strategy"
 			}
 
+			fn kind(&self) -> AvailabilityRecoveryStrategy {
+				AvailabilityRecoveryStrategy::FullFromBackers
+			}
+
 			async fn run(
 				mut self: Box<Self>,
 				_state: &mut State,
@@ -1417,6 +1428,10 @@ mod tests {
 				"invalid_strategy"
 			}
 
+			fn kind(&self) -> AvailabilityRecoveryStrategy {
+				AvailabilityRecoveryStrategy::SystematicChunks
+			}
+
 			async fn run(
 				mut self: Box<Self>,
 				_state: &mut State,
@@ -1464,7 +1479,11 @@ mod tests {
 				|sender| async move {
 					let task = RecoveryTask::new(sender, params, strategies);
 
-					assert_eq!(task.run().await.unwrap(), dummy_available_data());
+					let outcome = task.run().await.unwrap();
+					assert_eq!(outcome.available_data, dummy_available_data());
+					assert_eq!(outcome.strategy, AvailabilityRecoveryStrategy::AvailabilityStore);
+					assert_eq!(outcome.stats.len(), 1);
+					assert!(outcome.stats[0].succeeded);
 				},
 			);
 		}
@@ -1512,7 +1531,43 @@ mod tests {
 				|sender| async move {
 					let task = RecoveryTask::new(sender, params, strategies);
 
-					assert_eq!(task.run().await.unwrap(), dummy_available_data());
+					assert_eq!(task.run().await.unwrap().available_data, dummy_available_data());
+				},
+			);
+		}
+
+		// The outcome reports the strategy which recovered the data and all strategies tried.
+		{
+			let mut params = params.clone();
+			params.bypass_availability_store = true;
+			let mut strategies: VecDeque<Box<dyn RecoveryStrategy<TestSubsystemSender>>> =
+				VecDeque::new();
+			strategies.push_back(Box::new(UnavailableStrategy));
+			strategies.push_back(Box::new(GoodStrategy));
+			strategies.push_back(Box::new(InvalidStrategy));
+
+			test_harness(
+				|mut receiver: UnboundedReceiver<AllMessages>| async move {
+					// Shouldn't send any requests.
+					assert!(receiver.next().timeout(TIMEOUT).await.unwrap().is_none());
+				},
+				|sender| async move {
+					let task = RecoveryTask::new(sender, params, strategies);
+
+					let outcome = task.run().await.unwrap();
+					assert_eq!(outcome.available_data, dummy_available_data());
+					assert_eq!(outcome.strategy, AvailabilityRecoveryStrategy::Chunks);
+					assert_eq!(
+						outcome
+							.stats
+							.iter()
+							.map(|stats| (stats.strategy, stats.succeeded))
+							.collect::<Vec<_>>(),
+						vec![
+							(AvailabilityRecoveryStrategy::FullFromBackers, false),
+							(AvailabilityRecoveryStrategy::Chunks, true),
+						]
+					);
 				},
 			);
 		}
@@ -1540,7 +1595,7 @@ mod tests {
 				|sender| async move {
 					let task = RecoveryTask::new(sender, params, strategies);
 
-					assert_eq!(task.run().await.unwrap(), dummy_available_data());
+					assert_eq!(task.run().await.unwrap().available_data, dummy_available_data());
 				},
 			);
 		}
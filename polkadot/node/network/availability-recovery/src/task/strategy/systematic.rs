@@ -27,7 +27,7 @@ use crate::{
 };
 
 use polkadot_node_primitives::AvailableData;
-use polkadot_node_subsystem::{overseer, RecoveryError};
+use polkadot_node_subsystem::{messages::AvailabilityRecoveryStrategy, overseer, RecoveryError};
 use polkadot_primitives::{ChunkIndex, ValidatorIndex};
 
 use std::collections::VecDeque;
@@ -167,6 +167,10 @@ impl<Sender: overseer::AvailabilityRecoverySenderTrait> RecoveryStrategy<Sender>
 		"systematic_chunks"
 	}
 
+	fn kind(&self) -> AvailabilityRecoveryStrategy {
+		AvailabilityRecoveryStrategy::SystematicChunks
+	}
+
 	async fn run(
 		mut self: Box<Self>,
 		state: &mut State,
@@ -41,8 +41,9 @@ use polkadot_node_subsystem_test_helpers::{
 };
 use polkadot_node_subsystem_util::TimeoutExt;
 use polkadot_primitives::{
-	node_features, AuthorityDiscoveryId, Block, ExecutorParams, Hash, HeadData, IndexedVec,
-	MutateDescriptorV2, NodeFeatures, PersistedValidationData, SessionInfo, ValidatorId,
+	node_features, AuthorityDiscoveryId, Block, CoreIndex, ExecutorParams, GroupIndex, Hash,
+	HeadData, IndexedVec, MutateDescriptorV2, NodeFeatures, PersistedValidationData, SessionInfo,
+	ValidatorId,
 };
 use polkadot_primitives_test_helpers::{dummy_candidate_receipt, dummy_hash};
 use sc_network::{IfDisconnected, OutboundFailure, ProtocolName, RequestFailure};
@@ -1392,6 +1393,275 @@ fn fast_path_backing_group_recovers() {
 	});
 }
 
+#[test]
+fn requested_strategies_are_used_and_reported() {
+	let test_state = TestState::default();
+	let req_protocol_names = ReqProtocolNames::new(&GENESIS_HASH, None);
+	let subsystem = with_fast_path(
+		request_receiver(&req_protocol_names),
+		&req_protocol_names,
+		Metrics::new_dummy(),
+	);
+
+	test_harness(subsystem, |mut virtual_overseer| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(new_leaf(
+				test_state.current,
+				1,
+			))),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		// The backing group is supplied, but only regular chunks are requested.
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				RecoveryOptions {
+					backing_group: Some(GroupIndex(0)),
+					core_index: Some(test_state.core_index),
+					strategies: vec![AvailabilityRecoveryStrategy::Chunks],
+					..Default::default()
+				},
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api_session_info(&mut virtual_overseer).await;
+		test_state.test_runtime_api_node_features(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		test_state
+			.test_chunk_requests(
+				&req_protocol_names,
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.threshold(),
+				|_| Has::Yes,
+				false,
+			)
+			.await;
+
+		let outcome = rx.await.unwrap().unwrap();
+		assert_eq!(outcome.available_data, test_state.available_data);
+		assert_eq!(outcome.strategy, AvailabilityRecoveryStrategy::Chunks);
+		assert_eq!(
+			outcome
+				.stats
+				.iter()
+				.map(|stats| (stats.strategy, stats.succeeded))
+				.collect::<Vec<_>>(),
+			vec![
+				(AvailabilityRecoveryStrategy::AvailabilityStore, false),
+				(AvailabilityRecoveryStrategy::Chunks, true),
+			]
+		);
+
+		// The cached result reports the strategy which recovered the data.
+		let (tx, rx) = oneshot::channel();
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				RecoveryOptions::default(),
+				tx,
+			),
+		)
+		.await;
+
+		let outcome = rx.await.unwrap().unwrap();
+		assert_eq!(outcome.available_data, test_state.available_data);
+		assert_eq!(outcome.strategy, AvailabilityRecoveryStrategy::Chunks);
+		assert!(outcome.stats.is_empty());
+
+		virtual_overseer
+	});
+}
+
+#[test]
+fn preferred_validators_are_requested_first() {
+	let test_state = TestState::default();
+	let req_protocol_names = ReqProtocolNames::new(&GENESIS_HASH, None);
+	let subsystem = with_chunks_only(
+		request_receiver(&req_protocol_names),
+		&req_protocol_names,
+		Metrics::new_dummy(),
+	);
+
+	test_harness(subsystem, |mut virtual_overseer| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(new_leaf(
+				test_state.current,
+				1,
+			))),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		// Validator 5 is not part of the backing group.
+		let preferred = ValidatorIndex(5);
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				RecoveryOptions {
+					backing_group: Some(GroupIndex(0)),
+					preferred_validators: vec![preferred],
+					core_index: Some(test_state.core_index),
+					strategies: vec![
+						AvailabilityRecoveryStrategy::FullFromBackers,
+						AvailabilityRecoveryStrategy::Chunks,
+					],
+				},
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api_session_info(&mut virtual_overseer).await;
+		test_state.test_runtime_api_node_features(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::NetworkBridgeTx(
+				NetworkBridgeTxMessage::SendRequests(mut requests, IfDisconnected::ImmediateError)
+			) => {
+				assert_eq!(requests.len(), 1);
+
+				assert_matches!(
+					requests.pop().unwrap(),
+					Requests::AvailableDataFetchingV1(req) => {
+						assert_eq!(req.payload.candidate_hash, candidate_hash);
+						assert_eq!(
+							req.peer,
+							Recipient::Authority(
+								test_state.validator_authority_id[preferred.0 as usize].clone()
+							)
+						);
+
+						let _ = req.pending_response.send(Ok((
+							req_res::v1::AvailableDataFetchingResponse::from(Some(
+								test_state.available_data.clone(),
+							))
+							.encode(),
+							req_protocol_names.get_name(Protocol::AvailableDataFetchingV1),
+						)));
+					}
+				);
+			}
+		);
+
+		let outcome = rx.await.unwrap().unwrap();
+		assert_eq!(outcome.available_data, test_state.available_data);
+		assert_eq!(outcome.strategy, AvailabilityRecoveryStrategy::FullFromBackers);
+
+		virtual_overseer
+	});
+}
+
+#[test]
+fn out_of_range_preferred_validators_are_ignored() {
+	let test_state = TestState::default();
+	let req_protocol_names = ReqProtocolNames::new(&GENESIS_HASH, None);
+	let subsystem = with_chunks_only(
+		request_receiver(&req_protocol_names),
+		&req_protocol_names,
+		Metrics::new_dummy(),
+	);
+
+	test_harness(subsystem, |mut virtual_overseer| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(new_leaf(
+				test_state.current,
+				1,
+			))),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		let out_of_range = ValidatorIndex(test_state.validators.len() as u32);
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				RecoveryOptions {
+					backing_group: Some(GroupIndex(0)),
+					preferred_validators: vec![out_of_range],
+					core_index: Some(test_state.core_index),
+					strategies: vec![AvailabilityRecoveryStrategy::FullFromBackers],
+				},
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api_session_info(&mut virtual_overseer).await;
+		test_state.test_runtime_api_node_features(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+		let backers = test_state.validator_groups.get(GroupIndex(0)).unwrap();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+
+		// The data is requested from a backer instead.
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::NetworkBridgeTx(
+				NetworkBridgeTxMessage::SendRequests(mut requests, IfDisconnected::ImmediateError)
+			) => {
+				assert_eq!(requests.len(), 1);
+
+				assert_matches!(
+					requests.pop().unwrap(),
+					Requests::AvailableDataFetchingV1(req) => {
+						assert_eq!(req.payload.candidate_hash, candidate_hash);
+						assert!(backers.iter().any(|backer| {
+							req.peer ==
+								Recipient::Authority(
+									test_state.validator_authority_id[backer.0 as usize].clone(),
+								)
+						}));
+
+						let _ = req.pending_response.send(Ok((
+							req_res::v1::AvailableDataFetchingResponse::from(Some(
+								test_state.available_data.clone(),
+							))
+							.encode(),
+							req_protocol_names.get_name(Protocol::AvailableDataFetchingV1),
+						)));
+					}
+				);
+			}
+		);
+
+		let outcome = rx.await.unwrap().unwrap();
+		assert_eq!(outcome.available_data, test_state.available_data);
+		assert_eq!(outcome.strategy, AvailabilityRecoveryStrategy::FullFromBackers);
+
+		virtual_overseer
+	});
+}
+
 #[rstest]
 #[case(true, false)]
 #[case(false, true)]
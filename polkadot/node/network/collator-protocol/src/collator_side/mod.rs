@@ -21,7 +21,11 @@ use std::{
 
 use bitvec::{bitvec, vec::BitVec};
 use futures::{
-	channel::oneshot, future::Fuse, pin_mut, select, stream::FuturesUnordered, FutureExt, StreamExt,
+	channel::{mpsc, oneshot},
+	future::Fuse,
+	pin_mut, select,
+	stream::FuturesUnordered,
+	FutureExt, StreamExt,
 };
 use metrics::{CollationStats, CollationTracker};
 use schnellru::{ByLength, LruMap};
@@ -40,7 +44,8 @@ use polkadot_node_network_protocol::{
 use polkadot_node_primitives::{CollationSecondedSignal, PoV, Statement};
 use polkadot_node_subsystem::{
 	messages::{
-		ChainApiMessage, CollatorProtocolMessage, NetworkBridgeEvent, NetworkBridgeTxMessage,
+		ChainApiMessage, CollationEvent, CollatorProtocolMessage, NetworkBridgeEvent,
+		NetworkBridgeTxMessage,
 	},
 	overseer, FromOrchestra, OverseerSignal,
 };
@@ -289,6 +294,15 @@ struct PerSchedulingParent {
 	validator_group: HashMap<CoreIndex, ValidatorGroup>,
 	/// Distributed collations.
 	collations: HashMap<CandidateHash, CollationData>,
+	/// The order in which distributed collations are advertised and served.
+	///
+	/// Collations are appended as they are distributed and can be reordered through
+	/// [`CollatorProtocolMessage::ReorderCollations`].
+	advertisement_order: Vec<CandidateHash>,
+	/// Whether the advertisement order was explicitly set through
+	/// [`CollatorProtocolMessage::ReorderCollations`]. If so, queued fetch requests are served
+	/// in that order instead of the order they were received in.
+	reordered: bool,
 	/// Number of assignments per core
 	assignments: HashMap<CoreIndex, usize>,
 	/// The relay parent block number
@@ -341,12 +355,60 @@ impl PerSchedulingParent {
 		Ok(Self {
 			validator_group: validator_groups,
 			collations: HashMap::new(),
+			advertisement_order: Vec::new(),
+			reordered: false,
 			assignments,
 			block_number,
 			session_index,
 			v3_enabled,
 		})
 	}
+
+	/// Returns the position of the given candidate in the advertisement order.
+	///
+	/// Unknown candidates are ranked last.
+	fn advertisement_rank(&self, candidate_hash: &CandidateHash) -> usize {
+		self.advertisement_order
+			.iter()
+			.position(|hash| hash == candidate_hash)
+			.unwrap_or(self.advertisement_order.len())
+	}
+
+	/// Reorders the collations for the given core.
+	///
+	/// Collations in `order` come first, followed by the remaining collations of the core in their
+	/// previous order. Collations of other cores keep their positions.
+	fn reorder_collations(&mut self, core_index: CoreIndex, order: &[CandidateHash]) {
+		let slots = self
+			.advertisement_order
+			.iter()
+			.enumerate()
+			.filter(|(_, hash)| {
+				self.collations.get(*hash).is_some_and(|c| c.core_index() == &core_index)
+			})
+			.map(|(slot, _)| slot)
+			.collect::<Vec<_>>();
+
+		let mut reordered = Vec::with_capacity(slots.len());
+		for hash in order {
+			if slots.iter().any(|slot| &self.advertisement_order[*slot] == hash) &&
+				!reordered.contains(hash)
+			{
+				reordered.push(*hash);
+			}
+		}
+		for slot in &slots {
+			let hash = self.advertisement_order[*slot];
+			if !reordered.contains(&hash) {
+				reordered.push(hash);
+			}
+		}
+
+		for (slot, hash) in slots.into_iter().zip(reordered) {
+			self.advertisement_order[slot] = hash;
+		}
+		self.reordered = true;
+	}
 }
 
 struct State {
@@ -414,6 +476,9 @@ struct State {
 
 	/// Should we be connected to backers ?
 	connect_to_backers: bool,
+
+	/// Subscribers to [`CollationEvent`]s.
+	event_subscribers: Vec<mpsc::Sender<CollationEvent>>,
 }
 
 impl State {
@@ -442,10 +507,28 @@ impl State {
 			reputation,
 			collation_tracker: Default::default(),
 			connect_to_backers: false,
+			event_subscribers: Vec::new(),
 		}
 	}
 }
 
+/// Notifies all subscribers about the given collation event.
+///
+/// Subscribers that went away are removed. Subscribers that are not keeping up miss the event.
+fn notify_collation_event(
+	subscribers: &mut Vec<mpsc::Sender<CollationEvent>>,
+	event: CollationEvent,
+) {
+	subscribers.retain_mut(|sender| match sender.try_send(event.clone()) {
+		Ok(()) => true,
+		Err(err) if err.is_full() => {
+			gum::debug!(target: LOG_TARGET, ?event, "Collation event subscriber is lagging behind");
+			true
+		},
+		Err(_) => false,
+	});
+}
+
 /// Distribute a collation.
 ///
 /// Figure out the core our para is assigned to and the relevant validators.
@@ -604,6 +687,7 @@ async fn distribute_collation<Context>(
 
 	let para_head = receipt.descriptor.para_head();
 	let pov_hash = pov.hash();
+	per_scheduling_parent.advertisement_order.push(candidate_hash);
 	per_scheduling_parent.collations.insert(
 		candidate_hash,
 		CollationData {
@@ -906,7 +990,11 @@ async fn advertise_collation<Context>(
 	advertisement_timeouts: &mut FuturesUnordered<ResetInterestTimeout>,
 	metrics: &Metrics,
 ) {
-	for (candidate_hash, collation_and_core) in per_scheduling_parent.collations.iter_mut() {
+	for candidate_hash in &per_scheduling_parent.advertisement_order {
+		let Some(collation_and_core) = per_scheduling_parent.collations.get_mut(candidate_hash)
+		else {
+			continue;
+		};
 		let core_index = *collation_and_core.core_index();
 		let collation = collation_and_core.collation_mut();
 
@@ -1106,11 +1194,104 @@ async fn process_msg<Context>(
 				msg,
 			);
 		},
+		ReorderCollations { scheduling_parent, core_index, order } => {
+			reorder_collations(state, scheduling_parent, core_index, order);
+		},
+		CancelCollation { scheduling_parent, candidate_hash } => {
+			cancel_collation(state, scheduling_parent, candidate_hash);
+		},
+		SubscribeCollationEvents(sender) => {
+			state.event_subscribers.push(sender);
+		},
 	}
 
 	Ok(())
 }
 
+/// Applies a new advertisement order to the collations of the given core and re-sorts the
+/// queued fetch requests accordingly.
+fn reorder_collations(
+	state: &mut State,
+	scheduling_parent: Hash,
+	core_index: CoreIndex,
+	order: Vec<CandidateHash>,
+) {
+	let Some(per_scheduling_parent) = state.per_scheduling_parent.get_mut(&scheduling_parent)
+	else {
+		gum::debug!(
+			target: LOG_TARGET,
+			?scheduling_parent,
+			"Received `ReorderCollations` for a scheduling parent out of our view",
+		);
+		return;
+	};
+
+	per_scheduling_parent.reorder_collations(core_index, &order);
+
+	gum::debug!(
+		target: LOG_TARGET,
+		?scheduling_parent,
+		?core_index,
+		order = ?per_scheduling_parent.advertisement_order,
+		"Reordered collations",
+	);
+
+	if let Some(waiting) = state.waiting_collation_fetches.get_mut(&scheduling_parent) {
+		waiting
+			.req_queue
+			.make_contiguous()
+			.sort_by_key(|req| per_scheduling_parent.advertisement_rank(&req.candidate_hash()));
+	}
+}
+
+/// Withdraws a distributed collation.
+///
+/// Queued fetch requests for the collation are dropped, an upload in progress is not interrupted.
+fn cancel_collation(state: &mut State, scheduling_parent: Hash, candidate_hash: CandidateHash) {
+	let Some(per_scheduling_parent) = state.per_scheduling_parent.get_mut(&scheduling_parent)
+	else {
+		gum::debug!(
+			target: LOG_TARGET,
+			?scheduling_parent,
+			?candidate_hash,
+			"Received `CancelCollation` for a scheduling parent out of our view",
+		);
+		return;
+	};
+
+	let Some(collation_with_core) = per_scheduling_parent.collations.remove(&candidate_hash) else {
+		gum::debug!(
+			target: LOG_TARGET,
+			?scheduling_parent,
+			?candidate_hash,
+			"Received `CancelCollation` for an unknown collation",
+		);
+		return;
+	};
+
+	per_scheduling_parent.advertisement_order.retain(|hash| hash != &candidate_hash);
+	if let Some(validator_group) =
+		per_scheduling_parent.validator_group.get_mut(collation_with_core.core_index())
+	{
+		validator_group.advertised_to.remove(&candidate_hash);
+	}
+	state.collation_result_senders.remove(&candidate_hash);
+
+	if let Some(waiting) = state.waiting_collation_fetches.get_mut(&scheduling_parent) {
+		waiting.req_queue.retain(|req| req.candidate_hash() != candidate_hash);
+		waiting.waiting_peers.retain(|(_, hash)| hash != &candidate_hash);
+	}
+
+	gum::debug!(
+		target: LOG_TARGET,
+		?scheduling_parent,
+		?candidate_hash,
+		core_index = ?collation_with_core.core_index(),
+		status = ?collation_with_core.collation().status,
+		"Cancelled collation",
+	);
+}
+
 /// Issue a response to a previously requested collation.
 async fn send_collation(
 	state: &mut State,
@@ -1319,14 +1500,16 @@ async fn handle_incoming_request<Context>(
 					per_scheduling_parent.collations.get_mut(&req.payload.candidate_hash)
 				},
 			};
-			let (receipt, pov, parent_head_data) =
+			let (receipt, pov, parent_head_data, core_index) =
 				if let Some(collation_with_core) = collation_with_core {
+					let core_index = *collation_with_core.core_index();
 					let collation = collation_with_core.collation_mut();
 					collation.status.advance_to_requested();
 					(
 						collation.receipt.clone(),
 						collation.pov.clone(),
 						collation.parent_head_data.clone(),
+						core_index,
 					)
 				} else {
 					gum::warn!(
@@ -1358,8 +1541,33 @@ async fn handle_incoming_request<Context>(
 				return Ok(());
 			}
 
+			notify_collation_event(
+				&mut state.event_subscribers,
+				CollationEvent::Requested {
+					scheduling_parent,
+					candidate_hash,
+					core_index,
+					peer_id,
+				},
+			);
+
 			if waiting.collation_fetch_active {
-				waiting.req_queue.push_back(req);
+				if per_scheduling_parent.reordered {
+					// Serve requests in the order set by the collation producer, requests for the
+					// same collation stay in the order they were received in.
+					let rank = per_scheduling_parent.advertisement_rank(&candidate_hash);
+					let position = waiting
+						.req_queue
+						.iter()
+						.position(|queued| {
+							per_scheduling_parent.advertisement_rank(&queued.candidate_hash()) >
+								rank
+						})
+						.unwrap_or(waiting.req_queue.len());
+					waiting.req_queue.insert(position, req);
+				} else {
+					waiting.req_queue.push_back(req);
+				}
 			} else {
 				waiting.collation_fetch_active = true;
 				// Obtain a timer for sending collation
@@ -2101,6 +2309,16 @@ async fn run_inner<Context>(
 						// Update collation status to fetched.
 						if let Some(per_relay_parent) =  state.per_scheduling_parent.get_mut(&relay_parent) {
 							if let Some(collation_with_core) = per_relay_parent.collations.get_mut(&candidate_hash) {
+								notify_collation_event(
+									&mut state.event_subscribers,
+									CollationEvent::Fetched {
+										scheduling_parent: relay_parent,
+										candidate_hash,
+										core_index: *collation_with_core.core_index(),
+										peer_id,
+									},
+								);
+
 								let maybe_stats = collation_with_core.take_stats();
 								let our_para_id = collation_with_core.collation().receipt.descriptor.para_id();
 
@@ -2314,3 +2314,273 @@ fn no_duplicate_advertisement_on_authority_id_update() {
 		},
 	)
 }
+
+/// Builds `count` distinct candidates on the given scheduling parent and core.
+fn make_candidates(
+	test_state: &TestState,
+	scheduling_parent: Hash,
+	core_index: CoreIndex,
+	count: u8,
+) -> Vec<(CandidateReceipt, PoV)> {
+	(0..count)
+		.map(|i| {
+			let pov = PoV { block_data: BlockData(vec![i]) };
+			let candidate = TestCandidateBuilder {
+				para_id: test_state.para_id,
+				relay_parent: scheduling_parent,
+				pov_hash: pov.hash(),
+				core_index,
+				..Default::default()
+			}
+			.build();
+			(candidate, pov)
+		})
+		.collect()
+}
+
+/// Sends a collation fetching request for the given candidate and returns the response receiver.
+async fn request_collation(
+	req_v2_cfg: &mut sc_network::config::RequestResponseConfig,
+	test_state: &TestState,
+	peer: PeerId,
+	candidate_hash: CandidateHash,
+) -> oneshot::Receiver<sc_network::config::OutgoingResponse> {
+	let (pending_response, rx) = oneshot::channel();
+	req_v2_cfg
+		.inbound_queue
+		.as_mut()
+		.unwrap()
+		.send(RawIncomingRequest {
+			peer,
+			payload: CollationFetchingRequest {
+				scheduling_parent: test_state.scheduling_parent,
+				candidate_hash,
+				para_id: test_state.para_id,
+			}
+			.encode(),
+			pending_response,
+		})
+		.await
+		.unwrap();
+	rx
+}
+
+#[test]
+fn reordered_collations_are_advertised_in_order() {
+	let test_state = TestState::default();
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		|test_harness| async move {
+			let mut virtual_overseer = test_harness.virtual_overseer;
+			let req_v2_cfg = test_harness.req_v2_cfg;
+
+			overseer_send(&mut virtual_overseer, CollatorProtocolMessage::ConnectToBackingGroups)
+				.await;
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::CollateOn(test_state.para_id),
+			)
+			.await;
+			update_view(
+				Some(test_state.current_group_validator_authority_ids()),
+				&test_state,
+				&mut virtual_overseer,
+				vec![(test_state.scheduling_parent, 10)],
+				1,
+			)
+			.await;
+
+			let candidates =
+				make_candidates(&test_state, test_state.scheduling_parent, CoreIndex(0), 3);
+			for (candidate, pov) in &candidates {
+				distribute_collation_with_receipt(
+					&mut virtual_overseer,
+					test_state.current_group_validator_authority_ids(),
+					candidate.clone(),
+					pov.clone(),
+					Hash::zero(),
+				)
+				.await;
+			}
+			let hashes: Vec<_> = candidates.iter().map(|(candidate, _)| candidate.hash()).collect();
+
+			// Unknown hashes and duplicates are ignored, the unlisted collation goes last.
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::ReorderCollations {
+					scheduling_parent: test_state.scheduling_parent,
+					core_index: CoreIndex(0),
+					order: vec![hashes[2], CandidateHash(Hash::random()), hashes[0], hashes[2]],
+				},
+			)
+			.await;
+
+			let peer = test_state.current_group_validator_peer_ids()[0];
+			let validator_id = test_state.current_group_validator_authority_ids()[0].clone();
+			connect_peer(&mut virtual_overseer, peer, CollationVersion::V2, Some(validator_id))
+				.await;
+			expect_declare_msg(&mut virtual_overseer, &test_state, &peer).await;
+
+			send_peer_view_change(&mut virtual_overseer, &peer, vec![test_state.scheduling_parent])
+				.await;
+
+			for hash in [hashes[2], hashes[0], hashes[1]] {
+				expect_advertise_collation_msg(
+					&mut virtual_overseer,
+					&[peer],
+					test_state.scheduling_parent,
+					vec![hash],
+				)
+				.await;
+			}
+
+			TestHarness { virtual_overseer, req_v2_cfg }
+		},
+	)
+}
+
+#[test]
+fn cancelled_collation_is_withdrawn() {
+	let test_state = TestState::default();
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		|test_harness| async move {
+			let mut virtual_overseer = test_harness.virtual_overseer;
+			let mut req_v2_cfg = test_harness.req_v2_cfg;
+
+			let (events_tx, mut events_rx) = mpsc::channel(16);
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::SubscribeCollationEvents(events_tx),
+			)
+			.await;
+
+			overseer_send(&mut virtual_overseer, CollatorProtocolMessage::ConnectToBackingGroups)
+				.await;
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::CollateOn(test_state.para_id),
+			)
+			.await;
+			update_view(
+				Some(test_state.current_group_validator_authority_ids()),
+				&test_state,
+				&mut virtual_overseer,
+				vec![(test_state.scheduling_parent, 10)],
+				1,
+			)
+			.await;
+
+			// Fill all the slots of the core.
+			let mut candidates = make_candidates(
+				&test_state,
+				test_state.scheduling_parent,
+				CoreIndex(0),
+				SCHEDULING_LOOKAHEAD as u8 + 1,
+			);
+			let (extra_candidate, extra_pov) = candidates.pop().unwrap();
+			for (candidate, pov) in &candidates {
+				distribute_collation_with_receipt(
+					&mut virtual_overseer,
+					test_state.current_group_validator_authority_ids(),
+					candidate.clone(),
+					pov.clone(),
+					Hash::zero(),
+				)
+				.await;
+			}
+
+			let cancelled = candidates[0].0.hash();
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::CancelCollation {
+					scheduling_parent: test_state.scheduling_parent,
+					candidate_hash: cancelled,
+				},
+			)
+			.await;
+
+			// The cancelled collation freed up a slot.
+			distribute_collation_with_receipt(
+				&mut virtual_overseer,
+				test_state.current_group_validator_authority_ids(),
+				extra_candidate.clone(),
+				extra_pov.clone(),
+				Hash::zero(),
+			)
+			.await;
+
+			let peer = test_state.current_group_validator_peer_ids()[0];
+			let validator_id = test_state.current_group_validator_authority_ids()[0].clone();
+			connect_peer(&mut virtual_overseer, peer, CollationVersion::V2, Some(validator_id))
+				.await;
+			expect_declare_msg(&mut virtual_overseer, &test_state, &peer).await;
+
+			send_peer_view_change(&mut virtual_overseer, &peer, vec![test_state.scheduling_parent])
+				.await;
+
+			// The cancelled collation is not advertised anymore.
+			let advertised: Vec<_> = candidates[1..]
+				.iter()
+				.map(|(candidate, _)| candidate.hash())
+				.chain(std::iter::once(extra_candidate.hash()))
+				.collect();
+			expect_advertise_collation_msg(
+				&mut virtual_overseer,
+				&[peer],
+				test_state.scheduling_parent,
+				advertised,
+			)
+			.await;
+
+			// Nor served.
+			let rx = request_collation(&mut req_v2_cfg, &test_state, peer, cancelled).await;
+			assert_matches!(rx.await, Err(_));
+
+			let rx =
+				request_collation(&mut req_v2_cfg, &test_state, peer, extra_candidate.hash()).await;
+			assert_matches!(
+				rx.await,
+				Ok(full_response) => {
+					let (receipt, pov) = decode_collation_response(
+						full_response.result
+						.expect("We should have a proper answer").as_ref()
+					);
+					assert_eq!(receipt, extra_candidate);
+					assert_eq!(pov, extra_pov);
+				}
+			);
+
+			assert_eq!(
+				events_rx.next().timeout(TIMEOUT).await.flatten(),
+				Some(CollationEvent::Requested {
+					scheduling_parent: test_state.scheduling_parent,
+					candidate_hash: extra_candidate.hash(),
+					core_index: CoreIndex(0),
+					peer_id: peer,
+				}),
+			);
+			assert_eq!(
+				events_rx.next().timeout(TIMEOUT).await.flatten(),
+				Some(CollationEvent::Fetched {
+					scheduling_parent: test_state.scheduling_parent,
+					candidate_hash: extra_candidate.hash(),
+					core_index: CoreIndex(0),
+					peer_id: peer,
+				}),
+			);
+
+			TestHarness { virtual_overseer, req_v2_cfg }
+		},
+	)
+}
@@ -2240,6 +2240,13 @@ async fn process_msg<Context>(
 				"DistributeCollation message is not expected on the validator side of the protocol",
 			);
 		},
+		msg @ (ReorderCollations { .. } | CancelCollation { .. } | SubscribeCollationEvents(_)) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				"{:?} message is not expected on the validator side of the protocol",
+				msg,
+			);
+		},
 		NetworkBridgeUpdate(event) => {
 			if let Err(e) = handle_network_msg(ctx, state, keystore, event).await {
 				gum::warn!(
@@ -343,6 +343,13 @@ async fn process_msg<Sender: CollatorProtocolSenderTrait>(
 				"DisconnectFromBackingGroups message is not expected on the validator side of the protocol",
 			);
 		},
+		msg @ (ReorderCollations { .. } | CancelCollation { .. } | SubscribeCollationEvents(_)) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				"{:?} message is not expected on the validator side of the protocol",
+				msg,
+			);
+		},
 	}
 }
 
@@ -201,7 +201,7 @@ impl StatementDistributionSubsystem {
 				},
 			};
 
-			v2::dispatch_requests(&mut ctx, &mut state).await;
+			v2::dispatch_requests(&mut ctx, &mut state, &self.metrics).await;
 		}
 		Ok(())
 	}
@@ -226,10 +226,10 @@ impl StatementDistributionSubsystem {
 						v2::handle_active_leaves_update(ctx, state, activated, &metrics).await;
 					// Regardless of the result of leaf activation, we always prune before
 					// handling it to avoid leaks.
-					v2::handle_deactivate_leaves(state, &deactivated);
+					v2::handle_deactivate_leaves(state, &deactivated, &metrics);
 					res?;
 				} else {
-					v2::handle_deactivate_leaves(state, &deactivated);
+					v2::handle_deactivate_leaves(state, &deactivated, &metrics);
 				}
 			},
 			FromOrchestra::Signal(OverseerSignal::BlockFinalized(..)) => {
@@ -15,6 +15,7 @@
 //! Metrics for the statement distribution module
 
 use polkadot_node_subsystem_util::metrics::{self, prometheus};
+use std::time::Duration;
 
 /// Buckets more suitable for checking the typical latency values
 const HISTOGRAM_LATENCY_BUCKETS: &[f64] = &[
@@ -22,6 +23,10 @@ const HISTOGRAM_LATENCY_BUCKETS: &[f64] = &[
 	0.05, 0.1,
 ];
 
+/// Buckets suitable for the time it takes a candidate to propagate through the network.
+const HISTOGRAM_PROPAGATION_BUCKETS: &[f64] =
+	&[0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0, 18.0, 24.0];
+
 #[derive(Clone)]
 struct MetricsInner {
 	// V1
@@ -37,6 +42,12 @@ struct MetricsInner {
 	// V2+
 	peer_rate_limit_request_drop: prometheus::Counter<prometheus::U64>,
 	max_parallel_requests_reached: prometheus::Counter<prometheus::U64>,
+	statements_imported: prometheus::CounterVec<prometheus::U64>,
+	manifests_imported: prometheus::CounterVec<prometheus::U64>,
+	candidate_confirmation_latency: prometheus::Histogram,
+	candidate_backing_latency: prometheus::Histogram,
+	unbacked_candidates_pruned: prometheus::Counter<prometheus::U64>,
+	requests: prometheus::GaugeVec<prometheus::U64>,
 }
 
 /// Statement Distribution metrics.
@@ -143,6 +154,50 @@ impl Metrics {
 			metrics.max_parallel_requests_reached.inc();
 		}
 	}
+
+	/// Update the counter of freshly imported statements, labelled by where they came from.
+	pub fn on_statement_imported(&self, source: &'static str) {
+		if let Some(metrics) = &self.0 {
+			metrics.statements_imported.with_label_values(&[source]).inc();
+		}
+	}
+
+	/// Update the counter of manifests accepted by the grid, labelled by their kind.
+	pub fn on_manifest_imported(&self, kind: &'static str) {
+		if let Some(metrics) = &self.0 {
+			metrics.manifests_imported.with_label_values(&[kind]).inc();
+		}
+	}
+
+	/// Observe the time between first hearing of a candidate and learning its receipt.
+	pub fn on_candidate_confirmed(&self, latency: Duration) {
+		if let Some(metrics) = &self.0 {
+			metrics.candidate_confirmation_latency.observe(latency.as_secs_f64());
+		}
+	}
+
+	/// Observe the time between first hearing of a candidate and it being backed.
+	pub fn on_candidate_backed(&self, latency: Duration) {
+		if let Some(metrics) = &self.0 {
+			metrics.candidate_backing_latency.observe(latency.as_secs_f64());
+		}
+	}
+
+	/// Update the counter of candidates which went out of view without being backed.
+	pub fn on_unbacked_candidate_pruned(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.unbacked_candidates_pruned.inc();
+		}
+	}
+
+	/// Report the number of candidate requests waiting to be sent or retried, and the number of
+	/// requests awaiting a response.
+	pub fn on_requests(&self, queued: usize, in_flight: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.requests.with_label_values(&["queued"]).set(queued as u64);
+			metrics.requests.with_label_values(&["in_flight"]).set(in_flight as u64);
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -236,6 +291,63 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			statements_imported: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_statement_distribution_statements_imported_total",
+						"Number of statements imported for the first time, by where they came from.",
+					),
+					&["source"],
+				)?,
+				registry,
+			)?,
+			manifests_imported: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_statement_distribution_manifests_imported_total",
+						"Number of backed candidate manifests accepted from the grid, by kind.",
+					),
+					&["kind"],
+				)?,
+				registry,
+			)?,
+			candidate_confirmation_latency: prometheus::register(
+				prometheus::Histogram::with_opts(
+					prometheus::HistogramOpts::new(
+						"polkadot_parachain_statement_distribution_candidate_confirmation_latency",
+						"Time between first hearing of a candidate and learning its receipt.",
+					)
+					.buckets(HISTOGRAM_PROPAGATION_BUCKETS.into()),
+				)?,
+				registry,
+			)?,
+			candidate_backing_latency: prometheus::register(
+				prometheus::Histogram::with_opts(
+					prometheus::HistogramOpts::new(
+						"polkadot_parachain_statement_distribution_candidate_backing_latency",
+						"Time between first hearing of a candidate and it being backed.",
+					)
+					.buckets(HISTOGRAM_PROPAGATION_BUCKETS.into()),
+				)?,
+				registry,
+			)?,
+			unbacked_candidates_pruned: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_statement_distribution_unbacked_candidates_pruned_total",
+					"Number of candidates which went out of view without being backed.",
+				)?,
+				registry,
+			)?,
+			requests: prometheus::register(
+				prometheus::GaugeVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_statement_distribution_requests",
+						"Number of candidate requests, by whether they are queued or in flight.",
+					),
+					&["state"],
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -24,6 +24,7 @@ use polkadot_node_network_protocol::{
 	peer_set::{ProtocolVersion, ValidationVersion},
 	request_response::{
 		incoming::OutgoingResponse,
+		outgoing::Recipient,
 		v2::{AttestedCandidateRequest, AttestedCandidateResponse},
 		IncomingRequest, IncomingRequestReceiver, Requests,
 		MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS,
@@ -81,6 +82,7 @@ use candidates::{BadAdvertisement, Candidates, PostConfirmation};
 use cluster::{Accept as ClusterAccept, ClusterTracker, RejectIncoming as ClusterRejectIncoming};
 use grid::GridTracker;
 use groups::Groups;
+use propagation::{PropagationTracker, StatementSource};
 use requests::{CandidateIdentifier, RequestProperties};
 use statement_store::{StatementOrigin, StatementStore};
 
@@ -90,6 +92,7 @@ mod candidates;
 mod cluster;
 mod grid;
 mod groups;
+mod propagation;
 mod requests;
 mod statement_store;
 
@@ -310,6 +313,7 @@ pub(crate) struct State {
 	authorities: HashMap<AuthorityDiscoveryId, PeerId>,
 	request_manager: RequestManager,
 	response_manager: ResponseManager,
+	propagation: PropagationTracker,
 }
 
 impl State {
@@ -325,6 +329,7 @@ impl State {
 			authorities: HashMap::new(),
 			request_manager: RequestManager::new(),
 			response_manager: ResponseManager::new(),
+			propagation: PropagationTracker::default(),
 			unused_topologies: HashMap::new(),
 		}
 	}
@@ -775,7 +780,7 @@ fn find_active_validator_state(
 	})
 }
 
-pub(crate) fn handle_deactivate_leaves(state: &mut State, leaves: &[Hash]) {
+pub(crate) fn handle_deactivate_leaves(state: &mut State, leaves: &[Hash], metrics: &Metrics) {
 	// deactivate the leaf in the implicit view.
 	for leaf in leaves {
 		let pruned = state.implicit_view.deactivate_leaf(*leaf);
@@ -790,6 +795,13 @@ pub(crate) fn handle_deactivate_leaves(state: &mut State, leaves: &[Hash]) {
 					active_state.cluster_tracker.warn_if_too_many_pending_statements(pruned_rp)
 				});
 
+			let request_manager = &state.request_manager;
+			state.propagation.on_scheduling_parent_pruned(
+				pruned_rp,
+				|candidate_hash| request_manager.is_requested(candidate_hash),
+				metrics,
+			);
+
 			// clean up requests related to this scheduling parent.
 			state.request_manager.remove_by_relay_parent(*leaf);
 		}
@@ -1213,6 +1225,9 @@ pub(crate) async fn share_local_statement<Context>(
 				pvd.clone(),
 				local_group,
 			);
+			if post_confirmation.is_some() {
+				state.propagation.note_confirmed(relay_parent, candidate_hash, metrics);
+			}
 		};
 
 		match per_scheduling_parent.statement_store.insert(
@@ -1228,7 +1243,14 @@ pub(crate) async fn share_local_statement<Context>(
 				);
 				return Err(JfyiError::InvalidShare);
 			},
-			Ok(true) => {},
+			Ok(true) => state.propagation.note_statement(
+				relay_parent,
+				candidate_hash,
+				local_index,
+				is_seconded,
+				StatementSource::Local,
+				metrics,
+			),
 		}
 
 		{
@@ -1588,6 +1610,12 @@ async fn handle_incoming_statement<Context>(
 		(active, idx)
 	};
 
+	let source = if active.is_some() && cluster_sender_index.is_some() {
+		StatementSource::Cluster
+	} else {
+		StatementSource::Grid
+	};
+
 	let checked_statement = if let Some((active, cluster_sender_index)) =
 		active.zip(cluster_sender_index)
 	{
@@ -1724,6 +1752,14 @@ async fn handle_incoming_statement<Context>(
 	};
 
 	if was_fresh {
+		state.propagation.note_statement(
+			relay_parent,
+			candidate_hash,
+			originator_index,
+			matches!(statement, CompactStatement::Seconded(_)),
+			source,
+			metrics,
+		);
 		modify_reputation(reputation, ctx.sender(), peer, BENEFIT_VALID_STATEMENT_FIRST).await;
 		let is_importable = state.candidates.is_importable(&candidate_hash);
 
@@ -2487,6 +2523,14 @@ async fn handle_incoming_manifest<Context>(
 		None => return,
 	};
 
+	state.propagation.note_manifest(
+		manifest.relay_parent,
+		manifest.candidate_hash,
+		peer,
+		grid::ManifestKind::Full,
+		metrics,
+	);
+
 	let ManifestImportSuccess { scheduling_parent_state, per_session, acknowledge, sender_index } =
 		x;
 
@@ -2667,6 +2711,14 @@ async fn handle_incoming_acknowledgement<Context>(
 		None => return,
 	};
 
+	state.propagation.note_manifest(
+		relay_parent,
+		candidate_hash,
+		peer,
+		grid::ManifestKind::Acknowledgement,
+		metrics,
+	);
+
 	let ManifestImportSuccess { scheduling_parent_state, per_session, sender_index, .. } = x;
 
 	let local_validator = match scheduling_parent_state.local_validator.as_mut() {
@@ -2744,6 +2796,8 @@ pub(crate) async fn handle_backed_candidate_message<Context>(
 		"Candidate Backed - initiating grid distribution & child fetches"
 	);
 
+	state.propagation.note_backed(candidate_hash, metrics);
+
 	provide_candidate_to_grid(
 		ctx,
 		candidate_hash,
@@ -2858,8 +2912,13 @@ async fn apply_post_confirmation<Context>(
 
 /// Dispatch pending requests for candidate data & statements.
 #[overseer::contextbounds(StatementDistribution, prefix=self::overseer)]
-pub(crate) async fn dispatch_requests<Context>(ctx: &mut Context, state: &mut State) {
+pub(crate) async fn dispatch_requests<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	metrics: &Metrics,
+) {
 	if !state.request_manager.has_pending_requests() {
+		metrics.on_requests(state.request_manager.queued_count(), state.response_manager.len());
 		return;
 	}
 
@@ -2938,6 +2997,10 @@ pub(crate) async fn dispatch_requests<Context>(ctx: &mut Context, state: &mut St
 		request_props,
 		peer_advertised,
 	) {
+		if let Recipient::Peer(peer) = &request.peer {
+			state.propagation.note_request_sent(request.payload.candidate_hash, *peer);
+		}
+
 		// Peer is supposedly connected.
 		ctx.send_message(NetworkBridgeTxMessage::SendRequests(
 			vec![Requests::AttestedCandidateV2(request)],
@@ -2945,6 +3008,8 @@ pub(crate) async fn dispatch_requests<Context>(ctx: &mut Context, state: &mut St
 		))
 		.await;
 	}
+
+	metrics.on_requests(state.request_manager.queued_count(), state.response_manager.len());
 }
 
 /// Wait on the next incoming response. If there are no requests pending, this
@@ -2990,6 +3055,8 @@ pub(crate) async fn handle_response<Context>(
 		"Received response",
 	);
 
+	state.propagation.note_response(candidate_hash, peer);
+
 	let post_confirmation = {
 		let scheduling_parent_state = match state.per_scheduling_parent.get_mut(&relay_parent) {
 			None => return,
@@ -3058,16 +3125,28 @@ pub(crate) async fn handle_response<Context>(
 		};
 
 		for statement in statements {
-			let _ = scheduling_parent_state.statement_store.insert(
+			let originator = statement.validator_index();
+			let seconded = matches!(statement.payload(), CompactStatement::Seconded(_));
+			if let Ok(true) = scheduling_parent_state.statement_store.insert(
 				&per_session.groups,
 				statement,
 				StatementOrigin::Remote,
-			);
+			) {
+				state.propagation.note_statement(
+					relay_parent,
+					candidate_hash,
+					originator,
+					seconded,
+					StatementSource::Response,
+					metrics,
+				);
+			}
 		}
 
 		if let Some(post_confirmation) =
 			state.candidates.confirm_candidate(candidate_hash, candidate, pvd, group_index)
 		{
+			state.propagation.note_confirmed(relay_parent, candidate_hash, metrics);
 			post_confirmation
 		} else {
 			gum::warn!(
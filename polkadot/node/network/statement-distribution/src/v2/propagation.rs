@@ -0,0 +1,344 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A per-candidate record of how statements and manifests about a candidate reached us.
+//!
+//! None of this affects distribution; it only exists to make backing delays observable. Timings
+//! are fed into the metrics as they happen, and the full record of any candidate which goes out of
+//! view without having been backed is logged at `debug` level, so that operators can see who sent
+//! what, when, and which requests were still outstanding.
+
+use polkadot_node_network_protocol::PeerId;
+use polkadot_primitives::{CandidateHash, Hash, ValidatorIndex};
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+use super::grid::ManifestKind;
+use crate::{metrics::Metrics, LOG_TARGET};
+
+/// Where a freshly imported statement came from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatementSource {
+	/// The statement was issued by the local validator.
+	Local,
+	/// The statement was sent directly by a member of our cluster.
+	Cluster,
+	/// The statement was sent along the grid topology.
+	Grid,
+	/// The statement was part of a response to one of our requests.
+	Response,
+}
+
+impl StatementSource {
+	fn as_str(&self) -> &'static str {
+		match self {
+			StatementSource::Local => "local",
+			StatementSource::Cluster => "cluster",
+			StatementSource::Grid => "grid",
+			StatementSource::Response => "response",
+		}
+	}
+}
+
+#[derive(Debug)]
+struct StatementRecord {
+	originator: ValidatorIndex,
+	seconded: bool,
+	source: StatementSource,
+	after: Duration,
+}
+
+#[derive(Debug)]
+struct ManifestRecord {
+	peer: PeerId,
+	kind: ManifestKind,
+	after: Duration,
+}
+
+#[derive(Debug)]
+struct RequestRecord {
+	peer: PeerId,
+	sent_after: Duration,
+	answered_after: Option<Duration>,
+}
+
+/// Everything we learned about a single candidate, with timings relative to the moment we
+/// first heard of it.
+#[derive(Debug)]
+struct CandidatePropagation {
+	scheduling_parent: Hash,
+	first_seen: Instant,
+	confirmed_after: Option<Duration>,
+	backed_after: Option<Duration>,
+	statements: Vec<StatementRecord>,
+	manifests: Vec<ManifestRecord>,
+	requests: Vec<RequestRecord>,
+}
+
+impl CandidatePropagation {
+	fn new(scheduling_parent: Hash) -> Self {
+		CandidatePropagation {
+			scheduling_parent,
+			first_seen: Instant::now(),
+			confirmed_after: None,
+			backed_after: None,
+			statements: Vec::new(),
+			manifests: Vec::new(),
+			requests: Vec::new(),
+		}
+	}
+}
+
+/// Tracks the propagation of all candidates under the active scheduling parents.
+#[derive(Default)]
+pub struct PropagationTracker {
+	candidates: HashMap<CandidateHash, CandidatePropagation>,
+}
+
+impl PropagationTracker {
+	fn entry(
+		&mut self,
+		scheduling_parent: Hash,
+		candidate_hash: CandidateHash,
+	) -> &mut CandidatePropagation {
+		self.candidates
+			.entry(candidate_hash)
+			.or_insert_with(|| CandidatePropagation::new(scheduling_parent))
+	}
+
+	/// Note a statement which was imported for the first time.
+	pub fn note_statement(
+		&mut self,
+		scheduling_parent: Hash,
+		candidate_hash: CandidateHash,
+		originator: ValidatorIndex,
+		seconded: bool,
+		source: StatementSource,
+		metrics: &Metrics,
+	) {
+		let record = self.entry(scheduling_parent, candidate_hash);
+		let after = record.first_seen.elapsed();
+		record.statements.push(StatementRecord { originator, seconded, source, after });
+		metrics.on_statement_imported(source.as_str());
+	}
+
+	/// Note a manifest which was accepted by the grid tracker.
+	pub fn note_manifest(
+		&mut self,
+		scheduling_parent: Hash,
+		candidate_hash: CandidateHash,
+		peer: PeerId,
+		kind: ManifestKind,
+		metrics: &Metrics,
+	) {
+		let record = self.entry(scheduling_parent, candidate_hash);
+		let after = record.first_seen.elapsed();
+		record.manifests.push(ManifestRecord { peer, kind, after });
+		metrics.on_manifest_imported(match kind {
+			ManifestKind::Full => "full",
+			ManifestKind::Acknowledgement => "acknowledgement",
+		});
+	}
+
+	/// Note that the candidate was requested from the given peer.
+	pub fn note_request_sent(&mut self, candidate_hash: CandidateHash, peer: PeerId) {
+		if let Some(record) = self.candidates.get_mut(&candidate_hash) {
+			let sent_after = record.first_seen.elapsed();
+			record.requests.push(RequestRecord { peer, sent_after, answered_after: None });
+		}
+	}
+
+	/// Note that the given peer answered a request for the candidate, whether successfully or
+	/// not.
+	pub fn note_response(&mut self, candidate_hash: CandidateHash, peer: PeerId) {
+		if let Some(record) = self.candidates.get_mut(&candidate_hash) {
+			let answered_after = record.first_seen.elapsed();
+			if let Some(request) = record
+				.requests
+				.iter_mut()
+				.rev()
+				.find(|r| r.peer == peer && r.answered_after.is_none())
+			{
+				request.answered_after = Some(answered_after);
+			}
+		}
+	}
+
+	/// Note that the candidate receipt became known.
+	pub fn note_confirmed(
+		&mut self,
+		scheduling_parent: Hash,
+		candidate_hash: CandidateHash,
+		metrics: &Metrics,
+	) {
+		let record = self.entry(scheduling_parent, candidate_hash);
+		if record.confirmed_after.is_none() {
+			let after = record.first_seen.elapsed();
+			record.confirmed_after = Some(after);
+			metrics.on_candidate_confirmed(after);
+		}
+	}
+
+	/// Note that the candidate was backed.
+	pub fn note_backed(&mut self, candidate_hash: CandidateHash, metrics: &Metrics) {
+		let Some(record) = self.candidates.get_mut(&candidate_hash) else { return };
+		if record.backed_after.is_some() {
+			return;
+		}
+
+		let after = record.first_seen.elapsed();
+		record.backed_after = Some(after);
+		metrics.on_candidate_backed(after);
+
+		gum::trace!(
+			target: LOG_TARGET,
+			?candidate_hash,
+			?record,
+			"Candidate backed",
+		);
+	}
+
+	/// Drop the records of all candidates under the given scheduling parent, logging the ones
+	/// which were never backed.
+	///
+	/// `is_requested` reports whether there is still an outstanding request for a candidate.
+	pub fn on_scheduling_parent_pruned(
+		&mut self,
+		scheduling_parent: Hash,
+		is_requested: impl Fn(&CandidateHash) -> bool,
+		metrics: &Metrics,
+	) {
+		self.candidates.retain(|candidate_hash, record| {
+			if record.scheduling_parent != scheduling_parent {
+				return true;
+			}
+
+			if record.backed_after.is_none() {
+				metrics.on_unbacked_candidate_pruned();
+				gum::debug!(
+					target: LOG_TARGET,
+					?candidate_hash,
+					?scheduling_parent,
+					known_for = ?record.first_seen.elapsed(),
+					confirmed_after = ?record.confirmed_after,
+					request_outstanding = is_requested(candidate_hash),
+					statements = ?record.statements,
+					manifests = ?record.manifests,
+					requests = ?record.requests,
+					"Candidate went out of view without being backed",
+				);
+			}
+
+			false
+		});
+	}
+
+	#[cfg(test)]
+	fn len(&self) -> usize {
+		self.candidates.len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn records_are_pruned_with_their_scheduling_parent() {
+		let metrics = Metrics::default();
+		let mut tracker = PropagationTracker::default();
+		let candidate_a = CandidateHash(Hash::repeat_byte(1));
+		let candidate_b = CandidateHash(Hash::repeat_byte(2));
+		let parent_a = Hash::repeat_byte(10);
+		let parent_b = Hash::repeat_byte(20);
+
+		tracker.note_statement(
+			parent_a,
+			candidate_a,
+			ValidatorIndex(0),
+			true,
+			StatementSource::Cluster,
+			&metrics,
+		);
+		tracker.note_manifest(
+			parent_b,
+			candidate_b,
+			PeerId::random(),
+			ManifestKind::Full,
+			&metrics,
+		);
+		assert_eq!(tracker.len(), 2);
+
+		tracker.on_scheduling_parent_pruned(parent_a, |_| false, &metrics);
+		assert_eq!(tracker.len(), 1);
+		assert!(tracker.candidates.contains_key(&candidate_b));
+
+		tracker.on_scheduling_parent_pruned(parent_b, |_| false, &metrics);
+		assert_eq!(tracker.len(), 0);
+	}
+
+	#[test]
+	fn responses_are_matched_to_the_latest_request() {
+		let metrics = Metrics::default();
+		let mut tracker = PropagationTracker::default();
+		let candidate = CandidateHash(Hash::repeat_byte(1));
+		let peer_a = PeerId::random();
+		let peer_b = PeerId::random();
+
+		// Requests for unknown candidates aren't tracked.
+		tracker.note_request_sent(candidate, peer_a);
+		assert_eq!(tracker.len(), 0);
+
+		tracker.note_manifest(
+			Hash::repeat_byte(10),
+			candidate,
+			peer_a,
+			ManifestKind::Full,
+			&metrics,
+		);
+		tracker.note_request_sent(candidate, peer_a);
+		tracker.note_request_sent(candidate, peer_b);
+		tracker.note_response(candidate, peer_b);
+
+		let requests = &tracker.candidates[&candidate].requests;
+		assert_eq!(requests.len(), 2);
+		assert!(requests[0].answered_after.is_none());
+		assert!(requests[1].answered_after.is_some());
+	}
+
+	#[test]
+	fn confirmation_and_backing_are_noted_once() {
+		let metrics = Metrics::default();
+		let mut tracker = PropagationTracker::default();
+		let candidate = CandidateHash(Hash::repeat_byte(1));
+
+		// Backing of an unknown candidate is ignored.
+		tracker.note_backed(candidate, &metrics);
+		assert_eq!(tracker.len(), 0);
+
+		tracker.note_confirmed(Hash::repeat_byte(10), candidate, &metrics);
+		let confirmed_after = tracker.candidates[&candidate].confirmed_after;
+		assert!(confirmed_after.is_some());
+
+		tracker.note_confirmed(Hash::repeat_byte(10), candidate, &metrics);
+		assert_eq!(tracker.candidates[&candidate].confirmed_after, confirmed_after);
+
+		tracker.note_backed(candidate, &metrics);
+		assert!(tracker.candidates[&candidate].backed_after.is_some());
+	}
+}
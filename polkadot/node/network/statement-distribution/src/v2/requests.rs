@@ -275,6 +275,17 @@ impl RequestManager {
 		);
 	}
 
+	/// Returns true if there is a request for the given candidate, whether it is waiting to be
+	/// sent or in flight.
+	pub fn is_requested(&self, candidate_hash: &CandidateHash) -> bool {
+		self.unique_identifiers.contains_key(candidate_hash)
+	}
+
+	/// Returns the number of requests which are waiting to be sent or retried.
+	pub fn queued_count(&self) -> usize {
+		self.requests.values().filter(|request| !request.in_flight).count()
+	}
+
 	/// Returns true if there are pending requests that are dispatchable.
 	pub fn has_pending_requests(&self) -> bool {
 		for (_id, entry) in &self.requests {
@@ -444,7 +455,8 @@ impl ResponseManager {
 		})
 	}
 
-	fn len(&self) -> usize {
+	/// Returns the number of requests awaiting a response.
+	pub fn len(&self) -> usize {
 		self.pending_responses.len()
 	}
 
@@ -88,8 +88,15 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub prepare_workers_soft_max_num: Option<usize>,
 	/// An optional absolute number of pvf workers that can be spawned in the pvf prepare pool.
 	pub prepare_workers_hard_max_num: Option<usize>,
+	/// Whether prepared pvf artifacts should be kept across restarts.
+	pub persistent_pvf_artifacts: bool,
+	/// Restrictions applied to the pvf jobs on top of the default security features.
+	pub pvf_sandbox: polkadot_node_core_pvf::SandboxConfig,
 	/// How long finalized data should be kept in the availability store (in hours)
 	pub keep_finalized_for: Option<u32>,
+	/// Whether the availability store should keep the available data of finalized candidates
+	/// forever.
+	pub archive_available_data: bool,
 	pub overseer_gen: OverseerGenerator,
 	pub overseer_message_channel_capacity_override: Option<usize>,
 	#[allow(dead_code)]
@@ -211,7 +218,10 @@ where
 					execute_workers_max_num,
 					prepare_workers_soft_max_num,
 					prepare_workers_hard_max_num,
+					persistent_pvf_artifacts,
+					pvf_sandbox,
 					keep_finalized_for,
+					archive_available_data,
 					invulnerable_ah_collators,
 					collator_protocol_hold_off,
 					experimental_collator_protocol,
@@ -406,6 +416,8 @@ where
 					pvf_execute_workers_max_num: execute_workers_max_num.unwrap_or(4),
 					pvf_prepare_workers_soft_max_num: prepare_workers_soft_max_num.unwrap_or(1),
 					pvf_prepare_workers_hard_max_num: prepare_workers_hard_max_num.unwrap_or(2),
+					pvf_persistent_artifacts: persistent_pvf_artifacts,
+					pvf_sandbox,
 				})
 			} else {
 				None
@@ -443,8 +455,11 @@ where
 				keep_finalized_for: if matches!(config.chain_spec.identify_chain(), Chain::Rococo) {
 					keep_finalized_for.unwrap_or(1)
 				} else {
-					KEEP_FINALIZED_FOR_LIVE_NETWORKS
+					// Live networks can keep data for longer, but never for less than the
+					// availability period other validators rely on.
+					keep_finalized_for.unwrap_or_default().max(KEEP_FINALIZED_FOR_LIVE_NETWORKS)
 				},
+				archive_available_data,
 			};
 
 			Some(ExtendedOverseerGenArgs {
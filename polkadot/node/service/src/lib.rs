@@ -50,7 +50,9 @@ use crate::builder::{new_partial, new_partial_basics};
 #[cfg(feature = "full-node")]
 use {
 	polkadot_node_core_approval_voting as approval_voting_subsystem,
-	polkadot_node_core_av_store::Error as AvailabilityError,
+	polkadot_node_core_av_store::{
+		self as availability_store, Config as AvailabilityConfig, Error as AvailabilityError,
+	},
 	polkadot_node_core_chain_selection as chain_selection_subsystem,
 };
 
@@ -59,6 +61,8 @@ use polkadot_overseer::SpawnGlue;
 
 #[cfg(feature = "full-node")]
 pub use {
+	polkadot_node_core_av_store::ExportSelection as AvailabilityExportSelection,
+	polkadot_node_core_pvf::{CgroupLimits as PvfCgroupLimits, SandboxConfig as PvfSandboxConfig},
 	polkadot_overseer::{Handle, Overseer, OverseerConnector, OverseerHandle},
 	polkadot_primitives::runtime_api::ParachainHost,
 	relay_chain_selection::SelectRelayChain,
@@ -74,7 +78,9 @@ use prometheus_endpoint::Registry;
 use sc_service::SpawnTaskHandle;
 
 pub use chain_spec::{GenericChainSpec, RococoChainSpec, WestendChainSpec};
-pub use polkadot_primitives::{Block, BlockId, BlockNumber, CollatorPair, Hash, Id as ParaId};
+pub use polkadot_primitives::{
+	Block, BlockId, BlockNumber, CandidateHash, CollatorPair, Hash, Id as ParaId,
+};
 pub use sc_client_api::{Backend, CallExecutor};
 pub use sc_consensus::{BlockImport, LongestChain};
 pub use sc_executor::NativeExecutionDispatch;
@@ -321,6 +327,14 @@ pub fn open_database(db_source: &DatabaseSource) -> Result<Arc<dyn Database>, Er
 			path.clone(),
 			parachains_db::CacheSizes::default(),
 		)?,
+		// The parachains database is written by the subsystems, so the secondary instance keeps
+		// its own one.
+		DatabaseSource::RocksDbSecondary { secondary_path, .. } => {
+			parachains_db::open_creating_rocksdb(
+				secondary_path.clone(),
+				parachains_db::CacheSizes::default(),
+			)?
+		},
 		DatabaseSource::ParityDb { path, .. } => parachains_db::open_creating_paritydb(
 			path.parent().ok_or(Error::DatabasePathRequired)?.into(),
 			parachains_db::CacheSizes::default(),
@@ -495,6 +509,58 @@ pub fn revert_backend(
 	Ok(())
 }
 
+/// Writes the available data of the selected candidates in the parachains DB to `writer`.
+///
+/// The node must not be running. Returns the number of exported candidates.
+#[cfg(feature = "full-node")]
+pub fn export_availability(
+	config: &Configuration,
+	selection: AvailabilityExportSelection,
+	writer: &mut impl std::io::Write,
+) -> Result<usize, Error> {
+	let parachains_db = open_database(&config.database)?;
+
+	Ok(availability_store::export_candidates(
+		&parachains_db,
+		&offline_availability_config(),
+		selection,
+		writer,
+	)?)
+}
+
+/// Stores the available data exported by [`export_availability`] in the parachains DB.
+///
+/// The node must not be running. Imported candidates are treated as finalized now, so they are
+/// only kept for longer than usual by nodes archiving available data. Returns the number of
+/// imported candidates.
+#[cfg(feature = "full-node")]
+pub fn import_availability(
+	config: &Configuration,
+	reader: &mut impl std::io::Read,
+) -> Result<usize, Error> {
+	let parachains_db = open_database(&config.database)?;
+	let now = std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map_err(AvailabilityError::from)?;
+
+	Ok(availability_store::import_candidates(
+		&parachains_db,
+		&offline_availability_config(),
+		reader,
+		now,
+	)?)
+}
+
+#[cfg(feature = "full-node")]
+fn offline_availability_config() -> AvailabilityConfig {
+	AvailabilityConfig {
+		col_data: parachains_db::REAL_COLUMNS.col_availability_data,
+		col_meta: parachains_db::REAL_COLUMNS.col_availability_meta,
+		keep_finalized_for: KEEP_FINALIZED_FOR_LIVE_NETWORKS,
+		archive_available_data: false,
+	}
+}
+
 fn revert_chain_selection(db: Arc<dyn Database>, hash: Hash) -> sp_blockchain::Result<()> {
 	let config = chain_selection_subsystem::Config {
 		col_data: parachains_db::REAL_COLUMNS.col_chain_selection_data,
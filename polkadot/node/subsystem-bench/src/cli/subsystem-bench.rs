@@ -20,7 +20,10 @@
 use clap::Parser;
 use color_eyre::eyre;
 use colored::Colorize;
-use polkadot_subsystem_bench::{approval, availability, configuration, disputes, statement};
+use polkadot_subsystem_bench::{
+	approval, availability, collator_protocol, configuration, disputes, prospective_parachains,
+	replay, statement,
+};
 use pyroscope::PyroscopeAgent;
 use pyroscope_pprofrs::{pprof_backend, PprofConfig};
 use serde::{Deserialize, Serialize};
@@ -44,6 +47,12 @@ pub enum TestObjective {
 	StatementDistribution,
 	/// Benchmark the dispute-coordinator subsystem
 	DisputeCoordinator(disputes::DisputesOptions),
+	/// Benchmark the validator side of the collator-protocol subsystem
+	CollatorProtocolValidatorSide(collator_protocol::CollatorProtocolOptions),
+	/// Benchmark the prospective-parachains subsystem
+	ProspectiveParachains(prospective_parachains::ProspectiveParachainsOptions),
+	/// Replay approval-voting, dispute-coordinator and availability-store on a recorded DB
+	Replay(replay::ReplayOptions),
 }
 
 impl std::fmt::Display for TestObjective {
@@ -57,6 +66,9 @@ impl std::fmt::Display for TestObjective {
 				Self::ApprovalVoting(_) => "ApprovalVoting",
 				Self::StatementDistribution => "StatementDistribution",
 				Self::DisputeCoordinator(_) => "DisputeCoordinator",
+				Self::CollatorProtocolValidatorSide(_) => "CollatorProtocolValidatorSide",
+				Self::ProspectiveParachains(_) => "ProspectiveParachains",
+				Self::Replay(_) => "Replay",
 			}
 		)
 	}
@@ -178,6 +190,26 @@ impl BenchCli {
 					env.runtime()
 						.block_on(disputes::benchmark_dispute_coordinator(&mut env, &state))
 				},
+				TestObjective::CollatorProtocolValidatorSide(ref options) => {
+					let state = collator_protocol::TestState::new(&test_config, options);
+					let mut env = collator_protocol::prepare_test(&state, options, true);
+					env.runtime()
+						.block_on(collator_protocol::benchmark_collator_protocol(&mut env, &state))
+				},
+				TestObjective::ProspectiveParachains(ref options) => {
+					let state = prospective_parachains::TestState::new(&test_config, options);
+					let mut env = prospective_parachains::prepare_test(&state, true);
+					env.runtime().block_on(
+						prospective_parachains::benchmark_prospective_parachains(&mut env, &state),
+					)
+				},
+				TestObjective::Replay(ref options) => {
+					let state = replay::TestState::new(&test_config, options)?;
+					let mut env = replay::prepare_test(&state, true);
+					let (report, usage) = env.runtime().block_on(replay::replay(&mut env, &state));
+					println!("{report}");
+					usage
+				},
 			};
 			println!("\n{}\n{}", benchmark_name.purple(), usage);
 		}
@@ -0,0 +1,329 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Subsystem benchmark for the validator side of the collator protocol.
+//!
+//! Scenario:
+//! - A set of emulated collators, spread across all the cores, connects to the node under test and
+//!   declares for the para scheduled on their core. The collators of the paras scheduled on the
+//!   other cores are rejected by the node under test.
+//! - At every relay chain block each collator advertises a collation.
+//! - The collator protocol fetches the collations of our para over the emulated network, up to the
+//!   free claims of the claim queue, and asks candidate backing to second them. The backing mock
+//!   replies with a `Seconded` statement right away. The next block is imported once all the
+//!   expected collations are fetched.
+//!
+//! Both the legacy and the experimental validator side can be benchmarked.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies},
+	mock::{
+		candidate_backing::MockCandidateBacking,
+		chain_api::{ChainApiState, MockChainApi},
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		prospective_parachains::MockProspectiveParachains,
+		runtime_api::{MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains,
+	},
+	network::{new_network, NetworkEmulatorHandle, NetworkInterface, NetworkInterfaceReceiver},
+	usage::BenchmarkUsage,
+	NODE_UNDER_TEST,
+};
+use colored::Colorize;
+use itertools::Itertools;
+use polkadot_collator_protocol::{CollatorProtocolSubsystem, ProtocolSide, ReputationConfig};
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_network_protocol::{
+	peer_set::CollationVersion, v1 as protocol_v1, v2 as protocol_v2, CollationProtocols,
+	ObservedRole, OurView, PeerId,
+};
+use polkadot_node_subsystem::messages::{AllMessages, CollatorProtocolMessage, NetworkBridgeEvent};
+use polkadot_node_subsystem_util::database::{kvdb_impl::DbAdapter, Database};
+use polkadot_overseer::{
+	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+use polkadot_primitives::{AuthorityDiscoveryId, ValidatorId, ValidatorIndex};
+use polkadot_primitives_test_helpers::{dummy_head_data, dummy_pvd};
+use sc_keystore::LocalKeystore;
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use sp_core::Pair;
+use sp_keystore::{Keystore, KeystorePtr};
+use sp_runtime::RuntimeAppPublic;
+use std::{
+	collections::HashSet,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+pub use test_state::TestState;
+
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::collator-protocol";
+
+/// The maximum time to wait for the expected collations of a block to be fetched.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Parameters specific to the collator protocol benchmark
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct CollatorProtocolOptions {
+	#[clap(short, long, default_value_t = 10)]
+	/// The number of collators advertising a collation at every relay chain block, spread across
+	/// all the cores.
+	pub n_collators: usize,
+	#[clap(long, default_value_t = false)]
+	#[serde(default)]
+	/// Benchmark the experimental validator side instead of the legacy one.
+	pub experimental: bool,
+}
+
+pub fn make_keystore() -> KeystorePtr {
+	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+	Keystore::sr25519_generate_new(&*keystore, ValidatorId::ID, Some("//Node0"))
+		.expect("Insert key into keystore");
+	Keystore::sr25519_generate_new(&*keystore, AuthorityDiscoveryId::ID, Some("//Node0"))
+		.expect("Insert key into keystore");
+	keystore
+}
+
+fn build_overseer(
+	state: &TestState,
+	options: &CollatorProtocolOptions,
+	network: NetworkEmulatorHandle,
+	network_interface: NetworkInterface,
+	network_receiver: NetworkInterfaceReceiver,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		Default::default(),
+		Default::default(),
+		0,
+		MockRuntimeApiCoreState::Scheduled,
+	)
+	.with_claim_queue(state.claim_queue.clone());
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mock_chain_api = MockChainApi::new(chain_api_state);
+	let mock_prospective_parachains =
+		MockProspectiveParachains::with_validation_data(state.validation_data.clone());
+	let mock_candidate_backing = MockCandidateBacking::new(
+		state.config.clone(),
+		state
+			.test_authorities
+			.validator_pairs
+			.get(NODE_UNDER_TEST as usize)
+			.unwrap()
+			.clone(),
+		dummy_pvd(dummy_head_data(), 0),
+		state.own_backing_group.clone(),
+	)
+	.with_committed_receipts(state.committed_receipts());
+	let keystore = make_keystore();
+	let protocol_side = if options.experimental {
+		let db = DbAdapter::new(kvdb_memorydb::create(1), &[0]);
+		let db: Arc<dyn Database> = Arc::new(db);
+		ProtocolSide::ValidatorExperimental {
+			keystore,
+			metrics: Metrics::try_register(&dependencies.registry).unwrap(),
+			db,
+			reputation_config: ReputationConfig { col_reputation_data: 0, persist_interval: None },
+		}
+	} else {
+		ProtocolSide::Validator {
+			keystore,
+			eviction_policy: Default::default(),
+			metrics: Metrics::try_register(&dependencies.registry).unwrap(),
+			invulnerables: HashSet::new(),
+			collator_protocol_hold_off: None,
+		}
+	};
+	let subsystem = CollatorProtocolSubsystem::new(protocol_side);
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network,
+		network_interface.subsystem_sender(),
+		state.test_authorities.clone(),
+	);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, None);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_prospective_parachains(|_| mock_prospective_parachains)
+		.replace_candidate_backing(|_| mock_candidate_backing)
+		.replace_collator_protocol(|_| subsystem)
+		.replace_network_bridge_tx(|_| network_bridge_tx)
+		.replace_network_bridge_rx(|_| network_bridge_rx);
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(
+	state: &TestState,
+	options: &CollatorProtocolOptions,
+	with_prometheus_endpoint: bool,
+) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	let (network, network_interface, network_receiver) = new_network(
+		&state.config,
+		&dependencies,
+		&state.test_authorities,
+		vec![Arc::new(state.clone())],
+	);
+	let (overseer, overseer_handle) = build_overseer(
+		state,
+		options,
+		network.clone(),
+		network_interface,
+		network_receiver,
+		&dependencies,
+	);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+fn network_bridge_update(
+	event: NetworkBridgeEvent<polkadot_node_network_protocol::CollatorProtocolMessage>,
+) -> AllMessages {
+	AllMessages::CollatorProtocol(CollatorProtocolMessage::NetworkBridgeUpdate(event))
+}
+
+async fn connect_collators(env: &mut TestEnvironment, state: &TestState, peers: &[PeerId]) {
+	for ((peer_id, pair), para_id) in
+		peers.iter().zip(state.collator_pairs.iter()).zip(state.collator_paras.iter())
+	{
+		env.send_message(network_bridge_update(NetworkBridgeEvent::PeerConnected(
+			*peer_id,
+			ObservedRole::Full,
+			CollationVersion::V2.into(),
+			None,
+		)))
+		.await;
+
+		let declare = CollationProtocols::V2(protocol_v2::CollatorProtocolMessage::Declare(
+			pair.public(),
+			*para_id,
+			pair.sign(&protocol_v1::declare_signature_payload(peer_id)),
+		));
+		env.send_message(network_bridge_update(NetworkBridgeEvent::PeerMessage(*peer_id, declare)))
+			.await;
+	}
+}
+
+pub async fn benchmark_collator_protocol(
+	env: &mut TestEnvironment,
+	state: &TestState,
+) -> BenchmarkUsage {
+	let config = env.config().clone();
+
+	// Emulated collators reuse the network identities of connected peers outside of our backing
+	// group, so collation fetching goes through the emulated network.
+	let collator_peers = (0..config.n_validators)
+		.rev()
+		.filter(|index| !state.own_backing_group.contains(&ValidatorIndex(*index as u32)))
+		.filter(|index| {
+			env.network()
+				.is_peer_connected(&env.authorities().validator_authority_id[*index])
+		})
+		.map(|index| env.authorities().peer_ids[index])
+		.take(state.collator_pairs.len())
+		.collect_vec();
+	assert_eq!(
+		collator_peers.len(),
+		state.collator_pairs.len(),
+		"Not enough connected peers to emulate all collators"
+	);
+
+	let test_start = Instant::now();
+
+	for block_info in state.block_infos.iter() {
+		let block_num = block_info.number as usize;
+		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
+		env.metrics().set_current_block(block_num);
+		env.import_block(block_info.clone()).await;
+		env.send_message(network_bridge_update(NetworkBridgeEvent::OurViewChange(OurView::new(
+			[block_info.hash],
+			0,
+		))))
+		.await;
+
+		// Collators which connect before the subsystem knows about any leaf are disconnected.
+		if block_num == 1 {
+			connect_collators(env, state, &collator_peers).await;
+		}
+
+		let block_start = Instant::now();
+		let advertisements = state.advertisements.get(&block_info.hash).expect("pregenerated");
+		for (peer_id, (candidate_hash, parent_head_data_hash)) in
+			collator_peers.iter().zip(advertisements.iter())
+		{
+			let advertisement =
+				CollationProtocols::V2(protocol_v2::CollatorProtocolMessage::AdvertiseCollation {
+					scheduling_parent: block_info.hash,
+					candidate_hash: *candidate_hash,
+					parent_head_data_hash: *parent_head_data_hash,
+				});
+			env.send_message(network_bridge_update(NetworkBridgeEvent::PeerMessage(
+				*peer_id,
+				advertisement,
+			)))
+			.await;
+		}
+
+		let expected = state.expected_fetches[&block_info.hash];
+		loop {
+			let fetched = state.fetched_count(&block_info.hash);
+			gum::info!(target: LOG_TARGET, "Waiting for collations to be fetched: {fetched}/{expected}");
+			if fetched >= expected {
+				break;
+			}
+			assert!(
+				block_start.elapsed() < FETCH_TIMEOUT,
+				"Timed out waiting for collations to be fetched: {fetched}/{expected}"
+			);
+
+			tokio::time::sleep(Duration::from_millis(100)).await;
+		}
+		gum::info!(target: LOG_TARGET, "All collations fetched in {}", format!("{:?}ms", block_start.elapsed().as_millis()).cyan());
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{duration:?}ms").cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	env.stop().await;
+	env.collect_resource_usage(&["collator-protocol"], false)
+}
@@ -0,0 +1,290 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	collator_protocol::CollatorProtocolOptions,
+	configuration::{TestAuthorities, TestConfiguration},
+	environment::GENESIS_HASH,
+	mock::runtime_api::session_info_for_peers,
+	network::{HandleNetworkMessage, NetworkMessage},
+	NODE_UNDER_TEST,
+};
+use codec::Encode;
+use polkadot_node_network_protocol::request_response::{
+	v2::CollationFetchingResponse, ProtocolName, Requests,
+};
+use polkadot_node_primitives::{BlockData, PoV};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	BlockNumber, CandidateHash, CandidateReceiptV2 as CandidateReceipt, CollatorPair,
+	CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreIndex, Hash, HeadData, Header,
+	Id as ParaId, MutateDescriptorV2, PersistedValidationData, ValidationCode, ValidatorIndex,
+};
+use polkadot_primitives_test_helpers::make_candidate_v2;
+use sp_core::Pair;
+use std::{
+	collections::{BTreeMap, HashMap, HashSet, VecDeque},
+	sync::{Arc, Mutex},
+};
+
+const SESSION_INDEX: u32 = 0;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers, including genesis
+	pub block_headers: HashMap<Hash, Header>,
+	// The para scheduled on the core of the node under test
+	pub para_id: ParaId,
+	// The claim queue, every core is assigned to its own para
+	pub claim_queue: BTreeMap<CoreIndex, VecDeque<ParaId>>,
+	// Indices in the backing group where the node under test is
+	pub own_backing_group: Vec<ValidatorIndex>,
+	// Keys of the emulated collators
+	pub collator_pairs: Vec<CollatorPair>,
+	// The para of every emulated collator, the collators are spread across all the cores
+	pub collator_paras: Vec<ParaId>,
+	// Generated candidate receipts, one per block to feed the runtime api
+	pub candidate_receipts: HashMap<Hash, Vec<CandidateReceipt>>,
+	// Generated collations, the same PoV is shared by all collations of the same size
+	pub collations: HashMap<CandidateHash, (CommittedCandidateReceipt, Arc<PoV>)>,
+	// Advertisements per block, indexed by collator: candidate hash and parent head data hash
+	pub advertisements: HashMap<Hash, Vec<(CandidateHash, Hash)>>,
+	// Validation data of all collations built on top of a relay parent
+	pub validation_data: HashMap<Hash, PersistedValidationData>,
+	// The number of collations the node under test is expected to fetch at every relay parent
+	pub expected_fetches: HashMap<Hash, usize>,
+	// Map from relay parent to collations fetched by the node under test
+	pub fetch_tracker: Arc<Mutex<HashMap<Hash, HashSet<CandidateHash>>>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &CollatorProtocolOptions) -> Self {
+		let config = config.clone();
+		let test_authorities = config.generate_authorities();
+		let session_info = session_info_for_peers(&config, &test_authorities);
+		let own_backing_group = session_info
+			.validator_groups
+			.iter()
+			.find(|g| g.contains(&ValidatorIndex(NODE_UNDER_TEST)))
+			.unwrap()
+			.clone();
+		// The node under test is in the first group, which is assigned to the first core.
+		let para_id = ParaId::new(1);
+		let claim_queue: BTreeMap<_, VecDeque<_>> = (0..config.n_cores)
+			.map(|core_idx| {
+				let para_id = ParaId::new(core_idx as u32 + 1);
+				(
+					CoreIndex(core_idx as u32),
+					std::iter::repeat(para_id).take(config.scheduling_lookahead as usize).collect(),
+				)
+			})
+			.collect();
+
+		let block_infos: Vec<BlockInfo> =
+			(1..=config.num_blocks).map(generate_block_info).collect();
+		let block_headers = std::iter::once(generate_genesis_header())
+			.chain(block_infos.iter().map(generate_block_header))
+			.collect();
+
+		let collator_pairs =
+			(0..options.n_collators).map(|_| CollatorPair::generate().0).collect::<Vec<_>>();
+		let collator_cores = (0..options.n_collators)
+			.map(|collator_idx| CoreIndex((collator_idx % config.n_cores) as u32))
+			.collect::<Vec<_>>();
+		let collator_paras =
+			collator_cores.iter().map(|core| claim_queue[core][0]).collect::<Vec<_>>();
+		let expected_fetches = expected_fetches(
+			&block_infos,
+			collator_paras.iter().filter(|collator_para| **collator_para == para_id).count(),
+			config.scheduling_lookahead as usize,
+		);
+		let povs: HashMap<usize, Arc<PoV>> = config
+			.pov_sizes()
+			.iter()
+			.map(|&size| (size, Arc::new(PoV { block_data: BlockData(vec![0u8; size]) })))
+			.collect();
+		let validation_code_hash = ValidationCode(Vec::new()).hash();
+
+		let mut candidate_receipts = HashMap::new();
+		let mut collations = HashMap::new();
+		let mut advertisements = HashMap::new();
+		let mut validation_data = HashMap::new();
+		for block_info in block_infos.iter() {
+			// All collators build on top of the same parent head.
+			let parent_head = HeadData((block_info.number - 1).encode());
+			let mut block_advertisements = Vec::new();
+			for (collator_idx, (core_index, collator_para)) in
+				collator_cores.iter().zip(collator_paras.iter()).enumerate()
+			{
+				let pov_size = config.pov_sizes()[collator_idx % config.pov_sizes().len()];
+				let pov = povs.get(&pov_size).expect("PoVs are generated for all sizes").clone();
+				let (mut receipt, pvd) = make_candidate_v2(
+					block_info.hash,
+					block_info.number,
+					*collator_para,
+					parent_head.clone(),
+					HeadData((block_info.number, collator_idx as u32).encode()),
+					validation_code_hash,
+				);
+				receipt.descriptor.set_core_index(*core_index);
+				receipt.descriptor.set_session_index(SESSION_INDEX);
+				receipt.descriptor.set_pov_hash(pov.hash());
+
+				let candidate_hash = receipt.hash();
+				if collator_idx == 0 {
+					candidate_receipts.insert(block_info.hash, vec![receipt.to_plain()]);
+				}
+				validation_data.insert(block_info.hash, pvd);
+				block_advertisements.push((candidate_hash, parent_head.hash()));
+				collations.insert(candidate_hash, (receipt, pov));
+			}
+			advertisements.insert(block_info.hash, block_advertisements);
+		}
+
+		Self {
+			config,
+			test_authorities,
+			block_infos,
+			block_headers,
+			para_id,
+			claim_queue,
+			own_backing_group,
+			collator_pairs,
+			collator_paras,
+			candidate_receipts,
+			collations,
+			advertisements,
+			validation_data,
+			expected_fetches,
+			fetch_tracker: Default::default(),
+		}
+	}
+
+	/// Number of collations fetched for the given relay parent.
+	pub fn fetched_count(&self, relay_parent: &Hash) -> usize {
+		self.fetch_tracker
+			.lock()
+			.unwrap()
+			.get(relay_parent)
+			.map(|fetched| fetched.len())
+			.unwrap_or_default()
+	}
+
+	/// The receipts of all generated collations.
+	pub fn committed_receipts(&self) -> HashMap<CandidateHash, CommittedCandidateReceipt> {
+		self.collations
+			.iter()
+			.map(|(candidate_hash, (receipt, _))| (*candidate_hash, receipt.clone()))
+			.collect()
+	}
+}
+
+/// The number of collations of our para fetched at every relay parent.
+///
+/// The claim queue of our core holds `claim_queue_len` claims of our para. The first relay parent
+/// can claim all of them, while every next one only frees a single claim at the end of the queue.
+/// The collations fetched at the previous relay parents keep their claims.
+fn expected_fetches(
+	block_infos: &[BlockInfo],
+	n_collators: usize,
+	claim_queue_len: usize,
+) -> HashMap<Hash, usize> {
+	let mut claimed = 0;
+	block_infos
+		.iter()
+		.enumerate()
+		.map(|(index, block_info)| {
+			let claimable = index + claim_queue_len;
+			let fetched = n_collators.min(claimable - claimed);
+			claimed += fetched;
+			(block_info.hash, fetched)
+		})
+		.collect()
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	let mut info =
+		new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber);
+	info.parent_hash =
+		if block_num == 1 { GENESIS_HASH } else { Hash::repeat_byte((block_num - 1) as u8) };
+	info
+}
+
+fn generate_block_header(info: &BlockInfo) -> (Hash, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+fn generate_genesis_header() -> (Hash, Header) {
+	(
+		GENESIS_HASH,
+		Header {
+			digest: Default::default(),
+			number: 0,
+			parent_hash: Default::default(),
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(_authority_id, requests) => {
+				let Requests::CollationFetchingV2(req) = *requests else {
+					todo!("Wrong requests type in message: {:?}", requests);
+				};
+				let (receipt, pov) =
+					self.collations.get(&req.payload.candidate_hash).expect("pregenerated");
+				self.fetch_tracker
+					.lock()
+					.unwrap()
+					.entry(req.payload.scheduling_parent)
+					.or_default()
+					.insert(req.payload.candidate_hash);
+
+				let response =
+					CollationFetchingResponse::Collation(receipt.to_plain(), pov.as_ref().clone());
+				let _ = req.pending_response.send(Ok((response.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => {
+				todo!("Wrong message type: {:?}", message);
+			},
+		}
+	}
+}
@@ -19,7 +19,9 @@
 use crate::keyring::Keyring;
 use itertools::Itertools;
 use polkadot_node_network_protocol::authority_discovery::AuthorityDiscovery;
-use polkadot_primitives::{AssignmentId, AuthorityDiscoveryId, ValidatorId, ValidatorPair};
+use polkadot_primitives::{
+	AssignmentId, AuthorityDiscoveryId, ValidatorId, ValidatorPair, DEFAULT_SCHEDULING_LOOKAHEAD,
+};
 use rand::thread_rng;
 use rand_distr::{Distribution, Normal, Uniform};
 use sc_network::Multiaddr;
@@ -101,6 +103,9 @@ fn default_max_candidate_depth() -> u32 {
 fn default_allowed_ancestry_len() -> u32 {
 	2
 }
+fn default_scheduling_lookahead() -> u32 {
+	DEFAULT_SCHEDULING_LOOKAHEAD
+}
 
 /// The test input parameters
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -158,6 +163,9 @@ pub struct TestConfiguration {
 	/// Async Backing allowed_ancestry_len
 	#[serde(default = "default_allowed_ancestry_len")]
 	pub allowed_ancestry_len: u32,
+	/// Scheduling lookahead, the length of the claim queue
+	#[serde(default = "default_scheduling_lookahead")]
+	pub scheduling_lookahead: u32,
 }
 
 impl Default for TestConfiguration {
@@ -182,6 +190,7 @@ impl Default for TestConfiguration {
 			minimum_backing_votes: default_minimum_backing_votes(),
 			max_candidate_depth: default_max_candidate_depth(),
 			allowed_ancestry_len: default_allowed_ancestry_len(),
+			scheduling_lookahead: default_scheduling_lookahead(),
 		}
 	}
 }
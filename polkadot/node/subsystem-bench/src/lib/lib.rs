@@ -19,6 +19,7 @@ pub const NODE_UNDER_TEST: u32 = 0;
 
 pub mod approval;
 pub mod availability;
+pub mod collator_protocol;
 pub mod configuration;
 pub(crate) mod display;
 pub mod disputes;
@@ -26,6 +27,8 @@ pub(crate) mod environment;
 pub(crate) mod keyring;
 pub(crate) mod mock;
 pub(crate) mod network;
+pub mod prospective_parachains;
+pub mod replay;
 pub mod statement;
 pub mod usage;
 pub mod utils;
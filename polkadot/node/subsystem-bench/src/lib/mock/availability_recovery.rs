@@ -21,7 +21,8 @@ use std::sync::Arc;
 use futures::FutureExt;
 use polkadot_node_primitives::{AvailableData, BlockData, PoV};
 use polkadot_node_subsystem::{
-	messages::AvailabilityRecoveryMessage, overseer, SpawnedSubsystem, SubsystemError,
+	messages::{AvailabilityRecoveryMessage, AvailabilityRecoveryStrategy, RecoveryOutcome},
+	overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
 use polkadot_primitives::{Hash, HeadData, PersistedValidationData};
@@ -59,19 +60,36 @@ impl MockAvailabilityRecovery {
 				orchestra::FromOrchestra::Communication { msg } => match msg {
 					AvailabilityRecoveryMessage::RecoverAvailableData(receipt, _, _, _, tx) => {
 						gum::debug!(target: LOG_TARGET, "RecoverAvailableData for candidate {:?}", receipt.hash());
-						let available_data = AvailableData {
-							pov: Arc::new(PoV { block_data: BlockData(Vec::new()) }),
-							validation_data: PersistedValidationData {
-								parent_head: HeadData(Vec::new()),
-								relay_parent_number: 0,
-								relay_parent_storage_root: Hash::default(),
-								max_pov_size: 2,
-							},
-						};
-						tx.send(Ok(available_data)).unwrap();
+						tx.send(Ok(dummy_available_data())).unwrap();
+					},
+					AvailabilityRecoveryMessage::RecoverAvailableDataWithOptions(
+						receipt,
+						_,
+						_,
+						tx,
+					) => {
+						gum::debug!(target: LOG_TARGET, "RecoverAvailableDataWithOptions for candidate {:?}", receipt.hash());
+						tx.send(Ok(RecoveryOutcome {
+							available_data: dummy_available_data(),
+							strategy: AvailabilityRecoveryStrategy::AvailabilityStore,
+							stats: Vec::new(),
+						}))
+						.unwrap();
 					},
 				},
 			}
 		}
 	}
 }
+
+fn dummy_available_data() -> AvailableData {
+	AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(Vec::new()) }),
+		validation_data: PersistedValidationData {
+			parent_head: HeadData(Vec::new()),
+			relay_parent_number: 0,
+			relay_parent_storage_root: Hash::default(),
+			max_pov_size: 2,
+		},
+	}
+}
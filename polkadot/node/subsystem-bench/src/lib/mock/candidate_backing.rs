@@ -18,13 +18,17 @@
 
 use crate::{configuration::TestConfiguration, NODE_UNDER_TEST};
 use futures::FutureExt;
-use polkadot_node_primitives::{SignedFullStatementWithPVD, Statement, StatementWithPVD};
+use polkadot_node_primitives::{
+	SignedFullStatement, SignedFullStatementWithPVD, Statement, StatementWithPVD,
+};
 use polkadot_node_subsystem::{
-	messages::CandidateBackingMessage, overseer, SpawnedSubsystem, SubsystemError,
+	messages::{CandidateBackingMessage, CollatorProtocolMessage},
+	overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
 use polkadot_primitives::{
-	CandidateHash, Hash, PersistedValidationData, SigningContext, ValidatorIndex, ValidatorPair,
+	CandidateHash, CommittedCandidateReceiptV2 as CommittedCandidateReceipt, Hash,
+	PersistedValidationData, SigningContext, ValidatorIndex, ValidatorPair,
 };
 use sp_core::Pair;
 use std::collections::HashMap;
@@ -35,6 +39,8 @@ struct MockCandidateBackingState {
 	pair: ValidatorPair,
 	pvd: PersistedValidationData,
 	own_backing_group: Vec<ValidatorIndex>,
+	// Full receipts of the collations we may be asked to second
+	committed_receipts: HashMap<CandidateHash, CommittedCandidateReceipt>,
 }
 
 pub struct MockCandidateBacking {
@@ -49,7 +55,50 @@ impl MockCandidateBacking {
 		pvd: PersistedValidationData,
 		own_backing_group: Vec<ValidatorIndex>,
 	) -> Self {
-		Self { config, state: MockCandidateBackingState { pair, pvd, own_backing_group } }
+		Self {
+			config,
+			state: MockCandidateBackingState {
+				pair,
+				pvd,
+				own_backing_group,
+				committed_receipts: Default::default(),
+			},
+		}
+	}
+
+	/// Allows seconding of the given collations. Every `Second` request for one of them is
+	/// immediately answered with a `Seconded` statement to the collator protocol.
+	pub fn with_committed_receipts(
+		mut self,
+		committed_receipts: HashMap<CandidateHash, CommittedCandidateReceipt>,
+	) -> Self {
+		self.state.committed_receipts = committed_receipts;
+		self
+	}
+
+	fn sign_seconded(
+		&self,
+		scheduling_parent: Hash,
+		candidate_hash: CandidateHash,
+	) -> SignedFullStatement {
+		let receipt = self
+			.state
+			.committed_receipts
+			.get(&candidate_hash)
+			.expect("Collations are generated at test start")
+			.clone();
+		let statement = Statement::Seconded(receipt);
+		let context = SigningContext { parent_hash: scheduling_parent, session_index: 0 };
+		let payload = statement.to_compact().signing_payload(&context);
+
+		SignedFullStatement::new(
+			statement,
+			ValidatorIndex(NODE_UNDER_TEST),
+			self.state.pair.sign(&payload[..]),
+			&context,
+			&self.state.pair.public(),
+		)
+		.unwrap()
 	}
 
 	fn handle_statement(
@@ -164,6 +213,19 @@ impl MockCandidateBacking {
 								ctx.send_message(message).await;
 							}
 						},
+						CandidateBackingMessage::CanSecond(_request, tx) => {
+							let _ = tx.send(true);
+						},
+						CandidateBackingMessage::Second {
+							scheduling_parent, candidate, ..
+						} => {
+							let statement = self.sign_seconded(scheduling_parent, candidate.hash());
+							ctx.send_message(CollatorProtocolMessage::Seconded(
+								scheduling_parent,
+								statement,
+							))
+							.await;
+						},
 						_ => {
 							unimplemented!("Unexpected candidate-backing message")
 						},
@@ -139,6 +139,13 @@ impl MockNetworkBridgeTx {
 					NetworkBridgeTxMessage::ReportPeer(_) => {
 						// ignore rep changes
 					},
+					NetworkBridgeTxMessage::DisconnectPeers(_, _) => {
+						// ignore disconnects, emulated collators stay connected
+					},
+					NetworkBridgeTxMessage::SendCollationMessage(_, _) |
+					NetworkBridgeTxMessage::SendCollationMessages(_) => {
+						// ignore collation notifications, emulated collators don't need them
+					},
 					NetworkBridgeTxMessage::SendValidationMessage(peers, message) => {
 						for peer in peers {
 							self.to_network_interface
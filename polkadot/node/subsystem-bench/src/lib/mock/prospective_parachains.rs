@@ -21,13 +21,23 @@ use polkadot_node_subsystem::{
 	messages::ProspectiveParachainsMessage, overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
-use polkadot_primitives::Hash;
+use polkadot_primitives::{Hash, PersistedValidationData};
+use std::collections::HashMap;
 
-pub struct MockProspectiveParachains {}
+pub struct MockProspectiveParachains {
+	// Validation data per candidate relay parent
+	validation_data: HashMap<Hash, PersistedValidationData>,
+}
 
 impl MockProspectiveParachains {
 	pub fn new() -> Self {
-		Self {}
+		Self { validation_data: Default::default() }
+	}
+
+	/// Creates a mock answering `GetProspectiveValidationData` with the validation data of the
+	/// candidate relay parent.
+	pub fn with_validation_data(validation_data: HashMap<Hash, PersistedValidationData>) -> Self {
+		Self { validation_data }
 	}
 }
 
@@ -62,6 +72,10 @@ impl MockProspectiveParachains {
 						)
 						.unwrap();
 					},
+					ProspectiveParachainsMessage::GetProspectiveValidationData(req, tx) => {
+						tx.send(self.validation_data.get(&req.candidate_relay_parent).cloned())
+							.unwrap();
+					},
 					_ => {
 						unimplemented!("Unexpected chain-api message")
 					},
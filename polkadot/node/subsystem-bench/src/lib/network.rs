@@ -1031,7 +1031,7 @@ impl RequestExt for Requests {
 				}
 			},
 			// Requested by PeerId
-			Requests::AttestedCandidateV2(_) => None,
+			Requests::AttestedCandidateV2(_) | Requests::CollationFetchingV2(_) => None,
 			Requests::DisputeSendingV1(request) => {
 				if let Recipient::Authority(authority_id) = &request.peer {
 					Some(authority_id)
@@ -1051,6 +1051,10 @@ impl RequestExt for Requests {
 				Recipient::Authority(_) => None,
 				Recipient::Peer(peer_id) => Some(peer_id),
 			},
+			Requests::CollationFetchingV2(request) => match &request.peer {
+				Recipient::Authority(_) => None,
+				Recipient::Peer(peer_id) => Some(peer_id),
+			},
 			request => {
 				unimplemented!("peer_id() is not implemented for {:?}", request)
 			},
@@ -1064,6 +1068,7 @@ impl RequestExt for Requests {
 				outgoing_request.pending_response
 			},
 			Requests::DisputeSendingV1(outgoing_request) => outgoing_request.pending_response,
+			Requests::CollationFetchingV2(outgoing_request) => outgoing_request.pending_response,
 			_ => unimplemented!("unsupported request type"),
 		}
 	}
@@ -1083,6 +1088,9 @@ impl RequestExt for Requests {
 			Requests::DisputeSendingV1(outgoing_request) => {
 				std::mem::replace(&mut outgoing_request.pending_response, new_sender)
 			},
+			Requests::CollationFetchingV2(outgoing_request) => {
+				std::mem::replace(&mut outgoing_request.pending_response, new_sender)
+			},
 			_ => unimplemented!("unsupported request type"),
 		}
 	}
@@ -1098,6 +1106,9 @@ impl RequestExt for Requests {
 				outgoing_request.payload.encoded_size()
 			},
 			Requests::DisputeSendingV1(outgoing_request) => outgoing_request.payload.encoded_size(),
+			Requests::CollationFetchingV2(outgoing_request) => {
+				outgoing_request.payload.encoded_size()
+			},
 			_ => unimplemented!("received an unexpected request"),
 		}
 	}
@@ -0,0 +1,223 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Subsystem benchmark for the prospective parachains subsystem.
+//!
+//! Scenario:
+//! - Every para is assigned `cores_per_para` cores, so at every relay chain block it can get a
+//!   chain of that many candidates backed (elastic scaling).
+//! - At every relay chain block the fragment chains of all paras are rebuilt from the backing
+//!   constraints, which require the chain of the previous block to be included.
+//! - For every candidate of the chain the benchmark queries the hypothetical membership, introduces
+//!   it as seconded and marks it as backed, like candidate backing does.
+//! - Finally the backable candidates are requested for each para, like the provisioner does.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies},
+	mock::{
+		chain_api::{ChainApiState, MockChainApi},
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		runtime_api::{MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains,
+	},
+	network::{new_network, NetworkEmulatorHandle, NetworkInterface, NetworkInterfaceReceiver},
+	usage::BenchmarkUsage,
+};
+use colored::Colorize;
+use futures::channel::oneshot;
+use polkadot_node_core_prospective_parachains::ProspectiveParachainsSubsystem;
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_subsystem::messages::{
+	AllMessages, HypotheticalCandidate, HypotheticalMembershipRequest,
+	IntroduceSecondedCandidateRequest, ProspectiveParachainsMessage,
+};
+use polkadot_overseer::{
+	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc, time::Instant};
+pub use test_state::TestState;
+
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::prospective-parachains";
+
+/// Parameters specific to the prospective parachains benchmark
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct ProspectiveParachainsOptions {
+	#[clap(short, long, default_value_t = 3)]
+	/// The number of cores assigned to every para, which is also the length of the candidate
+	/// chain built at every relay chain block.
+	pub cores_per_para: usize,
+}
+
+fn build_overseer(
+	state: &TestState,
+	network: NetworkEmulatorHandle,
+	network_interface: NetworkInterface,
+	network_receiver: NetworkInterfaceReceiver,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		Default::default(),
+		Default::default(),
+		0,
+		MockRuntimeApiCoreState::Scheduled,
+	)
+	.with_claim_queue(state.claim_queue.clone())
+	.with_backing_constraints(state.backing_constraints.clone());
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mock_chain_api = MockChainApi::new(chain_api_state);
+	let subsystem =
+		ProspectiveParachainsSubsystem::new(Metrics::try_register(&dependencies.registry).unwrap());
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network,
+		network_interface.subsystem_sender(),
+		state.test_authorities.clone(),
+	);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, None);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_prospective_parachains(|_| subsystem)
+		.replace_network_bridge_tx(|_| network_bridge_tx)
+		.replace_network_bridge_rx(|_| network_bridge_rx);
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(state: &TestState, with_prometheus_endpoint: bool) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	// The subsystem doesn't use the network, peers never receive anything.
+	let (network, network_interface, network_receiver) =
+		new_network(&state.config, &dependencies, &state.test_authorities, vec![]);
+	let (overseer, overseer_handle) =
+		build_overseer(state, network.clone(), network_interface, network_receiver, &dependencies);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+pub async fn benchmark_prospective_parachains(
+	env: &mut TestEnvironment,
+	state: &TestState,
+) -> BenchmarkUsage {
+	let config = env.config().clone();
+
+	let test_start = Instant::now();
+
+	for block_info in state.block_infos.iter() {
+		let block_num = block_info.number as usize;
+		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
+		env.metrics().set_current_block(block_num);
+		env.import_block(block_info.clone()).await;
+
+		let block_start = Instant::now();
+		let mut backable_count = 0;
+		for para_id in state.para_ids.iter() {
+			let chain = state.candidates.get(&(block_info.hash, *para_id)).expect("pregenerated");
+			for (receipt, pvd) in chain.iter() {
+				let candidate_hash = receipt.hash();
+
+				let (tx, rx) = oneshot::channel();
+				env.send_message(AllMessages::ProspectiveParachains(
+					ProspectiveParachainsMessage::GetHypotheticalMembership(
+						HypotheticalMembershipRequest {
+							candidates: vec![HypotheticalCandidate::Complete {
+								candidate_hash,
+								receipt: Arc::new(receipt.clone()),
+								persisted_validation_data: pvd.clone(),
+							}],
+							fragment_chain_relay_parent: Some(block_info.hash),
+						},
+						tx,
+					),
+				))
+				.await;
+				let _ = rx.await.expect("Hypothetical membership is always answered");
+
+				let (tx, rx) = oneshot::channel();
+				env.send_message(AllMessages::ProspectiveParachains(
+					ProspectiveParachainsMessage::IntroduceSecondedCandidate(
+						IntroduceSecondedCandidateRequest {
+							candidate_para: *para_id,
+							candidate_receipt: receipt.clone(),
+							persisted_validation_data: pvd.clone(),
+						},
+						tx,
+					),
+				))
+				.await;
+				let introduced = rx.await.expect("Seconded candidates are always answered");
+				assert!(introduced, "Candidate {candidate_hash:?} was not introduced");
+
+				env.send_message(AllMessages::ProspectiveParachains(
+					ProspectiveParachainsMessage::CandidateBacked(*para_id, candidate_hash),
+				))
+				.await;
+			}
+
+			let (tx, rx) = oneshot::channel();
+			env.send_message(AllMessages::ProspectiveParachains(
+				ProspectiveParachainsMessage::GetBackableCandidates {
+					leaf: block_info.hash,
+					para_id: *para_id,
+					count: chain.len() as u32,
+					ancestors: HashSet::new(),
+					sender: tx,
+				},
+			))
+			.await;
+			let backable = rx.await.expect("Backable candidates are always answered");
+			if backable.len() != chain.len() {
+				gum::warn!(target: LOG_TARGET, ?para_id, "Expected {} backable candidates, got {}", chain.len(), backable.len());
+			}
+			backable_count += backable.len();
+		}
+
+		gum::info!(target: LOG_TARGET, "{} backable candidates in {}", backable_count, format!("{:?}ms", block_start.elapsed().as_millis()).cyan());
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{duration:?}ms").cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	env.stop().await;
+	env.collect_resource_usage(&["prospective-parachains"], false)
+}
@@ -0,0 +1,205 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	configuration::{TestAuthorities, TestConfiguration},
+	environment::GENESIS_HASH,
+	prospective_parachains::ProspectiveParachainsOptions,
+};
+use codec::Encode;
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	async_backing::{Constraints, InboundHrmpLimitations},
+	BlockNumber, CandidateReceiptV2 as CandidateReceipt,
+	CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreIndex, Hash, HeadData, Header,
+	Id as ParaId, PersistedValidationData, ValidationCode, ValidationCodeHash,
+};
+use polkadot_primitives_test_helpers::make_candidate_v2;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+// Must match the max PoV size used by the test helpers to build the validation data.
+const MAX_POV_SIZE: u32 = 1_000_000;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers, including genesis
+	pub block_headers: HashMap<Hash, Header>,
+	// All paras, each one is assigned `cores_per_para` consecutive cores
+	pub para_ids: Vec<ParaId>,
+	// The claim queue
+	pub claim_queue: BTreeMap<CoreIndex, VecDeque<ParaId>>,
+	// Backing constraints per block and para
+	pub backing_constraints: HashMap<(Hash, ParaId), Constraints>,
+	// Generated candidate receipts per block to feed the runtime api
+	pub candidate_receipts: HashMap<Hash, Vec<CandidateReceipt>>,
+	// Chains of candidates per block and para, one candidate per core of the para
+	pub candidates:
+		HashMap<(Hash, ParaId), Vec<(CommittedCandidateReceipt, PersistedValidationData)>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &ProspectiveParachainsOptions) -> Self {
+		let config = config.clone();
+		let test_authorities = config.generate_authorities();
+		let cores_per_para = options.cores_per_para;
+		assert!(
+			cores_per_para > 0 && config.n_cores >= cores_per_para,
+			"Every para needs at least one core"
+		);
+		let n_paras = config.n_cores / cores_per_para;
+		let para_ids = (1..=n_paras).map(|para| ParaId::new(para as u32)).collect::<Vec<_>>();
+		let claim_queue = (0..n_paras * cores_per_para)
+			.map(|core_idx| {
+				let para_id = para_ids[core_idx / cores_per_para];
+				(
+					CoreIndex(core_idx as u32),
+					std::iter::repeat(para_id).take(config.scheduling_lookahead as usize).collect(),
+				)
+			})
+			.collect();
+
+		let block_infos: Vec<BlockInfo> =
+			(1..=config.num_blocks).map(generate_block_info).collect();
+		let block_headers = std::iter::once(generate_genesis_header())
+			.chain(block_infos.iter().map(generate_block_header))
+			.collect();
+
+		let validation_code_hash = ValidationCode(Vec::new()).hash();
+		let mut backing_constraints = HashMap::new();
+		let mut candidate_receipts: HashMap<Hash, Vec<CandidateReceipt>> = HashMap::new();
+		let mut candidates = HashMap::new();
+		for block_info in block_infos.iter() {
+			for &para_id in para_ids.iter() {
+				// The chain built on top of the previous block has been included.
+				let required_parent = head_data(para_id, block_info.number - 1, cores_per_para - 1);
+				let min_relay_parent_number =
+					block_info.number.saturating_sub(config.scheduling_lookahead.saturating_sub(1));
+				backing_constraints.insert(
+					(block_info.hash, para_id),
+					make_constraints(
+						min_relay_parent_number,
+						required_parent.clone(),
+						validation_code_hash,
+					),
+				);
+
+				let mut parent_head = required_parent;
+				let chain = (0..cores_per_para)
+					.map(|depth| {
+						let output_head = head_data(para_id, block_info.number, depth);
+						let (receipt, pvd) = make_candidate_v2(
+							block_info.hash,
+							block_info.number,
+							para_id,
+							parent_head.clone(),
+							output_head.clone(),
+							validation_code_hash,
+						);
+						parent_head = output_head;
+						(receipt, pvd)
+					})
+					.collect::<Vec<_>>();
+
+				candidate_receipts
+					.entry(block_info.hash)
+					.or_default()
+					.extend(chain.iter().map(|(receipt, _)| receipt.to_plain()));
+				candidates.insert((block_info.hash, para_id), chain);
+			}
+		}
+
+		Self {
+			config,
+			test_authorities,
+			block_infos,
+			block_headers,
+			para_ids,
+			claim_queue,
+			backing_constraints,
+			candidate_receipts,
+			candidates,
+		}
+	}
+}
+
+fn head_data(para_id: ParaId, block_number: BlockNumber, depth: usize) -> HeadData {
+	HeadData((para_id, block_number, depth as u32).encode())
+}
+
+fn make_constraints(
+	min_relay_parent_number: BlockNumber,
+	required_parent: HeadData,
+	validation_code_hash: ValidationCodeHash,
+) -> Constraints {
+	Constraints {
+		min_relay_parent_number,
+		max_pov_size: MAX_POV_SIZE,
+		max_code_size: 1_000_000,
+		max_head_data_size: 20480,
+		ump_remaining: 10,
+		ump_remaining_bytes: 1_000,
+		max_ump_num_per_candidate: 10,
+		dmp_remaining_messages: vec![],
+		hrmp_inbound: InboundHrmpLimitations { valid_watermarks: vec![] },
+		hrmp_channels_out: vec![],
+		max_hrmp_num_per_candidate: 0,
+		required_parent,
+		validation_code_hash,
+		upgrade_restriction: None,
+		future_validation_code: None,
+	}
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	let mut info =
+		new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber);
+	info.parent_hash =
+		if block_num == 1 { GENESIS_HASH } else { Hash::repeat_byte((block_num - 1) as u8) };
+	info
+}
+
+fn generate_block_header(info: &BlockInfo) -> (Hash, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+fn generate_genesis_header() -> (Hash, Header) {
+	(
+		GENESIS_HASH,
+		Header {
+			digest: Default::default(),
+			number: 0,
+			parent_hash: Default::default(),
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
@@ -0,0 +1,260 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Access to a parachains DB recorded by a validator.
+
+use crate::replay::ReplayDatabase;
+use kvdb::{DBKeyValue, DBOp, DBTransaction, DBValue, KeyValueDB};
+use polkadot_node_subsystem_util::database::{kvdb_impl, paritydb_impl, Database};
+use std::{
+	collections::BTreeMap,
+	io::{self, Result},
+	path::Path,
+	sync::{Arc, RwLock},
+};
+
+// Must match the current column layout of the parachains DB in `polkadot-service`.
+const NUM_COLUMNS: u32 = 6;
+pub const COL_AVAILABILITY_DATA: u32 = 0;
+pub const COL_AVAILABILITY_META: u32 = 1;
+pub const COL_APPROVAL_DATA: u32 = 2;
+const COL_CHAIN_SELECTION_DATA: u32 = 3;
+pub const COL_DISPUTE_COORDINATOR_DATA: u32 = 4;
+const ORDERED_COL: &[u32] =
+	&[COL_AVAILABILITY_META, COL_CHAIN_SELECTION_DATA, COL_DISPUTE_COORDINATOR_DATA];
+
+/// Opens the recorded DB at `path`.
+///
+/// The recorded DB is opened read-only, RocksDB as a secondary instance. The returned DB keeps all
+/// changes in memory.
+pub fn open(path: &Path, database: ReplayDatabase) -> Result<Arc<dyn Database>> {
+	let mut secondary_dir = None;
+	let recorded: Arc<dyn Database> = match database {
+		ReplayDatabase::RocksDb => {
+			let dir = tempfile::tempdir()?;
+			let mut config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
+			config.create_if_missing = false;
+			config.secondary = Some(dir.path().to_path_buf());
+			let path = path.to_str().ok_or_else(|| {
+				io::Error::new(io::ErrorKind::Other, format!("Bad database path: {path:?}"))
+			})?;
+			let db = kvdb_rocksdb::Database::open(&config, path)?;
+			secondary_dir = Some(dir);
+			Arc::new(kvdb_impl::DbAdapter::new(db, ORDERED_COL))
+		},
+		ReplayDatabase::ParityDb => {
+			let mut options = parity_db::Options::with_columns(path, NUM_COLUMNS as u8);
+			for col in ORDERED_COL {
+				options.columns[*col as usize].btree_index = true;
+			}
+			let db = parity_db::Db::open_read_only(&options)
+				.map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{e:?}")))?;
+			Arc::new(paritydb_impl::DbAdapter::new(db, ORDERED_COL))
+		},
+	};
+
+	Ok(Arc::new(OverlayDb { _secondary_dir: secondary_dir, ..OverlayDb::new(recorded) }))
+}
+
+/// Changes of a column, `None` marks a deleted key.
+type ColumnChanges = BTreeMap<Vec<u8>, Option<DBValue>>;
+
+/// A copy-on-write overlay on top of the recorded DB.
+struct OverlayDb {
+	recorded: Arc<dyn Database>,
+	changes: RwLock<Vec<ColumnChanges>>,
+	/// Files of the secondary RocksDB instance, removed once the DB is dropped.
+	_secondary_dir: Option<tempfile::TempDir>,
+}
+
+impl OverlayDb {
+	fn new(recorded: Arc<dyn Database>) -> Self {
+		Self {
+			recorded,
+			changes: RwLock::new(vec![Default::default(); NUM_COLUMNS as usize]),
+			_secondary_dir: None,
+		}
+	}
+
+	fn changes_with_prefix(&self, col: u32, prefix: &[u8]) -> Vec<(Vec<u8>, Option<DBValue>)> {
+		self.changes.read().expect("Poisoned lock")[col as usize]
+			.range(prefix.to_vec()..)
+			.take_while(|(key, _)| key.starts_with(prefix))
+			.map(|(key, value)| (key.clone(), value.clone()))
+			.collect()
+	}
+}
+
+impl Database for OverlayDb {
+	fn is_indexed_column(&self, col: u32) -> bool {
+		self.recorded.is_indexed_column(col)
+	}
+}
+
+impl KeyValueDB for OverlayDb {
+	fn get(&self, col: u32, key: &[u8]) -> Result<Option<DBValue>> {
+		if let Some(value) = self.changes.read().expect("Poisoned lock")[col as usize].get(key) {
+			return Ok(value.clone());
+		}
+		self.recorded.get(col, key)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Result<Option<DBValue>> {
+		self.iter_with_prefix(col, prefix)
+			.next()
+			.transpose()
+			.map(|kv| kv.map(|(_, v)| v))
+	}
+
+	fn write(&self, transaction: DBTransaction) -> Result<()> {
+		for op in transaction.ops {
+			match op {
+				DBOp::Insert { col, key, value } => {
+					self.changes.write().expect("Poisoned lock")[col as usize]
+						.insert(key.to_vec(), Some(value));
+				},
+				DBOp::Delete { col, key } => {
+					self.changes.write().expect("Poisoned lock")[col as usize]
+						.insert(key.to_vec(), None);
+				},
+				DBOp::DeletePrefix { col, prefix } => {
+					let keys = self
+						.iter_with_prefix(col, &prefix)
+						.map(|kv| kv.map(|(key, _)| key.to_vec()))
+						.collect::<Result<Vec<_>>>()?;
+					let mut changes = self.changes.write().expect("Poisoned lock");
+					for key in keys {
+						changes[col as usize].insert(key, None);
+					}
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = Result<DBKeyValue>> + 'a> {
+		self.iter_with_prefix(col, &[])
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = Result<DBKeyValue>> + 'a> {
+		let mut changes = self.changes_with_prefix(col, prefix).into_iter().peekable();
+		let mut recorded = self.recorded.iter_with_prefix(col, prefix).peekable();
+
+		// Both iterators are ordered by key, changes take precedence over recorded entries.
+		Box::new(std::iter::from_fn(move || loop {
+			let recorded_key = match recorded.peek() {
+				Some(Ok((key, _))) => Some(key.to_vec()),
+				Some(Err(_)) => return recorded.next(),
+				None => None,
+			};
+			let changed_key = changes.peek().map(|(key, _)| key.clone());
+			match (recorded_key, changed_key) {
+				(None, None) => return None,
+				(Some(recorded_key), Some(changed_key)) if recorded_key < changed_key => {
+					return recorded.next()
+				},
+				(Some(_), None) => return recorded.next(),
+				(recorded_key, Some(changed_key)) => {
+					if recorded_key == Some(changed_key) {
+						let _ = recorded.next();
+					}
+					if let Some((key, Some(value))) = changes.next() {
+						return Some(Ok((key.into(), value)));
+					}
+				},
+			}
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const COL: u32 = COL_AVAILABILITY_META;
+
+	fn overlay(recorded: &[(&[u8], &[u8])]) -> OverlayDb {
+		let db = kvdb_memorydb::create(NUM_COLUMNS);
+		let mut transaction = DBTransaction::new();
+		for (key, value) in recorded {
+			transaction.put(COL, key, value);
+		}
+		db.write(transaction).unwrap();
+		OverlayDb::new(Arc::new(kvdb_impl::DbAdapter::new(db, ORDERED_COL)))
+	}
+
+	fn entries(db: &OverlayDb, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+		db.iter_with_prefix(COL, prefix)
+			.map(|kv| kv.map(|(key, value)| (key.to_vec(), value)))
+			.collect::<Result<_>>()
+			.unwrap()
+	}
+
+	#[test]
+	fn set_shadows_recorded_entries() {
+		let db = overlay(&[(b"a1", b"recorded"), (b"a3", b"recorded"), (b"b1", b"recorded")]);
+		let mut transaction = DBTransaction::new();
+		transaction.put(COL, b"a1", b"changed");
+		transaction.put(COL, b"a2", b"added");
+		db.write(transaction).unwrap();
+
+		assert_eq!(db.get(COL, b"a1").unwrap(), Some(b"changed".to_vec()));
+		assert_eq!(
+			entries(&db, b"a"),
+			vec![
+				(b"a1".to_vec(), b"changed".to_vec()),
+				(b"a2".to_vec(), b"added".to_vec()),
+				(b"a3".to_vec(), b"recorded".to_vec()),
+			]
+		);
+		assert_eq!(db.get_by_prefix(COL, b"a").unwrap(), Some(b"changed".to_vec()));
+	}
+
+	#[test]
+	fn delete_hides_recorded_entries() {
+		let db = overlay(&[(b"a1", b"recorded"), (b"a2", b"recorded")]);
+		let mut transaction = DBTransaction::new();
+		transaction.delete(COL, b"a1");
+		db.write(transaction).unwrap();
+
+		assert_eq!(db.get(COL, b"a1").unwrap(), None);
+		assert_eq!(entries(&db, b"a"), vec![(b"a2".to_vec(), b"recorded".to_vec())]);
+		assert_eq!(db.get_by_prefix(COL, b"a").unwrap(), Some(b"recorded".to_vec()));
+	}
+
+	#[test]
+	fn delete_prefix_hides_recorded_and_changed_entries() {
+		let db = overlay(&[(b"a1", b"recorded"), (b"b1", b"recorded")]);
+		let mut transaction = DBTransaction::new();
+		transaction.put(COL, b"a2", b"added");
+		transaction.delete_prefix(COL, b"a");
+		transaction.put(COL, b"a3", b"added after");
+		db.write(transaction).unwrap();
+
+		assert_eq!(db.get(COL, b"a1").unwrap(), None);
+		assert_eq!(db.get(COL, b"a2").unwrap(), None);
+		assert_eq!(entries(&db, b"a"), vec![(b"a3".to_vec(), b"added after".to_vec())]);
+		assert_eq!(
+			entries(&db, &[]),
+			vec![(b"a3".to_vec(), b"added after".to_vec()), (b"b1".to_vec(), b"recorded".to_vec()),]
+		);
+	}
+}
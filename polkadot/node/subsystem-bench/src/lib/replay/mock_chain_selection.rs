@@ -0,0 +1,66 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::replay::{ReplayClock, ReplayEvent, TimelineEntry, LOG_TARGET};
+use futures::FutureExt;
+use polkadot_node_primitives::approval::time::Clock;
+use polkadot_node_subsystem::{overseer, SpawnedSubsystem, SubsystemError};
+use polkadot_node_subsystem_types::messages::ChainSelectionMessage;
+use std::sync::{Arc, Mutex};
+
+/// Mock ChainSelection subsystem recording the decisions approval-voting and the
+/// dispute-coordinator take during the replay.
+pub struct MockChainSelection {
+	pub clock: ReplayClock,
+	pub events: Arc<Mutex<Vec<TimelineEntry>>>,
+}
+
+#[overseer::subsystem(ChainSelection, error=SubsystemError, prefix=self::overseer)]
+impl<Context> MockChainSelection {
+	fn start(self, ctx: Context) -> SpawnedSubsystem {
+		let future = self.run(ctx).map(|_| Ok(())).boxed();
+
+		SpawnedSubsystem { name: "mock-chain-selection", future }
+	}
+}
+
+#[overseer::contextbounds(ChainSelection, prefix = self::overseer)]
+impl MockChainSelection {
+	async fn run<Context>(self, mut ctx: Context) {
+		loop {
+			let msg = ctx.recv().await.expect("Should not fail");
+			let event = match msg {
+				orchestra::FromOrchestra::Signal(_) => continue,
+				orchestra::FromOrchestra::Communication { msg } => match msg {
+					ChainSelectionMessage::Approved(hash) => ReplayEvent::BlockApproved { hash },
+					ChainSelectionMessage::RevertBlocks(blocks) => {
+						ReplayEvent::BlocksReverted { blocks }
+					},
+					msg => {
+						gum::debug!(target: LOG_TARGET, ?msg, "Unexpected chain selection message");
+						continue;
+					},
+				},
+			};
+
+			gum::info!(target: LOG_TARGET, "{event}");
+			self.events
+				.lock()
+				.expect("Poisoned lock")
+				.push(TimelineEntry { tick: self.clock.tick_now(), event });
+		}
+	}
+}
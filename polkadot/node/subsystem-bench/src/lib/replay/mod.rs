@@ -0,0 +1,359 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline replay of a parachains DB recorded by a validator.
+//!
+//! The DB keeps the state of the subsystems rather than the messages they received, so the
+//! replay restarts approval-voting, the dispute-coordinator and the availability-store on top
+//! of the recorded state and lets them make their decisions again:
+//! - the recorded unfinalized blocks are imported in order, with the clock set to their slot;
+//! - the clock then moves forward until all recorded assignments are either approved or no-shows,
+//!   so pending wakeups of approval-voting fire;
+//! - finally the subsystems are asked which chains they would vote on and what they keep.
+//!
+//! Signatures can't be checked again without the session keys of the recording node, so the
+//! replayed subsystems run with an empty keystore: they neither sign nor participate.
+//! The recorded DB is never written to, the replayed subsystems write to an in-memory overlay.
+//! The node must be stopped to open its DB. Pruning of the availability-store follows the
+//! wall clock and doesn't happen during the first five minutes of the replay.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies},
+	mock::{
+		chain_api::{ChainApiState, MockChainApi},
+		runtime_api::{MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains, TestSyncOracle,
+	},
+	network::new_network,
+	usage::BenchmarkUsage,
+};
+use futures::channel::oneshot;
+use mock_chain_selection::MockChainSelection;
+use polkadot_node_core_approval_voting::{ApprovalVotingSubsystem, Config as ApprovalVotingConfig};
+use polkadot_node_core_av_store::{AvailabilityStoreSubsystem, Config as AvailabilityStoreConfig};
+use polkadot_node_core_dispute_coordinator::{
+	Config as DisputeCoordinatorConfig, DisputeCoordinatorSubsystem,
+};
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_primitives::{
+	approval::time::{Clock, Tick, TICK_DURATION_MILLIS},
+	DisputeStatus,
+};
+use polkadot_node_subsystem_types::messages::{
+	ApprovalVotingMessage, AvailabilityStoreMessage, DisputeCoordinatorMessage,
+};
+use polkadot_overseer::{
+	AllMessages, Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+pub use report::{
+	ApprovalDecision, AvailabilityDecision, DisputeDecision, ReplayEvent, ReplayReport,
+	TimelineEntry, UndisputedChainDecision,
+};
+use sc_keystore::LocalKeystore;
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use std::{
+	future::Future,
+	path::PathBuf,
+	pin::Pin,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+pub use test_state::TestState;
+
+mod db;
+mod mock_chain_selection;
+mod report;
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::replay";
+
+// How often a pending `Clock::wait` checks the replay clock.
+const CLOCK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+// How long the subsystems get to process a step of the replay.
+const SETTLE_DURATION: Duration = Duration::from_millis(500);
+// Finalized data is kept by the availability-store for a day, as on a validator.
+const KEEP_FINALIZED_FOR_HOURS: u32 = 25;
+
+/// The backend of the recorded DB.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, clap::ValueEnum)]
+pub enum ReplayDatabase {
+	RocksDb,
+	ParityDb,
+}
+
+/// Parameters specific to the replay
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct ReplayOptions {
+	#[clap(long)]
+	/// Path of the recorded parachains DB, e.g.
+	/// `<base-path>/chains/<chain>/db/full/parachains/db`.
+	pub db_path: PathBuf,
+	#[clap(long, value_enum, default_value_t = ReplayDatabase::RocksDb)]
+	/// The backend of the recorded DB.
+	pub database: ReplayDatabase,
+	#[clap(long, default_value_t = 6000)]
+	/// The relay chain slot duration, in milliseconds.
+	pub slot_duration_millis: u64,
+}
+
+/// A clock that only moves when the replay moves it.
+#[derive(Clone)]
+pub struct ReplayClock {
+	now: Arc<AtomicU64>,
+}
+
+impl ReplayClock {
+	fn new(tick: Tick) -> Self {
+		Self { now: Arc::new(AtomicU64::new(tick)) }
+	}
+
+	/// Moves the clock forward to `tick`, it never goes back.
+	pub fn set(&self, tick: Tick) {
+		self.now.fetch_max(tick, Ordering::SeqCst);
+	}
+}
+
+impl Clock for ReplayClock {
+	fn tick_now(&self) -> Tick {
+		self.now.load(Ordering::SeqCst)
+	}
+
+	fn wait(&self, tick: Tick) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
+		let now = self.now.clone();
+		Box::pin(async move {
+			while now.load(Ordering::SeqCst) < tick {
+				tokio::time::sleep(CLOCK_POLL_INTERVAL).await;
+			}
+		})
+	}
+}
+
+fn build_overseer(
+	state: &TestState,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		state.candidate_events.clone(),
+		None,
+		state.session_index,
+		MockRuntimeApiCoreState::Occupied,
+	);
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mock_chain_api = MockChainApi::new(chain_api_state);
+	let mock_chain_selection =
+		MockChainSelection { clock: state.clock.clone(), events: state.replayed_events.clone() };
+	let keystore = Arc::new(LocalKeystore::in_memory());
+	let approval_voting = ApprovalVotingSubsystem::with_config_and_clock(
+		ApprovalVotingConfig {
+			col_approval_data: db::COL_APPROVAL_DATA,
+			slot_duration_millis: state.slot_duration_millis,
+		},
+		state.db.clone(),
+		keystore.clone(),
+		Box::new(TestSyncOracle {}),
+		Metrics::try_register(&dependencies.registry).unwrap(),
+		Arc::new(state.clock.clone()),
+		Arc::new(SpawnGlue(spawn_task_handle.clone())),
+		1,
+		Duration::from_secs(1),
+	);
+	let dispute_coordinator = DisputeCoordinatorSubsystem::new(
+		state.db.clone(),
+		DisputeCoordinatorConfig { col_dispute_data: db::COL_DISPUTE_COORDINATOR_DATA },
+		keystore,
+		Metrics::try_register(&dependencies.registry).unwrap(),
+	);
+	let availability_store = AvailabilityStoreSubsystem::new(
+		state.db.clone(),
+		AvailabilityStoreConfig {
+			col_data: db::COL_AVAILABILITY_DATA,
+			col_meta: db::COL_AVAILABILITY_META,
+			keep_finalized_for: KEEP_FINALIZED_FOR_HOURS,
+			archive_available_data: false,
+		},
+		Box::new(TestSyncOracle {}),
+		Metrics::try_register(&dependencies.registry).unwrap(),
+	);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_chain_selection(|_| mock_chain_selection)
+		.replace_approval_voting(|_| approval_voting)
+		.replace_dispute_coordinator(|_| dispute_coordinator)
+		.replace_availability_store(|_| availability_store);
+
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(state: &TestState, with_prometheus_endpoint: bool) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	let (network, _network_interface, _network_receiver) =
+		new_network(&state.config, &dependencies, &state.test_authorities, vec![]);
+	let (overseer, overseer_handle) = build_overseer(state, &dependencies);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+/// Replays the recorded blocks and collects the decisions of the replayed subsystems.
+pub async fn replay(
+	env: &mut TestEnvironment,
+	state: &TestState,
+) -> (ReplayReport, BenchmarkUsage) {
+	for (block, block_info) in state.blocks.iter().zip(state.block_infos.iter()) {
+		gum::info!(target: LOG_TARGET, "Replaying block #{} {:?}", block.block_number, block.block_hash);
+		state.clock.set(state.block_tick(block));
+		env.import_block(block_info.clone()).await;
+		tokio::time::sleep(SETTLE_DURATION).await;
+	}
+
+	gum::info!(target: LOG_TARGET, "Waiting for the recorded assignments to conclude");
+	state.clock.set(state.final_tick());
+	tokio::time::sleep(SETTLE_DURATION).await;
+
+	let mut report = ReplayReport { timeline: state.recorded_timeline(), ..Default::default() };
+	let (finalized_number, finalized_hash) = state.finalized_block();
+
+	for leaf in state.leaves() {
+		let leaf_id = (leaf.block_number, leaf.block_hash);
+
+		let (tx, rx) = oneshot::channel();
+		env.send_message(AllMessages::ApprovalVoting(ApprovalVotingMessage::ApprovedAncestor(
+			leaf.block_hash,
+			finalized_number,
+			tx,
+		)))
+		.await;
+		let approved_ancestor = rx
+			.await
+			.ok()
+			.flatten()
+			.map(|approved_ancestor| (approved_ancestor.number, approved_ancestor.hash));
+		report.approvals.push(ApprovalDecision { leaf: leaf_id, approved_ancestor });
+
+		let (tx, rx) = oneshot::channel();
+		env.send_message(AllMessages::DisputeCoordinator(
+			DisputeCoordinatorMessage::DetermineUndisputedChain {
+				base: (finalized_number, finalized_hash),
+				block_descriptions: state.block_descriptions(leaf),
+				tx,
+			},
+		))
+		.await;
+		if let Ok(undisputed) = rx.await {
+			report
+				.undisputed_chains
+				.push(UndisputedChainDecision { leaf: leaf_id, undisputed });
+		}
+	}
+
+	let (tx, rx) = oneshot::channel();
+	env.send_message(AllMessages::DisputeCoordinator(DisputeCoordinatorMessage::RecentDisputes(
+		tx,
+	)))
+	.await;
+	let recent_disputes = rx.await.unwrap_or_default();
+
+	let (tx, rx) = oneshot::channel();
+	env.send_message(AllMessages::DisputeCoordinator(
+		DisputeCoordinatorMessage::QueryCandidateVotes(
+			recent_disputes.keys().copied().collect(),
+			tx,
+		),
+	))
+	.await;
+	let votes = rx.await.unwrap_or_default();
+
+	for (session, candidate, votes) in votes {
+		let Some(status) = recent_disputes.get(&(session, candidate)).copied() else { continue };
+		let concluded = match status {
+			DisputeStatus::ConcludedFor(timestamp) => Some((timestamp, true)),
+			DisputeStatus::ConcludedAgainst(timestamp) => Some((timestamp, false)),
+			DisputeStatus::Active | DisputeStatus::Confirmed => None,
+		};
+		if let Some((timestamp, valid)) = concluded {
+			report.timeline.push(TimelineEntry {
+				tick: timestamp * 1000 / TICK_DURATION_MILLIS,
+				event: ReplayEvent::DisputeConcluded { session, candidate, valid },
+			});
+		}
+		report.disputes.push(DisputeDecision {
+			session,
+			candidate,
+			status,
+			valid_votes: votes.valid.raw().len(),
+			invalid_votes: votes.invalid.len(),
+		});
+	}
+
+	for candidate in state.candidates.keys().copied() {
+		let (tx, rx) = oneshot::channel();
+		env.send_message(AllMessages::AvailabilityStore(
+			AvailabilityStoreMessage::QueryDataAvailability(candidate, tx),
+		))
+		.await;
+		let data_available = rx.await.unwrap_or_default();
+
+		let (tx, rx) = oneshot::channel();
+		env.send_message(AllMessages::AvailabilityStore(AvailabilityStoreMessage::QueryAllChunks(
+			candidate, tx,
+		)))
+		.await;
+		let chunks = rx.await.map(|chunks| chunks.len()).unwrap_or_default();
+
+		report
+			.availability
+			.push(AvailabilityDecision { candidate, data_available, chunks });
+	}
+
+	report
+		.timeline
+		.extend(state.replayed_events.lock().expect("Poisoned lock").drain(..));
+	report.timeline.sort_by_key(|entry| entry.tick);
+
+	env.stop().await;
+	let usage = env.collect_resource_usage(
+		&["approval-voting", "dispute-coordinator", "availability-store"],
+		false,
+	);
+
+	(report, usage)
+}
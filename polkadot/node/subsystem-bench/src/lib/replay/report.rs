@@ -0,0 +1,190 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The outcome of a replay.
+
+use colored::Colorize;
+use polkadot_node_primitives::{
+	approval::time::{Tick, TICK_DURATION_MILLIS},
+	DisputeStatus,
+};
+use polkadot_primitives::{
+	BlockNumber, CandidateHash, DelayTranche, Hash, SessionIndex, ValidatorIndex,
+};
+use std::fmt::{self, Display};
+
+/// Something that happened at a given tick.
+#[derive(Debug, Clone)]
+pub struct TimelineEntry {
+	pub tick: Tick,
+	pub event: ReplayEvent,
+}
+
+/// Events of the timeline. Block imports and assignments are recorded in the DB, the rest are
+/// reached by the subsystems during the replay.
+#[derive(Debug, Clone)]
+pub enum ReplayEvent {
+	/// A relay chain block was imported.
+	BlockImported {
+		number: BlockNumber,
+		hash: Hash,
+		session: SessionIndex,
+		candidates: usize,
+		/// The number of candidates approved before the recording ended.
+		approved: usize,
+	},
+	/// A validator was assigned to check a candidate.
+	Assignment {
+		block: Hash,
+		candidate: CandidateHash,
+		validator: ValidatorIndex,
+		tranche: DelayTranche,
+	},
+	/// A dispute concluded.
+	DisputeConcluded { session: SessionIndex, candidate: CandidateHash, valid: bool },
+	/// Approval-voting approved a block.
+	BlockApproved { hash: Hash },
+	/// The dispute-coordinator asked to revert blocks.
+	BlocksReverted { blocks: Vec<(BlockNumber, Hash)> },
+}
+
+/// The highest approved ancestor of a leaf, according to approval-voting.
+#[derive(Debug, Clone)]
+pub struct ApprovalDecision {
+	pub leaf: (BlockNumber, Hash),
+	pub approved_ancestor: Option<(BlockNumber, Hash)>,
+}
+
+/// The highest block of a leaf's chain without disputed candidates, according to the
+/// dispute-coordinator.
+#[derive(Debug, Clone)]
+pub struct UndisputedChainDecision {
+	pub leaf: (BlockNumber, Hash),
+	pub undisputed: (BlockNumber, Hash),
+}
+
+/// The state of a dispute, according to the dispute-coordinator.
+#[derive(Debug, Clone)]
+pub struct DisputeDecision {
+	pub session: SessionIndex,
+	pub candidate: CandidateHash,
+	pub status: DisputeStatus,
+	pub valid_votes: usize,
+	pub invalid_votes: usize,
+}
+
+/// The data kept for a candidate, according to the availability-store.
+#[derive(Debug, Clone)]
+pub struct AvailabilityDecision {
+	pub candidate: CandidateHash,
+	pub data_available: bool,
+	pub chunks: usize,
+}
+
+/// The timeline of a replay and the decisions the subsystems reached.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+	pub timeline: Vec<TimelineEntry>,
+	pub approvals: Vec<ApprovalDecision>,
+	pub undisputed_chains: Vec<UndisputedChainDecision>,
+	pub disputes: Vec<DisputeDecision>,
+	pub availability: Vec<AvailabilityDecision>,
+}
+
+impl Display for ReplayEvent {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::BlockImported { number, hash, session, candidates, approved } => write!(
+				f,
+				"Block #{number} {hash:?} imported in session {session}, {approved}/{candidates} candidates approved"
+			),
+			Self::Assignment { block, candidate, validator, tranche } => write!(
+				f,
+				"Validator {} assigned to {candidate:?} of block {block:?} in tranche {tranche}",
+				validator.0
+			),
+			Self::DisputeConcluded { session, candidate, valid } => write!(
+				f,
+				"Dispute for {candidate:?} of session {session} concluded {}",
+				if *valid { "valid" } else { "invalid" }
+			),
+			Self::BlockApproved { hash } => write!(f, "Block {hash:?} approved"),
+			Self::BlocksReverted { blocks } => write!(f, "Blocks reverted: {blocks:?}"),
+		}
+	}
+}
+
+impl Display for ReplayReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let start = self.timeline.first().map(|entry| entry.tick).unwrap_or_default();
+
+		writeln!(f, "{}", "Timeline".bright_purple())?;
+		for entry in self.timeline.iter() {
+			let elapsed = entry.tick.saturating_sub(start) * TICK_DURATION_MILLIS;
+			writeln!(f, "{:>10} {}", format!("+{elapsed}ms").cyan(), entry.event)?;
+		}
+
+		writeln!(f, "{}", "Approval voting".bright_purple())?;
+		for decision in self.approvals.iter() {
+			let (number, hash) = decision.leaf;
+			match decision.approved_ancestor {
+				Some((approved_number, approved_hash)) => writeln!(
+					f,
+					"Leaf #{number} {hash:?}: approved up to #{approved_number} {approved_hash:?}"
+				)?,
+				None => writeln!(f, "Leaf #{number} {hash:?}: {}", "nothing approved".red())?,
+			}
+		}
+
+		writeln!(f, "{}", "Dispute coordinator".bright_purple())?;
+		for decision in self.undisputed_chains.iter() {
+			let (number, hash) = decision.leaf;
+			let (undisputed_number, undisputed_hash) = decision.undisputed;
+			writeln!(
+				f,
+				"Leaf #{number} {hash:?}: undisputed up to #{undisputed_number} {undisputed_hash:?}"
+			)?;
+		}
+		for decision in self.disputes.iter() {
+			writeln!(
+				f,
+				"Dispute for {:?} of session {}: {:?}, {} valid and {} invalid votes",
+				decision.candidate,
+				decision.session,
+				decision.status,
+				decision.valid_votes,
+				decision.invalid_votes
+			)?;
+		}
+
+		writeln!(f, "{}", "Availability store".bright_purple())?;
+		for decision in self.availability.iter() {
+			writeln!(
+				f,
+				"Candidate {:?}: {}, {} chunks",
+				decision.candidate,
+				if decision.data_available {
+					"data available".normal()
+				} else {
+					"data missing".red()
+				},
+				decision.chunks
+			)?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,285 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	configuration::{TestAuthorities, TestConfiguration},
+	replay::{
+		db::{self, COL_APPROVAL_DATA},
+		ReplayEvent, ReplayOptions, TimelineEntry,
+	},
+};
+use polkadot_node_core_approval_voting::approval_db::{
+	common::{load_all_blocks, load_block_entry, load_candidate_entry, Config as ApprovalDbConfig},
+	v3::{BlockEntry, CandidateEntry},
+};
+use polkadot_node_primitives::approval::time::{slot_number_to_tick, Tick};
+use polkadot_node_subsystem::messages::BlockDescription;
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_node_subsystem_util::database::Database;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	BlockNumber, CandidateEvent, CandidateHash, CandidateReceiptV2 as CandidateReceipt, Hash,
+	Header, SessionIndex, Slot,
+};
+use std::{
+	collections::HashMap,
+	io::{self, Result},
+	sync::{Arc, Mutex},
+};
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config, the number of validators and cores is taken from the recording
+	pub config: TestConfiguration,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// The recorded DB, with an in-memory overlay for the writes of the replayed subsystems
+	pub db: Arc<dyn Database>,
+	// Relay chain slot duration
+	pub slot_duration_millis: u64,
+	// Recorded blocks, ascending by number
+	pub blocks: Vec<BlockEntry>,
+	// Recorded candidates of all blocks
+	pub candidates: HashMap<CandidateHash, CandidateEntry>,
+	// Relay chain block infos, in import order
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<Hash, Header>,
+	// Candidate receipts per block to feed the runtime api
+	pub candidate_receipts: HashMap<Hash, Vec<CandidateReceipt>>,
+	// Inclusion events per block to feed the runtime api
+	pub candidate_events: HashMap<Hash, Vec<CandidateEvent>>,
+	// The session of the last recorded block
+	pub session_index: SessionIndex,
+	// The clock driving the replayed subsystems
+	pub clock: ReplayClock,
+	// Decisions of the replayed subsystems, as seen by chain selection
+	pub replayed_events: Arc<Mutex<Vec<TimelineEntry>>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &ReplayOptions) -> Result<Self> {
+		let db = db::open(&options.db_path, options.database)?;
+		let db_config = ApprovalDbConfig { col_approval_data: COL_APPROVAL_DATA };
+
+		let mut blocks = Vec::new();
+		let mut candidates = HashMap::new();
+		for block_hash in load_all_blocks(&*db, &db_config).map_err(other_error)? {
+			let Some(block_entry) =
+				load_block_entry(&*db, &db_config, &block_hash).map_err(other_error)?
+			else {
+				continue;
+			};
+			for (_, candidate_hash) in block_entry.candidates.iter() {
+				if let Some(candidate_entry) =
+					load_candidate_entry(&*db, &db_config, candidate_hash).map_err(other_error)?
+				{
+					candidates.insert(*candidate_hash, candidate_entry);
+				}
+			}
+			blocks.push(block_entry);
+		}
+		if blocks.is_empty() {
+			return Err(io::Error::new(
+				io::ErrorKind::NotFound,
+				"The DB doesn't contain any unfinalized block recorded by approval-voting",
+			));
+		}
+		blocks.sort_by_key(|block| block.block_number);
+
+		let mut config = config.clone();
+		config.num_blocks = blocks.len();
+		if let Some(candidate_entry) = candidates.values().next() {
+			config.n_validators = candidate_entry.approvals.len();
+		}
+		let max_core_index = blocks
+			.iter()
+			.flat_map(|block| block.candidates.iter().map(|(core_index, _)| core_index.0 as usize))
+			.max();
+		if let Some(max_core_index) = max_core_index {
+			config.n_cores = config.n_cores.max(max_core_index + 1);
+		}
+		// Every core needs a backing group.
+		config.max_validators_per_core =
+			config.max_validators_per_core.min(config.n_validators / config.n_cores).max(1);
+		let test_authorities = config.generate_authorities();
+
+		let block_infos = blocks
+			.iter()
+			.map(|block| {
+				let mut info = new_block_import_info(block.block_hash, block.block_number);
+				info.parent_hash = block.parent_hash;
+				info
+			})
+			.collect();
+		let block_headers = blocks
+			.iter()
+			.map(|block| {
+				(
+					block.block_hash,
+					Header {
+						digest: Default::default(),
+						number: block.block_number,
+						parent_hash: block.parent_hash,
+						extrinsics_root: Default::default(),
+						state_root: Default::default(),
+					},
+				)
+			})
+			.collect();
+
+		let mut candidate_receipts: HashMap<Hash, Vec<CandidateReceipt>> = HashMap::new();
+		let mut candidate_events: HashMap<Hash, Vec<CandidateEvent>> = HashMap::new();
+		for block in blocks.iter() {
+			let receipts = candidate_receipts.entry(block.block_hash).or_default();
+			let events = candidate_events.entry(block.block_hash).or_default();
+			for (core_index, candidate_hash) in block.candidates.iter() {
+				let Some(candidate_entry) = candidates.get(candidate_hash) else { continue };
+				let Some(approval_entry) = candidate_entry.block_assignments.get(&block.block_hash)
+				else {
+					continue;
+				};
+				receipts.push(candidate_entry.candidate.clone());
+				events.push(CandidateEvent::CandidateIncluded(
+					candidate_entry.candidate.clone(),
+					Default::default(),
+					*core_index,
+					approval_entry.backing_group,
+				));
+			}
+		}
+
+		let session_index = blocks.last().map(|block| block.session).unwrap_or_default();
+		let start_tick = slot_number_to_tick(options.slot_duration_millis, blocks[0].slot);
+
+		Ok(Self {
+			config,
+			test_authorities,
+			db,
+			slot_duration_millis: options.slot_duration_millis,
+			blocks,
+			candidates,
+			block_infos,
+			block_headers,
+			candidate_receipts,
+			candidate_events,
+			session_index,
+			clock: ReplayClock::new(start_tick),
+			replayed_events: Default::default(),
+		})
+	}
+
+	/// The tick at which the slot of `block` started.
+	pub fn block_tick(&self, block: &BlockEntry) -> Tick {
+		slot_number_to_tick(self.slot_duration_millis, block.slot)
+	}
+
+	/// The last finalized block, parent of the oldest recorded block.
+	pub fn finalized_block(&self) -> (BlockNumber, Hash) {
+		let oldest = self.blocks.first().expect("Recording is not empty; qed");
+		(oldest.block_number.saturating_sub(1), oldest.parent_hash)
+	}
+
+	/// Recorded blocks without recorded children.
+	pub fn leaves(&self) -> Vec<&BlockEntry> {
+		self.blocks
+			.iter()
+			.filter(|block| {
+				!block.children.iter().any(|child| self.block_headers.contains_key(child))
+			})
+			.collect()
+	}
+
+	/// Descriptions of all blocks between the last finalized block and `leaf`, ascending.
+	pub fn block_descriptions(&self, leaf: &BlockEntry) -> Vec<BlockDescription> {
+		let blocks_by_hash = self
+			.blocks
+			.iter()
+			.map(|block| (block.block_hash, block))
+			.collect::<HashMap<_, _>>();
+		let mut descriptions = Vec::new();
+		let mut current = Some(leaf);
+		while let Some(block) = current {
+			descriptions.push(BlockDescription {
+				block_hash: block.block_hash,
+				session: block.session,
+				candidates: block
+					.candidates
+					.iter()
+					.map(|(_, candidate_hash)| *candidate_hash)
+					.collect(),
+			});
+			current = blocks_by_hash.get(&block.parent_hash).copied();
+		}
+		descriptions.reverse();
+		descriptions
+	}
+
+	/// Events recorded by approval-voting, in chronological order.
+	pub fn recorded_timeline(&self) -> Vec<TimelineEntry> {
+		let mut timeline = Vec::new();
+		for block in self.blocks.iter() {
+			timeline.push(TimelineEntry {
+				tick: self.block_tick(block),
+				event: ReplayEvent::BlockImported {
+					number: block.block_number,
+					hash: block.block_hash,
+					session: block.session,
+					candidates: block.candidates.len(),
+					approved: block.approved_bitfield.count_ones(),
+				},
+			});
+
+			for (_, candidate_hash) in block.candidates.iter() {
+				let Some(approval_entry) = self
+					.candidates
+					.get(candidate_hash)
+					.and_then(|entry| entry.block_assignments.get(&block.block_hash))
+				else {
+					continue;
+				};
+				for tranche_entry in approval_entry.tranches.iter() {
+					for (validator, tick) in tranche_entry.assignments.iter() {
+						timeline.push(TimelineEntry {
+							tick: *tick,
+							event: ReplayEvent::Assignment {
+								block: block.block_hash,
+								candidate: *candidate_hash,
+								validator: *validator,
+								tranche: tranche_entry.tranche,
+							},
+						});
+					}
+				}
+			}
+		}
+
+		// The sort is stable, so blocks stay ahead of the assignments received in the same tick.
+		timeline.sort_by_key(|entry| entry.tick);
+		timeline
+	}
+
+	/// The tick after which all recorded assignments turned into no-shows if not approved.
+	pub fn final_tick(&self) -> Tick {
+		let ticks_per_slot = slot_number_to_tick(self.slot_duration_millis, Slot::from(1));
+		let last_tick = self.recorded_timeline().last().map(|entry| entry.tick).unwrap_or_default();
+		last_tick + (self.config.no_show_slots as Tick + 1) * ticks_per_slot
+	}
+}
+
+fn other_error(err: impl std::fmt::Debug) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, format!("{err:?}"))
+}
@@ -22,7 +22,7 @@
 //!
 //! Subsystems' APIs are defined separately from their implementation, leading to easier mocking.
 
-use futures::channel::oneshot;
+use futures::channel::{mpsc, oneshot};
 use sc_network::{Multiaddr, ReputationChange};
 use thiserror::Error;
 
@@ -60,6 +60,7 @@ use polkadot_statement_table::v2::Misbehavior;
 use std::{
 	collections::{BTreeMap, HashMap, HashSet, VecDeque},
 	sync::Arc,
+	time::Duration,
 };
 
 /// Network events as transmitted to other subsystems, wrapped in their message types.
@@ -308,6 +309,62 @@ pub enum CollatorProtocolMessage {
 	/// A message sent by Cumulus consensus engine to the collator protocol to
 	/// disconnect from backing groups.
 	DisconnectFromBackingGroups,
+	/// Set the order in which collations built on the given scheduling parent for the given core
+	/// are advertised and served to validators.
+	///
+	/// Collations listed in `order` take precedence, in the given order. Collations that are not
+	/// listed keep their relative order and come after the listed ones. Unknown candidate hashes
+	/// are ignored.
+	ReorderCollations {
+		/// The scheduling parent the collations are built on.
+		scheduling_parent: Hash,
+		/// The core the collations are intended for.
+		core_index: CoreIndex,
+		/// The preferred order of the collations.
+		order: Vec<CandidateHash>,
+	},
+	/// Withdraw a previously distributed collation.
+	///
+	/// The collation is no longer advertised and pending fetch requests for it are dropped.
+	/// An upload that is already in progress is not interrupted. The core slot the collation
+	/// occupied becomes available for another collation.
+	CancelCollation {
+		/// The scheduling parent the collation is built on.
+		scheduling_parent: Hash,
+		/// The hash of the candidate to withdraw.
+		candidate_hash: CandidateHash,
+	},
+	/// Subscribe to [`CollationEvent`]s emitted while serving collations to validators.
+	///
+	/// The subscription ends once the receiving side is dropped.
+	SubscribeCollationEvents(mpsc::Sender<CollationEvent>),
+}
+
+/// Events emitted by the collator side of the collator protocol while serving collations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollationEvent {
+	/// A validator requested a collation.
+	Requested {
+		/// The scheduling parent the collation is built on.
+		scheduling_parent: Hash,
+		/// The hash of the requested candidate.
+		candidate_hash: CandidateHash,
+		/// The core the collation is intended for.
+		core_index: CoreIndex,
+		/// The validator that requested the collation.
+		peer_id: PeerId,
+	},
+	/// A validator finished fetching a collation.
+	Fetched {
+		/// The scheduling parent the collation is built on.
+		scheduling_parent: Hash,
+		/// The hash of the fetched candidate.
+		candidate_hash: CandidateHash,
+		/// The core the collation is intended for.
+		core_index: CoreIndex,
+		/// The validator that fetched the collation.
+		peer_id: PeerId,
+	},
 }
 
 impl Default for CollatorProtocolMessage {
@@ -556,6 +613,70 @@ pub enum AvailabilityRecoveryMessage {
 		                     * prefer systematic chunk recovery. */
 		oneshot::Sender<Result<AvailableData, crate::errors::RecoveryError>>,
 	),
+	/// Recover available data from validators on the network, using the given options, and report
+	/// back which strategy recovered it.
+	///
+	/// If a recovery of the same candidate is already ongoing, the request joins it and the
+	/// options are ignored.
+	RecoverAvailableDataWithOptions(
+		CandidateReceipt,
+		SessionIndex,
+		RecoveryOptions,
+		oneshot::Sender<Result<RecoveryOutcome, crate::errors::RecoveryError>>,
+	),
+}
+
+/// A way of recovering the available data of a candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AvailabilityRecoveryStrategy {
+	/// The data was found in the local availability store.
+	///
+	/// The store is always queried first (unless bypassed), so this is only ever reported and is
+	/// ignored in `RecoveryOptions::strategies`.
+	AvailabilityStore,
+	/// Fetch the full data from one of the backers.
+	FullFromBackers,
+	/// Fetch the systematic chunks and reconstruct the data without decoding. Requires the core
+	/// index of the candidate and the availability chunk mapping node feature.
+	SystematicChunks,
+	/// Fetch regular chunks from all validators and reconstruct the data.
+	Chunks,
+}
+
+/// Options of a `RecoverAvailableDataWithOptions` request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryOptions {
+	/// Backing group of the candidate, to request the full data and systematic chunks from.
+	pub backing_group: Option<GroupIndex>,
+	/// Validators to request the full data and systematic chunks from before the backing group.
+	pub preferred_validators: Vec<ValidatorIndex>,
+	/// Core the candidate was backed on. Needed for systematic chunk recovery.
+	pub core_index: Option<CoreIndex>,
+	/// Strategies to try, in order. Strategies which can't be used for the candidate are
+	/// skipped. If empty, the subsystem picks the strategies as for `RecoverAvailableData`.
+	pub strategies: Vec<AvailabilityRecoveryStrategy>,
+}
+
+/// How long a recovery strategy ran for and whether it recovered the data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryStrategyStats {
+	/// The strategy.
+	pub strategy: AvailabilityRecoveryStrategy,
+	/// Time spent running the strategy.
+	pub duration: Duration,
+	/// Whether the strategy recovered the data.
+	pub succeeded: bool,
+}
+
+/// The result of a successful `RecoverAvailableDataWithOptions` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryOutcome {
+	/// The recovered data.
+	pub available_data: AvailableData,
+	/// The strategy which recovered the data.
+	pub strategy: AvailabilityRecoveryStrategy,
+	/// The strategies tried, in order. Empty if the result was cached by the subsystem.
+	pub stats: Vec<RecoveryStrategyStats>,
 }
 
 /// Bitfield distribution message.
@@ -68,6 +68,8 @@ pub enum MalusType {
 	None,
 	/// Submit the same collations to all assigned cores.
 	DuplicateCollations,
+	/// Submit two conflicting collations on top of the same parent head for the same core.
+	EquivocatingCollations,
 }
 
 #[allow(missing_docs)]
@@ -27,10 +27,10 @@ use polkadot_node_primitives::{
 use polkadot_node_subsystem::messages::CollatorProtocolMessage;
 use polkadot_primitives::{
 	CandidateCommitments, CandidateDescriptorV2, CandidateReceiptV2, ClaimQueueOffset, CollatorId,
-	CollatorPair, CoreIndex, Hash, Id as ParaId, OccupiedCoreAssumption,
-	DEFAULT_CLAIM_QUEUE_OFFSET,
+	CollatorPair, CoreIndex, Hash, Id as ParaId, OccupiedCoreAssumption, PersistedValidationData,
+	SessionIndex, ValidationCodeHash, DEFAULT_CLAIM_QUEUE_OFFSET,
 };
-use polkadot_service::{Handle, NewFull, ParachainHost};
+use polkadot_service::{FullClient, Handle, NewFull, ParachainHost};
 use sc_client_api::client::BlockchainEvents;
 use sp_core::Pair;
 
@@ -52,6 +52,8 @@ pub const LOG_TARGET: &str = "parachain::undying-collator";
 const DEFAULT_POV_SIZE: usize = 1000;
 /// Default PVF time complexity - 1 signature per block.
 const DEFAULT_PVF_COMPLEXITY: u32 = 1;
+/// Number of tombstones placed by every block.
+const TOMBSTONES: u64 = 1_000;
 
 /// Calculates the head and state for the block with the given `number`.
 fn calculate_head_and_state_for_number(
@@ -78,7 +80,7 @@ fn calculate_head_and_state_for_number(
 	while head.number < number {
 		let block = BlockData {
 			state,
-			tombstones: 1_000,
+			tombstones: TOMBSTONES,
 			iterations: pvf_complexity,
 			experimental_send_approved_peer,
 		};
@@ -154,11 +156,46 @@ impl State {
 	) -> Result<(BlockData, HeadData, UpwardMessages), StateMismatch> {
 		self.best_block = parent_head.number;
 
-		let state = if let Some(state) = self
-			.number_to_head
-			.get(&self.best_block)
-			.and_then(|head_data| self.head_to_state.get(head_data).cloned())
-		{
+		let (block, new_head, new_state, upward_messages) =
+			self.build_block(parent_head, TOMBSTONES)?;
+
+		let new_head_arc = Arc::new(new_head.clone());
+
+		self.head_to_state.insert(new_head_arc.clone(), new_state);
+		self.number_to_head.insert(new_head.number, new_head_arc);
+
+		Ok((block, new_head, upward_messages))
+	}
+
+	/// Produce a block on top of `parent_head` which conflicts with the one produced by
+	/// [`Self::advance`].
+	///
+	/// The block places one tombstone less, so it results in a different [`HeadData`]. Its state is
+	/// kept around, so that we can continue building on top of it when it gets included.
+	fn advance_conflicting(
+		&mut self,
+		parent_head: HeadData,
+	) -> Result<(BlockData, HeadData, UpwardMessages), StateMismatch> {
+		let (block, new_head, new_state, upward_messages) =
+			self.build_block(parent_head, TOMBSTONES - 1)?;
+
+		self.head_to_state.insert(Arc::new(new_head.clone()), new_state);
+
+		Ok((block, new_head, upward_messages))
+	}
+
+	/// Execute a block placing `tombstones` on top of `parent_head`.
+	fn build_block(
+		&self,
+		parent_head: HeadData,
+		tombstones: u64,
+	) -> Result<(BlockData, HeadData, GraveyardState, UpwardMessages), StateMismatch> {
+		let state = if let Some(state) =
+			self.head_to_state.get(&parent_head).cloned().or_else(|| {
+				self.number_to_head
+					.get(&parent_head.number)
+					.and_then(|head_data| self.head_to_state.get(head_data).cloned())
+			}) {
 			state
 		} else {
 			let (_, state) = calculate_head_and_state_for_number(
@@ -170,10 +207,10 @@ impl State {
 			state
 		};
 
-		// Start with prev state and transaction to execute (place 1000 tombstones).
+		// Start with prev state and transaction to execute.
 		let block = BlockData {
 			state,
-			tombstones: 1000,
+			tombstones,
 			iterations: self.pvf_complexity,
 			experimental_send_approved_peer: self.experimental_send_approved_peer,
 		};
@@ -181,13 +218,196 @@ impl State {
 		let (new_head, new_state, upward_messages) =
 			execute(parent_head.hash(), parent_head, block.clone())?;
 
-		let new_head_arc = Arc::new(new_head.clone());
+		Ok((block, new_head, new_state, upward_messages))
+	}
+}
 
-		self.head_to_state.insert(new_head_arc.clone(), new_state);
-		self.number_to_head.insert(new_head.number, new_head_arc);
+/// The cores assigned to a parachain and the data needed to build its candidates on top of a
+/// relay parent.
+struct CandidateContext {
+	/// The cores assigned to the parachain at the default claim queue offset, in ascending order.
+	scheduled_cores: Vec<CoreIndex>,
+	validation_data: PersistedValidationData,
+	validation_code_hash: ValidationCodeHash,
+	session_index: SessionIndex,
+	n_validators: usize,
+}
 
-		Ok((block, new_head, upward_messages))
+/// Query the [`CandidateContext`] of `para_id` at `relay_parent` from the runtime APIs.
+///
+/// Returns `None`, after logging why, if a runtime API call fails or no core is assigned to the
+/// parachain.
+fn candidate_context(
+	client: &FullClient,
+	relay_parent: Hash,
+	para_id: ParaId,
+) -> Option<CandidateContext> {
+	// Get the list of cores assigned to the parachain.
+	let claim_queue = match client.runtime_api().claim_queue(relay_parent) {
+		Ok(claim_queue) => claim_queue,
+		Err(error) => {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to query claim queue runtime API: {error:?}",
+			);
+			return None;
+		},
+	};
+
+	let claim_queue_offset = ClaimQueueOffset(DEFAULT_CLAIM_QUEUE_OFFSET);
+
+	let scheduled_cores: Vec<CoreIndex> = claim_queue
+		.iter()
+		.filter_map(move |(core_index, paras)| {
+			paras
+				.get(claim_queue_offset.0 as usize)
+				.and_then(|core_para_id| (core_para_id == &para_id).then_some(*core_index))
+		})
+		.collect();
+
+	if scheduled_cores.is_empty() {
+		log::info!(
+			target: LOG_TARGET,
+			"Scheduled cores is empty.",
+		);
+		return None;
+	}
+
+	// Fetch validation data for the collation.
+	let validation_data = match client.runtime_api().persisted_validation_data(
+		relay_parent,
+		para_id,
+		OccupiedCoreAssumption::Included,
+	) {
+		Ok(Some(validation_data)) => validation_data,
+		Ok(None) => {
+			log::info!(
+				target: LOG_TARGET,
+				"Persisted validation data is None.",
+			);
+			return None;
+		},
+		Err(error) => {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to query persisted validation data runtime API: {error:?}",
+			);
+			return None;
+		},
+	};
+
+	// Fetch the validation code hash.
+	let validation_code_hash = match client.runtime_api().validation_code_hash(
+		relay_parent,
+		para_id,
+		OccupiedCoreAssumption::Included,
+	) {
+		Ok(Some(validation_code_hash)) => validation_code_hash,
+		Ok(None) => {
+			log::info!(
+				target: LOG_TARGET,
+				"Validation code hash is None.",
+			);
+			return None;
+		},
+		Err(error) => {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to query validation code hash runtime API: {error:?}",
+			);
+			return None;
+		},
+	};
+
+	// Fetch the session index.
+	let session_index = match client.runtime_api().session_index_for_child(relay_parent) {
+		Ok(session_index) => session_index,
+		Err(error) => {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to query session index for child runtime API: {error:?}",
+			);
+			return None;
+		},
+	};
+
+	// Fetch the session info.
+	let n_validators = match client.runtime_api().session_info(relay_parent, session_index) {
+		Ok(Some(session_info)) => session_info.validators.len(),
+		Ok(None) => {
+			log::info!(
+				target: LOG_TARGET,
+				"Session info is None.",
+			);
+			return None;
+		},
+		Err(error) => {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to query session info runtime API: {error:?}",
+			);
+			return None;
+		},
+	};
+
+	Some(CandidateContext {
+		scheduled_cores,
+		validation_data,
+		validation_code_hash,
+		session_index,
+		n_validators,
+	})
+}
+
+/// Compress the PoV of `collation` and compute the erasure root and the commitments of the
+/// resulting candidate.
+fn candidate_parts(
+	collation: Collation,
+	validation_data: PersistedValidationData,
+	n_validators: usize,
+) -> Option<(PoV, Hash, CandidateCommitments)> {
+	// Apply compression to the block data.
+	let pov = collation.proof_of_validity.into_compressed();
+	let encoded_size = pov.encoded_size();
+	let max_pov_size = validation_data.max_pov_size as usize;
+
+	// As long as `POV_BOMB_LIMIT` is at least `max_pov_size`, this ensures
+	// that honest collators never produce a PoV which is uncompressed.
+	//
+	// As such, honest collators never produce an uncompressed PoV which starts
+	// with a compression magic number, which would lead validators to
+	// reject the collation.
+	if encoded_size > max_pov_size {
+		log::error!(
+			target: LOG_TARGET,
+			"PoV size {encoded_size} exceeded maximum size of {max_pov_size}",
+		);
+		return None;
 	}
+
+	let available_data = AvailableData { validation_data, pov: Arc::new(pov.clone()) };
+	let chunks = match polkadot_erasure_coding::obtain_chunks_v1(n_validators, &available_data) {
+		Ok(chunks) => chunks,
+		Err(error) => {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to obtain chunks v1: {error:?}",
+			);
+			return None;
+		},
+	};
+	let erasure_root = polkadot_erasure_coding::branches(&chunks).root();
+
+	let commitments = CandidateCommitments {
+		upward_messages: collation.upward_messages,
+		horizontal_messages: collation.horizontal_messages,
+		new_validation_code: collation.new_validation_code,
+		head_data: collation.head_data,
+		processed_downward_messages: collation.processed_downward_messages,
+		hrmp_watermark: collation.hrmp_watermark,
+	};
+
+	Some((pov, erasure_root, commitments))
 }
 
 /// The collator of the undying parachain.
@@ -419,36 +639,16 @@ impl Collator {
 				while let Some(notification) = import_notifications.next().await {
 					let relay_parent = notification.hash;
 
-					// Get the list of cores assigned to the parachain.
-					let claim_queue = match client.runtime_api().claim_queue(relay_parent) {
-						Ok(claim_queue) => claim_queue,
-						Err(error) => {
-							log::error!(
-								target: LOG_TARGET,
-								"Failed to query claim queue runtime API: {error:?}",
-							);
-							continue;
-						},
-					};
-
-					let claim_queue_offset = ClaimQueueOffset(DEFAULT_CLAIM_QUEUE_OFFSET);
-
-					let scheduled_cores: Vec<CoreIndex> = claim_queue
-						.iter()
-						.filter_map(move |(core_index, paras)| {
-							paras.get(claim_queue_offset.0 as usize).and_then(|core_para_id| {
-								(core_para_id == &para_id).then_some(*core_index)
-							})
-						})
-						.collect();
-
-					if scheduled_cores.is_empty() {
-						log::info!(
-							target: LOG_TARGET,
-							"Scheduled cores is empty.",
-						);
+					let Some(CandidateContext {
+						scheduled_cores,
+						validation_data,
+						validation_code_hash,
+						session_index,
+						n_validators,
+					}) = candidate_context(&client, relay_parent, para_id)
+					else {
 						continue;
-					}
+					};
 
 					if scheduled_cores.len() == 1 {
 						log::info!(
@@ -458,29 +658,6 @@ impl Collator {
 						);
 					}
 
-					// Fetch validation data for the collation.
-					let validation_data = match client.runtime_api().persisted_validation_data(
-						relay_parent,
-						para_id,
-						OccupiedCoreAssumption::Included,
-					) {
-						Ok(Some(validation_data)) => validation_data,
-						Ok(None) => {
-							log::info!(
-								target: LOG_TARGET,
-								"Persisted validation data is None.",
-							);
-							continue;
-						},
-						Err(error) => {
-							log::error!(
-								target: LOG_TARGET,
-								"Failed to query persisted validation data runtime API: {error:?}",
-							);
-							continue;
-						},
-					};
-
 					// Generate the collation.
 					let collation =
 						match collation_function(relay_parent, &validation_data).await {
@@ -495,129 +672,160 @@ impl Collator {
 						}
 						.collation;
 
-					// Fetch the validation code hash.
-					let validation_code_hash = match client.runtime_api().validation_code_hash(
-						relay_parent,
-						para_id,
-						OccupiedCoreAssumption::Included,
-					) {
-						Ok(Some(validation_code_hash)) => validation_code_hash,
-						Ok(None) => {
-							log::info!(
-								target: LOG_TARGET,
-								"Validation code hash is None.",
-							);
-							continue;
-						},
-						Err(error) => {
-							log::error!(
-								target: LOG_TARGET,
-								"Failed to query validation code hash runtime API: {error:?}",
-							);
-							continue;
-						},
+					let persisted_validation_data_hash = validation_data.hash();
+					let parent_head_data = validation_data.parent_head.clone();
+					let parent_head_data_hash = validation_data.parent_head.hash();
+
+					let Some((pov, erasure_root, commitments)) =
+						candidate_parts(collation, validation_data, n_validators)
+					else {
+						continue;
 					};
+					let pov_hash = pov.hash();
 
-					// Fetch the session index.
-					let session_index =
-						match client.runtime_api().session_index_for_child(relay_parent) {
-							Ok(session_index) => session_index,
-							Err(error) => {
-								log::error!(
-									target: LOG_TARGET,
-									"Failed to query session index for child runtime API: {error:?}",
-								);
-								continue;
-							},
+					// Submit the same collation to all assigned cores.
+					for core_index in &scheduled_cores {
+						let candidate_receipt = CandidateReceiptV2 {
+							descriptor: CandidateDescriptorV2::new(
+								para_id,
+								relay_parent,
+								*core_index,
+								session_index,
+								persisted_validation_data_hash,
+								pov_hash,
+								erasure_root,
+								commitments.head_data.hash(),
+								validation_code_hash,
+							),
+							commitments_hash: commitments.hash(),
 						};
 
-					let persisted_validation_data_hash = validation_data.hash();
-					let parent_head_data = validation_data.parent_head.clone();
-					let parent_head_data_hash = validation_data.parent_head.hash();
+						// We cannot use SubmitCollation here because it includes an additional
+						// check for the core index by calling `parse_ump_signals`. This check
+						// enforces that the parachain always selects the correct core by comparing
+						// the descriptor and commitments core indexes. To bypass this check, we are
+						// simulating the behavior of SubmitCollation while skipping ump signals
+						// validation.
+						overseer_handle
+							.send_msg(
+								CollatorProtocolMessage::DistributeCollation {
+									candidate_receipt,
+									parent_head_data_hash,
+									pov: pov.clone(),
+									parent_head_data: parent_head_data.clone(),
+									result_sender: None,
+									core_index: *core_index,
+								},
+								"Collator",
+							)
+							.await;
+					}
+				}
+			});
+	}
 
-					// Apply compression to the block data.
-					let pov = {
-						let pov = collation.proof_of_validity.into_compressed();
-						let encoded_size = pov.encoded_size();
-						let max_pov_size = validation_data.max_pov_size as usize;
-
-						// As long as `POV_BOMB_LIMIT` is at least `max_pov_size`, this ensures
-						// that honest collators never produce a PoV which is uncompressed.
-						//
-						// As such, honest collators never produce an uncompressed PoV which starts
-						// with a compression magic number, which would lead validators to
-						// reject the collation.
-						if encoded_size > max_pov_size {
-							log::error!(
-								target: LOG_TARGET,
-								"PoV size {encoded_size} exceeded maximum size of {max_pov_size}",
-							);
-							continue;
-						}
+	/// Send two conflicting collations for every relay chain block.
+	///
+	/// Both collations are built on top of the same parent head and for the same core, so the
+	/// validators receive two different advertisements from this collator for the same slot. Only
+	/// one of them can end up being included.
+	pub fn send_equivocating_collations(
+		&self,
+		full_node: &NewFull,
+		mut overseer_handle: Handle,
+		para_id: ParaId,
+	) {
+		let client = full_node.client.clone();
+		let state = self.state.clone();
 
-						pov
-					};
+		let collation_function =
+			self.create_collation_function(full_node.task_manager.spawn_handle());
 
-					let pov_hash = pov.hash();
+		full_node
+			.task_manager
+			.spawn_handle()
+			.spawn("malus-undying-collator", None, async move {
+				let mut import_notifications = client.import_notification_stream();
 
-					// Fetch the session info.
-					let session_info =
-						match client.runtime_api().session_info(relay_parent, session_index) {
-							Ok(Some(session_info)) => session_info,
-							Ok(None) => {
-								log::info!(
-									target: LOG_TARGET,
-									"Session info is None.",
-								);
-								continue;
-							},
+				while let Some(notification) = import_notifications.next().await {
+					let relay_parent = notification.hash;
+
+					let Some(CandidateContext {
+						scheduled_cores,
+						validation_data,
+						validation_code_hash,
+						session_index,
+						n_validators,
+					}) = candidate_context(&client, relay_parent, para_id)
+					else {
+						continue;
+					};
+					// Equivocate on the first core assigned to the parachain.
+					let core_index = scheduled_cores[0];
+
+					let parent_head =
+						match HeadData::decode(&mut &validation_data.parent_head.0[..]) {
+							Ok(parent_head) => parent_head,
 							Err(error) => {
 								log::error!(
 									target: LOG_TARGET,
-									"Failed to query session info runtime API: {error:?}",
+									"Requested to build on top of malformed head-data: {error:?}",
 								);
 								continue;
 							},
 						};
 
-					let n_validators = session_info.validators.len();
+					// The honest collation, it is also the one tracked as our best block.
+					let Some(collation) = collation_function(relay_parent, &validation_data).await
+					else {
+						log::info!(
+							target: LOG_TARGET,
+							"Collation result is None.",
+						);
+						continue;
+					};
 
-					let available_data =
-						AvailableData { validation_data, pov: Arc::new(pov.clone()) };
-					let chunks = match polkadot_erasure_coding::obtain_chunks_v1(
-						n_validators,
-						&available_data,
-					) {
-						Ok(chunks) => chunks,
+					let conflicting_block = state.lock().unwrap().advance_conflicting(parent_head);
+					let conflicting_collation = match conflicting_block {
+						Ok((block_data, head_data, upward_messages)) => Collation {
+							upward_messages,
+							horizontal_messages: Default::default(),
+							new_validation_code: None,
+							head_data: head_data.encode().into(),
+							proof_of_validity: MaybeCompressedPoV::Raw(PoV {
+								block_data: block_data.encode().into(),
+							}),
+							processed_downward_messages: 0,
+							hrmp_watermark: validation_data.relay_parent_number,
+						},
 						Err(error) => {
 							log::error!(
 								target: LOG_TARGET,
-								"Failed to obtain chunks v1: {error:?}",
+								"Unable to build conflicting block: {error:?}",
 							);
 							continue;
 						},
 					};
-					let erasure_root = polkadot_erasure_coding::branches(&chunks).root();
-
-					let commitments = CandidateCommitments {
-						upward_messages: collation.upward_messages,
-						horizontal_messages: collation.horizontal_messages,
-						new_validation_code: collation.new_validation_code,
-						head_data: collation.head_data,
-						processed_downward_messages: collation.processed_downward_messages,
-						hrmp_watermark: collation.hrmp_watermark,
-					};
 
-					// Submit the same collation to all assigned cores.
-					for core_index in &scheduled_cores {
+					let persisted_validation_data_hash = validation_data.hash();
+					let parent_head_data = validation_data.parent_head.clone();
+					let parent_head_data_hash = validation_data.parent_head.hash();
+
+					for collation in [collation.collation, conflicting_collation] {
+						let Some((pov, erasure_root, commitments)) =
+							candidate_parts(collation, validation_data.clone(), n_validators)
+						else {
+							continue;
+						};
+
 						let candidate_receipt = CandidateReceiptV2 {
 							descriptor: CandidateDescriptorV2::new(
 								para_id,
 								relay_parent,
-								*core_index,
+								core_index,
 								session_index,
 								persisted_validation_data_hash,
-								pov_hash,
+								pov.hash(),
 								erasure_root,
 								commitments.head_data.hash(),
 								validation_code_hash,
@@ -625,21 +833,24 @@ impl Collator {
 							commitments_hash: commitments.hash(),
 						};
 
-						// We cannot use SubmitCollation here because it includes an additional
-						// check for the core index by calling `parse_ump_signals`. This check
-						// enforces that the parachain always selects the correct core by comparing
-						// the descriptor and commitments core indexes. To bypass this check, we are
-						// simulating the behavior of SubmitCollation while skipping ump signals
-						// validation.
+						log::info!(
+							target: LOG_TARGET,
+							"Distributing equivocating collation {:?} on relay-parent({})",
+							candidate_receipt.hash(),
+							relay_parent,
+						);
+
+						// See `send_same_collations_to_all_assigned_cores` for why we can't use
+						// `SubmitCollation`.
 						overseer_handle
 							.send_msg(
 								CollatorProtocolMessage::DistributeCollation {
 									candidate_receipt,
 									parent_head_data_hash,
-									pov: pov.clone(),
+									pov,
 									parent_head_data: parent_head_data.clone(),
 									result_sender: None,
-									core_index: *core_index,
+									core_index,
 								},
 								"Collator",
 							)
@@ -744,4 +955,22 @@ mod tests {
 
 		assert_eq!(second_head, head);
 	}
+
+	#[test]
+	fn conflicting_block_can_be_built_upon() {
+		let collator = Collator::new(1_000, 1, false);
+		let mut state = collator.state.lock().unwrap();
+		let genesis_head = state.number_to_head.get(&0).unwrap().as_ref().clone();
+
+		let honest_head = state.advance(genesis_head.clone()).unwrap().1;
+		let conflicting_head = state.advance_conflicting(genesis_head).unwrap().1;
+		assert_eq!(honest_head.number, conflicting_head.number);
+		assert_ne!(honest_head, conflicting_head);
+		// The best block is still the honest one.
+		assert_eq!(**state.number_to_head.get(&1).unwrap(), honest_head);
+
+		let next_head = state.advance(conflicting_head.clone()).unwrap().1;
+		assert_eq!(next_head.number, 2);
+		assert_eq!(next_head.parent_hash, conflicting_head.hash());
+	}
 }
@@ -109,8 +109,11 @@ fn main() -> Result<()> {
 						hwbench: None,
 						execute_workers_max_num: None,
 						prepare_workers_hard_max_num: None,
+						persistent_pvf_artifacts: false,
+						pvf_sandbox: Default::default(),
 						prepare_workers_soft_max_num: None,
 						keep_finalized_for: None,
+						archive_available_data: false,
 						invulnerable_ah_collators: HashSet::new(),
 						collator_protocol_hold_off: None,
 						experimental_collator_protocol: false,
@@ -158,12 +161,17 @@ fn main() -> Result<()> {
 
 				// If the collator is configured to behave maliciously, simulate the specified
 				// malicious behavior.
-				if cli.run.malus_type == MalusType::DuplicateCollations {
-					collator.send_same_collations_to_all_assigned_cores(
-						&full_node,
-						overseer_handle,
-						para_id,
-					);
+				match cli.run.malus_type {
+					MalusType::None => {},
+					MalusType::DuplicateCollations => collator
+						.send_same_collations_to_all_assigned_cores(
+							&full_node,
+							overseer_handle,
+							para_id,
+						),
+					MalusType::EquivocatingCollations => {
+						collator.send_equivocating_collations(&full_node, overseer_handle, para_id)
+					},
 				}
 
 				Ok(full_node.task_manager)
@@ -0,0 +1,360 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Precheck, compile and execute a PVF locally, the same way validators do.
+//!
+//! This lets parachain teams check a runtime upgrade before submitting it: the wasm blob is
+//! prepared in the PVF prepare worker with the given executor parameters, and, if a sample PoV is
+//! provided, the resulting artifact is run against it in the PVF execute worker.
+
+use clap::{Parser, ValueEnum};
+use codec::Decode;
+use polkadot_node_core_pvf::{
+	offline::{self, OfflineConfig},
+	EXECUTE_BINARY_NAME, PREPARE_BINARY_NAME,
+};
+use polkadot_node_core_pvf_common::{
+	execute::ValidationContext,
+	prepare::{PrepareJobKind, PrepareSuccess},
+	pvf::PvfPrepData,
+};
+use polkadot_node_primitives::{BlockData, PoV, VALIDATION_CODE_BOMB_LIMIT};
+use polkadot_primitives::{
+	executor_params::{
+		DEFAULT_APPROVAL_EXECUTION_TIMEOUT, DEFAULT_BACKING_EXECUTION_TIMEOUT,
+		DEFAULT_LENIENT_PREPARATION_TIMEOUT, DEFAULT_PRECHECK_PREPARATION_TIMEOUT,
+	},
+	BlockNumber, CandidateDescriptorV2, CandidateReceiptV2, CoreIndex, ExecutorParams, Hash,
+	HeadData, Id as ParaId, PersistedValidationData, PvfExecKind, PvfPrepKind, SessionIndex,
+	MAX_POV_SIZE,
+};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum PrepareKind {
+	/// Prepare the PVF as for a pre-checking vote, with the pre-checking timeout.
+	Precheck,
+	/// Prepare the PVF as for a candidate validation, with the lenient timeout.
+	Compile,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ExecKind {
+	/// Execute with the backing timeout.
+	Backing,
+	/// Execute with the approval timeout.
+	Approval,
+}
+
+#[derive(Debug, Parser)]
+struct Opt {
+	/// The wasm blob of the PVF, compressed or not.
+	#[arg(long)]
+	wasm: PathBuf,
+
+	/// A file with the SCALE-encoded executor parameters. The default ones are used if omitted.
+	#[arg(long)]
+	executor_params: Option<PathBuf>,
+
+	/// How to prepare the PVF.
+	#[arg(long, ignore_case = true, value_enum, default_value_t = PrepareKind::Precheck)]
+	prepare_kind: PrepareKind,
+
+	/// The maximum size of the decompressed PVF.
+	#[arg(long, default_value_t = VALIDATION_CODE_BOMB_LIMIT as u32)]
+	code_bomb_limit: u32,
+
+	/// The directory containing the `polkadot-prepare-worker` and `polkadot-execute-worker`
+	/// binaries. Defaults to the directory of this binary.
+	#[arg(long)]
+	workers_path: Option<PathBuf>,
+
+	/// The node version the workers are expected to have. The check is skipped if omitted.
+	#[arg(long)]
+	node_version: Option<String>,
+
+	/// A file with the block data of a sample PoV, compressed or not. If given, the prepared
+	/// artifact is executed against it.
+	#[arg(long, requires = "parent_head")]
+	pov: Option<PathBuf>,
+
+	/// A file with the raw head data of the parent block of the sample PoV.
+	#[arg(long)]
+	parent_head: Option<PathBuf>,
+
+	/// The relay-chain block number the sample PoV is built in the context of.
+	#[arg(long, default_value_t = 0)]
+	relay_parent_number: BlockNumber,
+
+	/// The relay-chain storage root the sample PoV is built in the context of. Zero if omitted.
+	#[arg(long)]
+	relay_parent_storage_root: Option<Hash>,
+
+	/// The relay parent of the sample candidate. Zero if omitted.
+	#[arg(long)]
+	relay_parent: Option<Hash>,
+
+	/// The scheduling parent of the sample candidate. If given, the candidate uses a v3
+	/// descriptor.
+	#[arg(long)]
+	scheduling_parent: Option<Hash>,
+
+	/// The para id of the sample candidate.
+	#[arg(long, default_value_t = 0)]
+	para_id: u32,
+
+	/// The session index of the sample candidate.
+	#[arg(long, default_value_t = 0)]
+	session_index: SessionIndex,
+
+	/// Which execution timeout to apply.
+	#[arg(long, ignore_case = true, value_enum, default_value_t = ExecKind::Approval)]
+	exec_kind: ExecKind,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+	sp_tracing::try_init_simple();
+
+	run(Opt::parse()).await.map(|_| ())
+}
+
+/// Prepares the PVF and, if a sample PoV is given, executes it, printing the statistics.
+///
+/// Returns the outcome of the execution, if any.
+async fn run(opt: Opt) -> Result<Option<offline::ExecuteSuccess>> {
+	let workers_path = match &opt.workers_path {
+		Some(path) => path.clone(),
+		None => std::env::current_exe()?
+			.parent()
+			.ok_or("could not determine the directory of the current binary")?
+			.to_path_buf(),
+	};
+	let cache_dir = tempfile::tempdir()?;
+	let config = OfflineConfig {
+		cache_path: cache_dir.path().to_path_buf(),
+		node_version: opt.node_version.clone(),
+		prepare_worker_program_path: workers_path.join(PREPARE_BINARY_NAME),
+		execute_worker_program_path: workers_path.join(EXECUTE_BINARY_NAME),
+		worker_spawn_timeout: Duration::from_secs(3),
+	};
+
+	let executor_params = match &opt.executor_params {
+		Some(path) => ExecutorParams::decode(&mut &std::fs::read(path)?[..])?,
+		None => ExecutorParams::default(),
+	};
+	let (prep_kind, prep_timeout) = match opt.prepare_kind {
+		PrepareKind::Precheck => (
+			PrepareJobKind::Prechecking,
+			executor_params
+				.pvf_prep_timeout(PvfPrepKind::Precheck)
+				.unwrap_or(DEFAULT_PRECHECK_PREPARATION_TIMEOUT),
+		),
+		PrepareKind::Compile => (
+			PrepareJobKind::Compilation,
+			executor_params
+				.pvf_prep_timeout(PvfPrepKind::Prepare)
+				.unwrap_or(DEFAULT_LENIENT_PREPARATION_TIMEOUT),
+		),
+	};
+
+	let code = std::fs::read(&opt.wasm)?;
+	let pvf = PvfPrepData::from_code(
+		code,
+		executor_params.clone(),
+		prep_timeout,
+		prep_kind,
+		opt.code_bomb_limit,
+	);
+
+	println!("PVF code hash: {:?}", pvf.code_hash());
+	let artifact = offline::prepare_pvf(&config, pvf.clone()).await?;
+	print_prepare_stats(&artifact);
+
+	let Some(pov_path) = &opt.pov else { return Ok(None) };
+
+	let exec_timeout = match opt.exec_kind {
+		ExecKind::Backing => executor_params
+			.pvf_exec_timeout(PvfExecKind::Backing)
+			.unwrap_or(DEFAULT_BACKING_EXECUTION_TIMEOUT),
+		ExecKind::Approval => executor_params
+			.pvf_exec_timeout(PvfExecKind::Approval)
+			.unwrap_or(DEFAULT_APPROVAL_EXECUTION_TIMEOUT),
+	};
+	let pov = PoV { block_data: BlockData(std::fs::read(pov_path)?) };
+	let parent_head =
+		HeadData(std::fs::read(opt.parent_head.as_ref().expect("required by `pov`; qed"))?);
+	let pvd = PersistedValidationData {
+		parent_head,
+		relay_parent_number: opt.relay_parent_number,
+		relay_parent_storage_root: opt.relay_parent_storage_root.unwrap_or_default(),
+		max_pov_size: MAX_POV_SIZE,
+	};
+
+	let relay_parent = opt.relay_parent.unwrap_or_default();
+	// Only the parts of the receipt the execute worker looks at need to be meaningful.
+	let descriptor = match opt.scheduling_parent {
+		Some(scheduling_parent) => CandidateDescriptorV2::new_v3(
+			ParaId::from(opt.para_id),
+			relay_parent,
+			CoreIndex(0),
+			opt.session_index,
+			pvd.hash(),
+			pov.hash(),
+			Hash::zero(),
+			Hash::zero(),
+			pvf.code_hash(),
+			scheduling_parent,
+		),
+		None => CandidateDescriptorV2::new(
+			ParaId::from(opt.para_id),
+			relay_parent,
+			CoreIndex(0),
+			opt.session_index,
+			pvd.hash(),
+			pov.hash(),
+			Hash::zero(),
+			Hash::zero(),
+			pvf.code_hash(),
+		),
+	};
+	let validation_context = ValidationContext {
+		candidate_receipt: CandidateReceiptV2 { descriptor, commitments_hash: Hash::zero() },
+		pvd: Arc::new(pvd),
+		pov: Arc::new(pov),
+		executor_params,
+		exec_timeout,
+		v3_enabled: opt.scheduling_parent.is_some(),
+	};
+
+	let outcome = offline::execute_pvf(&config, &pvf, &artifact, validation_context).await?;
+	println!("Execution CPU time: {}ms", outcome.cpu_time.as_millis());
+	println!("Uncompressed PoV size: {} bytes", outcome.pov_size);
+	println!("New head data: 0x{}", hex(&outcome.result.head_data.0));
+	println!("New validation code: {}", outcome.result.new_validation_code.is_some());
+	println!("Upward messages: {}", outcome.result.upward_messages.len());
+	println!("Horizontal messages: {}", outcome.result.horizontal_messages.len());
+	println!("Processed downward messages: {}", outcome.result.processed_downward_messages);
+	println!("HRMP watermark: {}", outcome.result.hrmp_watermark);
+
+	Ok(Some(outcome))
+}
+
+fn print_prepare_stats(artifact: &PrepareSuccess) {
+	let stats = &artifact.stats;
+	println!("Compilation CPU time: {}ms", stats.cpu_time_elapsed.as_millis());
+	println!("Decompressed code size: {} bytes", stats.observed_wasm_code_len);
+	println!("Artifact size: {} bytes", artifact.size);
+
+	let memory_stats = &stats.memory_stats;
+	#[cfg(target_os = "linux")]
+	if let Some(max_rss) = memory_stats.max_rss {
+		// `ru_maxrss` is reported in kilobytes.
+		println!("Peak resident memory (max_rss): {} bytes", max_rss * 1024);
+	}
+	#[cfg(target_os = "linux")]
+	if let Some(tracker_stats) = &memory_stats.memory_tracker_stats {
+		println!("Peak resident memory (tracker): {} bytes", tracker_stats.resident);
+		println!("Peak allocated memory (tracker): {} bytes", tracker_stats.allocated);
+	}
+	println!("Peak tracked allocation: {} bytes", memory_stats.peak_tracked_alloc);
+}
+
+fn hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::CommandFactory;
+	use codec::Encode;
+	use polkadot_node_core_pvf::testing::build_workers_and_get_paths;
+	use test_parachain_adder::{
+		hash_state, BlockData as AdderBlockData, HeadData as AdderHeadData,
+	};
+
+	/// Writes the given file into the directory and returns its path as an argument.
+	fn write(dir: &tempfile::TempDir, name: &str, contents: &[u8]) -> String {
+		let path = dir.path().join(name);
+		std::fs::write(&path, contents).unwrap();
+		path.to_str().unwrap().to_owned()
+	}
+
+	fn opt(dir: &tempfile::TempDir, wasm: &[u8], args: &[&str]) -> Opt {
+		let (prepare_worker_path, _) = build_workers_and_get_paths();
+		let workers_path = prepare_worker_path.parent().unwrap().to_str().unwrap().to_owned();
+		let wasm = write(dir, "pvf.wasm", wasm);
+		Opt::parse_from(
+			["polkadot-pvf-check", "--wasm", &wasm, "--workers-path", &workers_path]
+				.into_iter()
+				.chain(args.iter().copied()),
+		)
+	}
+
+	#[test]
+	fn cli_is_valid() {
+		Opt::command().debug_assert();
+	}
+
+	#[tokio::test]
+	async fn prepares_and_executes_adder() {
+		let dir = tempfile::tempdir().unwrap();
+		let parent_head =
+			AdderHeadData { number: 0, parent_hash: [0; 32], post_state: hash_state(0) };
+		let pov = write(&dir, "pov", &AdderBlockData { state: 0, add: 512 }.encode());
+		let parent = write(&dir, "parent_head", &parent_head.encode());
+
+		let opt = opt(
+			&dir,
+			test_parachain_adder::wasm_binary_unwrap(),
+			&["--prepare-kind", "compile", "--pov", &pov, "--parent-head", &parent],
+		);
+		let outcome = run(opt).await.unwrap().expect("a PoV was given");
+
+		let new_head = AdderHeadData::decode(&mut &outcome.result.head_data.0[..]).unwrap();
+		assert_eq!(new_head.number, 1);
+		assert_eq!(new_head.parent_hash, parent_head.hash());
+		assert_eq!(new_head.post_state, hash_state(512));
+	}
+
+	#[tokio::test]
+	async fn prechecks_without_pov() {
+		let dir = tempfile::tempdir().unwrap();
+		let opt = opt(&dir, test_parachain_adder::wasm_binary_unwrap(), &[]);
+
+		assert!(run(opt).await.unwrap().is_none());
+	}
+
+	#[tokio::test]
+	async fn rejects_invalid_pvf() {
+		let dir = tempfile::tempdir().unwrap();
+		let opt = opt(&dir, b"definitely not wasm", &[]);
+
+		let err = run(opt).await.unwrap_err();
+		assert!(
+			matches!(
+				err.downcast_ref::<offline::Error>(),
+				Some(offline::Error::Prepare(polkadot_node_core_pvf::PrepareError::Prevalidation(
+					_
+				)))
+			),
+			"unexpected error: {err}"
+		);
+	}
+}
@@ -119,6 +119,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		false,
 		1,
 		sc_network_statement::config::DEFAULT_STATEMENTS_PER_SECOND,
+		None,
 		|_, _| (),
 	)
 	.expect("creating a full node doesn't fail")
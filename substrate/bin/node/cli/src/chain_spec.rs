@@ -37,6 +37,7 @@ use sp_consensus_beefy::ecdsa_crypto::AuthorityId as BeefyId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_core::crypto::UncheckedInto;
 use sp_mixnet::types::AuthorityId as MixnetId;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
 
 pub use kitchensink_runtime::RuntimeGenesisConfig;
 pub use node_primitives::{AccountId, Balance, Signature};
@@ -56,6 +57,29 @@ pub struct Extensions {
 	pub bad_blocks: sc_client_api::BadBlocks<Block>,
 	/// The light sync state extension used by the sync-state rpc.
 	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// The GRANDPA authority set hard forks.
+	pub grandpa_hard_forks: GrandpaHardForks,
+}
+
+/// The GRANDPA authority set hard forks of the chain, if any.
+pub type GrandpaHardForks = Option<Vec<GrandpaHardFork>>;
+
+/// A GRANDPA authority set change that is not signalled by the runtime.
+///
+/// See [`sc_consensus_grandpa::AuthoritySetHardFork`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrandpaHardFork {
+	/// The new authority set id.
+	pub set_id: u64,
+	/// The hash of the block at which the hard fork is applied.
+	pub block_hash: <Block as BlockT>::Hash,
+	/// The number of the block at which the hard fork is applied.
+	pub block_number: NumberFor<Block>,
+	/// The authorities of the new set with their weights.
+	pub authorities: Vec<(GrandpaId, u64)>,
+	/// The latest block finalized before the hard fork, forcing the change when set.
+	pub last_finalized: Option<NumberFor<Block>>,
 }
 
 /// Specialized `ChainSpec`.
@@ -474,6 +498,7 @@ pub(crate) mod tests {
 					false,
 					1,
 					sc_network_statement::config::DEFAULT_STATEMENTS_PER_SECOND,
+					None,
 					|_, _| (),
 				)?;
 			Ok(sc_service_test::TestNetComponents::new(
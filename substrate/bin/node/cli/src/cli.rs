@@ -58,6 +58,22 @@ pub struct Cli {
 	#[arg(long, default_value_t = 50_000)]
 	pub statement_rate_limit: u32,
 
+	/// Enable the index of the events and the extrinsic signers of the finalized blocks.
+	///
+	/// The index is populated in the background and serves the `archive_v1_queryEvents` and
+	/// `archive_v1_queryExtrinsics` RPC methods. It is kept in the offchain storage and needs the
+	/// state of the finalized blocks, so the node should run with
+	/// `--state-pruning archive-canonical`.
+	#[arg(long)]
+	pub enable_archive_index: bool,
+
+	/// The height of the first block indexed by the archive index.
+	///
+	/// The earlier blocks are never indexed, e.g. when their state was already pruned. Only
+	/// relevant when `--enable-archive-index` is used.
+	#[arg(long, default_value_t = 0)]
+	pub archive_index_from: u64,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
@@ -115,6 +131,10 @@ pub enum Subcommand {
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Export or import a snapshot of the finalized state.
+	#[command(subcommand)]
+	Snapshot(sc_cli::SnapshotSubcommand),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use polkadot_sdk::*;
+use polkadot_sdk::{sc_consensus_grandpa as grandpa, *};
 
 use super::benchmarking::{inherent_benchmark_data, RemarkBuilder, TransferKeepAliveBuilder};
 use crate::{
@@ -215,6 +215,30 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
+		Some(Subcommand::Snapshot(sc_cli::SnapshotSubcommand::Export(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, backend, task_manager, other, .. } =
+					new_partial(&config, None)?;
+				let (_, import_setup, ..) = other;
+				let warp_sync_provider = Arc::new(grandpa::warp_proof::NetworkProvider::new(
+					backend,
+					import_setup.1.shared_authority_set().clone(),
+					service::grandpa_hard_forks(&*config.chain_spec),
+				));
+				let verifier = service::genesis_warp_sync_verifier(&client, &*config.chain_spec)?;
+				Ok((cmd.run(client, warp_sync_provider, verifier), task_manager))
+			})
+		},
+		Some(Subcommand::Snapshot(sc_cli::SnapshotSubcommand::Import(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, task_manager, import_queue, .. } =
+					new_partial(&config, None)?;
+				let verifier = service::genesis_warp_sync_verifier(&client, &*config.chain_spec)?;
+				Ok((cmd.run(client, import_queue, verifier), task_manager))
+			})
+		},
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.database))
@@ -26,8 +26,8 @@ use polkadot_sdk::{
 	sp_consensus_beefy as beefy_primitives, *,
 };
 
-use crate::Cli;
-use codec::Encode;
+use crate::{chain_spec::GrandpaHardForks, Cli};
+use codec::{Decode, Encode};
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 use frame_system_rpc_runtime_api::AccountNonceApi;
 use futures::prelude::*;
@@ -38,15 +38,22 @@ use sc_consensus_babe::{self, SlotProportion};
 use sc_network::{
 	event::Event, service::traits::NetworkService, NetworkBackend, NetworkEventStream,
 };
-use sc_network_sync::{strategy::warp::WarpSyncConfig, SyncingService};
+use sc_network_sync::{
+	strategy::warp::{Verifier, WarpSyncConfig},
+	SyncingService,
+};
+use sc_rpc_spec_v2::archive::index::{
+	ArchiveIndex, ArchiveIndexApiServer, ArchiveIndexStore, ArchiveIndexer, FrameIndexExtractor,
+};
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager};
 use sc_statement_store::Store as StatementStore;
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool::TransactionPoolHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::ProvideRuntimeApi;
+use sp_consensus_grandpa::AuthorityList;
 use sp_core::crypto::Pair;
-use sp_runtime::{generic, traits::Block as BlockT, SaturatedConversion};
+use sp_runtime::{generic, traits::Block as BlockT, BuildStorage, SaturatedConversion};
 use sp_transaction_storage_proof::runtime_api::TransactionStorageApi;
 use std::{path::Path, sync::Arc};
 
@@ -249,11 +256,12 @@ pub fn new_partial(
 		.build(),
 	);
 
-	let (grandpa_block_import, grandpa_link) = grandpa::block_import(
+	let (grandpa_block_import, grandpa_link) = grandpa::block_import_with_authority_set_hard_forks(
 		client.clone(),
 		GRANDPA_JUSTIFICATION_PERIOD,
 		&(client.clone() as Arc<_>),
 		select_chain.clone(),
+		grandpa_hard_forks(&*config.chain_spec),
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 	let justification_import = grandpa_block_import.clone();
@@ -392,6 +400,82 @@ pub fn new_partial(
 	})
 }
 
+/// Creates an RPC replica following the database of another node, opened as a secondary instance.
+///
+/// Only the RPC servers are started, the blocks are imported and finalized by the primary node.
+pub fn new_rpc_replica(config: Configuration) -> Result<TaskManager, ServiceError> {
+	let sc_service::PartialComponents {
+		client,
+		backend,
+		mut task_manager,
+		keystore_container,
+		import_queue,
+		transaction_pool,
+		other: (rpc_builder, ..),
+		..
+	} = new_partial(&config, None)?;
+
+	// No block is imported by the replica, but dropping the import queue would stop its essential
+	// worker task and with it the whole node.
+	task_manager.keep_alive(import_queue);
+
+	sc_service::spawn_rpc_replica_tasks(sc_service::SpawnRpcReplicaTasksParams {
+		config,
+		client,
+		backend,
+		task_manager: &mut task_manager,
+		keystore: keystore_container.keystore(),
+		transaction_pool,
+		rpc_builder: Box::new(rpc_builder),
+		tracing_execute_block: None,
+	})?;
+
+	Ok(task_manager)
+}
+
+/// Creates the verifier of the GRANDPA warp sync proofs starting at the genesis block.
+///
+/// The genesis authorities are read from the genesis storage built from the chain spec, so the
+/// state of the genesis block does not need to be kept by the node. The authority set hard forks
+/// configured in the chain spec are applied as well.
+pub fn genesis_warp_sync_verifier(
+	client: &FullClient,
+	chain_spec: &dyn sc_service::ChainSpec,
+) -> Result<Box<dyn Verifier<Block>>, ServiceError> {
+	// The storage key of `pallet_grandpa::Authorities`.
+	let key = [sp_core::twox_128(b"Grandpa"), sp_core::twox_128(b"Authorities")].concat();
+	let storage = chain_spec.build_storage().map_err(ServiceError::Other)?;
+	let authorities = storage
+		.top
+		.get(&key)
+		.and_then(|authorities| AuthorityList::decode(&mut &authorities[..]).ok())
+		.ok_or_else(|| ServiceError::Other("Genesis GRANDPA authorities are missing".into()))?;
+
+	Ok(grandpa::warp_proof::genesis_verifier(
+		client.chain_info().genesis_hash,
+		authorities,
+		grandpa_hard_forks(chain_spec),
+	))
+}
+
+/// Returns the GRANDPA authority set hard forks configured in the chain spec.
+pub fn grandpa_hard_forks(
+	chain_spec: &dyn sc_service::ChainSpec,
+) -> Vec<grandpa::AuthoritySetHardFork<Block>> {
+	sc_chain_spec::get_extension::<GrandpaHardForks>(chain_spec.extensions())
+		.cloned()
+		.flatten()
+		.unwrap_or_default()
+		.into_iter()
+		.map(|fork| grandpa::AuthoritySetHardFork {
+			set_id: fork.set_id,
+			block: (fork.block_hash, fork.block_number),
+			authorities: fork.authorities,
+			last_finalized: fork.last_finalized,
+		})
+		.collect()
+}
+
 /// Result of [`new_full_base`].
 pub struct NewFullBase {
 	/// The task manager of the node.
@@ -415,6 +499,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	disable_hardware_benchmarks: bool,
 	statement_network_workers: usize,
 	statement_rate_limit: u32,
+	archive_index_from: Option<u64>,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
 			Block,
@@ -528,7 +613,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
 		backend.clone(),
 		import_setup.1.shared_authority_set().clone(),
-		Vec::default(),
+		grandpa_hard_forks(&*config.chain_spec),
 	));
 
 	let (network, system_rpc_tx, tx_handler_controller, sync_service) =
@@ -562,6 +647,47 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		task_manager.spawn_handle().spawn("mixnet", None, mixnet);
 	}
 
+	let archive_index = match archive_index_from {
+		Some(from) => {
+			if !config.state_pruning.as_ref().is_some_and(|pruning| pruning.is_archive()) {
+				log::warn!(
+					"The archive index needs the state of the finalized blocks, consider running \
+					 the node with `--state-pruning archive-canonical`"
+				);
+			}
+			let store = ArchiveIndexStore::new(backend.offchain_storage().ok_or_else(|| {
+				ServiceError::Other("The archive index requires the offchain storage".into())
+			})?);
+			let indexer = ArchiveIndexer::<FullBackend, _, _, _>::new(
+				client.clone(),
+				store.clone(),
+				Arc::new(FrameIndexExtractor::new(client.clone())),
+			)
+			.with_start_height(from);
+			task_manager.spawn_handle().spawn_blocking(
+				"archive-indexer",
+				Some("rpc"),
+				indexer.run(),
+			);
+			Some(store)
+		},
+		None => None,
+	};
+	let rpc_builder = {
+		let client = client.clone();
+		move |subscription_executor: node_rpc::SubscriptionTaskExecutor| {
+			let mut module = rpc_builder(subscription_executor)?;
+			if let Some(store) = &archive_index {
+				module
+					.merge(
+						ArchiveIndex::<Block, _, _>::new(client.clone(), store.clone()).into_rpc(),
+					)
+					.map_err(|e| ServiceError::Application(e.into()))?;
+			}
+			Ok(module)
+		}
+	};
+
 	let net_config_path = config.network.net_config_path.clone();
 	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
@@ -840,6 +966,10 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
+	if config.database.is_secondary() {
+		return new_rpc_replica(config);
+	}
+
 	let mixnet_config = cli.mixnet_params.config(config.role.is_authority());
 	let database_path = config.database.path().map(Path::to_path_buf);
 
@@ -851,6 +981,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 				cli.no_hardware_benchmarks,
 				cli.statement_network_workers,
 				cli.statement_rate_limit,
+				cli.enable_archive_index.then_some(cli.archive_index_from),
 				|_, _| (),
 			)
 			.map(|NewFullBase { task_manager, .. }| task_manager)?;
@@ -863,6 +994,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 				cli.no_hardware_benchmarks,
 				cli.statement_network_workers,
 				cli.statement_rate_limit,
+				cli.enable_archive_index.then_some(cli.archive_index_from),
 				|_, _| (),
 			)
 			.map(|NewFullBase { task_manager, .. }| task_manager)?;
@@ -952,6 +1084,7 @@ mod tests {
 						false,
 						1,
 						50_000,
+						None,
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
 							setup_handles = Some((block_import.clone(), babe_link.clone()));
@@ -1172,6 +1305,7 @@ mod tests {
 						false,
 						1,
 						50_000,
+						None,
 						|_, _| (),
 					)?;
 				Ok(sc_service_test::TestNetComponents::new(
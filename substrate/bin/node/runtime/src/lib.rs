@@ -2880,6 +2880,9 @@ mod runtime {
 	#[runtime::pallet_index(85)]
 	pub type Oracle = pallet_oracle::Pallet<Runtime>;
 
+	#[runtime::pallet_index(86)]
+	pub type OracleIncentives = pallet_oracle::incentives::Pallet<Runtime>;
+
 	#[runtime::pallet_index(89)]
 	pub type MetaTx = pallet_meta_tx::Pallet<Runtime>;
 
@@ -3011,6 +3014,7 @@ parameter_types! {
 	pub const RootOperatorAccountId: AccountId = AccountId::new([0xffu8; 32]);
 
 	pub const OracleMaxFeedValues: u32 = 10;
+	pub const OracleMaxHistoryLength: u32 = 100;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -3047,8 +3051,11 @@ parameter_types! {
 }
 
 impl pallet_oracle::Config for Runtime {
-	type OnNewData = ();
-	type CombineData = pallet_oracle::DefaultCombineData<Self, ConstU32<5>, ConstU64<3600>>;
+	type OnNewData = OracleIncentives;
+	type CombineData = pallet_oracle::HistoryCombineData<
+		Self,
+		pallet_oracle::DefaultCombineData<Self, ConstU32<5>, ConstU64<3600>>,
+	>;
 	type Time = Timestamp;
 	type OracleKey = u32;
 	type OracleValue = u128;
@@ -3057,10 +3064,34 @@ impl pallet_oracle::Config for Runtime {
 	type WeightInfo = ();
 	type MaxHasDispatchedSize = OracleMaxHasDispatchedSize;
 	type MaxFeedValues = OracleMaxFeedValues;
+	type MaxHistoryLength = OracleMaxHistoryLength;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = OracleBenchmarkingHelper;
 }
 
+parameter_types! {
+	pub const OracleFeederBond: Balance = 100 * DOLLARS;
+	pub const OracleRewardPerRound: Balance = DOLLARS;
+	pub const OracleRoundLength: BlockNumber = HOURS;
+	pub const OracleMaxDeviation: Permill = Permill::from_percent(5);
+	pub const OracleSlashFraction: Perbill = Perbill::from_percent(10);
+	pub const OracleRewardPotId: PalletId = PalletId(*b"py/orinc");
+}
+
+impl pallet_oracle::incentives::Config for Runtime {
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type FeederBond = OracleFeederBond;
+	type RewardPerRound = OracleRewardPerRound;
+	type RoundLength = OracleRoundLength;
+	type MaxDeviation = OracleMaxDeviation;
+	type MaxStrikes = ConstU32<3>;
+	type SlashFraction = OracleSlashFraction;
+	type RewardPotId = OracleRewardPotId;
+	type MaxMembers = TechnicalMaxMembers;
+	type WeightInfo = pallet_oracle::incentives::weights::SubstrateWeight<Runtime>;
+}
+
 /// MMR helper types.
 mod mmr {
 	use super::*;
@@ -3173,6 +3204,7 @@ mod benches {
 		[pallet_nomination_pools, NominationPoolsBench::<Runtime>]
 		[pallet_offences, OffencesBench::<Runtime>]
 		[pallet_oracle, Oracle]
+		[pallet_oracle::incentives, OracleIncentives]
 		[pallet_preimage, Preimage]
 		[pallet_proxy, Proxy]
 		[pallet_ranked_collective, RankedCollective]
@@ -3430,6 +3462,7 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 		}
 	}
 
+	#[api_version(2)]
 	impl polkadot_sdk::pallet_oracle_runtime_api::OracleApi<Block, u32, u32, u128> for Runtime {
 		fn get_value(_provider_id: u32, key: u32) -> Option<u128> {
 			// ProviderId is unused as we only have 1 provider
@@ -3442,6 +3475,10 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 				.map(|(k, v)| (k, v.map(|tv| tv.value)))
 				.collect()
 		}
+
+		fn get_history(_provider_id: u32, key: u32) -> Vec<u128> {
+			pallet_oracle::History::<Runtime>::get(&key).into_iter().map(|tv| tv.value).collect()
+		}
 	}
 
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce> for Runtime {
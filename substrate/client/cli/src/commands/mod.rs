@@ -23,10 +23,12 @@ mod chain_info_cmd;
 mod check_block_cmd;
 mod export_blocks_cmd;
 mod export_chain_spec_cmd;
+mod export_snapshot_cmd;
 mod export_state_cmd;
 mod generate;
 mod generate_node_key;
 mod import_blocks_cmd;
+mod import_snapshot_cmd;
 mod insert_key;
 mod inspect_key;
 mod inspect_node_key;
@@ -35,6 +37,7 @@ mod purge_chain_cmd;
 mod revert_cmd;
 mod run_cmd;
 mod sign;
+mod snapshot;
 mod test;
 pub mod utils;
 mod vanity;
@@ -43,9 +46,10 @@ mod verify;
 pub use self::{
 	build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd,
 	export_blocks_cmd::ExportBlocksCmd, export_chain_spec_cmd::ExportChainSpecCmd,
-	export_state_cmd::ExportStateCmd, generate::GenerateCmd,
-	generate_node_key::GenerateKeyCmdCommon, import_blocks_cmd::ImportBlocksCmd,
+	export_snapshot_cmd::ExportSnapshotCmd, export_state_cmd::ExportStateCmd,
+	generate::GenerateCmd, generate_node_key::GenerateKeyCmdCommon,
+	import_blocks_cmd::ImportBlocksCmd, import_snapshot_cmd::ImportSnapshotCmd,
 	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
 	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	sign::SignCmd, snapshot::SnapshotSubcommand, vanity::VanityCmd, verify::VerifyCmd,
 };
@@ -0,0 +1,33 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! State snapshot related CLI utilities
+
+use super::{export_snapshot_cmd::ExportSnapshotCmd, import_snapshot_cmd::ImportSnapshotCmd};
+
+/// State snapshot utilities for the cli.
+///
+/// Running the subcommands requires the node's client and consensus components, so they are
+/// dispatched by the node.
+#[derive(Debug, clap::Subcommand)]
+pub enum SnapshotSubcommand {
+	/// Export the state of the latest finalized block together with the proof of its finality.
+	Export(ExportSnapshotCmd),
+
+	/// Bootstrap the node from a snapshot, without syncing the state over the network.
+	Import(ImportSnapshotCmd),
+}
@@ -219,6 +219,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.database_params().and_then(|x| x.database()))
 	}
 
+	/// Get the path to the database of the primary node, when the database is opened as a
+	/// read-only secondary instance.
+	///
+	/// By default this is retrieved from `DatabaseParams` if it is available. Otherwise its `None`.
+	fn database_primary_path(&self) -> Result<Option<PathBuf>> {
+		Ok(self.database_params().and_then(|x| x.database_primary_path().cloned()))
+	}
+
 	/// Get the database configuration object for the parameters provided
 	fn database_config(
 		&self,
@@ -229,6 +237,29 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let role_dir = "full";
 		let rocksdb_path = base_path.join("db").join(role_dir);
 		let paritydb_path = base_path.join("paritydb").join(role_dir);
+
+		if let Some(primary_path) = self.database_primary_path()? {
+			// ParityDB has no secondary mode following the writes of a running primary. Its
+			// directory is identified by the `metadata` file, like `sc-client-db` does.
+			if primary_path.join("metadata").exists() {
+				return Err(
+					"ParityDB primaries are not supported, only a RocksDB database can be \
+					opened as a secondary instance"
+						.into(),
+				);
+			}
+
+			return match database {
+				#[cfg(feature = "rocksdb")]
+				Database::RocksDb | Database::Auto => Ok(DatabaseSource::RocksDbSecondary {
+					path: primary_path,
+					secondary_path: rocksdb_path,
+					cache_size,
+				}),
+				_ => Err("Only the RocksDB database can be opened as a secondary instance".into()),
+			};
+		}
+
 		Ok(match database {
 			#[cfg(feature = "rocksdb")]
 			Database::RocksDb => DatabaseSource::RocksDb { path: rocksdb_path, cache_size },
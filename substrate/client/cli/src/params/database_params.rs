@@ -18,6 +18,7 @@
 
 use crate::arg_enums::Database;
 use clap::Args;
+use std::path::PathBuf;
 
 /// Parameters for database
 #[derive(Debug, Clone, PartialEq, Args)]
@@ -29,6 +30,19 @@ pub struct DatabaseParams {
 	/// Limit the memory the database cache can use.
 	#[arg(long = "db-cache", value_name = "MiB")]
 	pub database_cache_size: Option<usize>,
+
+	/// Open the RocksDB database of another node as a read-only secondary instance.
+	///
+	/// The path must point to the database directory of the primary node, e.g.
+	/// `<base-path>/chains/<chain>/db/full`. The node periodically catches up with the best and
+	/// finalized blocks written by the primary and can serve the state queries over RPC. The
+	/// primary must run with `--state-pruning archive`. The secondary node runs as an RPC replica:
+	/// no networking, block import, authoring or offchain workers are started.
+	///
+	/// ParityDB primaries are not supported: ParityDB can't be read while another node writes to
+	/// it, so the primary must run with `--database rocksdb`.
+	#[arg(long = "db-primary-path", value_name = "PATH")]
+	pub database_primary_path: Option<PathBuf>,
 }
 
 impl DatabaseParams {
@@ -41,4 +55,9 @@ impl DatabaseParams {
 	pub fn database_cache_size(&self) -> Option<usize> {
 		self.database_cache_size
 	}
+
+	/// The path to the database of the primary node, if opened as a secondary instance.
+	pub fn database_primary_path(&self) -> Option<&PathBuf> {
+		self.database_primary_path.as_ref()
+	}
 }
@@ -17,7 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::{Args, ValueEnum};
-use sc_transaction_pool::TransactionPoolOptions;
+use sc_transaction_pool::{MempoolJournalConfig, TransactionPoolOptions};
+use std::{path::PathBuf, time::Duration};
 
 /// Type of transaction pool to be used
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -60,17 +61,41 @@ pub struct TransactionPoolParams {
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::ForkAware)]
 	pub pool_type: TransactionPoolType,
+
+	/// Persist the transactions kept in the pool to the journal file at the given path.
+	///
+	/// Transactions found in the journal are revalidated and put back into the pool on start-up.
+	/// Only supported by the fork-aware transaction pool.
+	#[arg(long, value_name = "PATH")]
+	pub pool_journal: Option<PathBuf>,
+
+	/// Transactions that entered the pool longer ago than this are not restored from the journal.
+	///
+	/// Defaults to 3600s.
+	#[arg(long, value_name = "SECONDS", requires = "pool_journal")]
+	pub pool_journal_max_age: Option<u64>,
 }
 
 impl TransactionPoolParams {
 	/// Fill the given `PoolConfiguration` by looking at the cli parameters.
 	pub fn transaction_pool(&self, is_dev: bool) -> TransactionPoolOptions {
-		TransactionPoolOptions::new_with_params(
+		let options = TransactionPoolOptions::new_with_params(
 			self.pool_limit,
 			self.pool_kbytes * 1024,
 			self.tx_ban_seconds,
 			self.pool_type.into(),
 			is_dev,
-		)
+		);
+
+		match &self.pool_journal {
+			Some(path) => {
+				let mut journal = MempoolJournalConfig::new(path);
+				if let Some(max_age) = self.pool_journal_max_age {
+					journal = journal.with_max_age(Duration::from_secs(max_age));
+				}
+				options.with_journal(journal)
+			},
+			None => options,
+		}
 	}
 }
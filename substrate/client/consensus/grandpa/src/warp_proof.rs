@@ -386,13 +386,42 @@ where
 	}
 }
 
+/// Creates a verifier of the warp sync proofs starting at the genesis block.
+///
+/// Unlike the verifier created by the [`NetworkProvider`], which starts at the current authority
+/// set of the node, this one only trusts the given genesis authorities. It can be used to verify
+/// the proofs that were not received over the network, e.g. the ones kept in a state snapshot.
+pub fn genesis_verifier<Block: BlockT>(
+	genesis_hash: Block::Hash,
+	genesis_authorities: AuthorityList,
+	hard_forks: Vec<AuthoritySetHardFork<Block>>,
+) -> Box<dyn Verifier<Block>>
+where
+	NumberFor<Block>: BlockNumberOps,
+{
+	Box::new(GrandpaVerifier {
+		state: VerifierState {
+			set_id: 0,
+			authorities: genesis_authorities,
+			next_proof_context: genesis_hash,
+		},
+		hard_forks: hard_forks
+			.into_iter()
+			.map(|fork| (fork.block, (fork.set_id, fork.authorities)))
+			.collect(),
+		eras_synced: 0,
+	})
+}
+
 #[cfg(test)]
 mod tests {
-	use super::WarpSyncProof;
+	use super::{genesis_verifier, WarpSyncProof};
 	use crate::{AuthoritySetChanges, GrandpaJustification};
+	use assert_matches::assert_matches;
 	use codec::Encode;
 	use rand::prelude::*;
 	use sc_block_builder::BlockBuilderBuilder;
+	use sc_network_sync::strategy::warp::{EncodedProof, VerificationResult, Verifier};
 	use sp_blockchain::HeaderBackend;
 	use sp_consensus::BlockOrigin;
 	use sp_consensus_grandpa::GRANDPA_ENGINE_ID;
@@ -505,8 +534,9 @@ mod tests {
 			WarpSyncProof::generate(&*backend, genesis_hash, &authority_set_changes).unwrap();
 
 		// verifying the proof should yield the last set id and authorities
-		let (new_set_id, new_authorities) =
-			warp_sync_proof.verify(0, genesis_authorities, &Default::default()).unwrap();
+		let (new_set_id, new_authorities) = warp_sync_proof
+			.verify(0, genesis_authorities.clone(), &Default::default())
+			.unwrap();
 
 		let expected_authorities = current_authorities
 			.iter()
@@ -515,5 +545,15 @@ mod tests {
 
 		assert_eq!(new_set_id, current_set_id);
 		assert_eq!(new_authorities, expected_authorities);
+
+		// the verifier starting at genesis should accept the proof as well
+		let mut verifier = genesis_verifier::<substrate_test_runtime_client::runtime::Block>(
+			genesis_hash,
+			genesis_authorities,
+			Vec::new(),
+		);
+		let result = verifier.verify(&EncodedProof(warp_sync_proof.encode())).unwrap();
+		assert_matches!(result, VerificationResult::Complete(header, _) if header.number == 100);
+		assert_eq!(verifier.next_proof_context(), client.hash(100).unwrap().unwrap());
 	}
 }
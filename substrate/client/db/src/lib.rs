@@ -386,6 +386,21 @@ pub enum DatabaseSource {
 		cache_size: usize,
 	},
 
+	/// Open a RocksDB database, written by another process, as a read-only secondary instance.
+	///
+	/// The secondary instance does not see the changes of the primary until it catches up with
+	/// it, see [`Backend::try_catch_up_with_primary`]. The primary must keep the state of all the
+	/// blocks (archive mode), as the non-canonicalized state is only kept in its memory.
+	#[cfg(feature = "rocksdb")]
+	RocksDbSecondary {
+		/// Path to the database of the primary instance.
+		path: PathBuf,
+		/// Path to the directory keeping the info logs of the secondary instance.
+		secondary_path: PathBuf,
+		/// Cache size in MiB.
+		cache_size: usize,
+	},
+
 	/// Load a ParityDb database from a given path.
 	ParityDb {
 		/// Path to the database.
@@ -413,11 +428,22 @@ impl DatabaseSource {
 			DatabaseSource::Auto { paritydb_path, .. } => Some(paritydb_path),
 			#[cfg(feature = "rocksdb")]
 			DatabaseSource::RocksDb { path, .. } => Some(path),
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::RocksDbSecondary { path, .. } => Some(path),
 			DatabaseSource::ParityDb { path } => Some(path),
 			DatabaseSource::Custom { .. } => None,
 		}
 	}
 
+	/// Returns `true` if the database is opened as a read-only secondary instance.
+	pub fn is_secondary(&self) -> bool {
+		#[cfg(feature = "rocksdb")]
+		if let DatabaseSource::RocksDbSecondary { .. } = self {
+			return true;
+		}
+		false
+	}
+
 	/// Set path for databases that are stored on disk.
 	pub fn set_path(&mut self, p: &Path) -> bool {
 		match self {
@@ -430,6 +456,11 @@ impl DatabaseSource {
 				*path = p.into();
 				true
 			},
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::RocksDbSecondary { ref mut path, .. } => {
+				*path = p.into();
+				true
+			},
 			DatabaseSource::ParityDb { ref mut path } => {
 				*path = p.into();
 				true
@@ -445,6 +476,8 @@ impl std::fmt::Display for DatabaseSource {
 			DatabaseSource::Auto { .. } => "Auto",
 			#[cfg(feature = "rocksdb")]
 			DatabaseSource::RocksDb { .. } => "RocksDb",
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::RocksDbSecondary { .. } => "RocksDbSecondary",
 			DatabaseSource::ParityDb { .. } => "ParityDb",
 			DatabaseSource::Custom { .. } => "Custom",
 		};
@@ -559,6 +592,18 @@ impl<Block: BlockT> BlockchainDb<Block> {
 		meta.block_gap = gap;
 	}
 
+	/// Re-read the meta and the leaves from the database, after it was changed by another
+	/// process.
+	fn reload(&self) -> ClientResult<()> {
+		let meta = read_meta::<Block>(&*self.db, columns::HEADER)?;
+		let leaves = LeafSet::read_from_db(&*self.db, columns::META, meta_keys::LEAF_PREFIX)?;
+		*self.meta.write() = meta;
+		*self.leaves.write() = leaves;
+		// The cache also remembers the headers that were missing.
+		self.header_cache.lock().clear();
+		Ok(())
+	}
+
 	/// Empty the cache of pinned items.
 	fn clear_pinning_cache(&self) {
 		self.pinned_blocks_cache.write().clear();
@@ -1179,7 +1224,8 @@ impl<Block: BlockT> Backend<Block> {
 		let (needs_init, db) =
 			match crate::utils::open_database::<Block>(db_source, DatabaseType::Full, false) {
 				Ok(db) => (false, db),
-				Err(OpenDbError::DoesNotExist) => {
+				// The secondary instance can not create the database of the primary one.
+				Err(OpenDbError::DoesNotExist) if !db_source.is_secondary() => {
 					let db =
 						crate::utils::open_database::<Block>(db_source, DatabaseType::Full, true)?;
 					(true, db)
@@ -1190,6 +1236,17 @@ impl<Block: BlockT> Backend<Block> {
 		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, needs_init)
 	}
 
+	/// Catch up with the changes made to the database by the primary instance.
+	///
+	/// Only meaningful when the database is opened as a read-only secondary instance, see
+	/// [`DatabaseSource::RocksDbSecondary`]. Updates the best and finalized blocks and the leaves
+	/// to the ones last written by the primary. The backend doesn't emit any import or finality
+	/// notifications for them.
+	pub fn try_catch_up_with_primary(&self) -> ClientResult<()> {
+		self.storage.db.try_catch_up_with_primary()?;
+		self.blockchain.reload()
+	}
+
 	/// Reset the shared trie cache.
 	pub fn reset_trie_cache(&self) {
 		if let Some(cache) = &self.shared_trie_cache {
@@ -1306,6 +1363,15 @@ impl<Block: BlockT> Backend<Block> {
 		apply_state_commit(&mut db_init_transaction, state_db_init_commit_set);
 
 		let state_pruning_used = state_db.pruning_mode();
+		// The primary instance keeps the non-canonicalized state in memory, so the secondary
+		// instance only sees the state of all the blocks when the primary does not prune it.
+		if config.source.is_secondary() && state_pruning_used != PruningMode::ArchiveAll {
+			return Err(sp_blockchain::Error::Backend(format!(
+				"The secondary database instance requires the primary to run with the \
+				 `ArchiveAll` state pruning mode (`--state-pruning archive`), found {:?}",
+				state_pruning_used
+			)));
+		}
 		let is_archive_pruning = state_pruning_used.is_archive();
 		let blockchain = BlockchainDb::new(db.clone())?;
 
@@ -1363,7 +1429,10 @@ impl<Block: BlockT> Backend<Block> {
 			});
 		}
 
-		db.commit(db_init_transaction)?;
+		// The secondary instance is read-only, the primary already initialized the database.
+		if !config.source.is_secondary() {
+			db.commit(db_init_transaction)?;
+		}
 
 		Ok(backend)
 	}
@@ -5495,4 +5564,52 @@ pub(crate) mod tests {
 		assert!(bc.body(blocks[3]).unwrap().is_some());
 		assert!(bc.body(blocks[4]).unwrap().is_some());
 	}
+
+	#[cfg(feature = "rocksdb")]
+	#[test]
+	fn secondary_follows_primary_after_catch_up() {
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let path = db_dir.path().join("primary");
+		let settings = |source| DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::ArchiveAll),
+			source,
+			blocks_pruning: BlocksPruning::KeepAll,
+			pruning_filters: Default::default(),
+			metrics_registry: None,
+		};
+
+		let primary = Backend::<Block>::new(
+			settings(DatabaseSource::RocksDb { path: path.clone(), cache_size: 16 }),
+			0,
+		)
+		.unwrap();
+		let genesis = insert_header(&primary, 0, Default::default(), None, Default::default());
+
+		let secondary = Backend::<Block>::new(
+			settings(DatabaseSource::RocksDbSecondary {
+				path,
+				secondary_path: db_dir.path().join("secondary"),
+				cache_size: 16,
+			}),
+			0,
+		)
+		.unwrap();
+		assert_eq!(secondary.blockchain().info().best_hash, genesis);
+
+		let block1 = insert_header(&primary, 1, genesis, None, Default::default());
+		let block2 = insert_header(&primary, 2, block1, None, Default::default());
+		primary.finalize_block(block1, None).unwrap();
+
+		// The changes of the primary are only visible after catching up.
+		let info = secondary.blockchain().info();
+		assert_eq!((info.best_hash, info.finalized_hash), (genesis, genesis));
+
+		secondary.try_catch_up_with_primary().unwrap();
+		let info = secondary.blockchain().info();
+		assert_eq!((info.best_hash, info.best_number), (block2, 2));
+		assert_eq!((info.finalized_hash, info.finalized_number), (block1, 1));
+		assert_eq!(secondary.blockchain().leaves().unwrap(), vec![block2]);
+		assert!(secondary.blockchain().header(block2).unwrap().is_some());
+	}
 }
@@ -189,7 +189,9 @@ pub fn open_database<Block: BlockT>(
 	// Maybe migrate (copy) the database to a type specific subdirectory to make it
 	// possible that light and full databases coexist
 	// NOTE: This function can be removed in a few releases
-	maybe_migrate_to_type_subdir::<Block>(db_source, db_type)?;
+	if !db_source.is_secondary() {
+		maybe_migrate_to_type_subdir::<Block>(db_source, db_type)?;
+	}
 
 	open_database_at::<Block>(db_source, db_type, create)
 }
@@ -203,7 +205,11 @@ fn open_database_at<Block: BlockT>(
 		DatabaseSource::ParityDb { path } => open_parity_db::<Block>(path, db_type, create)?,
 		#[cfg(feature = "rocksdb")]
 		DatabaseSource::RocksDb { path, cache_size } => {
-			open_kvdb_rocksdb::<Block>(path, db_type, create, *cache_size)?
+			open_kvdb_rocksdb::<Block>(path, None, db_type, create, *cache_size)?
+		},
+		#[cfg(feature = "rocksdb")]
+		DatabaseSource::RocksDbSecondary { path, secondary_path, cache_size } => {
+			open_kvdb_rocksdb::<Block>(path, Some(secondary_path), db_type, false, *cache_size)?
 		},
 		DatabaseSource::Custom { db, require_create_flag } => {
 			if *require_create_flag && !create {
@@ -213,7 +219,7 @@ fn open_database_at<Block: BlockT>(
 		},
 		DatabaseSource::Auto { paritydb_path, rocksdb_path, cache_size } => {
 			// check if rocksdb exists first, if not, open paritydb
-			match open_kvdb_rocksdb::<Block>(rocksdb_path, db_type, false, *cache_size) {
+			match open_kvdb_rocksdb::<Block>(rocksdb_path, None, db_type, false, *cache_size) {
 				Ok(db) => db,
 				Err(OpenDbError::NotEnabled(_)) | Err(OpenDbError::DoesNotExist) => {
 					open_parity_db::<Block>(paritydb_path, db_type, create)?
@@ -304,24 +310,41 @@ fn open_parity_db<Block: BlockT>(path: &Path, db_type: DatabaseType, create: boo
 	}
 }
 
+/// Opens the RocksDB database at the given path.
+///
+/// If the `secondary_path` is given, the database is opened as a read-only secondary instance
+/// of the database written by another process. It is never created nor upgraded in this case.
 #[cfg(any(feature = "rocksdb", test))]
 fn open_kvdb_rocksdb<Block: BlockT>(
 	path: &Path,
+	secondary_path: Option<&Path>,
 	db_type: DatabaseType,
 	create: bool,
 	cache_size: usize,
 ) -> OpenDbResult {
-	// first upgrade database to required version
-	match crate::upgrade::upgrade_db::<Block>(path, db_type) {
-		// in case of missing version file, assume that database simply does not exist at given
-		// location
-		Ok(_) | Err(crate::upgrade::UpgradeError::MissingDatabaseVersionFile) => (),
-		Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err.to_string()).into()),
+	if secondary_path.is_some() {
+		// only the primary instance may upgrade the database
+		match crate::upgrade::check_version(path) {
+			Ok(_) => (),
+			Err(crate::upgrade::UpgradeError::MissingDatabaseVersionFile) => {
+				return Err(OpenDbError::DoesNotExist)
+			},
+			Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err.to_string()).into()),
+		}
+	} else {
+		// first upgrade database to required version
+		match crate::upgrade::upgrade_db::<Block>(path, db_type) {
+			// in case of missing version file, assume that database simply does not exist at given
+			// location
+			Ok(_) | Err(crate::upgrade::UpgradeError::MissingDatabaseVersionFile) => (),
+			Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err.to_string()).into()),
+		}
 	}
 
 	// and now open database assuming that it has the latest version
 	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
-	db_config.create_if_missing = create;
+	db_config.create_if_missing = create && secondary_path.is_none();
+	db_config.secondary = secondary_path.map(Path::to_path_buf);
 
 	let mut memory_budget = std::collections::HashMap::new();
 	match db_type {
@@ -349,14 +372,17 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	db_config.memory_budget = memory_budget;
 
 	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
-	// write database version only after the database is successfully opened
-	crate::upgrade::update_version(path)?;
+	if secondary_path.is_none() {
+		// write database version only after the database is successfully opened
+		crate::upgrade::update_version(path)?;
+	}
 	Ok(sp_database::as_rocksdb_database(db))
 }
 
 #[cfg(not(any(feature = "rocksdb", test)))]
 fn open_kvdb_rocksdb<Block: BlockT>(
 	_path: &Path,
+	_secondary_path: Option<&Path>,
 	_db_type: DatabaseType,
 	_create: bool,
 	_cache_size: usize,
@@ -863,4 +889,43 @@ mod tests {
 			assert!(db_res.is_ok(), "Existing parity database should be reopened");
 		}
 	}
+
+	#[cfg(feature = "rocksdb")]
+	#[test]
+	fn test_open_database_rocksdb_secondary() {
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let rocksdb_path = db_dir.path().join("rocksdb_path");
+		let secondary = DatabaseSource::RocksDbSecondary {
+			path: rocksdb_path.clone(),
+			secondary_path: db_dir.path().join("secondary"),
+			cache_size: 128,
+		};
+
+		// it should not create the database of the primary
+		assert!(matches!(
+			open_database::<Block>(&secondary, DatabaseType::Full, true),
+			Err(OpenDbError::DoesNotExist)
+		));
+
+		let primary = open_database::<Block>(
+			&DatabaseSource::RocksDb { path: rocksdb_path, cache_size: 128 },
+			DatabaseType::Full,
+			true,
+		)
+		.unwrap();
+		let secondary = open_database::<Block>(&secondary, DatabaseType::Full, true)
+			.expect("Existing rocksdb database should be opened as secondary");
+
+		let mut transaction = Transaction::new();
+		transaction.set(COLUMN_META, b"key", b"value");
+		primary.commit(transaction.clone()).unwrap();
+
+		// the changes of the primary are only visible after catching up
+		assert_eq!(secondary.get(COLUMN_META, b"key"), None);
+		secondary.try_catch_up_with_primary().unwrap();
+		assert_eq!(secondary.get(COLUMN_META, b"key"), Some(b"value".to_vec()));
+
+		// the secondary is read-only
+		assert!(secondary.commit(transaction).is_err());
+	}
 }
@@ -0,0 +1,53 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API trait of the archive index methods.
+
+use crate::archive::{
+	error::Error,
+	index::types::{EventItem, EventQuery, ExtrinsicItem, ExtrinsicQuery, QueryPage},
+};
+use jsonrpsee::proc_macros::rpc;
+
+#[rpc(client, server)]
+pub trait ArchiveIndexApi {
+	/// Queries the indexed events involving the given account.
+	///
+	/// Returns a page of events ordered by their position in the chain, and the cursor of the
+	/// next page if there are more matching events.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "archive_v1_queryEvents")]
+	fn archive_v1_query_events(&self, query: EventQuery) -> Result<QueryPage<EventItem>, Error>;
+
+	/// Queries the indexed extrinsics signed by the given account.
+	///
+	/// Returns a page of extrinsics ordered by their position in the chain, and the cursor of the
+	/// next page if there are more matching extrinsics.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "archive_v1_queryExtrinsics")]
+	fn archive_v1_query_extrinsics(
+		&self,
+		query: ExtrinsicQuery,
+	) -> Result<QueryPage<ExtrinsicItem>, Error>;
+}
@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API implementation for the archive index.
+
+use crate::{
+	archive::{
+		error::Error as ArchiveError,
+		index::{
+			store::{ArchiveIndexStore, EventFilter, QueryRange},
+			types::{EventItem, EventQuery, ExtrinsicItem, ExtrinsicQuery, IndexCursor, QueryPage},
+			ArchiveIndexApiServer,
+		},
+	},
+	hex_string,
+};
+
+use codec::Encode;
+use sc_client_api::BlockBackend;
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::OffchainStorage;
+use sp_runtime::{traits::Block as BlockT, SaturatedConversion};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The number of items returned by a query if no limit is provided.
+const DEFAULT_QUERY_LIMIT: u32 = 100;
+/// The maximum number of items returned by a single query.
+const MAX_QUERY_LIMIT: u32 = 1000;
+
+/// An API for the archive index RPC calls.
+pub struct ArchiveIndex<Block: BlockT, Client, S> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// The index populated by the [`ArchiveIndexer`](super::ArchiveIndexer).
+	store: ArchiveIndexStore<S>,
+	/// Phantom member to pin the block type.
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block: BlockT, Client, S> ArchiveIndex<Block, Client, S> {
+	/// Create a new [`ArchiveIndex`].
+	pub fn new(client: Arc<Client>, store: ArchiveIndexStore<S>) -> Self {
+		Self { client, store, _phantom: PhantomData }
+	}
+}
+
+/// Parse the hex-encoded account.
+fn parse_account(account: String) -> Result<Vec<u8>, ArchiveError> {
+	match array_bytes::hex2bytes(&account) {
+		Ok(account) if !account.is_empty() => Ok(account),
+		_ => Err(ArchiveError::InvalidParam(format!("Invalid account: {account}"))),
+	}
+}
+
+impl<Block, Client, S> ArchiveIndex<Block, Client, S>
+where
+	Block: BlockT + 'static,
+	Client: BlockBackend<Block> + HeaderBackend<Block> + 'static,
+	S: OffchainStorage + 'static,
+{
+	/// Builds the range of the query, limited to the already indexed blocks.
+	fn query_range(
+		from_height: u64,
+		to_height: Option<u64>,
+		indexed_height: u64,
+		start_after: Option<IndexCursor>,
+		limit: Option<u32>,
+	) -> Result<QueryRange, ArchiveError> {
+		let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+		if limit == 0 || limit > MAX_QUERY_LIMIT {
+			return Err(ArchiveError::InvalidParam(format!(
+				"Invalid limit: {limit}, expected a value between 1 and {MAX_QUERY_LIMIT}"
+			)));
+		}
+
+		let to = to_height.map_or(indexed_height, |to| to.min(indexed_height));
+		Ok(QueryRange { from: from_height, to, start_after, limit: limit as usize })
+	}
+
+	/// Returns the hash of the finalized block at the given height.
+	fn block_hash(&self, height: u64) -> Result<Block::Hash, ArchiveError> {
+		self.client
+			.hash(height.saturated_into())
+			.ok()
+			.flatten()
+			.ok_or_else(|| ArchiveError::Index(format!("Block hash is not present: #{height}")))
+	}
+}
+
+impl<Block, Client, S> ArchiveIndexApiServer for ArchiveIndex<Block, Client, S>
+where
+	Block: BlockT + 'static,
+	Client: BlockBackend<Block> + HeaderBackend<Block> + 'static,
+	S: OffchainStorage + 'static,
+{
+	fn archive_v1_query_events(
+		&self,
+		query: EventQuery,
+	) -> Result<QueryPage<EventItem>, ArchiveError> {
+		let account = parse_account(query.account)?;
+		if query.event_index.is_some() && query.pallet_index.is_none() {
+			return Err(ArchiveError::InvalidParam(
+				"eventIndex is only valid together with palletIndex".to_string(),
+			));
+		}
+
+		let Some(indexed_height) = self.store.last_indexed() else {
+			return Ok(QueryPage { items: vec![], next: None, indexed_height: None });
+		};
+		let range = Self::query_range(
+			query.from_height,
+			query.to_height,
+			indexed_height,
+			query.start_after,
+			query.limit,
+		)?;
+
+		let filter =
+			EventFilter { pallet_index: query.pallet_index, event_index: query.event_index };
+		let (events, next) = self.store.query_events(&account, filter, range);
+
+		let items = events
+			.into_iter()
+			.map(|(height, event)| {
+				Ok(EventItem {
+					block_height: height,
+					block_hash: hex_string(&self.block_hash(height)?.as_ref()),
+					index: event.index,
+					extrinsic_index: event.extrinsic_index,
+					pallet_index: event.pallet_index,
+					event_index: event.event_index,
+					event: hex_string(&event.data),
+				})
+			})
+			.collect::<Result<Vec<_>, ArchiveError>>()?;
+
+		Ok(QueryPage { items, next, indexed_height: Some(indexed_height) })
+	}
+
+	fn archive_v1_query_extrinsics(
+		&self,
+		query: ExtrinsicQuery,
+	) -> Result<QueryPage<ExtrinsicItem>, ArchiveError> {
+		let signer = parse_account(query.account)?;
+
+		let Some(indexed_height) = self.store.last_indexed() else {
+			return Ok(QueryPage { items: vec![], next: None, indexed_height: None });
+		};
+		let range = Self::query_range(
+			query.from_height,
+			query.to_height,
+			indexed_height,
+			query.start_after,
+			query.limit,
+		)?;
+
+		let (cursors, next) = self.store.query_extrinsics(&signer, range);
+
+		let mut block: Option<(u64, Block::Hash, Vec<Block::Extrinsic>)> = None;
+		let mut items = Vec::with_capacity(cursors.len());
+		for cursor in cursors {
+			if block.as_ref().map_or(true, |(height, _, _)| *height != cursor.height) {
+				let hash = self.block_hash(cursor.height)?;
+				let body = self.client.block_body(hash).ok().flatten().ok_or_else(|| {
+					ArchiveError::Index(format!("Block body is not present: {hash}"))
+				})?;
+				block = Some((cursor.height, hash, body));
+			}
+			let Some((height, hash, body)) = block.as_ref() else { continue };
+			let Some(extrinsic) = body.get(cursor.index as usize) else { continue };
+			items.push(ExtrinsicItem {
+				block_height: *height,
+				block_hash: hex_string(&hash.as_ref()),
+				index: cursor.index,
+				extrinsic: hex_string(&extrinsic.encode()),
+			});
+		}
+
+		Ok(QueryPage { items, next, indexed_height: Some(indexed_height) })
+	}
+}
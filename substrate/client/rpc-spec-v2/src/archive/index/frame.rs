@@ -0,0 +1,324 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Index extractor of the FRAME based runtimes.
+
+use super::{
+	indexer::IndexExtractor,
+	types::{BlockIndexData, IndexedEvent, IndexedExtrinsic},
+};
+use codec::{Compact, Decode, Encode};
+use frame_metadata::{v15::StorageEntryType, RuntimeMetadata, RuntimeMetadataPrefixed};
+use parking_lot::Mutex;
+use scale_info::{form::PortableForm, PortableRegistry, TypeDef, TypeDefPrimitive};
+use sp_api::{Core, Metadata, ProvideRuntimeApi};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Zero};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The version of the metadata describing the events and the extrinsics.
+const METADATA_VERSION: u32 = 15;
+
+/// The mask of the extrinsic version byte holding the type of the extrinsic.
+const EXTRINSIC_TYPE_MASK: u8 = 0b1100_0000;
+/// The type of the signed extrinsics.
+const SIGNED_EXTRINSIC: u8 = 0b1000_0000;
+
+/// The types of the runtime needed to extract the data to be indexed.
+struct RuntimeTypes {
+	/// The types of the runtime.
+	registry: PortableRegistry,
+	/// The type of the `RuntimeEvent`.
+	event_ty: u32,
+	/// The type of the address of the signed extrinsics.
+	address_ty: u32,
+	/// The type of the `AccountId`, taken from the keys of the `System::Account` storage map.
+	account_ty: u32,
+}
+
+impl RuntimeTypes {
+	/// Extracts the types from the encoded metadata.
+	fn new(metadata: &[u8]) -> Result<Self, String> {
+		let RuntimeMetadata::V15(metadata) = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|error| format!("Failed to decode the metadata: {error}"))?
+			.1
+		else {
+			return Err("Unexpected metadata version".into());
+		};
+
+		let account_ty = metadata
+			.pallets
+			.iter()
+			.find(|pallet| pallet.name == "System")
+			.and_then(|pallet| pallet.storage.as_ref())
+			.and_then(|storage| storage.entries.iter().find(|entry| entry.name == "Account"))
+			.and_then(|entry| match &entry.ty {
+				StorageEntryType::Map { key, .. } => Some(key.id),
+				StorageEntryType::Plain(_) => None,
+			})
+			.ok_or_else(|| "The `System::Account` storage map is missing".to_string())?;
+
+		Ok(Self {
+			registry: metadata.types,
+			event_ty: metadata.outer_enums.event_enum_ty.id,
+			address_ty: metadata.extrinsic.address_ty.id,
+			account_ty,
+		})
+	}
+
+	/// Skips the value of the type `ty` in the `input`, collecting the encoded accounts found in
+	/// the value.
+	fn skip(
+		&self,
+		ty: u32,
+		input: &mut &[u8],
+		accounts: &mut Vec<Vec<u8>>,
+	) -> Result<(), codec::Error> {
+		let start = *input;
+		match self.resolve(ty)? {
+			TypeDef::Composite(composite) => {
+				for field in &composite.fields {
+					self.skip(field.ty.id, input, accounts)?;
+				}
+			},
+			TypeDef::Variant(variant) => {
+				let index = u8::decode(input)?;
+				let variant = variant
+					.variants
+					.iter()
+					.find(|variant| variant.index == index)
+					.ok_or("Unknown variant")?;
+				for field in &variant.fields {
+					self.skip(field.ty.id, input, accounts)?;
+				}
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				self.skip_items(sequence.type_param.id, len, input, accounts)?;
+			},
+			TypeDef::Array(array) => {
+				self.skip_items(array.type_param.id, array.len, input, accounts)?
+			},
+			TypeDef::Tuple(tuple) => {
+				for field in &tuple.fields {
+					self.skip(field.id, input, accounts)?;
+				}
+			},
+			TypeDef::Primitive(primitive) => skip_primitive(primitive, input)?,
+			// The compact encoding tells its own length, whatever the type.
+			TypeDef::Compact(_) => {
+				Compact::<u128>::decode(input)?;
+			},
+			TypeDef::BitSequence(bits) => {
+				let len = Compact::<u32>::decode(input)?.0 as usize;
+				let store_bits = match self.resolve(bits.bit_store_type.id)? {
+					TypeDef::Primitive(TypeDefPrimitive::U8) => 8,
+					TypeDef::Primitive(TypeDefPrimitive::U16) => 16,
+					TypeDef::Primitive(TypeDefPrimitive::U32) => 32,
+					TypeDef::Primitive(TypeDefPrimitive::U64) => 64,
+					_ => return Err("Invalid bit store type".into()),
+				};
+				skip_bytes(len.div_ceil(store_bits) * store_bits / 8, input)?;
+			},
+		}
+
+		if ty == self.account_ty {
+			accounts.push(start[..start.len() - input.len()].to_vec());
+		}
+		Ok(())
+	}
+
+	/// Skips `len` items of the type `ty`.
+	fn skip_items(
+		&self,
+		ty: u32,
+		len: u32,
+		input: &mut &[u8],
+		accounts: &mut Vec<Vec<u8>>,
+	) -> Result<(), codec::Error> {
+		// Bytes are common enough to be worth skipping at once.
+		if ty != self.account_ty &&
+			matches!(self.resolve(ty)?, TypeDef::Primitive(TypeDefPrimitive::U8))
+		{
+			return skip_bytes(len as usize, input);
+		}
+		for _ in 0..len {
+			self.skip(ty, input, accounts)?;
+		}
+		Ok(())
+	}
+
+	/// Returns the definition of the type `ty`.
+	fn resolve(&self, ty: u32) -> Result<&TypeDef<PortableForm>, codec::Error> {
+		Ok(&self.registry.resolve(ty).ok_or("Unknown type")?.type_def)
+	}
+}
+
+/// Skips the encoded primitive value in the `input`.
+fn skip_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<(), codec::Error> {
+	let len = match primitive {
+		TypeDefPrimitive::Bool | TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+		TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+		TypeDefPrimitive::Char | TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+		TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+		TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+		TypeDefPrimitive::Str => Compact::<u32>::decode(input)?.0 as usize,
+	};
+	skip_bytes(len, input)
+}
+
+/// Skips `len` bytes of the `input`.
+fn skip_bytes(len: usize, input: &mut &[u8]) -> Result<(), codec::Error> {
+	*input = input.get(len..).ok_or("Not enough data to skip")?;
+	Ok(())
+}
+
+/// Extracts the data to be indexed from the blocks of the FRAME based runtimes.
+///
+/// The `frame_system::EventRecord`s and the addresses of the signed extrinsics are decoded using
+/// the metadata of the runtime which executed the block, and the accounts are the values of the
+/// `AccountId` type of the runtime found in them. The signer of an extrinsic is the first account
+/// found in its address.
+pub struct FrameIndexExtractor<Block, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// The types of the last runtime used, with its spec version.
+	types: Mutex<Option<(u32, Arc<RuntimeTypes>)>>,
+	/// Phantom member to pin the block type.
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, Client> FrameIndexExtractor<Block, Client> {
+	/// Creates a new [`FrameIndexExtractor`].
+	pub fn new(client: Arc<Client>) -> Self {
+		Self { client, types: Mutex::new(None), _phantom: PhantomData }
+	}
+}
+
+impl<Block, Client> FrameIndexExtractor<Block, Client>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: Core<Block> + Metadata<Block>,
+{
+	/// Returns the types of the runtime at the given block.
+	fn runtime_types(&self, at: Block::Hash) -> Result<Arc<RuntimeTypes>, String> {
+		let api = self.client.runtime_api();
+		let spec_version = api.version(at).map_err(|error| error.to_string())?.spec_version;
+
+		let mut types = self.types.lock();
+		match &*types {
+			Some((version, types)) if *version == spec_version => Ok(types.clone()),
+			_ => {
+				let metadata = api
+					.metadata_at_version(at, METADATA_VERSION)
+					.map_err(|error| error.to_string())?
+					.ok_or_else(|| format!("Metadata V{METADATA_VERSION} is not supported"))?;
+				let runtime_types = Arc::new(RuntimeTypes::new(&metadata)?);
+				*types = Some((spec_version, runtime_types.clone()));
+				Ok(runtime_types)
+			},
+		}
+	}
+}
+
+/// Decodes the encoded `Vec<frame_system::EventRecord>`.
+fn extract_events<Block: BlockT>(
+	types: &RuntimeTypes,
+	mut input: &[u8],
+) -> Result<Vec<IndexedEvent>, codec::Error> {
+	let input = &mut input;
+	let len = Compact::<u32>::decode(input)?.0;
+	(0..len)
+		.map(|index| -> Result<_, codec::Error> {
+			// `Phase::ApplyExtrinsic` is the first variant of the phase, followed by the
+			// finalization and the initialization.
+			let extrinsic_index = match u8::decode(input)? {
+				0 => Some(u32::decode(input)?),
+				1 | 2 => None,
+				_ => return Err("Invalid event phase".into()),
+			};
+
+			let start = *input;
+			let mut accounts = Vec::new();
+			types.skip(types.event_ty, input, &mut accounts)?;
+			let data = start[..start.len() - input.len()].to_vec();
+			let &[pallet_index, event_index, ..] = &data[..] else {
+				return Err("Invalid event".into());
+			};
+
+			// The topics.
+			Vec::<Block::Hash>::decode(input)?;
+
+			Ok(IndexedEvent { index, extrinsic_index, pallet_index, event_index, accounts, data })
+		})
+		.collect()
+}
+
+/// Returns the signer of the extrinsic, `None` if the extrinsic is not signed.
+fn extract_signer(types: &RuntimeTypes, extrinsic: &[u8]) -> Result<Option<Vec<u8>>, codec::Error> {
+	let input = &mut &extrinsic[..];
+	// The extrinsics are encoded with their length.
+	Compact::<u32>::decode(input)?;
+	if u8::decode(input)? & EXTRINSIC_TYPE_MASK != SIGNED_EXTRINSIC {
+		return Ok(None);
+	}
+
+	let mut accounts = Vec::new();
+	types.skip(types.address_ty, input, &mut accounts)?;
+	Ok(accounts.into_iter().next())
+}
+
+impl<Block, Client> IndexExtractor<Block> for FrameIndexExtractor<Block, Client>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block> + Send + Sync,
+	Client::Api: Core<Block> + Metadata<Block>,
+{
+	fn extract(&self, block: &Block, events: Option<&[u8]>) -> Result<BlockIndexData, String> {
+		// The block was executed by the runtime of its parent.
+		let header = block.header();
+		let at = match header.number().is_zero() {
+			true => header.hash(),
+			false => *header.parent_hash(),
+		};
+		let types = self.runtime_types(at)?;
+
+		let events = events
+			.map(|events| extract_events::<Block>(&types, events))
+			.transpose()
+			.map_err(|error| format!("Failed to decode the events: {error}"))?
+			.unwrap_or_default();
+
+		let extrinsics = block
+			.extrinsics()
+			.iter()
+			.enumerate()
+			.filter_map(|(index, extrinsic)| {
+				extract_signer(&types, &extrinsic.encode())
+					.map_err(|error| format!("Failed to decode the extrinsic #{index}: {error}"))
+					.transpose()
+					.map(|signer| {
+						signer.map(|signer| IndexedExtrinsic { index: index as u32, signer })
+					})
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(BlockIndexData { events, extrinsics })
+	}
+}
@@ -0,0 +1,183 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Background worker populating the archive index.
+
+use super::{store::ArchiveIndexStore, types::BlockIndexData};
+use crate::archive::archive::LOG_TARGET;
+use futures::StreamExt;
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents, StorageKey, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::{offchain::OffchainStorage, twox_128};
+use sp_runtime::{traits::Block as BlockT, SaturatedConversion};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Extracts the data to be indexed from the block.
+///
+/// The events and the signers can only be decoded with the types of the runtime, so the extractor
+/// is provided by the node.
+pub trait IndexExtractor<Block: BlockT>: Send + Sync {
+	/// Extracts the data to be indexed from the given block.
+	///
+	/// The `events` are the encoded content of the `System::Events` storage item at the given
+	/// block, if present.
+	fn extract(&self, block: &Block, events: Option<&[u8]>) -> Result<BlockIndexData, String>;
+}
+
+impl<Block, F> IndexExtractor<Block> for F
+where
+	Block: BlockT,
+	F: Fn(&Block, Option<&[u8]>) -> Result<BlockIndexData, String> + Send + Sync,
+{
+	fn extract(&self, block: &Block, events: Option<&[u8]>) -> Result<BlockIndexData, String> {
+		self(block, events)
+	}
+}
+
+/// Returns the storage key of the `System::Events` storage item.
+fn system_events_key() -> StorageKey {
+	StorageKey([twox_128(b"System"), twox_128(b"Events")].concat())
+}
+
+/// Indexes the finalized blocks.
+///
+/// Only the finalized blocks are indexed, so the index does not need to handle the forks. The
+/// state of the indexed blocks must be available, so the node is expected to keep the state of
+/// all the blocks (archive mode).
+pub struct ArchiveIndexer<BE, Block: BlockT, Client, S> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// The index being populated.
+	store: ArchiveIndexStore<S>,
+	/// The extractor of the data to be indexed.
+	extractor: Arc<dyn IndexExtractor<Block>>,
+	/// The height of the first block to index.
+	start_height: u64,
+	/// Phantom member to pin the backend type.
+	_phantom: PhantomData<BE>,
+}
+
+impl<BE, Block, Client, S> ArchiveIndexer<BE, Block, Client, S>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ BlockchainEvents<Block>
+		+ StorageProvider<Block, BE>
+		+ 'static,
+	S: OffchainStorage,
+{
+	/// Creates a new [`ArchiveIndexer`].
+	pub fn new(
+		client: Arc<Client>,
+		store: ArchiveIndexStore<S>,
+		extractor: Arc<dyn IndexExtractor<Block>>,
+	) -> Self {
+		Self { client, store, extractor, start_height: 0, _phantom: PhantomData }
+	}
+
+	/// Only index the blocks from the given height on.
+	///
+	/// The earlier blocks are never indexed, e.g. because their state is no longer available.
+	pub fn with_start_height(mut self, start_height: u64) -> Self {
+		self.start_height = start_height;
+		self
+	}
+
+	/// Runs the indexer.
+	///
+	/// Catches up with the finalized chain first, then indexes the blocks as they are finalized.
+	/// Accessing the database is blocking, so the returned future should be spawned as a blocking
+	/// task.
+	pub async fn run(mut self) {
+		let mut finality_notifications = self.client.finality_notification_stream();
+		loop {
+			self.index_finalized();
+			if finality_notifications.next().await.is_none() {
+				log::debug!(target: LOG_TARGET, "Finality notifications stream ended");
+				return;
+			}
+		}
+	}
+
+	/// Indexes all the finalized blocks that were not indexed yet.
+	pub(crate) fn index_finalized(&mut self) {
+		let finalized: u64 = self.client.info().finalized_number.saturated_into();
+		let mut height = self
+			.store
+			.last_indexed()
+			.map_or(self.start_height, |last| (last + 1).max(self.start_height));
+
+		while height <= finalized {
+			if let Err(error) = self.index_block(height) {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to index block #{height}, will retry on the next finalized block: {error}"
+				);
+				return;
+			}
+			height += 1;
+		}
+	}
+
+	/// Indexes the finalized block at the given height.
+	///
+	/// Fails if the block or its events are not available yet, to be retried later. The blocks
+	/// whose data can't be extracted never will, so they are skipped instead of stalling the
+	/// indexer.
+	fn index_block(&mut self, height: u64) -> Result<(), String> {
+		let hash = self
+			.client
+			.hash(height.saturated_into())
+			.map_err(|error| error.to_string())?
+			.ok_or_else(|| "Block hash is not present".to_string())?;
+		let block = self
+			.client
+			.block(hash)
+			.map_err(|error| error.to_string())?
+			.ok_or_else(|| format!("Block body is not present: {hash}"))?
+			.block;
+		let events = self
+			.client
+			.storage(hash, &system_events_key())
+			.map_err(|error| error.to_string())?;
+
+		let data = match self.extractor.extract(&block, events.as_ref().map(|events| &events.0[..]))
+		{
+			Ok(data) => data,
+			Err(error) => {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to extract the data of block #{height} ({hash}), skipping it: {error}"
+				);
+				self.store.skip_block(height, error);
+				return Ok(());
+			},
+		};
+		log::trace!(
+			target: LOG_TARGET,
+			"Indexing block #{height} ({hash}): {} events, {} extrinsics",
+			data.events.len(),
+			data.extrinsics.len()
+		);
+		self.store.insert_block(height, data);
+
+		Ok(())
+	}
+}
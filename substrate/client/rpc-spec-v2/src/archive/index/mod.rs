@@ -0,0 +1,57 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Optional secondary index over the events and the extrinsic signers of the finalized blocks.
+//!
+//! The index is populated by the [`ArchiveIndexer`] running in the background, which extracts the
+//! data of every finalized block using the [`IndexExtractor`] provided by the node, and keeps it in
+//! the [`ArchiveIndexStore`] on top of the node's offchain storage. The [`FrameIndexExtractor`]
+//! extracts the data of the FRAME based runtimes using their metadata. The [`ArchiveIndex`] serves
+//! the paginated `archive_v1_queryEvents` and `archive_v1_queryExtrinsics` methods from the index.
+//!
+//! The index is not enabled by default. A node willing to serve the queries shall spawn the
+//! indexer as a blocking task and merge the [`ArchiveIndex`] into its RPC module, e.g.:
+//!
+//! ```ignore
+//! let store = ArchiveIndexStore::new(backend.offchain_storage().expect("offchain storage"));
+//! let extractor = Arc::new(FrameIndexExtractor::new(client.clone()));
+//! let indexer = ArchiveIndexer::new(client.clone(), store.clone(), extractor);
+//! task_manager.spawn_handle().spawn_blocking("archive-indexer", Some("rpc"), indexer.run());
+//! module.merge(ArchiveIndex::new(client.clone(), store).into_rpc())?;
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+mod archive_index;
+mod frame;
+mod indexer;
+mod store;
+mod types;
+
+pub mod api;
+
+pub use api::ArchiveIndexApiServer;
+pub use archive_index::ArchiveIndex;
+pub use frame::FrameIndexExtractor;
+pub use indexer::{ArchiveIndexer, IndexExtractor};
+pub use store::{ArchiveIndexStore, ARCHIVE_INDEX_PREFIX};
+pub use types::{
+	BlockIndexData, EventItem, EventQuery, ExtrinsicItem, ExtrinsicQuery, IndexCursor,
+	IndexedEvent, IndexedExtrinsic, QueryPage,
+};
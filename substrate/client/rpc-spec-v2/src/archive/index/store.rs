@@ -0,0 +1,319 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistent storage of the archive index.
+//!
+//! The index is kept in the offchain storage of the node under the [`ARCHIVE_INDEX_PREFIX`]. For
+//! every account, the positions of the events and extrinsics are kept in posting lists, each one
+//! covering [`BUCKET_SIZE`] consecutive blocks. The indexed events themselves are kept per block.
+
+use super::types::{BlockIndexData, IndexCursor, IndexedEvent};
+use codec::{Decode, Encode};
+use sp_core::offchain::OffchainStorage;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The prefix of all the keys of the archive index within the offchain storage.
+pub const ARCHIVE_INDEX_PREFIX: &[u8] = b"archive_index";
+
+/// The key of the height of the last indexed block.
+const LAST_INDEXED_KEY: &[u8] = b"last_indexed";
+/// The kind of the posting lists of the events.
+const EVENTS_KIND: &[u8] = b"ev";
+/// The kind of the posting lists of the extrinsics.
+const EXTRINSICS_KIND: &[u8] = b"xt";
+/// The kind of the per-block list of the indexed events.
+const BLOCK_EVENTS_KIND: &[u8] = b"be";
+/// The kind of the marker of a block that could not be indexed.
+const SKIPPED_KIND: &[u8] = b"sk";
+
+/// The number of consecutive blocks covered by a single posting list.
+const BUCKET_SIZE: u64 = 1024;
+
+/// The maximum number of blocks searched by a single query.
+///
+/// A query over a larger range returns a cursor at the last searched block, to be continued with.
+pub(crate) const MAX_QUERY_BLOCK_RANGE: u64 = 64 * BUCKET_SIZE;
+
+/// Something that has a position in the index.
+trait Positioned {
+	fn cursor(&self) -> IndexCursor;
+}
+
+/// The position of an event kept in the posting list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+struct EventPointer {
+	height: u64,
+	index: u32,
+	pallet_index: u8,
+	event_index: u8,
+}
+
+impl Positioned for EventPointer {
+	fn cursor(&self) -> IndexCursor {
+		IndexCursor { height: self.height, index: self.index }
+	}
+}
+
+/// The position of an extrinsic kept in the posting list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+struct ExtrinsicPointer {
+	height: u64,
+	index: u32,
+}
+
+impl Positioned for ExtrinsicPointer {
+	fn cursor(&self) -> IndexCursor {
+		IndexCursor { height: self.height, index: self.index }
+	}
+}
+
+/// Restricts the events returned by the query.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct EventFilter {
+	/// Only return the events of the given pallet.
+	pub(crate) pallet_index: Option<u8>,
+	/// Only return the events of the given variant.
+	pub(crate) event_index: Option<u8>,
+}
+
+impl EventFilter {
+	fn matches(&self, pointer: &EventPointer) -> bool {
+		self.pallet_index.map_or(true, |index| index == pointer.pallet_index) &&
+			self.event_index.map_or(true, |index| index == pointer.event_index)
+	}
+}
+
+/// The range of blocks and the pagination of the query.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QueryRange {
+	/// The first block height to search (inclusive).
+	pub(crate) from: u64,
+	/// The last block height to search (inclusive).
+	pub(crate) to: u64,
+	/// Only return the items after the given cursor.
+	pub(crate) start_after: Option<IndexCursor>,
+	/// The maximum number of items to return.
+	pub(crate) limit: usize,
+}
+
+/// The archive index kept in the offchain storage.
+///
+/// The clones of the store share the underlying database when backed by the offchain storage of
+/// the node's backend.
+#[derive(Clone)]
+pub struct ArchiveIndexStore<S> {
+	storage: S,
+}
+
+impl<S: OffchainStorage> ArchiveIndexStore<S> {
+	/// Creates a new [`ArchiveIndexStore`] on top of the given offchain storage.
+	pub fn new(storage: S) -> Self {
+		Self { storage }
+	}
+
+	/// Returns the height of the last indexed block.
+	pub fn last_indexed(&self) -> Option<u64> {
+		self.get(LAST_INDEXED_KEY)
+	}
+
+	/// Adds the data of the given block to the index.
+	///
+	/// Blocks are expected to be inserted in the order of their heights, the blocks that were
+	/// already indexed are ignored.
+	pub fn insert_block(&mut self, height: u64, data: BlockIndexData) {
+		if self.last_indexed().is_some_and(|last| height <= last) {
+			return;
+		}
+
+		let mut events = BTreeMap::<Vec<u8>, Vec<EventPointer>>::new();
+		for event in &data.events {
+			let pointer = EventPointer {
+				height,
+				index: event.index,
+				pallet_index: event.pallet_index,
+				event_index: event.event_index,
+			};
+			for account in event.accounts.iter().collect::<BTreeSet<_>>() {
+				events.entry(account.clone()).or_default().push(pointer);
+			}
+		}
+		for (account, mut pointers) in events {
+			pointers.sort_by_key(|pointer| pointer.index);
+			self.append(EVENTS_KIND, &account, height, pointers);
+		}
+
+		let mut extrinsics = BTreeMap::<Vec<u8>, Vec<ExtrinsicPointer>>::new();
+		for extrinsic in data.extrinsics {
+			extrinsics
+				.entry(extrinsic.signer)
+				.or_default()
+				.push(ExtrinsicPointer { height, index: extrinsic.index });
+		}
+		for (signer, mut pointers) in extrinsics {
+			pointers.sort_by_key(|pointer| pointer.index);
+			self.append(EXTRINSICS_KIND, &signer, height, pointers);
+		}
+
+		let events = data
+			.events
+			.into_iter()
+			.filter(|event| !event.accounts.is_empty())
+			.collect::<Vec<_>>();
+		if !events.is_empty() {
+			self.storage.set(
+				ARCHIVE_INDEX_PREFIX,
+				&(BLOCK_EVENTS_KIND, height).encode(),
+				&events.encode(),
+			);
+		}
+
+		self.storage.set(ARCHIVE_INDEX_PREFIX, LAST_INDEXED_KEY, &height.encode());
+	}
+
+	/// Marks the block at the given height as skipped, with the reason why it could not be
+	/// indexed.
+	///
+	/// Nothing is indexed for the block, the indexing continues with the next one.
+	pub fn skip_block(&mut self, height: u64, reason: String) {
+		if self.last_indexed().is_some_and(|last| height <= last) {
+			return;
+		}
+
+		self.storage
+			.set(ARCHIVE_INDEX_PREFIX, &(SKIPPED_KIND, height).encode(), &reason.encode());
+		self.storage.set(ARCHIVE_INDEX_PREFIX, LAST_INDEXED_KEY, &height.encode());
+	}
+
+	/// Returns the reason why the block at the given height was skipped, `None` if it was not.
+	pub fn skipped_reason(&self, height: u64) -> Option<String> {
+		self.get(&(SKIPPED_KIND, height).encode())
+	}
+
+	/// Returns the events involving the given account, together with the heights of their blocks.
+	///
+	/// The cursor of the next page is returned if there are more matching events.
+	pub(crate) fn query_events(
+		&self,
+		account: &[u8],
+		filter: EventFilter,
+		range: QueryRange,
+	) -> (Vec<(u64, IndexedEvent)>, Option<IndexCursor>) {
+		let (pointers, next) =
+			self.collect::<EventPointer>(EVENTS_KIND, account, range, |pointer| {
+				filter.matches(pointer)
+			});
+
+		let mut block: Option<(u64, Vec<IndexedEvent>)> = None;
+		let events = pointers
+			.into_iter()
+			.filter_map(|pointer| {
+				if block.as_ref().map_or(true, |(height, _)| *height != pointer.height) {
+					let events =
+						self.get(&(BLOCK_EVENTS_KIND, pointer.height).encode()).unwrap_or_default();
+					block = Some((pointer.height, events));
+				}
+				let (_, events) = block.as_ref()?;
+				events
+					.iter()
+					.find(|event| event.index == pointer.index)
+					.map(|event| (pointer.height, event.clone()))
+			})
+			.collect();
+
+		(events, next)
+	}
+
+	/// Returns the positions of the extrinsics signed by the given account.
+	///
+	/// The cursor of the next page is returned if there are more matching extrinsics.
+	pub(crate) fn query_extrinsics(
+		&self,
+		signer: &[u8],
+		range: QueryRange,
+	) -> (Vec<IndexCursor>, Option<IndexCursor>) {
+		let (pointers, next) =
+			self.collect::<ExtrinsicPointer>(EXTRINSICS_KIND, signer, range, |_| true);
+		(pointers.iter().map(Positioned::cursor).collect(), next)
+	}
+
+	/// Appends the pointers of the block at the given height to the posting list of the account.
+	fn append<P: Positioned + Encode + Decode>(
+		&mut self,
+		kind: &[u8],
+		account: &[u8],
+		height: u64,
+		pointers: Vec<P>,
+	) {
+		let key = (kind, account, height / BUCKET_SIZE).encode();
+		let mut list = self.get::<Vec<P>>(&key).unwrap_or_default();
+		// The block could have been partially indexed before the node was stopped.
+		if list.last().is_some_and(|pointer| pointer.cursor().height >= height) {
+			return;
+		}
+		list.extend(pointers);
+		self.storage.set(ARCHIVE_INDEX_PREFIX, &key, &list.encode());
+	}
+
+	/// Collects the pointers of the account matching the query.
+	///
+	/// At most [`MAX_QUERY_BLOCK_RANGE`] blocks are searched, the returned cursor then points past
+	/// the last searched block if the range is not exhausted.
+	fn collect<P: Positioned + Decode>(
+		&self,
+		kind: &[u8],
+		account: &[u8],
+		range: QueryRange,
+		matches: impl Fn(&P) -> bool,
+	) -> (Vec<P>, Option<IndexCursor>) {
+		let from = range.start_after.map_or(range.from, |after| after.height.max(range.from));
+		let mut items = Vec::new();
+		if from > range.to {
+			return (items, None);
+		}
+		let to = range.to.min(from.saturating_add(MAX_QUERY_BLOCK_RANGE - 1));
+
+		for bucket in from / BUCKET_SIZE..=to / BUCKET_SIZE {
+			let key = (kind, account, bucket).encode();
+			for pointer in self.get::<Vec<P>>(&key).unwrap_or_default() {
+				let cursor = pointer.cursor();
+				if cursor.height < from ||
+					cursor.height > to ||
+					range.start_after.is_some_and(|after| cursor <= after) ||
+					!matches(&pointer)
+				{
+					continue;
+				}
+				if items.len() >= range.limit {
+					let next = items.last().map(Positioned::cursor);
+					return (items, next);
+				}
+				items.push(pointer);
+			}
+		}
+
+		let next = (to < range.to).then_some(IndexCursor { height: to, index: u32::MAX });
+		(items, next)
+	}
+
+	/// Reads and decodes the value kept under the given key.
+	fn get<T: Decode>(&self, key: &[u8]) -> Option<T> {
+		self.storage
+			.get(ARCHIVE_INDEX_PREFIX, key)
+			.and_then(|value| T::decode(&mut &value[..]).ok())
+	}
+}
@@ -0,0 +1,397 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	store::{EventFilter, QueryRange, MAX_QUERY_BLOCK_RANGE},
+	*,
+};
+use crate::hex_string;
+
+use assert_matches::assert_matches;
+use codec::Encode;
+use jsonrpsee::{MethodsError as Error, RpcModule};
+use sc_client_api::{Backend as _, StorageKey, StorageProvider};
+use sp_consensus::BlockOrigin;
+use sp_core::{offchain::storage::InMemOffchainStorage, twox_128};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::sync::Arc;
+use substrate_test_runtime_client::{prelude::*, runtime};
+
+type Block = substrate_test_runtime_client::runtime::Block;
+
+const ALICE: &[u8] = b"alice";
+const BOB: &[u8] = b"bob";
+
+fn event(index: u32, pallet_index: u8, event_index: u8, accounts: &[&[u8]]) -> IndexedEvent {
+	IndexedEvent {
+		index,
+		extrinsic_index: Some(index),
+		pallet_index,
+		event_index,
+		accounts: accounts.iter().map(|account| account.to_vec()).collect(),
+		data: vec![pallet_index, event_index, index as u8],
+	}
+}
+
+fn range(from: u64, to: u64, start_after: Option<IndexCursor>, limit: usize) -> QueryRange {
+	QueryRange { from, to, start_after, limit }
+}
+
+fn cursor(height: u64, index: u32) -> IndexCursor {
+	IndexCursor { height, index }
+}
+
+#[test]
+fn store_paginates_across_buckets() {
+	let mut store = ArchiveIndexStore::new(InMemOffchainStorage::default());
+	assert_eq!(store.last_indexed(), None);
+
+	for height in [1, 2, 2000] {
+		store.insert_block(
+			height,
+			BlockIndexData {
+				events: vec![],
+				extrinsics: vec![
+					IndexedExtrinsic { index: 1, signer: ALICE.to_vec() },
+					IndexedExtrinsic { index: 0, signer: ALICE.to_vec() },
+					IndexedExtrinsic { index: 2, signer: BOB.to_vec() },
+				],
+			},
+		);
+	}
+	assert_eq!(store.last_indexed(), Some(2000));
+
+	let (page, next) = store.query_extrinsics(ALICE, range(0, 2000, None, 4));
+	assert_eq!(page, vec![cursor(1, 0), cursor(1, 1), cursor(2, 0), cursor(2, 1)]);
+	assert_eq!(next, Some(cursor(2, 1)));
+
+	let (page, next) = store.query_extrinsics(ALICE, range(0, 2000, next, 4));
+	assert_eq!(page, vec![cursor(2000, 0), cursor(2000, 1)]);
+	assert_eq!(next, None);
+
+	// The range is respected.
+	let (page, next) = store.query_extrinsics(BOB, range(2, 1999, None, 10));
+	assert_eq!(page, vec![cursor(2, 2)]);
+	assert_eq!(next, None);
+
+	// Unknown accounts have nothing indexed.
+	assert_eq!(store.query_extrinsics(b"charlie", range(0, 2000, None, 10)), (vec![], None));
+}
+
+#[test]
+fn store_filters_events() {
+	let mut store = ArchiveIndexStore::new(InMemOffchainStorage::default());
+	store.insert_block(
+		1,
+		BlockIndexData {
+			events: vec![
+				event(0, 5, 2, &[ALICE, BOB]),
+				event(1, 5, 3, &[ALICE, ALICE]),
+				event(2, 6, 2, &[BOB]),
+				event(3, 0, 0, &[]),
+			],
+			extrinsics: vec![],
+		},
+	);
+
+	let (events, next) = store.query_events(ALICE, EventFilter::default(), range(0, 1, None, 10));
+	assert_eq!(
+		events,
+		vec![(1, event(0, 5, 2, &[ALICE, BOB])), (1, event(1, 5, 3, &[ALICE, ALICE]))]
+	);
+	assert_eq!(next, None);
+
+	let filter = EventFilter { pallet_index: Some(5), event_index: Some(3) };
+	let (events, _) = store.query_events(ALICE, filter, range(0, 1, None, 10));
+	assert_eq!(events, vec![(1, event(1, 5, 3, &[ALICE, ALICE]))]);
+
+	let filter = EventFilter { pallet_index: Some(6), event_index: None };
+	let (events, _) = store.query_events(BOB, filter, range(0, 1, None, 10));
+	assert_eq!(events, vec![(1, event(2, 6, 2, &[BOB]))]);
+}
+
+#[test]
+fn store_ignores_already_indexed_blocks() {
+	let mut store = ArchiveIndexStore::new(InMemOffchainStorage::default());
+	let data = BlockIndexData {
+		events: vec![event(0, 1, 1, &[ALICE])],
+		extrinsics: vec![IndexedExtrinsic { index: 0, signer: ALICE.to_vec() }],
+	};
+
+	store.insert_block(1, data.clone());
+	store.insert_block(1, data.clone());
+	store.insert_block(0, data);
+
+	let (extrinsics, _) = store.query_extrinsics(ALICE, range(0, 1, None, 10));
+	assert_eq!(extrinsics, vec![cursor(1, 0)]);
+	let (events, _) = store.query_events(ALICE, EventFilter::default(), range(0, 1, None, 10));
+	assert_eq!(events.len(), 1);
+}
+
+#[test]
+fn store_limits_the_searched_blocks() {
+	let mut store = ArchiveIndexStore::new(InMemOffchainStorage::default());
+	for height in [1, MAX_QUERY_BLOCK_RANGE + 1] {
+		store.insert_block(
+			height,
+			BlockIndexData {
+				events: vec![],
+				extrinsics: vec![IndexedExtrinsic { index: 0, signer: ALICE.to_vec() }],
+			},
+		);
+	}
+
+	// The search stops after `MAX_QUERY_BLOCK_RANGE` blocks, to be continued from there.
+	let to = MAX_QUERY_BLOCK_RANGE + 1;
+	let (page, next) = store.query_extrinsics(ALICE, range(0, to, None, 10));
+	assert_eq!(page, vec![cursor(1, 0)]);
+	assert_eq!(next, Some(cursor(MAX_QUERY_BLOCK_RANGE - 1, u32::MAX)));
+
+	let (page, next) = store.query_extrinsics(ALICE, range(0, to, next, 10));
+	assert_eq!(page, vec![cursor(MAX_QUERY_BLOCK_RANGE + 1, 0)]);
+	assert_eq!(next, None);
+
+	// Nothing is found in the searched blocks.
+	let (page, next) = store.query_extrinsics(ALICE, range(2, to, None, 10));
+	assert_eq!(page, vec![]);
+	assert_eq!(next, Some(cursor(MAX_QUERY_BLOCK_RANGE + 1, u32::MAX)));
+}
+
+#[tokio::test]
+async fn indexer_skips_blocks_it_cannot_extract() {
+	let builder = TestClientBuilder::new();
+	let backend = builder.backend();
+	let client = Arc::new(builder.build());
+	let store = ArchiveIndexStore::new(backend.offchain_storage().unwrap());
+
+	// Every extrinsic is attributed to Alice, the data of the block #3 can't be extracted.
+	let extractor = |block: &Block, _: Option<&[u8]>| -> Result<BlockIndexData, String> {
+		if *block.header().number() == 3 {
+			return Err("Invalid events".into());
+		}
+		Ok(BlockIndexData {
+			events: vec![],
+			extrinsics: (0..block.extrinsics().len() as u32)
+				.map(|index| IndexedExtrinsic { index, signer: ALICE.to_vec() })
+				.collect(),
+		})
+	};
+	let mut indexer =
+		ArchiveIndexer::<Backend, _, _, _>::new(client.clone(), store.clone(), Arc::new(extractor))
+			.with_start_height(2);
+
+	let mut parent_hash = client.chain_info().genesis_hash;
+	for nonce in 0..4 {
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(parent_hash)
+			.fetch_parent_block_number(&*client)
+			.unwrap()
+			.build()
+			.unwrap();
+		builder
+			.push_transfer(runtime::Transfer {
+				from: Sr25519Keyring::Alice.into(),
+				to: Sr25519Keyring::Ferdie.into(),
+				amount: 42,
+				nonce,
+			})
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		parent_hash = block.header.hash();
+		client.import(BlockOrigin::Own, block).await.unwrap();
+	}
+	client.finalize_block(parent_hash, None).unwrap();
+	indexer.index_finalized();
+
+	// The blocks before the start height are not indexed and the block #3 is skipped.
+	assert_eq!(store.last_indexed(), Some(4));
+	assert_eq!(store.skipped_reason(3), Some("Invalid events".to_string()));
+	assert_eq!(store.skipped_reason(2), None);
+	let (extrinsics, _) = store.query_extrinsics(ALICE, range(0, 4, None, 10));
+	assert_eq!(extrinsics, vec![cursor(2, 0), cursor(4, 0)]);
+}
+
+#[tokio::test]
+async fn archive_index_queries_finalized_blocks() {
+	let builder = TestClientBuilder::new();
+	let backend = builder.backend();
+	let client = Arc::new(builder.build());
+	let store = ArchiveIndexStore::new(backend.offchain_storage().unwrap());
+
+	// Every extrinsic is attributed to Alice and emits a single event.
+	let extractor = |block: &Block, _: Option<&[u8]>| -> Result<BlockIndexData, String> {
+		Ok(BlockIndexData {
+			events: (0..block.extrinsics().len() as u32)
+				.map(|index| event(index, 1, 0, &[ALICE]))
+				.collect(),
+			extrinsics: (0..block.extrinsics().len() as u32)
+				.map(|index| IndexedExtrinsic { index, signer: ALICE.to_vec() })
+				.collect(),
+		})
+	};
+	let mut indexer =
+		ArchiveIndexer::<Backend, _, _, _>::new(client.clone(), store.clone(), Arc::new(extractor));
+	let api: RpcModule<_> = ArchiveIndex::<Block, _, _>::new(client.clone(), store).into_rpc();
+
+	// Nothing is indexed yet.
+	let query = serde_json::json!({ "account": hex_string(&ALICE) });
+	let page: QueryPage<ExtrinsicItem> =
+		api.call("archive_v1_queryExtrinsics", [query.clone()]).await.unwrap();
+	assert_eq!(page, QueryPage { items: vec![], next: None, indexed_height: None });
+
+	let mut blocks = vec![];
+	let mut parent_hash = client.chain_info().genesis_hash;
+	for nonce in 0..2 {
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(parent_hash)
+			.fetch_parent_block_number(&*client)
+			.unwrap()
+			.build()
+			.unwrap();
+		builder
+			.push_transfer(runtime::Transfer {
+				from: Sr25519Keyring::Alice.into(),
+				to: Sr25519Keyring::Ferdie.into(),
+				amount: 42,
+				nonce,
+			})
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		parent_hash = block.header.hash();
+		client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+		blocks.push(block);
+	}
+
+	// Only the finalized blocks are indexed.
+	client.finalize_block(blocks[0].header.hash(), None).unwrap();
+	indexer.index_finalized();
+	let page: QueryPage<ExtrinsicItem> =
+		api.call("archive_v1_queryExtrinsics", [query.clone()]).await.unwrap();
+	assert_eq!(page.indexed_height, Some(1));
+	assert_eq!(page.items.len(), 1);
+
+	client.finalize_block(blocks[1].header.hash(), None).unwrap();
+	indexer.index_finalized();
+
+	let query = serde_json::json!({ "account": hex_string(&ALICE), "limit": 1 });
+	let page: QueryPage<ExtrinsicItem> =
+		api.call("archive_v1_queryExtrinsics", [query]).await.unwrap();
+	assert_eq!(
+		page,
+		QueryPage {
+			items: vec![ExtrinsicItem {
+				block_height: 1,
+				block_hash: format!("{:?}", blocks[0].header.hash()),
+				index: 0,
+				extrinsic: hex_string(&blocks[0].extrinsics[0].encode()),
+			}],
+			next: Some(cursor(1, 0)),
+			indexed_height: Some(2),
+		}
+	);
+
+	let query = serde_json::json!({
+		"account": hex_string(&ALICE),
+		"limit": 1,
+		"startAfter": { "height": 1, "index": 0 },
+	});
+	let page: QueryPage<EventItem> = api.call("archive_v1_queryEvents", [query]).await.unwrap();
+	assert_eq!(
+		page,
+		QueryPage {
+			items: vec![EventItem {
+				block_height: 2,
+				block_hash: format!("{:?}", blocks[1].header.hash()),
+				index: 0,
+				extrinsic_index: Some(0),
+				pallet_index: 1,
+				event_index: 0,
+				event: hex_string(&event(0, 1, 0, &[]).data),
+			}],
+			next: None,
+			indexed_height: Some(2),
+		}
+	);
+
+	// Invalid parameters are rejected.
+	let query = serde_json::json!({ "account": "0xzz" });
+	let err = api
+		.call::<_, QueryPage<EventItem>>("archive_v1_queryEvents", [query])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001);
+
+	let query = serde_json::json!({ "account": hex_string(&ALICE), "eventIndex": 0 });
+	let err = api
+		.call::<_, QueryPage<EventItem>>("archive_v1_queryEvents", [query])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001);
+
+	let query = serde_json::json!({ "account": hex_string(&ALICE), "limit": 0 });
+	let err = api
+		.call::<_, QueryPage<ExtrinsicItem>>("archive_v1_queryExtrinsics", [query])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001);
+}
+
+#[tokio::test]
+async fn frame_extractor_extracts_accounts_of_events_and_signers() {
+	let client = Arc::new(TestClientBuilder::new().build());
+	let extractor = FrameIndexExtractor::new(client.clone());
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder
+		.push(runtime::ExtrinsicBuilder::new_storage_change(b"key".to_vec(), None).build())
+		.unwrap();
+	builder
+		.push_transfer(runtime::Transfer {
+			from: Sr25519Keyring::Alice.into(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 42,
+			nonce: 0,
+		})
+		.unwrap();
+	let block = builder.build().unwrap().block;
+	let hash = block.header.hash();
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let events_key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+	let events = client.storage(hash, &events_key).unwrap().unwrap();
+	let data = extractor.extract(&block, Some(&events.0)).unwrap();
+
+	// Only the signed transfer has a signer.
+	let alice = Sr25519Keyring::Alice.public();
+	let bob = Sr25519Keyring::Bob.public();
+	assert_eq!(data.extrinsics, vec![IndexedExtrinsic { index: 1, signer: alice.encode() }]);
+
+	// The transfer event involves both accounts.
+	let transfer = (alice, bob, 42u64).encode();
+	let transfer = data
+		.events
+		.iter()
+		.find(|event| event.data.ends_with(&transfer))
+		.expect("The transfer event is extracted");
+	assert_eq!(transfer.extrinsic_index, Some(1));
+	assert_eq!(transfer.accounts, vec![alice.encode(), bob.encode()]);
+}
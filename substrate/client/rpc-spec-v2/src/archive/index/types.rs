@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Types of the archive index.
+
+use codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+/// An event extracted from the block, to be indexed.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct IndexedEvent {
+	/// The position of the event record within the block.
+	pub index: u32,
+	/// The index of the extrinsic which emitted the event, `None` if the event was emitted
+	/// during the block initialization or finalization.
+	pub extrinsic_index: Option<u32>,
+	/// The index of the pallet which emitted the event.
+	pub pallet_index: u8,
+	/// The index of the event variant within the pallet.
+	pub event_index: u8,
+	/// The encoded accounts involved in the event.
+	///
+	/// The event can be queried by any of these accounts. Events without accounts are not
+	/// indexed.
+	pub accounts: Vec<Vec<u8>>,
+	/// The SCALE-encoded event.
+	pub data: Vec<u8>,
+}
+
+/// A signed extrinsic extracted from the block, to be indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedExtrinsic {
+	/// The position of the extrinsic within the block.
+	pub index: u32,
+	/// The encoded signer of the extrinsic.
+	pub signer: Vec<u8>,
+}
+
+/// All the data of a single block to be indexed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockIndexData {
+	/// The events of the block.
+	pub events: Vec<IndexedEvent>,
+	/// The signed extrinsics of the block.
+	pub extrinsics: Vec<IndexedExtrinsic>,
+}
+
+/// The position of an item in the index, used for pagination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexCursor {
+	/// The height of the block containing the item.
+	pub height: u64,
+	/// The position of the item within the block.
+	pub index: u32,
+}
+
+/// The parameters of the `archive_v1_queryEvents` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventQuery {
+	/// The hex-encoded account involved in the events.
+	pub account: String,
+	/// The first block height to search (inclusive).
+	#[serde(default)]
+	pub from_height: u64,
+	/// The last block height to search (inclusive). Defaults to the last indexed block.
+	#[serde(default)]
+	pub to_height: Option<u64>,
+	/// Only return events emitted by the pallet with the given index.
+	#[serde(default)]
+	pub pallet_index: Option<u8>,
+	/// Only return events with the given variant index. Requires `palletIndex`.
+	#[serde(default)]
+	pub event_index: Option<u8>,
+	/// Continue the query after the given cursor, as returned by the previous page.
+	#[serde(default)]
+	pub start_after: Option<IndexCursor>,
+	/// The maximum number of items to return.
+	#[serde(default)]
+	pub limit: Option<u32>,
+}
+
+/// The parameters of the `archive_v1_queryExtrinsics` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicQuery {
+	/// The hex-encoded signer of the extrinsics.
+	pub account: String,
+	/// The first block height to search (inclusive).
+	#[serde(default)]
+	pub from_height: u64,
+	/// The last block height to search (inclusive). Defaults to the last indexed block.
+	#[serde(default)]
+	pub to_height: Option<u64>,
+	/// Continue the query after the given cursor, as returned by the previous page.
+	#[serde(default)]
+	pub start_after: Option<IndexCursor>,
+	/// The maximum number of items to return.
+	#[serde(default)]
+	pub limit: Option<u32>,
+}
+
+/// An event returned by the `archive_v1_queryEvents` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventItem {
+	/// The height of the block containing the event.
+	pub block_height: u64,
+	/// The hex-encoded hash of the block containing the event.
+	pub block_hash: String,
+	/// The position of the event record within the block.
+	pub index: u32,
+	/// The index of the extrinsic which emitted the event.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub extrinsic_index: Option<u32>,
+	/// The index of the pallet which emitted the event.
+	pub pallet_index: u8,
+	/// The index of the event variant within the pallet.
+	pub event_index: u8,
+	/// The hex-encoded SCALE-encoded event.
+	pub event: String,
+}
+
+/// An extrinsic returned by the `archive_v1_queryExtrinsics` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicItem {
+	/// The height of the block containing the extrinsic.
+	pub block_height: u64,
+	/// The hex-encoded hash of the block containing the extrinsic.
+	pub block_hash: String,
+	/// The position of the extrinsic within the block.
+	pub index: u32,
+	/// The hex-encoded SCALE-encoded extrinsic.
+	pub extrinsic: String,
+}
+
+/// A single page of the query results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPage<Item> {
+	/// The items of the page.
+	pub items: Vec<Item>,
+	/// The cursor to continue the query with, `None` if there are no more items.
+	///
+	/// A single query only searches a limited number of blocks, so the page can be empty while
+	/// the cursor is still returned.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub next: Option<IndexCursor>,
+	/// The height of the last indexed block, `None` if nothing was indexed yet.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub indexed_height: Option<u64>,
+}
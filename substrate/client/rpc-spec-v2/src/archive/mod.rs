@@ -31,6 +31,7 @@ mod types;
 pub mod api;
 pub mod archive;
 pub mod error;
+pub mod index;
 
 pub use api::ArchiveApiServer;
 pub use archive::Archive;
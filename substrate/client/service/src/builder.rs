@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	build_network_future, build_system_rpc_future,
+	build_network_future, build_replica_system_rpc_future, build_system_rpc_future,
 	client::{Client, ClientConfig},
 	config::{Configuration, ExecutorConfiguration, KeystoreConfig, Multiaddr, PrometheusConfig},
 	error::Error,
@@ -27,7 +27,7 @@ use crate::{
 };
 use futures::{select, FutureExt, StreamExt};
 use jsonrpsee::RpcModule;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use prometheus_endpoint::Registry;
 use sc_chain_spec::{get_extension, ChainSpec};
 use sc_client_api::{
@@ -527,6 +527,8 @@ where
 	TBackend: 'static + sc_client_api::backend::Backend<TBl> + Send,
 	TExPool: MaintainedTransactionPool<Block = TBl, Hash = <TBl as BlockT>::Hash> + 'static,
 {
+	ensure_primary_database(&config)?;
+
 	let chain_info = client.usage_info().chain;
 
 	sp_session::generate_initial_session_keys(
@@ -679,6 +681,203 @@ where
 	Ok(in_memory_rpc_handle)
 }
 
+/// Parameters to pass into [`spawn_rpc_replica_tasks`].
+pub struct SpawnRpcReplicaTasksParams<'a, TBl: BlockT, TRtApi, TExec, TExPool, TRpc> {
+	/// The service configuration, using a [`crate::DatabaseSource::RocksDbSecondary`] database.
+	pub config: Configuration,
+	/// A shared client returned by `new_full_parts`.
+	pub client: Arc<TFullClient<TBl, TRtApi, TExec>>,
+	/// A shared backend returned by `new_full_parts`.
+	pub backend: Arc<TFullBackend<TBl>>,
+	/// A task manager returned by `new_full_parts`.
+	pub task_manager: &'a mut TaskManager,
+	/// A shared keystore returned by `new_full_parts`.
+	pub keystore: KeystorePtr,
+	/// A shared transaction pool.
+	pub transaction_pool: Arc<TExPool>,
+	/// Builds additional [`RpcModule`]s that should be added to the server
+	pub rpc_builder: Box<dyn Fn(SubscriptionTaskExecutor) -> Result<RpcModule<TRpc>, Error>>,
+	/// Optional [`TracingExecuteBlock`] handle.
+	///
+	/// Will be used by the `trace_block` RPC to execute the actual block.
+	pub tracing_execute_block: Option<Arc<dyn TracingExecuteBlock<TBl>>>,
+}
+
+/// Spawn the tasks that are required to run an RPC replica of a node.
+///
+/// The replica reads a secondary instance of the database of another node, and follows the blocks
+/// imported and finalized by it. No network, block import, authoring or offchain workers are
+/// running, and transactions submitted through the RPC are not propagated.
+pub fn spawn_rpc_replica_tasks<TBl, TRtApi, TExec, TExPool, TRpc>(
+	SpawnRpcReplicaTasksParams {
+		mut config,
+		task_manager,
+		client,
+		backend,
+		keystore,
+		transaction_pool,
+		rpc_builder,
+		tracing_execute_block: execute_block,
+	}: SpawnRpcReplicaTasksParams<TBl, TRtApi, TExec, TExPool, TRpc>,
+) -> Result<RpcHandlers, Error>
+where
+	TFullClient<TBl, TRtApi, TExec>: ProvideRuntimeApi<TBl> + CallApiAt<TBl>,
+	<TFullClient<TBl, TRtApi, TExec> as ProvideRuntimeApi<TBl>>::Api:
+		sp_api::Metadata<TBl>
+			+ sp_transaction_pool::runtime_api::TaggedTransactionQueue<TBl>
+			+ sp_session::SessionKeys<TBl>
+			+ sp_api::ApiExt<TBl>,
+	TBl: BlockT,
+	TBl::Hash: Unpin,
+	TBl::Header: Unpin,
+	TRtApi: Send + Sync + 'static,
+	TExec: CodeExecutor + RuntimeVersionOf + Clone,
+	TExPool: MaintainedTransactionPool<Block = TBl, Hash = <TBl as BlockT>::Hash> + 'static,
+{
+	if !config.database.is_secondary() {
+		return Err(Error::Other("An RPC replica requires a secondary database".into()));
+	}
+
+	info!("📦 Highest known block at #{}", client.usage_info().chain.best_number);
+
+	let spawn_handle = task_manager.spawn_handle();
+
+	spawn_handle.spawn_blocking(
+		"db-secondary-catch-up",
+		None,
+		follow_primary_database(client.clone(), backend.clone()),
+	);
+
+	// Inform the tx pool about imported and finalized blocks.
+	spawn_handle.spawn(
+		"txpool-notifications",
+		Some("transaction-pool"),
+		sc_transaction_pool::notification_future(client.clone(), transaction_pool.clone()),
+	);
+
+	if let Some(PrometheusConfig { port, registry }) = config.prometheus_config.clone() {
+		spawn_handle.spawn(
+			"prometheus-endpoint",
+			None,
+			prometheus_endpoint::init_prometheus(port, registry).map(drop),
+		);
+	}
+
+	let (system_rpc_tx, system_rpc_rx) = tracing_unbounded("mpsc_system_rpc", 10_000);
+	spawn_handle.spawn(
+		"system-rpc-handler",
+		Some("networking"),
+		build_replica_system_rpc_future(client.clone(), system_rpc_rx),
+	);
+
+	let rpc_id_provider = config.rpc.id_provider.take();
+
+	let rpc_v2_metrics = config
+		.prometheus_registry()
+		.map(|registry| sc_rpc_spec_v2::transaction::TransactionMetrics::new(registry))
+		.transpose()?;
+
+	let gen_rpc_module = || {
+		gen_rpc_module(GenRpcModuleParams {
+			spawn_handle: task_manager.spawn_handle(),
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			keystore: keystore.clone(),
+			system_rpc_tx: system_rpc_tx.clone(),
+			impl_name: config.impl_name.clone(),
+			impl_version: config.impl_version.clone(),
+			chain_spec: config.chain_spec.as_ref(),
+			state_pruning: &config.state_pruning,
+			blocks_pruning: config.blocks_pruning,
+			backend: backend.clone(),
+			rpc_builder: &*rpc_builder,
+			metrics: rpc_v2_metrics.clone(),
+			tracing_execute_block: execute_block.clone(),
+		})
+	};
+
+	let rpc_server_handle = start_rpc_servers(
+		&config.rpc,
+		config.prometheus_registry(),
+		&config.tokio_handle,
+		gen_rpc_module,
+		rpc_id_provider,
+	)?;
+
+	let listen_addrs = rpc_server_handle
+		.listen_addrs()
+		.into_iter()
+		.map(|socket_addr| {
+			let mut multiaddr: Multiaddr = socket_addr.ip().into();
+			multiaddr.push(Protocol::Tcp(socket_addr.port()));
+			multiaddr
+		})
+		.collect();
+
+	let in_memory_rpc = {
+		let mut module = gen_rpc_module()?;
+		module.extensions_mut().insert(DenyUnsafe::No);
+		module
+	};
+
+	let in_memory_rpc_handle = RpcHandlers::new(Arc::new(in_memory_rpc), listen_addrs);
+
+	task_manager.keep_alive((config.base_path, rpc_server_handle));
+
+	Ok(in_memory_rpc_handle)
+}
+
+/// Periodically catches up the secondary database instance with the primary one.
+///
+/// The import and finality notifications are emitted for the changes found after each catch up.
+async fn follow_primary_database<TBl, TRtApi, TExec>(
+	client: Arc<TFullClient<TBl, TRtApi, TExec>>,
+	backend: Arc<TFullBackend<TBl>>,
+) where
+	TBl: BlockT,
+	TExec: CodeExecutor + RuntimeVersionOf + Clone,
+{
+	use sc_client_api::backend::Backend as _;
+	use sp_blockchain::Backend as _;
+
+	const CATCH_UP_INTERVAL: Duration = Duration::from_millis(500);
+
+	loop {
+		futures_timer::Delay::new(CATCH_UP_INTERVAL).await;
+
+		let previous = client.chain_info();
+		let previous_leaves = match backend.blockchain().leaves() {
+			Ok(leaves) => leaves,
+			Err(e) => {
+				warn!("Failed to read the leaves of the database: {e}");
+				continue;
+			},
+		};
+
+		if let Err(e) = backend.try_catch_up_with_primary() {
+			warn!("Failed to catch up with the primary database: {e}");
+			continue;
+		}
+
+		if let Err(e) = client.notify_external_changes(&previous, &previous_leaves) {
+			warn!("Failed to notify the blocks imported by the primary database: {e}");
+		}
+	}
+}
+
+/// Fails if the database is a secondary instance, which is read-only.
+fn ensure_primary_database(config: &Configuration) -> Result<(), Error> {
+	if config.database.is_secondary() {
+		return Err(Error::Other(
+			"A secondary database is read-only and can only be used to run an RPC replica, \
+			 see `spawn_rpc_replica_tasks`"
+				.into(),
+		));
+	}
+
+	Ok(())
+}
+
 /// Returns a future that forwards imported transactions to the transaction networking protocol.
 pub async fn propagate_transaction_notifications<Block, ExPool>(
 	transaction_pool: Arc<ExPool>,
@@ -1029,6 +1228,8 @@ where
 		metrics,
 	} = params;
 
+	ensure_primary_database(config)?;
+
 	let block_announce_validator = if let Some(f) = block_announce_validator_builder {
 		f(client.clone())
 	} else {
@@ -0,0 +1,352 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Export and import of the finalized state snapshots.
+//!
+//! A snapshot contains the state of a finalized block together with the warp sync proofs of its
+//! finality, starting at the genesis block. A fresh node can import the snapshot instead of
+//! downloading the state over the network: the proofs are verified against the trusted genesis
+//! authorities and the state is checked against the state root of the proven header.
+//!
+//! The state follows the finalized block and its proofs as a stream of key-value pairs, so it is
+//! never collected in memory while exporting. The import is not streamed: the whole state is read
+//! into memory and imported at once together with the finalized block, the same way warp sync
+//! imports the downloaded state, so the importing node needs enough memory to hold it.
+
+use crate::error::Error;
+use codec::{Decode, Encode, Input, IoReader};
+use futures::{future, prelude::*};
+use log::info;
+use parking_lot::Mutex;
+use sc_client_api::{BlockBackend, HeaderBackend, StorageProvider, UsageProvider};
+use sc_consensus::{
+	import_queue::{BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sc_network_sync::strategy::warp::{
+	EncodedProof, VerificationResult, Verifier, WarpSyncProvider,
+};
+use sp_consensus::BlockOrigin;
+use sp_core::storage::{well_known_keys, ChildInfo};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
+	Justifications,
+};
+use sp_state_machine::{KeyValueStates, KeyValueStorageLevel};
+use std::{
+	io::{Read, Write},
+	pin::Pin,
+	sync::Arc,
+	task::Poll,
+};
+
+/// The magic bytes every snapshot starts with.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"substate";
+
+/// The version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// The block of a snapshot, followed by its state.
+#[derive(Encode, Decode)]
+struct SnapshotHeader<B: BlockT> {
+	/// The genesis hash of the chain.
+	genesis_hash: B::Hash,
+	/// The encoded warp sync proofs, proving the finality of the `header` starting at genesis.
+	finality_proofs: Vec<Vec<u8>>,
+	/// The header of the finalized block.
+	header: B::Header,
+	/// The body of the finalized block.
+	body: Option<Vec<B::Extrinsic>>,
+	/// The justifications of the finalized block.
+	justifications: Option<Justifications>,
+}
+
+/// An item of the snapshot state.
+#[derive(Encode, Decode)]
+enum StateEntry {
+	/// Starts a trie, all the following key-value pairs belong to it.
+	///
+	/// Contains the prefixed storage key of the child trie, `None` for the top trie.
+	Trie(Option<Vec<u8>>),
+	/// A key-value pair of the current trie.
+	KeyValue(Vec<u8>, Vec<u8>),
+	/// The end of the state.
+	End,
+}
+
+/// Exports the snapshot of the latest finalized block with a finality proof.
+///
+/// The `verifier` must start at the genesis block, it is used to make sure the generated proofs
+/// can be verified by the importing node. The state of the proven block must be available, i.e.
+/// it must not be pruned.
+pub fn export_snapshot<B, BA, C>(
+	client: Arc<C>,
+	warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	mut verifier: Box<dyn Verifier<B>>,
+	mut output: impl Write,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+	C: HeaderBackend<B> + BlockBackend<B> + UsageProvider<B> + StorageProvider<B, BA>,
+{
+	let genesis_hash = client.info().genesis_hash;
+
+	let mut finality_proofs = Vec::new();
+	let mut start = genesis_hash;
+	let (header, justifications) = loop {
+		let EncodedProof(proof) = warp_sync_provider
+			.generate(start)
+			.map_err(|e| Error::Other(format!("Failed to generate the finality proof: {e}")))?;
+		let result = verifier
+			.verify(&EncodedProof(proof.clone()))
+			.map_err(|e| Error::Other(format!("Failed to verify the finality proof: {e}")))?;
+		finality_proofs.push(proof);
+
+		match result {
+			VerificationResult::Complete(header, justifications) => {
+				let justifications = justifications
+					.into_iter()
+					.last()
+					.filter(|(proven, _)| proven.hash() == header.hash())
+					.map(|(_, justifications)| justifications);
+				break (header, justifications);
+			},
+			VerificationResult::Partial(_) => {
+				let next = verifier.next_proof_context();
+				if next == start {
+					return Err("The finality proof does not make any progress".into());
+				}
+				start = next;
+			},
+		}
+	};
+
+	let hash = header.hash();
+	info!("Exporting snapshot of block #{} ({hash:?})", header.number());
+
+	let body = client.block_body(hash)?;
+	let snapshot_header =
+		SnapshotHeader::<B> { genesis_hash, finality_proofs, header, body, justifications };
+	output.write_all(&SNAPSHOT_MAGIC)?;
+	output.write_all(&SNAPSHOT_VERSION.encode())?;
+	output.write_all(&snapshot_header.encode())?;
+
+	// The default child tries are written after the top trie, without their roots.
+	let mut children = Vec::new();
+	output.write_all(&StateEntry::Trie(None).encode())?;
+	for (key, value) in client.storage_pairs(hash, None, None)? {
+		match key.0.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			Some(child_key) => children.push(ChildInfo::new_default(child_key)),
+			None => output.write_all(&StateEntry::KeyValue(key.0, value.0).encode())?,
+		}
+	}
+	for child_info in children {
+		let child_storage_key = child_info.prefixed_storage_key().into_inner();
+		output.write_all(&StateEntry::Trie(Some(child_storage_key)).encode())?;
+		for key in client.child_storage_keys(hash, child_info.clone(), None, None)? {
+			if let Some(value) = client.child_storage(hash, &child_info, &key)? {
+				output.write_all(&StateEntry::KeyValue(key.0, value.0).encode())?;
+			}
+		}
+	}
+	output.write_all(&StateEntry::End.encode())?;
+	output.flush()?;
+
+	info!("🎉 Exported snapshot of block {hash:?}");
+	Ok(())
+}
+
+/// Imports the snapshot exported by [`export_snapshot`].
+///
+/// The `verifier` must start at the genesis block, which makes the genesis authorities the only
+/// trusted input. The finality proofs are verified before reading the state, the state root is
+/// checked by the client while importing the finalized block of the snapshot together with its
+/// state.
+///
+/// The whole state is held in memory until it is imported.
+pub fn import_snapshot<B, IQ, C>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	verifier: Box<dyn Verifier<B>>,
+	input: impl Read,
+) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>
+where
+	C: HeaderBackend<B> + Send + Sync + 'static,
+	B: BlockT,
+	IQ: ImportQueue<B> + 'static,
+{
+	let block = match read_snapshot(&*client, verifier, input) {
+		Ok(block) => block,
+		Err(e) => return future::ready(Err(e)).boxed(),
+	};
+
+	import_queue.service_ref().import_blocks(BlockOrigin::File, vec![block]);
+
+	struct WaitLink {
+		result: Mutex<Option<Result<(), String>>>,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink {
+		fn blocks_processed(
+			&self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			if let Some((result, _)) = results.into_iter().next() {
+				*self.result.lock() = Some(result.map(|_| ()).map_err(|e| e.to_string()));
+			}
+		}
+	}
+
+	let link = WaitLink { result: Mutex::new(None) };
+	let import = future::poll_fn(move |cx| {
+		import_queue.poll_actions(cx, &link);
+
+		match link.result.lock().take() {
+			Some(Ok(())) => {
+				info!("🎉 Imported snapshot. Finalized: #{}", client.info().finalized_number);
+				Poll::Ready(Ok(()))
+			},
+			Some(Err(e)) => {
+				Poll::Ready(Err(Error::Other(format!("Failed to import the snapshot: {e}"))))
+			},
+			None => Poll::Pending,
+		}
+	});
+	Box::pin(import)
+}
+
+/// Reads the snapshot and verifies its finality proofs.
+///
+/// Returns the finalized block of the snapshot together with its state, ready to be imported.
+fn read_snapshot<B, C>(
+	client: &C,
+	mut verifier: Box<dyn Verifier<B>>,
+	input: impl Read,
+) -> Result<IncomingBlock<B>, Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+{
+	let mut reader = IoReader(input);
+	let magic = decode::<[u8; 8]>(&mut reader)?;
+	if magic != SNAPSHOT_MAGIC {
+		return Err("The input is not a snapshot".into());
+	}
+	let version = decode::<u32>(&mut reader)?;
+	if version != SNAPSHOT_VERSION {
+		return Err(Error::Other(format!(
+			"Unsupported snapshot version {version}, expected {SNAPSHOT_VERSION}"
+		)));
+	}
+	let snapshot = decode::<SnapshotHeader<B>>(&mut reader)?;
+
+	let info = client.info();
+	if snapshot.genesis_hash != info.genesis_hash {
+		return Err(Error::Other(format!(
+			"Snapshot genesis hash {:?} does not match the chain genesis hash {:?}",
+			snapshot.genesis_hash, info.genesis_hash
+		)));
+	}
+	if *snapshot.header.number() <= info.finalized_number {
+		return Err(Error::Other(format!(
+			"Snapshot of block #{} is not above the finalized block #{}",
+			snapshot.header.number(),
+			info.finalized_number
+		)));
+	}
+
+	let hash = snapshot.header.hash();
+	verify_finality_proofs(&mut *verifier, snapshot.finality_proofs, hash)?;
+
+	info!("Importing snapshot of block #{} ({hash:?})", snapshot.header.number());
+
+	let mut state = Vec::new();
+	loop {
+		match decode::<StateEntry>(&mut reader)? {
+			StateEntry::Trie(child_storage_key) => {
+				let (state_root, parent_storage_keys) = match child_storage_key {
+					Some(child_storage_key) => (child_storage_key.clone(), vec![child_storage_key]),
+					None => (Vec::new(), Vec::new()),
+				};
+				state.push(KeyValueStorageLevel {
+					state_root,
+					parent_storage_keys,
+					key_values: Vec::new(),
+				});
+			},
+			StateEntry::KeyValue(key, value) => state
+				.last_mut()
+				.ok_or("The snapshot state does not start with a trie")?
+				.key_values
+				.push((key, value)),
+			StateEntry::End => break,
+		}
+	}
+
+	Ok(IncomingBlock {
+		hash,
+		header: Some(snapshot.header),
+		body: snapshot.body,
+		indexed_body: None,
+		justifications: snapshot.justifications,
+		origin: None,
+		allow_missing_state: true,
+		import_existing: true,
+		skip_execution: true,
+		state: Some(ImportedState { block: hash, state: KeyValueStates(state) }),
+	})
+}
+
+/// Decodes the next item of the snapshot.
+fn decode<T: Decode>(input: &mut impl Input) -> Result<T, Error> {
+	T::decode(input).map_err(|e| Error::Other(format!("Failed to decode the snapshot: {e}")))
+}
+
+/// Verifies that the proofs prove the finality of the block with the given hash.
+fn verify_finality_proofs<B: BlockT>(
+	verifier: &mut dyn Verifier<B>,
+	finality_proofs: Vec<Vec<u8>>,
+	hash: B::Hash,
+) -> Result<(), Error> {
+	let count = finality_proofs.len();
+	for (index, proof) in finality_proofs.into_iter().enumerate() {
+		let result = verifier
+			.verify(&EncodedProof(proof))
+			.map_err(|e| Error::Other(format!("Invalid finality proof: {e}")))?;
+		match result {
+			VerificationResult::Complete(header, _) if index + 1 == count => {
+				if header.hash() != hash {
+					return Err(Error::Other(format!(
+						"The finality proof is for block {:?}, expected {hash:?}",
+						header.hash()
+					)));
+				}
+				return Ok(());
+			},
+			VerificationResult::Complete(..) => {
+				return Err("The finality proof is complete before its end".into())
+			},
+			VerificationResult::Partial(_) => {},
+		}
+	}
+
+	Err("The finality proof is incomplete".into())
+}
@@ -1081,6 +1081,107 @@ where
 		Ok(())
 	}
 
+	/// Emits the import and finality notifications for blocks written to the database by another
+	/// process, e.g. the primary instance followed by a secondary database.
+	///
+	/// `previous` and `previous_leaves` describe the chain before the database was reloaded. The
+	/// blocks enacted on the best chain are announced as if they had been imported one after the
+	/// other, followed by a finality notification if the finalized block changed. Storage change
+	/// notifications are not emitted.
+	pub fn notify_external_changes(
+		&self,
+		previous: &BlockchainInfo<Block>,
+		previous_leaves: &[Block::Hash],
+	) -> sp_blockchain::Result<()> {
+		// Only the last blocks are announced individually when a lot of blocks were imported at
+		// once, like during the initial sync of the primary.
+		const MAX_IMPORT_NOTIFICATIONS: usize = 64;
+
+		let blockchain = self.backend.blockchain();
+		let info = blockchain.info();
+
+		if info.best_hash != previous.best_hash {
+			let route = sp_blockchain::tree_route(blockchain, previous.best_hash, info.best_hash)?;
+			let enacted = route.enacted();
+			let skipped = enacted.len().saturating_sub(MAX_IMPORT_NOTIFICATIONS);
+
+			for (index, block) in enacted.iter().enumerate().skip(skipped) {
+				let header = blockchain
+					.header(block.hash)?
+					.ok_or_else(|| Error::MissingHeader(format!("{:?}", block.hash)))?;
+				let tree_route =
+					if index == skipped && (skipped > 0 || !route.retracted().is_empty()) {
+						let route_to_parent = sp_blockchain::tree_route(
+							blockchain,
+							previous.best_hash,
+							*header.parent_hash(),
+						)?;
+						Some(Arc::new(route_to_parent))
+					} else {
+						None
+					};
+				let notification = BlockImportNotification::new(
+					block.hash,
+					BlockOrigin::NetworkBroadcast,
+					header,
+					true,
+					tree_route,
+					self.unpin_worker_sender.clone(),
+				);
+
+				self.pin_for_notification(block.hash);
+				self.notify_imported(Some(notification), ImportNotificationAction::Both, None)?;
+			}
+		}
+
+		if info.finalized_hash != previous.finalized_hash {
+			let route = sp_blockchain::tree_route(
+				blockchain,
+				previous.finalized_hash,
+				info.finalized_hash,
+			)?;
+			let finalized = route.enacted().iter().map(|block| block.hash).collect::<Vec<_>>();
+			let header = blockchain
+				.header(info.finalized_hash)?
+				.ok_or_else(|| Error::MissingHeader(format!("{:?}", info.finalized_hash)))?;
+
+			// The leaves pruned by the primary which are not on the finalized chain.
+			let leaves = blockchain.leaves()?;
+			let stale_blocks = previous_leaves
+				.iter()
+				.filter(|leaf| !leaves.contains(leaf))
+				.filter(|leaf| {
+					sp_blockchain::tree_route(blockchain, info.finalized_hash, **leaf)
+						.map_or(true, |route| {
+							!route.retracted().is_empty() && !route.enacted().is_empty()
+						})
+				})
+				.map(|leaf| StaleBlock { hash: *leaf, is_head: true })
+				.collect();
+
+			let notification = FinalityNotification::from_summary(
+				FinalizeSummary { header, finalized, stale_blocks },
+				self.unpin_worker_sender.clone(),
+			);
+
+			self.pin_for_notification(info.finalized_hash);
+			self.notify_finalized(Some(notification))?;
+		}
+
+		Ok(())
+	}
+
+	fn pin_for_notification(&self, hash: Block::Hash) {
+		if let Err(err) = self.backend.pin_block(hash) {
+			debug!("Unable to pin block for notification. hash: {}, Error: {}", hash, err);
+		} else {
+			let _ = self
+				.unpin_worker_sender
+				.unbounded_send(UnpinWorkerMessage::AnnouncePin(hash))
+				.map_err(|e| log::error!("Unable to send AnnouncePin worker message: {e}"));
+		}
+	}
+
 	/// Attempts to revert the chain by `n` blocks guaranteeing that no block is
 	/// reverted past the last finalized block. Returns the number of blocks
 	/// that were successfully reverted.
@@ -60,9 +60,10 @@ pub use self::{
 		build_network_advanced, build_polkadot_syncing_strategy, gen_rpc_module, init_telemetry,
 		new_client, new_db_backend, new_full_client, new_full_parts, new_full_parts_record_import,
 		new_full_parts_with_genesis_builder, new_wasm_executor,
-		propagate_transaction_notifications, spawn_tasks, BuildNetworkAdvancedParams,
-		BuildNetworkParams, DefaultSyncingEngineConfig, KeystoreContainer, SpawnTasksParams,
-		TFullBackend, TFullCallExecutor, TFullClient,
+		propagate_transaction_notifications, spawn_rpc_replica_tasks, spawn_tasks,
+		BuildNetworkAdvancedParams, BuildNetworkParams, DefaultSyncingEngineConfig,
+		KeystoreContainer, SpawnRpcReplicaTasksParams, SpawnTasksParams, TFullBackend,
+		TFullCallExecutor, TFullClient,
 	},
 	client::{ClientConfig, LocalCallExecutor},
 	error::Error,
@@ -380,6 +381,60 @@ pub async fn build_system_rpc_future<
 	debug!("`NetworkWorker` has terminated, shutting down the system RPC future.");
 }
 
+/// Builds a future that processes system RPC requests of a node without networking, like a replica
+/// following a primary database.
+///
+/// The requests about the network are answered as if no peer was connected, the requests that
+/// can't be answered without a network are dropped.
+pub async fn build_replica_system_rpc_future<
+	B: BlockT,
+	C: HeaderBackend<B> + Send + Sync + 'static,
+>(
+	client: Arc<C>,
+	mut rpc_rx: TracingUnboundedReceiver<sc_rpc::system::Request<B>>,
+) {
+	// Current best block at initialization, to report to the RPC layer.
+	let starting_block = client.info().best_number;
+
+	while let Some(req) = rpc_rx.next().await {
+		match req {
+			sc_rpc::system::Request::Health(sender) => {
+				let _ = sender.send(sc_rpc::system::Health {
+					peers: 0,
+					is_syncing: false,
+					should_have_peers: false,
+				});
+			},
+			sc_rpc::system::Request::Peers(sender) => {
+				let _ = sender.send(Vec::new());
+			},
+			sc_rpc::system::Request::LocalListenAddresses(sender) => {
+				let _ = sender.send(Vec::new());
+			},
+			sc_rpc::system::Request::NetworkReservedPeers(sender) => {
+				let _ = sender.send(Vec::new());
+			},
+			sc_rpc::system::Request::NodeRoles(sender) => {
+				let _ = sender.send(vec![sc_rpc::system::NodeRole::Full]);
+			},
+			sc_rpc::system::Request::SyncState(sender) => {
+				let best_number = client.info().best_number;
+				let _ = sender.send(sc_rpc::system::SyncState {
+					starting_block,
+					current_block: best_number,
+					highest_block: best_number,
+				});
+			},
+			sc_rpc::system::Request::LocalPeerId(_) |
+			sc_rpc::system::Request::NetworkState(_) |
+			sc_rpc::system::Request::NetworkAddReservedPeer(..) |
+			sc_rpc::system::Request::NetworkRemoveReservedPeer(..) => {},
+		}
+	}
+
+	debug!("RPC requests stream has terminated, shutting down the system RPC future.");
+}
+
 /// Starts RPC servers.
 pub fn start_rpc_servers<R>(
 	rpc_configuration: &RpcConfiguration,
@@ -0,0 +1,279 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use codec::{Decode, Encode};
+use futures::executor::block_on;
+use sc_block_builder::BlockBuilderBuilder;
+use sc_client_api::{HeaderBackend, StorageProvider};
+use sc_consensus::{import_queue::BasicQueue, BlockImportParams, ForkChoiceStrategy};
+use sc_network_sync::strategy::warp::{
+	EncodedProof, VerificationResult, Verifier, WarpSyncProvider,
+};
+use sc_service::chain_ops::{export_snapshot, import_snapshot};
+use sp_consensus::BlockOrigin;
+use sp_core::{testing::TaskExecutor, H256};
+use sp_runtime::traits::Header as HeaderT;
+use sp_storage::{ChildInfo, StorageKey};
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::{Block, ExtrinsicBuilder, Header},
+	BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt, TestClient,
+	TestClientBuilder, TestClientBuilderExt,
+};
+
+const CHILD_KEY: &[u8] = b"child-key";
+const CHILD_VALUE: &[u8] = b"child-value";
+const STORAGE_KEY: &[u8] = b"snapshot-key";
+const STORAGE_VALUE: &[u8] = b"snapshot-value";
+
+/// The offset of the genesis hash in the snapshot, following the magic and the version.
+const GENESIS_HASH_OFFSET: usize = 8 + 4;
+/// The offset of the encoded finality proofs in the snapshot.
+const PROOFS_OFFSET: usize = GENESIS_HASH_OFFSET + 32;
+
+/// The number of blocks proven by a single proof of the [`TestWarpSyncProvider`].
+const BLOCKS_PER_PROOF: u64 = 2;
+
+/// A finality proof of the tests, proving the finality of `header` starting at the `start` block.
+#[derive(Encode, Decode)]
+struct TestProof {
+	start: H256,
+	header: Header,
+	complete: bool,
+}
+
+/// Generates the proofs of the finalized blocks, every proof covering up to [`BLOCKS_PER_PROOF`]
+/// blocks.
+struct TestWarpSyncProvider(Arc<TestClient>);
+
+impl WarpSyncProvider<Block> for TestWarpSyncProvider {
+	fn generate(
+		&self,
+		start: H256,
+	) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>> {
+		let info = self.0.info();
+		let start_number = self.0.number(start)?.ok_or("Unknown start block")?;
+		let number = (start_number + BLOCKS_PER_PROOF).min(info.finalized_number);
+		let hash = self.0.hash(number)?.ok_or("Unknown block")?;
+		let header = self.0.header(hash)?.ok_or("Unknown header")?;
+		Ok(EncodedProof(
+			TestProof { start, header, complete: number == info.finalized_number }.encode(),
+		))
+	}
+
+	fn create_verifier(&self) -> Box<dyn Verifier<Block>> {
+		Box::new(TestVerifier(self.0.info().genesis_hash))
+	}
+}
+
+/// Accepts the chain of proofs starting at the trusted block.
+struct TestVerifier(H256);
+
+impl Verifier<Block> for TestVerifier {
+	fn verify(
+		&mut self,
+		proof: &EncodedProof,
+	) -> Result<VerificationResult<Block>, Box<dyn std::error::Error + Send + Sync>> {
+		let proof = TestProof::decode(&mut &proof.0[..])?;
+		if proof.start != self.0 {
+			return Err("The proof does not start at the trusted block".into());
+		}
+		self.0 = proof.header.hash();
+		Ok(match proof.complete {
+			true => VerificationResult::Complete(proof.header, Vec::new()),
+			false => VerificationResult::Partial(Vec::new()),
+		})
+	}
+
+	fn next_proof_context(&self) -> H256 {
+		self.0
+	}
+
+	fn status(&self) -> Option<String> {
+		None
+	}
+}
+
+/// Imports the blocks as finalized, like the consensus block import does for the justified block
+/// of the snapshot.
+struct FinalizingVerifier;
+
+#[async_trait::async_trait]
+impl sc_consensus::Verifier<Block> for FinalizingVerifier {
+	async fn verify(
+		&self,
+		mut block: BlockImportParams<Block>,
+	) -> Result<BlockImportParams<Block>, String> {
+		block.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+		block.finalized = true;
+		Ok(block)
+	}
+}
+
+fn child_info() -> ChildInfo {
+	ChildInfo::new_default(b"snapshot-child")
+}
+
+/// Creates a client with a child trie at genesis.
+fn client() -> Arc<TestClient> {
+	Arc::new(
+		TestClientBuilder::new()
+			.add_extra_child_storage(&child_info(), CHILD_KEY, CHILD_VALUE)
+			.build(),
+	)
+}
+
+/// Creates a client with 5 finalized blocks and exports its snapshot.
+fn export() -> (Arc<TestClient>, Vec<u8>) {
+	let client = client();
+	for number in 0..5 {
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.info().best_hash)
+			.with_parent_block_number(number)
+			.build()
+			.unwrap();
+		if number == 0 {
+			builder
+				.push(
+					ExtrinsicBuilder::new_storage_change(
+						STORAGE_KEY.to_vec(),
+						Some(STORAGE_VALUE.to_vec()),
+					)
+					.build(),
+				)
+				.unwrap();
+		}
+		let block = builder.build().unwrap().block;
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+	}
+	client.finalize_block(client.info().best_hash, None).unwrap();
+
+	let mut snapshot = Vec::new();
+	export_snapshot(
+		client.clone(),
+		Arc::new(TestWarpSyncProvider(client.clone())),
+		Box::new(TestVerifier(client.info().genesis_hash)),
+		&mut snapshot,
+	)
+	.unwrap();
+	(client, snapshot)
+}
+
+/// Imports the snapshot into the `client`, trusting the `trusted` block.
+fn import(client: Arc<TestClient>, trusted: H256, snapshot: &[u8]) -> Result<(), String> {
+	let import_queue = BasicQueue::new(
+		FinalizingVerifier,
+		Box::new(client.clone()),
+		None,
+		&TaskExecutor::new(),
+		None,
+	);
+	block_on(import_snapshot(client, import_queue, Box::new(TestVerifier(trusted)), snapshot))
+		.map_err(|e| e.to_string())
+}
+
+/// Replaces the only occurrence of `from` in the `snapshot` by `to`.
+fn replace(snapshot: &mut [u8], from: &[u8], to: &[u8]) {
+	let mut positions =
+		snapshot.windows(from.len()).enumerate().filter(|(_, window)| *window == from);
+	let (position, _) = positions.next().unwrap();
+	assert!(positions.next().is_none());
+	snapshot[position..position + to.len()].copy_from_slice(to);
+}
+
+#[test]
+fn snapshot_export_import_round_trip() {
+	let (source, snapshot) = export();
+	let finalized = source.info().finalized_hash;
+
+	let client = client();
+	import(client.clone(), client.info().genesis_hash, &snapshot).unwrap();
+
+	let info = client.info();
+	assert_eq!(info.finalized_hash, finalized);
+	assert_eq!(info.finalized_number, 5);
+	assert_eq!(client.header(finalized).unwrap(), source.header(finalized).unwrap());
+	assert_eq!(
+		client.storage(finalized, &StorageKey(STORAGE_KEY.to_vec())).unwrap().unwrap().0,
+		STORAGE_VALUE
+	);
+	assert_eq!(
+		client
+			.child_storage(finalized, &child_info(), &StorageKey(CHILD_KEY.to_vec()))
+			.unwrap()
+			.unwrap()
+			.0,
+		CHILD_VALUE
+	);
+	assert_eq!(
+		client.storage_pairs(finalized, None, None).unwrap().collect::<Vec<_>>(),
+		source.storage_pairs(finalized, None, None).unwrap().collect::<Vec<_>>(),
+	);
+}
+
+#[test]
+fn snapshot_with_tampered_state_is_rejected() {
+	let (_, mut snapshot) = export();
+	replace(&mut snapshot, STORAGE_VALUE, b"tampered-value");
+
+	let client = client();
+	let result = import(client.clone(), client.info().genesis_hash, &snapshot);
+
+	assert!(result.unwrap_err().contains("Failed to import the snapshot"));
+	assert_eq!(client.info().finalized_number, 0);
+}
+
+#[test]
+fn snapshot_of_other_genesis_is_rejected() {
+	let (source, mut snapshot) = export();
+	let genesis_hash = source.info().genesis_hash;
+	snapshot[GENESIS_HASH_OFFSET..PROOFS_OFFSET].copy_from_slice(H256::repeat_byte(1).as_ref());
+
+	let client = client();
+	let result = import(client.clone(), genesis_hash, &snapshot);
+
+	assert!(result.unwrap_err().contains("does not match the chain genesis hash"));
+	assert_eq!(client.info().finalized_number, 0);
+}
+
+#[test]
+fn snapshot_proving_from_other_genesis_is_rejected() {
+	let (_, snapshot) = export();
+
+	let client = client();
+	let result = import(client.clone(), H256::repeat_byte(1), &snapshot);
+
+	assert!(result.unwrap_err().contains("Invalid finality proof"));
+	assert_eq!(client.info().finalized_number, 0);
+}
+
+#[test]
+fn snapshot_with_truncated_proofs_is_rejected() {
+	let (source, mut snapshot) = export();
+	// Three proofs are needed for the five blocks.
+	let proofs = Vec::<Vec<u8>>::decode(&mut &snapshot[PROOFS_OFFSET..]).unwrap();
+	assert_eq!(proofs.len(), 3);
+	let truncated = proofs[..2].to_vec();
+	snapshot.splice(PROOFS_OFFSET..PROOFS_OFFSET + proofs.encoded_size(), truncated.encode());
+
+	let client = client();
+	let result = import(client.clone(), source.info().genesis_hash, &snapshot);
+
+	assert!(result.unwrap_err().contains("The finality proof is incomplete"));
+	assert_eq!(client.info().finalized_number, 0);
+}
@@ -20,7 +20,10 @@
 
 use crate::{
 	common::api::FullChainApi,
-	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
+	fork_aware_txpool::{
+		ForkAwareTxPool as ForkAwareFullPool, MempoolJournalConfig, PriorityPolicy,
+		TransactionPoolPolicy,
+	},
 	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
 	single_state_txpool::BasicPool as SingleStateFullPool,
 	TransactionPoolWrapper, LOG_TARGET,
@@ -45,11 +48,16 @@ pub enum TransactionPoolType {
 pub struct TransactionPoolOptions {
 	txpool_type: TransactionPoolType,
 	options: Options,
+	journal: Option<MempoolJournalConfig>,
 }
 
 impl Default for TransactionPoolOptions {
 	fn default() -> Self {
-		Self { txpool_type: TransactionPoolType::SingleState, options: Default::default() }
+		Self {
+			txpool_type: TransactionPoolType::SingleState,
+			options: Default::default(),
+			journal: None,
+		}
 	}
 }
 
@@ -81,7 +89,16 @@ impl TransactionPoolOptions {
 			Duration::from_secs(30 * 60)
 		};
 
-		TransactionPoolOptions { options, txpool_type }
+		TransactionPoolOptions { options, txpool_type, journal: None }
+	}
+
+	/// Persists the transactions kept in the mempool using the given journal, so they survive a
+	/// restart of the node.
+	///
+	/// Only supported by the fork-aware transaction pool.
+	pub fn with_journal(mut self, journal: MempoolJournalConfig) -> Self {
+		self.journal = Some(journal);
+		self
 	}
 
 	/// Creates predefined options for benchmarking
@@ -100,6 +117,7 @@ impl TransactionPoolOptions {
 				ban_time: Duration::from_secs(30 * 60),
 			},
 			txpool_type: TransactionPoolType::SingleState,
+			journal: None,
 		}
 	}
 }
@@ -175,6 +193,7 @@ pub struct Builder<'a, Block, Client> {
 	prometheus: Option<&'a PrometheusRegistry>,
 	client: Arc<Client>,
 	spawner: Box<dyn SpawnEssentialNamed>,
+	policy: Option<Arc<dyn TransactionPoolPolicy<Block>>>,
 	_phantom: PhantomData<(Client, Block)>,
 }
 
@@ -207,6 +226,7 @@ where
 			client,
 			is_validator,
 			prometheus: None,
+			policy: None,
 		}
 	}
 
@@ -222,6 +242,15 @@ where
 		self
 	}
 
+	/// Sets the admission, ordering and eviction policy of the transaction pool.
+	///
+	/// Only supported by the fork-aware transaction pool, [`PriorityPolicy`] is used if not
+	/// provided.
+	pub fn with_policy(mut self, policy: Arc<dyn TransactionPoolPolicy<Block>>) -> Self {
+		self.policy = Some(policy);
+		self
+	}
+
 	/// Creates an instance of transaction pool.
 	pub fn build(self) -> TransactionPoolHandle<Block, Client> {
 		tracing::info!(
@@ -229,22 +258,40 @@ where
 			txpool_type = ?self.options.txpool_type,
 			ready = ?self.options.options.ready,
 			future = ?self.options.options.future,
+			journal = ?self.options.journal,
+			custom_policy = self.policy.is_some(),
 			"Creating transaction pool"
 		);
 		TransactionPoolWrapper::<Block, Client>(match self.options.txpool_type {
-			TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
-				self.options.options,
-				self.is_validator,
-				self.prometheus,
-				self.spawner,
-				self.client,
-			)),
+			TransactionPoolType::SingleState => {
+				if self.options.journal.is_some() {
+					tracing::warn!(
+						target: LOG_TARGET,
+						"Mempool journal is not supported by the single-state transaction pool"
+					);
+				}
+				if self.policy.is_some() {
+					tracing::warn!(
+						target: LOG_TARGET,
+						"Custom policy is not supported by the single-state transaction pool"
+					);
+				}
+				Box::new(SingleStateFullPool::new_full(
+					self.options.options,
+					self.is_validator,
+					self.prometheus,
+					self.spawner,
+					self.client,
+				))
+			},
 			TransactionPoolType::ForkAware => Box::new(ForkAwareFullPool::new_full(
 				self.options.options,
 				self.is_validator,
 				self.prometheus,
 				self.spawner,
 				self.client,
+				self.options.journal,
+				self.policy.unwrap_or_else(|| Arc::new(PriorityPolicy)),
 			)),
 		})
 	}
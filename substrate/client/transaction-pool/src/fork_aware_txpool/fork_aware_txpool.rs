@@ -23,7 +23,8 @@ use super::{
 	import_notification_sink::MultiViewImportNotificationSink,
 	metrics::{EventsMetricsCollector, MetricsLink as PrometheusMetrics},
 	multi_view_listener::MultiViewListener,
-	tx_mem_pool::{InsertionInfo, TxMemPool},
+	policy::{PolicyTransaction, PriorityPolicy, TransactionPoolPolicy},
+	tx_mem_pool::{InsertionInfo, Journal, JournalEntry, MempoolJournalConfig, TxMemPool},
 	view::View,
 	view_store::ViewStore,
 };
@@ -54,6 +55,7 @@ use futures::{
 	prelude::*,
 	FutureExt,
 };
+use itertools::Itertools;
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
@@ -173,9 +175,12 @@ where
 	/// any view.
 	import_notification_sink: MultiViewImportNotificationSink<Block::Hash, ExtrinsicHash<ChainApi>>,
 
-	/// Externally provided pool options.
+	/// Externally provided pool options, with the view limits set by the policy.
 	options: Options,
 
+	/// Ranks the transactions evicted from the views, by priority if not set.
+	view_eviction_rank: Option<graph::EvictionRank<ChainApi>>,
+
 	/// Is node the validator.
 	is_validator: IsValidator,
 
@@ -200,6 +205,11 @@ where
 
 	/// Stats for submit_and_watch call durations
 	submit_and_watch_stats: DurationSlidingStats,
+
+	/// Transactions restored from the mempool journal.
+	///
+	/// They are inserted into the mempool when the first chain event is processed.
+	journal_entries: Mutex<Option<Vec<JournalEntry<ExtrinsicHash<ChainApi>>>>>,
 }
 
 impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
@@ -267,6 +277,8 @@ where
 			Default::default(),
 			mempool_max_transactions_count,
 			ready_limits.total_bytes + future_limits.total_bytes,
+			None,
+			Arc::new(PriorityPolicy),
 		);
 		let mempool = Arc::from(mempool);
 
@@ -311,6 +323,7 @@ where
 				revalidation_queue: Arc::from(revalidation_worker::RevalidationQueue::new()),
 				import_notification_sink,
 				options,
+				view_eviction_rank: None,
 				is_validator: false.into(),
 				metrics: Default::default(),
 				events_metrics_collector: EventsMetricsCollector::default(),
@@ -321,6 +334,7 @@ where
 				submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 					STAT_SLIDING_WINDOW,
 				)),
+				journal_entries: Default::default(),
 			}
 			.inject_initial_view(best_block_hash),
 			[combined_tasks, mempool_task],
@@ -391,6 +405,12 @@ where
 	///
 	/// The txpool essential tasks (including a revalidation worker) are spawned using provided
 	/// spawner.
+	///
+	/// If the `journal` is provided, the transactions kept in the mempool are persisted to disk,
+	/// and the transactions persisted by the previous instance are restored.
+	///
+	/// The `policy` is consulted for admission, ordering and eviction of the transactions kept in
+	/// the mempool.
 	pub fn new_with_background_worker(
 		options: Options,
 		is_validator: IsValidator,
@@ -399,6 +419,8 @@ where
 		spawner: impl SpawnEssentialNamed,
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
+		journal: Option<MempoolJournalConfig>,
+		policy: Arc<dyn TransactionPoolPolicy<Block>>,
 	) -> Self {
 		let metrics = PrometheusMetrics::new(prometheus);
 		let (events_metrics_collector, event_metrics_task) =
@@ -414,13 +436,40 @@ where
 		let (import_notification_sink, import_notification_sink_task) =
 			MultiViewImportNotificationSink::new_with_worker();
 
+		let (journal, journal_entries) = match journal.as_ref().map(Journal::open).transpose() {
+			Ok(Some((journal, entries, journal_task))) => {
+				spawner.spawn_essential_blocking(
+					"txpool-journal",
+					Some("transaction-pool"),
+					journal_task,
+				);
+				(Some(journal), Some(entries))
+			},
+			Ok(None) => (None, None),
+			Err(error) => {
+				warn!(
+					target: LOG_TARGET,
+					path = ?journal.map(|journal| journal.path),
+					%error,
+					"Failed to open the mempool journal, transactions will not be persisted"
+				);
+				(None, None)
+			},
+		};
+
 		let (mempool, blocking_mempool_task) = TxMemPool::new(
 			pool_api.clone(),
 			listener.clone(),
 			metrics.clone(),
 			options.total_count(),
 			options.ready.total_bytes + options.future.total_bytes,
+			journal,
+			policy.clone(),
 		);
+
+		let (ready, future) = policy.view_limits(options.ready.clone(), options.future.clone());
+		let options = Options { ready, future, ..options };
+		let view_eviction_rank = Self::view_eviction_rank(policy);
 		let mempool = Arc::from(mempool);
 
 		let (dropped_stream_controller, dropped_stream) =
@@ -469,6 +518,7 @@ where
 			revalidation_queue: Arc::from(revalidation_queue),
 			import_notification_sink,
 			options,
+			view_eviction_rank: Some(view_eviction_rank),
 			metrics,
 			events_metrics_collector,
 			is_validator,
@@ -478,10 +528,28 @@ where
 			submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 				STAT_SLIDING_WINDOW,
 			)),
+			journal_entries: Mutex::new(journal_entries),
 		}
 		.inject_initial_view(best_block_hash)
 	}
 
+	/// Ranks the transactions of the views by the policy when their limits are exceeded.
+	fn view_eviction_rank(
+		policy: Arc<dyn TransactionPoolPolicy<Block>>,
+	) -> graph::EvictionRank<ChainApi> {
+		Arc::new(move |tx: &graph::base_pool::Transaction<_, _>| {
+			policy.rank(&PolicyTransaction {
+				hash: tx.hash,
+				tx: &tx.data,
+				bytes: tx.bytes,
+				source: tx.source.source.clone(),
+				timestamp: tx.source.timestamp,
+				priority: Some(tx.priority),
+				watched: false,
+			})
+		})
+	}
+
 	/// Get access to the underlying api
 	pub fn api(&self) -> &ChainApi {
 		&self.api
@@ -1368,6 +1436,7 @@ where
 					self.options.clone(),
 					self.metrics.clone(),
 					self.is_validator.clone(),
+					self.view_eviction_rank.clone(),
 				)
 			};
 		debug!(
@@ -1556,9 +1625,14 @@ where
 			.with_transactions(|iter| {
 				iter.filter(|(hash, _)| !view.is_imported(&hash) && !included_xts.contains(&hash))
 					.map(|(k, v)| (*k, v.clone()))
+					// Transactions ranked higher by the policy (older first within the same rank)
+					// are submitted first.
+					.sorted_by_cached_key(|(_, tx)| {
+						(std::cmp::Reverse(tx.rank()), tx.source().timestamp)
+					})
 					// todo [#8835]: better approach is needed - maybe time-budget approach?
 					.take(MEMPOOL_TO_VIEW_BATCH_SIZE)
-					.collect::<HashMap<_, _>>()
+					.collect::<Vec<_>>()
 			})
 			.await
 			.into_iter()
@@ -1982,6 +2056,11 @@ where
 
 		self.view_store.finish_background_revalidations().await;
 
+		let journal_entries = self.journal_entries.lock().take();
+		if let Some(journal_entries) = journal_entries {
+			self.mempool.restore_from_journal(journal_entries).await;
+		}
+
 		let prev_finalized_block = self.enactment_state.lock().recent_finalized_block();
 
 		let compute_tree_route = |from, to| -> Result<TreeRoute<Block>, String> {
@@ -2089,6 +2168,8 @@ where
 		prometheus: Option<&PrometheusRegistry>,
 		spawner: impl SpawnEssentialNamed,
 		client: Arc<Client>,
+		journal: Option<MempoolJournalConfig>,
+		policy: Arc<dyn TransactionPoolPolicy<Block>>,
 	) -> Self {
 		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus, &spawner));
 		let pool = Self::new_with_background_worker(
@@ -2099,6 +2180,8 @@ where
 			spawner,
 			client.usage_info().chain.best_hash,
 			client.usage_info().chain.finalized_hash,
+			journal,
+			policy,
 		);
 
 		pool
@@ -119,6 +119,18 @@
 //! are also periodically verified at every finalized block and removed from the *mempool* if no
 //! longer valid. This is process is called [*mempool* revalidation](#mempool-pruningrevalidation).
 //!
+//! Optionally, the *mempool* content can be persisted in an on-disk [journal][`journal`]. Every
+//! insertion and removal is appended to the journal file. When the pool is started, the
+//! transactions found in the journal (except the stale ones) are put back into the *mempool* while
+//! processing the first chain event, from where they are submitted to the views and revalidated as
+//! usual.
+//!
+//! Admission to the *mempool*, the order in which its transactions are submitted to the newly
+//! created view and the choice of transactions evicted when the *mempool* is full are delegated to
+//! the [`TransactionPoolPolicy`]. The policy also provides the ready and future limits of every
+//! view and ranks the transactions evicted from a view when these limits are exceeded. The default
+//! [`PriorityPolicy`] ranks transactions by the priority returned from runtime validation.
+//!
 //! ## Flows
 //!
 //! The transaction pool internally is executing numerous tasks. This includes handling submitted
@@ -323,6 +335,9 @@
 //! [`mp::revalidate`]: crate::fork_aware_txpool::tx_mem_pool::TxMemPool::revalidate
 //! [`batch_size`]: crate::fork_aware_txpool::tx_mem_pool::TXMEMPOOL_MAX_REVALIDATION_BATCH_SIZE
 //! [`TxInMemPool`]: crate::fork_aware_txpool::tx_mem_pool::TxInMemPool
+//! [`journal`]: crate::fork_aware_txpool::tx_mem_pool::journal
+//! [`TransactionPoolPolicy`]: crate::fork_aware_txpool::policy::TransactionPoolPolicy
+//! [`PriorityPolicy`]: crate::fork_aware_txpool::policy::PriorityPolicy
 //! [`MultiViewListener`]: crate::fork_aware_txpool::multi_view_listener::MultiViewListener
 //! [`Pool`]: crate::graph::Pool
 //! [`Watcher`]: crate::graph::watcher::Watcher
@@ -371,12 +386,17 @@ pub(crate) mod fork_aware_txpool;
 mod import_notification_sink;
 mod metrics;
 mod multi_view_listener;
+mod policy;
 mod revalidation_worker;
 mod tx_mem_pool;
 mod view;
 mod view_store;
 
 pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use policy::{
+	MempoolUsage, PolicyTransaction, PriorityPolicy, TransactionPoolPolicy, TransactionRank,
+};
+pub use tx_mem_pool::MempoolJournalConfig;
 
 mod stream_map_util {
 	use futures::Stream;
@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Pluggable admission, ordering and eviction policy of the fork-aware transaction pool.
+//!
+//! The [`TransactionPoolPolicy`] is consulted by the memory pool whenever a transaction is about
+//! to enter it, when the transactions have to be ordered (e.g. when the memory pool is resubmitted
+//! to the newly created view) and when the limits of the memory pool force some transactions to
+//! be evicted in favour of a new one. It also sets the limits of the ready and future queues of
+//! the views, and ranks the transactions evicted from a view once its limits are exceeded.
+//!
+//! The [`PriorityPolicy`] implements the default behaviour: every transaction is admitted as long
+//! as it fits into the limits, and transactions are ordered by the priority returned from the
+//! runtime's `validate_transaction` (older transactions win the ties).
+//!
+//! Note: the order in which ready transactions are provided to the block builder is still defined
+//! by the views (runtime priority and the dependencies between transactions expressed by tags).
+
+use crate::PoolLimit;
+use sc_transaction_pool_api::{TransactionPriority, TransactionSource};
+use sp_runtime::traits::Block as BlockT;
+use std::{cmp::Ordering, time::Instant};
+
+/// The rank of the transaction within the memory pool, as assigned by the
+/// [`TransactionPoolPolicy`].
+///
+/// Transactions are ordered by the `lane` first and by the `priority` within the lane.
+/// Transactions of unknown priority (not yet validated) are ranked above all the transactions of
+/// known priority within the same lane, so they are not evicted before being validated.
+///
+/// Transactions with the lowest rank are the first ones to be evicted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionRank {
+	/// The lane of the transaction. Higher lanes are ranked above the lower ones.
+	///
+	/// Can be used to reserve the space for some class of transactions (e.g. operational).
+	pub lane: u8,
+	/// The priority of the transaction within the lane.
+	pub priority: Option<TransactionPriority>,
+}
+
+impl TransactionRank {
+	/// Creates a new rank within the given lane.
+	pub fn new(lane: u8, priority: Option<TransactionPriority>) -> Self {
+		Self { lane, priority }
+	}
+}
+
+impl From<Option<TransactionPriority>> for TransactionRank {
+	fn from(priority: Option<TransactionPriority>) -> Self {
+		Self::new(0, priority)
+	}
+}
+
+impl Ord for TransactionRank {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.lane
+			.cmp(&other.lane)
+			.then_with(|| match (&self.priority, &other.priority) {
+				(Some(a), Some(b)) => a.cmp(b),
+				(Some(_), None) => Ordering::Less,
+				(None, Some(_)) => Ordering::Greater,
+				(None, None) => Ordering::Equal,
+			})
+	}
+}
+
+impl PartialOrd for TransactionRank {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+/// The view of the transaction provided to the [`TransactionPoolPolicy`].
+#[derive(Debug)]
+pub struct PolicyTransaction<'a, Block: BlockT> {
+	/// The hash of the transaction.
+	pub hash: Block::Hash,
+	/// The transaction itself.
+	pub tx: &'a Block::Extrinsic,
+	/// The size of the encoded transaction.
+	pub bytes: usize,
+	/// The source of the transaction.
+	pub source: TransactionSource,
+	/// The moment the transaction entered the pool.
+	pub timestamp: Option<Instant>,
+	/// The priority of the transaction, if it was already validated.
+	pub priority: Option<TransactionPriority>,
+	/// Indicates if the transaction is watched.
+	///
+	/// Not known to the views, always `false` when ranking the transactions of a view.
+	pub watched: bool,
+}
+
+/// Current usage and limits of the memory pool, provided to the [`TransactionPoolPolicy`] on
+/// admission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MempoolUsage {
+	/// Number of transactions kept in the memory pool.
+	pub count: usize,
+	/// Total size of the transactions kept in the memory pool.
+	pub bytes: usize,
+	/// Maximum number of transactions in the memory pool.
+	pub max_count: usize,
+	/// Maximum total size of the transactions in the memory pool.
+	pub max_bytes: usize,
+}
+
+/// The admission, ordering and eviction policy of the fork-aware transaction pool.
+///
+/// All the methods are called while the memory pool is locked, so they shall be cheap. The
+/// `note_*` callbacks allow the policy to keep its own bookkeeping (e.g. per-sender counters) in
+/// sync with the content of the memory pool.
+pub trait TransactionPoolPolicy<Block: BlockT>: Send + Sync {
+	/// Decides if the transaction can enter the memory pool.
+	///
+	/// Called before the transaction is inserted, the limits of the memory pool are enforced
+	/// independently. Rejected transactions are reported as `ImmediatelyDropped`.
+	fn admit(&self, _tx: &PolicyTransaction<'_, Block>, _usage: &MempoolUsage) -> bool {
+		true
+	}
+
+	/// Computes the rank of the transaction.
+	///
+	/// Called when the transaction enters the memory pool and every time its priority is updated.
+	/// Also called when the limits of a view are exceeded: the ready transactions of the lowest
+	/// rank and the future transactions of the lowest lane are evicted from the view first.
+	fn rank(&self, tx: &PolicyTransaction<'_, Block>) -> TransactionRank {
+		tx.priority.into()
+	}
+
+	/// Decides if the `new` transaction may evict the `existing` one when the memory pool is full.
+	///
+	/// Eviction candidates are visited from the lowest rank, and the eviction stops at the first
+	/// transaction that cannot be replaced.
+	fn can_replace(
+		&self,
+		new: &PolicyTransaction<'_, Block>,
+		existing: &PolicyTransaction<'_, Block>,
+	) -> bool {
+		match self.rank(existing).cmp(&self.rank(new)) {
+			Ordering::Less => true,
+			Ordering::Equal => existing.timestamp >= new.timestamp,
+			Ordering::Greater => false,
+		}
+	}
+
+	/// Returns the limits of the ready and future queues of every view.
+	///
+	/// Called once, when the pool is created, with the limits configured for the pool. The limits
+	/// of the memory pool are not affected.
+	fn view_limits(&self, ready: PoolLimit, future: PoolLimit) -> (PoolLimit, PoolLimit) {
+		(ready, future)
+	}
+
+	/// Notifies the policy that the transaction entered the memory pool.
+	fn note_inserted(&self, _tx: &PolicyTransaction<'_, Block>) {}
+
+	/// Notifies the policy that the transaction was removed from the memory pool.
+	fn note_removed(&self, _tx: &PolicyTransaction<'_, Block>) {}
+}
+
+/// The default policy: transactions are ordered by their priority, no extra admission rules are
+/// applied.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriorityPolicy;
+
+impl<Block: BlockT> TransactionPoolPolicy<Block> for PriorityPolicy {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rank_ordering_works() {
+		assert!(TransactionRank::new(0, Some(10)) < TransactionRank::new(0, Some(11)));
+		assert!(TransactionRank::new(0, Some(u64::MAX)) < TransactionRank::new(0, None));
+		assert!(TransactionRank::new(0, None) < TransactionRank::new(1, Some(0)));
+		assert_eq!(TransactionRank::from(Some(7)), TransactionRank::new(0, Some(7)));
+	}
+}
@@ -213,7 +213,15 @@ mod tests {
 		let block0 = api.expect_hash_and_number(0);
 
 		let view = Arc::new(
-			View::new(api.clone(), block0, Default::default(), Default::default(), false.into()).0,
+			View::new(
+				api.clone(),
+				block0,
+				Default::default(),
+				Default::default(),
+				false.into(),
+				None,
+			)
+			.0,
 		);
 		let queue = Arc::new(RevalidationQueue::new());
 
@@ -31,6 +31,7 @@
 //! See <https://github.com/paritytech/polkadot-sdk/issues/8912> for some more information. The implementation of the
 //! bridging is based on passing messages from sync context to tokio thread.
 
+use codec::{Decode, Encode};
 use futures::{future::join_all, FutureExt};
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -52,23 +53,31 @@ use std::{
 		},
 		Arc,
 	},
-	time::Instant,
+	time::{Duration, Instant},
 };
 use tracing::{debug, trace};
 
 use crate::{
 	common::tracing_log_xt::log_xt_trace,
-	graph::{self, base_pool::TimedTransactionSource, ExtrinsicFor, ExtrinsicHash},
+	graph::{
+		self, base_pool::TimedTransactionSource, ExtrinsicFor, ExtrinsicHash, RawExtrinsicFor,
+	},
 	ValidateTransactionPriority, LOG_TARGET,
 };
 
 use super::{
-	metrics::MetricsLink as PrometheusMetrics, multi_view_listener::MultiViewListener,
+	metrics::MetricsLink as PrometheusMetrics,
+	multi_view_listener::MultiViewListener,
+	policy::{MempoolUsage, PolicyTransaction, TransactionPoolPolicy, TransactionRank},
 	view_store::ViewStore,
 };
 
+mod journal;
 mod tx_mem_pool_map;
 
+pub use journal::MempoolJournalConfig;
+pub(super) use journal::{Journal, JournalEntry};
+
 /// The minimum interval between single transaction revalidations. Given in blocks.
 pub(crate) const TXMEMPOOL_REVALIDATION_PERIOD: u64 = 10;
 
@@ -132,6 +141,9 @@ where
 	/// Priority of transaction at some block. It is assumed it will not be changed often. None if
 	/// not known.
 	priority: RwLock<Option<TransactionPriority>>,
+	/// Rank of the transaction assigned by the [`TransactionPoolPolicy`], defines the order of
+	/// eviction. Updated together with the priority.
+	rank: RwLock<TransactionRank>,
 }
 
 impl<ChainApi, Block> TxInMemPool<ChainApi, Block>
@@ -205,6 +217,28 @@ where
 			validated_at: AtomicU64::new(validated_at),
 			bytes,
 			priority: priority.into(),
+			rank: TransactionRank::from(priority).into(),
+		}
+	}
+
+	/// Creates a new instance of wrapper for a transaction restored from the journal.
+	///
+	/// Restored transactions are never watched and are due for revalidation.
+	fn new_restored(
+		source: TransactionSource,
+		tx: ExtrinsicFor<ChainApi>,
+		bytes: usize,
+		priority: Option<TransactionPriority>,
+		timestamp: Instant,
+	) -> Self {
+		Self {
+			watched: false,
+			tx,
+			source: TimedTransactionSource { source, timestamp: Some(timestamp) },
+			validated_at: AtomicU64::new(0),
+			bytes,
+			priority: priority.into(),
+			rank: TransactionRank::from(priority).into(),
 		}
 	}
 
@@ -224,6 +258,42 @@ where
 	pub(crate) fn priority(&self) -> Option<TransactionPriority> {
 		*self.priority.read()
 	}
+
+	/// Returns the rank of the transaction assigned by the policy.
+	pub(crate) fn rank(&self) -> TransactionRank {
+		*self.rank.read()
+	}
+
+	/// Provides the view of this transaction for the [`TransactionPoolPolicy`].
+	fn policy_transaction(&self, hash: ExtrinsicHash<ChainApi>) -> PolicyTransaction<'_, Block> {
+		PolicyTransaction {
+			hash,
+			tx: &self.tx,
+			bytes: self.bytes,
+			source: self.source.source,
+			timestamp: self.source.timestamp,
+			priority: self.priority(),
+			watched: self.watched,
+		}
+	}
+
+	/// Builds the journal entry for this transaction.
+	fn journal_entry(
+		&self,
+		hash: ExtrinsicHash<ChainApi>,
+	) -> JournalEntry<ExtrinsicHash<ChainApi>> {
+		let now = journal::unix_now();
+		JournalEntry {
+			hash,
+			source: self.source.source,
+			inserted_at: self
+				.source
+				.timestamp
+				.map_or(now, |timestamp| now.saturating_sub(timestamp.elapsed().as_secs())),
+			priority: self.priority(),
+			tx: self.tx.encode(),
+		}
+	}
 }
 
 impl<ChainApi, Block> std::fmt::Debug for TxInMemPool<ChainApi, Block>
@@ -239,6 +309,7 @@ where
 			.field("source", &self.source)
 			.field("validated_at", &self.validated_at)
 			.field("priority", &self.priority)
+			.field("rank", &self.rank)
 			.finish()
 	}
 }
@@ -254,36 +325,12 @@ where
 			self.bytes == other.bytes &&
 			self.source == other.source &&
 			*self.priority.read() == *other.priority.read() &&
+			*self.rank.read() == *other.rank.read() &&
 			self.validated_at.load(atomic::Ordering::Relaxed) ==
 				other.validated_at.load(atomic::Ordering::Relaxed)
 	}
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-struct MempoolTxPriority(pub Option<TransactionPriority>);
-
-impl Ord for MempoolTxPriority {
-	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-		match (&self.0, &other.0) {
-			(Some(a), Some(b)) => a.cmp(b),
-			(Some(_), None) => std::cmp::Ordering::Less,
-			(None, Some(_)) => std::cmp::Ordering::Greater,
-			(None, None) => std::cmp::Ordering::Equal,
-		}
-	}
-}
-impl From<Option<TransactionPriority>> for MempoolTxPriority {
-	fn from(value: Option<TransactionPriority>) -> Self {
-		MempoolTxPriority(value)
-	}
-}
-
-impl PartialOrd for MempoolTxPriority {
-	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-		Some(self.cmp(other))
-	}
-}
-
 impl<ChainApi, Block> tx_mem_pool_map::Size for Arc<TxInMemPool<ChainApi, Block>>
 where
 	Block: BlockT,
@@ -299,11 +346,11 @@ where
 	Block: BlockT,
 	ChainApi: graph::ChainApi<Block = Block> + 'static,
 {
-	type Priority = MempoolTxPriority;
+	type Priority = TransactionRank;
 	type Timestamp = Option<Instant>;
 
 	fn priority(&self) -> Self::Priority {
-		TxInMemPool::priority(self).into()
+		self.rank()
 	}
 
 	fn timestamp(&self) -> Self::Timestamp {
@@ -313,7 +360,7 @@ where
 
 type InternalTxMemPoolMap<ChainApi, Block> = tx_mem_pool_map::SizeTrackedStore<
 	ExtrinsicHash<ChainApi>,
-	tx_mem_pool_map::PriorityKey<MempoolTxPriority, Option<Instant>>,
+	tx_mem_pool_map::PriorityKey<TransactionRank, Option<Instant>>,
 	Arc<TxInMemPool<ChainApi, Block>>,
 >;
 
@@ -361,6 +408,15 @@ where
 
 	/// Maximal size of encodings of all transactions in the memory pool.
 	max_transactions_total_bytes: usize,
+
+	/// Optional on-disk journal of the transactions kept in the memory pool.
+	///
+	/// Records are sent while holding the lock on `transactions`, so the journal reflects the
+	/// order of the changes.
+	journal: Option<Journal<ExtrinsicHash<ChainApi>>>,
+
+	/// The admission, ordering and eviction policy.
+	policy: Arc<dyn TransactionPoolPolicy<Block>>,
 }
 
 /// Helper structure to encapsulate a result of [`TxMemPool::try_insert`].
@@ -387,13 +443,15 @@ where
 	<Block as BlockT>::Hash: Unpin,
 {
 	/// Creates a new `TxMemPool` instance with the given API, listener, metrics,
-	/// and max transaction count.
+	/// max transaction count, optional journal and policy.
 	pub(super) fn new(
 		api: Arc<ChainApi>,
 		listener: Arc<MultiViewListener<ChainApi>>,
 		metrics: PrometheusMetrics,
 		max_transactions_count: usize,
 		max_transactions_total_bytes: usize,
+		journal: Option<Journal<ExtrinsicHash<ChainApi>>>,
+		policy: Arc<dyn TransactionPoolPolicy<Block>>,
 	) -> (Self, TxMemPoolBlockingTask) {
 		let (sync_channel, rx) = sync_bridge_channel();
 		let task = Self::sync_bridge_task(rx);
@@ -406,6 +464,8 @@ where
 				metrics,
 				max_transactions_count,
 				max_transactions_total_bytes,
+				journal,
+				policy,
 			},
 			task.boxed(),
 		)
@@ -428,9 +488,25 @@ where
 			sync_channel,
 			max_transactions_count,
 			max_transactions_total_bytes,
+			journal: None,
+			policy: Arc::new(super::policy::PriorityPolicy),
 		}
 	}
 
+	/// Sets the policy of the `TxMemPool` instance created for testing purposes.
+	#[cfg(test)]
+	fn with_policy(mut self, policy: Arc<dyn TransactionPoolPolicy<Block>>) -> Self {
+		self.policy = policy;
+		self
+	}
+
+	/// Sets the journal of the `TxMemPool` instance created for testing purposes.
+	#[cfg(test)]
+	fn with_journal(mut self, journal: Journal<ExtrinsicHash<ChainApi>>) -> Self {
+		self.journal = Some(journal);
+		self
+	}
+
 	/// Retrieves a transaction by its hash if it exists in the memory pool.
 	pub(super) async fn get_by_hash(
 		&self,
@@ -463,8 +539,29 @@ where
 			current_total_bytes > self.max_transactions_total_bytes
 	}
 
+	/// Returns true if the policy admits the transaction into the memory pool.
+	///
+	/// The rank of the transaction is also assigned here, so it is known before the transaction
+	/// is inserted.
+	fn admit(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		tx: &TxInMemPool<ChainApi, Block>,
+		count: usize,
+	) -> bool {
+		let policy_tx = tx.policy_transaction(tx_hash);
+		*tx.rank.write() = self.policy.rank(&policy_tx);
+		let usage = MempoolUsage {
+			count,
+			bytes: self.transactions.bytes(),
+			max_count: self.max_transactions_count,
+			max_bytes: self.max_transactions_total_bytes,
+		};
+		self.policy.admit(&policy_tx, &usage)
+	}
+
 	/// Attempts to insert a transaction into the memory pool, ensuring it does not
-	/// exceed the maximum allowed transaction count.
+	/// exceed the maximum allowed transaction count and is admitted by the policy.
 	async fn try_insert(
 		&self,
 		tx_hash: ExtrinsicHash<ChainApi>,
@@ -478,8 +575,15 @@ where
 			self.is_limit_exceeded(transactions.len() + 1, bytes + tx.bytes),
 			transactions.contains_key(&tx_hash),
 		) {
+			(false, false) if !self.admit(tx_hash, &tx, transactions.len()) => {
+				Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped)
+			},
 			(false, false) => {
 				let source = tx.source();
+				if let Some(journal) = &self.journal {
+					journal.note_inserted(tx.journal_entry(tx_hash));
+				}
+				self.policy.note_inserted(&tx.policy_transaction(tx_hash));
 				transactions.insert(tx_hash, Arc::from(tx));
 				Ok(InsertionInfo::new(tx_hash, source))
 			},
@@ -500,8 +604,9 @@ where
 	/// Attempts to insert a new transaction in the memory pool and drop some worse existing
 	/// transactions.
 	///
-	/// A "worse" transaction means transaction with lower priority, or older transaction with the
-	/// same prio.
+	/// Whether the existing transaction is "worse" and can be replaced is decided by the
+	/// [`TransactionPoolPolicy`]. By default it means transaction with lower priority, or newer
+	/// transaction with the same prio.
 	///
 	/// This operation will not overflow the limit of the mempool. It means that cumulative
 	/// size of removed transactions will be equal (or greated) then size of newly inserted
@@ -530,22 +635,43 @@ where
 			return Err(sc_transaction_pool_api::error::Error::AlreadyImported(Box::new(hash)));
 		}
 
-		// When pushing higher prio transaction, we need to find a number of lower prio txs, such
+		if !self.admit(hash, &new_tx, transactions.len()) {
+			return Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped);
+		}
+
+		// When pushing higher rank transaction, we need to find a number of lower rank txs, such
 		// that the sum of their bytes is ge then size of new tx. Otherwise we could overflow size
-		// limits. Naive way to do it - rev-sort by priority and eat the tail.
+		// limits. Naive way to do it - rev-sort by rank and eat the tail, as long as the policy
+		// allows to replace them.
 
-		// reverse (oldest, lowest prio last)
+		// reverse (oldest, lowest rank last)
 		let source = new_tx.source();
 		let new_tx = Arc::new(new_tx);
-		let insertion_result = transactions.try_insert_with_replacement(
+		let new_policy_tx = new_tx.policy_transaction(hash);
+		let insertion_result = transactions.try_insert_with_replacement_by(
 			self.max_transactions_total_bytes,
 			hash,
-			new_tx,
+			new_tx.clone(),
+			|worst_hash, worst_tx| {
+				self.policy
+					.can_replace(&new_policy_tx, &worst_tx.policy_transaction(*worst_hash))
+			},
 		);
 		debug_assert!(!self.is_limit_exceeded(transactions.len(), self.transactions.bytes()));
 		match insertion_result {
 			None => Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped),
-			Some(to_be_removed) => Ok(InsertionInfo::new_with_removed(hash, source, to_be_removed)),
+			Some(removed) => {
+				for (removed_hash, removed_tx) in &removed {
+					self.policy.note_removed(&removed_tx.policy_transaction(*removed_hash));
+				}
+				self.policy.note_inserted(&new_policy_tx);
+				let to_be_removed = removed.into_iter().map(|(hash, _)| hash).collect::<Vec<_>>();
+				if let Some(journal) = &self.journal {
+					journal.note_removed(to_be_removed.clone());
+					journal.note_inserted(new_tx.journal_entry(hash));
+				}
+				Ok(InsertionInfo::new_with_removed(hash, source, to_be_removed))
+			},
 		}
 	}
 
@@ -610,8 +736,77 @@ where
 		log_xt_trace!(target: LOG_TARGET, tx_hashes, "mempool::remove_transaction");
 		let mut transactions = self.transactions.write().await;
 		for tx_hash in tx_hashes {
-			transactions.remove(tx_hash);
+			if let Some(tx) = transactions.remove(tx_hash) {
+				self.policy.note_removed(&tx.policy_transaction(*tx_hash));
+			}
+		}
+		if let Some(journal) = &self.journal {
+			journal.note_removed(tx_hashes.to_vec());
+		}
+	}
+
+	/// Inserts the transactions restored from the journal into the memory pool.
+	///
+	/// Transactions are inserted in the order of decreasing priority (and the order they entered
+	/// the pool for equal priorities), as long as they fit into the limits of the memory pool.
+	/// Restored transactions are submitted to the views and revalidated as any other transaction
+	/// kept in the memory pool. The journal is compacted afterwards, dropping the transactions that
+	/// were not restored.
+	pub(super) async fn restore_from_journal(
+		&self,
+		mut entries: Vec<JournalEntry<ExtrinsicHash<ChainApi>>>,
+	) {
+		let total = entries.len();
+		entries.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.inserted_at.cmp(&b.inserted_at)));
+
+		let now = journal::unix_now();
+		let mut restored = 0;
+		for entry in entries {
+			let tx = match RawExtrinsicFor::<ChainApi>::decode(&mut &entry.tx[..]) {
+				Ok(tx) => Arc::new(tx),
+				Err(error) => {
+					debug!(
+						target: LOG_TARGET,
+						tx_hash = ?entry.hash,
+						%error,
+						"mempool::restore_from_journal: cannot decode transaction"
+					);
+					continue;
+				},
+			};
+			let (hash, length) = self.api.hash_and_length(&tx);
+			let age = Duration::from_secs(now.saturating_sub(entry.inserted_at));
+			let timestamp = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+			let tx = TxInMemPool::new_restored(entry.source, tx, length, entry.priority, timestamp);
+			if self.try_insert(hash, tx).await.is_ok() {
+				restored += 1;
+			}
+		}
+
+		debug!(
+			target: LOG_TARGET,
+			total,
+			restored,
+			mempool_len = self.len(),
+			"mempool::restore_from_journal"
+		);
+
+		self.compact_journal(true).await;
+	}
+
+	/// Rewrites the journal with the transactions currently kept in the memory pool, if it grew
+	/// too large or if `force` is set.
+	async fn compact_journal(&self, force: bool) {
+		let Some(journal) = &self.journal else { return };
+		let transactions = self.transactions.read().await;
+		if !force && !journal.needs_compaction(self.transactions.bytes()) {
+			return;
 		}
+
+		let entries = transactions
+			.with_items(|iter| iter.map(|(hash, tx)| tx.journal_entry(*hash)).collect::<Vec<_>>());
+		trace!(target: LOG_TARGET, count = entries.len(), "mempool::compact_journal");
+		journal.compact(entries);
 	}
 
 	/// Revalidates a batch of transactions against the provided finalized block.
@@ -750,8 +945,13 @@ where
 		log_xt_trace!(target: LOG_TARGET, finalized_xts, "purged finalized transactions");
 		let mut transactions = self.transactions.write().await;
 		finalized_xts.iter().for_each(|t| {
-			transactions.remove(t);
+			if let Some(tx) = transactions.remove(t) {
+				self.policy.note_removed(&tx.policy_transaction(*t));
+			}
 		});
+		if let Some(journal) = &self.journal {
+			journal.note_removed(finalized_xts.clone());
+		}
 	}
 
 	/// Revalidates transactions in the memory pool against a given finalized block and removes
@@ -766,9 +966,15 @@ where
 		{
 			let mut transactions = self.transactions.write().await;
 			invalid_hashes_subtrees.iter().for_each(|tx_hash| {
-				transactions.remove(&tx_hash);
+				if let Some(tx) = transactions.remove(&tx_hash) {
+					self.policy.note_removed(&tx.policy_transaction(*tx_hash));
+				}
 			});
+			if let Some(journal) = &self.journal {
+				journal.note_removed(invalid_hashes_subtrees.iter().copied().collect());
+			}
 		};
+		self.compact_journal(false).await;
 
 		// note: here the consistency is assumed: it is expected that transaction will be
 		// actually removed from the listener with Invalid event. This means assumption that no view
@@ -800,6 +1006,7 @@ where
 
 			transactions.update_item(&hash, |t| {
 				*t.priority.write() = Some(priority);
+				*t.rank.write() = self.policy.rank(&t.policy_transaction(hash));
 			});
 		}
 	}
@@ -1023,7 +1230,8 @@ where
 #[cfg(test)]
 mod tx_mem_pool_tests {
 	use futures::future::join_all;
-	use substrate_test_runtime::{AccountId, Extrinsic, ExtrinsicBuilder, Transfer, H256};
+	use std::sync::atomic::AtomicUsize;
+	use substrate_test_runtime::{AccountId, Block, Extrinsic, ExtrinsicBuilder, Transfer, H256};
 	use substrate_test_runtime_client::Sr25519Keyring::*;
 
 	use crate::{
@@ -1393,4 +1601,196 @@ mod tx_mem_pool_tests {
 			sc_transaction_pool_api::error::Error::ImmediatelyDropped
 		));
 	}
+
+	#[tokio::test]
+	async fn restore_from_journal_prefers_high_priority_and_obeys_limit() {
+		sp_tracing::try_init_simple();
+		let max = 3;
+		let api = Arc::from(TestApi::default());
+		let mempool = TxMemPool::new_test(api.clone(), max, usize::MAX);
+
+		let now = journal::unix_now();
+		let mut entries = (0..5u64)
+			.map(|x| {
+				let xt = uxt(x);
+				JournalEntry {
+					hash: api.hash_and_length(&xt).0,
+					source: TransactionSource::External,
+					inserted_at: now - 10,
+					priority: Some(x),
+					tx: xt.encode(),
+				}
+			})
+			.collect::<Vec<_>>();
+		let hashes = entries.iter().map(|entry| entry.hash).collect::<Vec<_>>();
+		entries.push(JournalEntry {
+			hash: H256::repeat_byte(0xff),
+			source: TransactionSource::External,
+			inserted_at: now,
+			priority: Some(u64::MAX),
+			tx: vec![0xff, 0xff],
+		});
+
+		mempool.restore_from_journal(entries).await;
+
+		assert_eq!(mempool.unwatched_and_watched_count().await, (max, 0));
+		for hash in &hashes[..2] {
+			assert!(mempool.get_by_hash(*hash).await.is_none());
+		}
+		for hash in &hashes[2..] {
+			let tx = mempool.get_by_hash(*hash).await.unwrap();
+			assert!(tx.source().timestamp.unwrap().elapsed() >= Duration::from_secs(10));
+		}
+	}
+
+	#[tokio::test]
+	async fn journal_is_kept_until_restored_and_then_compacted() {
+		sp_tracing::try_init_simple();
+		let max = 2;
+		let api = Arc::from(TestApi::default());
+		let dir = tempfile::tempdir().unwrap();
+		let config = MempoolJournalConfig::new(dir.path().join("journal"));
+
+		let now = journal::unix_now();
+		let entries = (0..3u64)
+			.map(|x| {
+				let xt = uxt(x);
+				JournalEntry {
+					hash: api.hash_and_length(&xt).0,
+					source: TransactionSource::External,
+					inserted_at: now,
+					priority: Some(x),
+					tx: xt.encode(),
+				}
+			})
+			.collect::<Vec<_>>();
+		let (journal, _, task) = Journal::open(&config).unwrap();
+		entries.iter().cloned().for_each(|entry| journal.note_inserted(entry));
+		drop(journal);
+		task.await;
+
+		// The node stops before the transactions were restored: they are all kept.
+		let (_journal, restored, _task) = Journal::<H256>::open(&config).unwrap();
+		assert_eq!(restored, entries);
+
+		let (journal, restored, task) = Journal::open(&config).unwrap();
+		let mempool = TxMemPool::new_test(api.clone(), max, usize::MAX).with_journal(journal);
+		mempool.restore_from_journal(restored).await;
+		drop(mempool);
+		task.await;
+
+		// Only the transactions that made it into the mempool are left in the journal.
+		let (_journal, restored, _task) = Journal::<H256>::open(&config).unwrap();
+		let mut restored = restored.into_iter().map(|entry| entry.hash).collect::<Vec<_>>();
+		restored.sort();
+		let mut expected = entries[1..].iter().map(|entry| entry.hash).collect::<Vec<_>>();
+		expected.sort();
+		assert_eq!(restored, expected);
+	}
+
+	/// Puts the local transactions into the higher lane.
+	struct LocalLanePolicy;
+
+	impl TransactionPoolPolicy<Block> for LocalLanePolicy {
+		fn rank(&self, tx: &PolicyTransaction<'_, Block>) -> TransactionRank {
+			let lane = if tx.source == TransactionSource::Local { 1 } else { 0 };
+			TransactionRank::new(lane, tx.priority)
+		}
+	}
+
+	/// Admits at most `max_external` external transactions.
+	struct ExternalQuotaPolicy {
+		max_external: usize,
+		external: AtomicUsize,
+	}
+
+	impl TransactionPoolPolicy<Block> for ExternalQuotaPolicy {
+		fn admit(&self, tx: &PolicyTransaction<'_, Block>, _: &MempoolUsage) -> bool {
+			tx.source != TransactionSource::External ||
+				self.external.load(atomic::Ordering::Relaxed) < self.max_external
+		}
+
+		fn note_inserted(&self, tx: &PolicyTransaction<'_, Block>) {
+			if tx.source == TransactionSource::External {
+				self.external.fetch_add(1, atomic::Ordering::Relaxed);
+			}
+		}
+
+		fn note_removed(&self, tx: &PolicyTransaction<'_, Block>) {
+			if tx.source == TransactionSource::External {
+				self.external.fetch_sub(1, atomic::Ordering::Relaxed);
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn replacing_txs_respects_policy_lanes() {
+		sp_tracing::try_init_simple();
+		let max = 2;
+		let api = Arc::from(TestApi::default());
+		let mempool = TxMemPool::new_test(api.clone(), usize::MAX, max * LARGE_XT_SIZE)
+			.with_policy(Arc::new(LocalLanePolicy));
+
+		let xts = (0..max).map(|x| Arc::from(large_uxt(x))).collect::<Vec<_>>();
+		let hashes = xts.iter().map(|xt| api.hash_and_length(xt).0).collect::<Vec<_>>();
+		let results = mempool.extend_unwatched(TransactionSource::External, 0, &xts).await;
+		assert!(results.iter().all(Result::is_ok));
+		for hash in &hashes {
+			mempool.update_transaction_priority(*hash, Some(10)).await;
+		}
+
+		// external transaction of lower priority cannot replace anything
+		let xt = Arc::from(large_uxt(97));
+		let result = mempool
+			.try_insert_with_replacement(xt, 1, TransactionSource::External, 0, false)
+			.await;
+		assert!(matches!(
+			result.unwrap_err(),
+			sc_transaction_pool_api::error::Error::ImmediatelyDropped
+		));
+
+		// local transaction of lower priority is in the higher lane
+		let xt = Arc::from(large_uxt(98));
+		let hash = api.hash_and_length(&xt).0;
+		let result = mempool
+			.try_insert_with_replacement(xt, 1, TransactionSource::Local, 0, false)
+			.await
+			.unwrap();
+
+		assert_eq!(result.hash, hash);
+		assert_eq!(result.removed.len(), 1);
+		assert!(hashes.contains(&result.removed[0]));
+		assert_eq!(
+			mempool.get_by_hash(hash).await.unwrap().rank(),
+			TransactionRank::new(1, Some(1))
+		);
+	}
+
+	#[tokio::test]
+	async fn admission_respects_policy() {
+		sp_tracing::try_init_simple();
+		let api = Arc::from(TestApi::default());
+		let mempool = TxMemPool::new_test(api.clone(), usize::MAX, usize::MAX).with_policy(
+			Arc::new(ExternalQuotaPolicy { max_external: 2, external: AtomicUsize::new(0) }),
+		);
+
+		let xts = (0..3).map(|x| Arc::from(uxt(x))).collect::<Vec<_>>();
+		let results = mempool.extend_unwatched(TransactionSource::External, 0, &xts).await;
+		assert!(results.iter().take(2).all(Result::is_ok));
+		assert!(matches!(
+			results.into_iter().last().unwrap().unwrap_err(),
+			sc_transaction_pool_api::error::Error::ImmediatelyDropped
+		));
+
+		// local transactions are not limited
+		let results = mempool.extend_unwatched(TransactionSource::Local, 0, &xts[2..]).await;
+		assert!(results.iter().all(Result::is_ok));
+
+		// removal makes room for another external transaction
+		mempool.remove_transactions(&[api.hash_and_length(&xts[0]).0]).await;
+		let xt = Arc::from(uxt(3));
+		let results = mempool.extend_unwatched(TransactionSource::External, 0, &[xt]).await;
+		assert!(results.iter().all(Result::is_ok));
+		assert_eq!(mempool.len(), 3);
+	}
 }
@@ -0,0 +1,426 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk journal of the transactions kept in the `TxMemPool`.
+//!
+//! Every insertion into and removal from the mempool is appended to the journal file as a single
+//! SCALE-encoded record. Writing happens on a dedicated blocking task, so the mempool only pays for
+//! encoding the record.
+//!
+//! When the pool is created, the journal is replayed into the set of transactions that were in the
+//! mempool when the node stopped. Entries older than [`MempoolJournalConfig::max_age`] are dropped,
+//! the file is rewritten with the remaining ones and those are handed back to the mempool, which
+//! inserts them again (respecting its limits). From there they are submitted to the views and
+//! revalidated like any other mempool transaction. Once they are restored, the journal is compacted
+//! to drop the transactions that did not make it back into the mempool. Until then, the journal
+//! still holds all of them, so stopping the node before the restoration loses nothing.
+//!
+//! As removals only append records, the file grows over time. The mempool periodically requests a
+//! compaction, which rewrites the journal with the currently kept transactions only.
+
+use codec::{Decode, Encode};
+use sc_transaction_pool_api::{TransactionPriority, TransactionSource};
+use std::{
+	collections::HashMap,
+	fs::{self, File, OpenOptions},
+	io::{self, Write},
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		mpsc::{channel, Receiver, Sender},
+		Arc,
+	},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+use super::TxMemPoolBlockingTask;
+use crate::LOG_TARGET;
+
+/// The default maximal age of the journal entries that are restored.
+pub const DEFAULT_JOURNAL_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// The journal is compacted once the size of the file exceeds twice the size of the transactions
+/// kept in the mempool by at least this number of bytes.
+const JOURNAL_COMPACTION_SLACK: u64 = 4 * 1024 * 1024;
+
+/// Version of the journal file format, stored as the first byte of the file.
+const JOURNAL_VERSION: u8 = 1;
+
+/// Configuration of the on-disk mempool journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolJournalConfig {
+	/// Path to the journal file.
+	pub path: PathBuf,
+	/// Transactions that entered the pool longer ago than this are not restored.
+	pub max_age: Duration,
+}
+
+impl MempoolJournalConfig {
+	/// Creates a new journal configuration with the default maximal age of the entries.
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self { path: path.into(), max_age: DEFAULT_JOURNAL_MAX_AGE }
+	}
+
+	/// Sets the maximal age of the restored entries.
+	pub fn with_max_age(mut self, max_age: Duration) -> Self {
+		self.max_age = max_age;
+		self
+	}
+}
+
+/// A transaction stored in the journal.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub(crate) struct JournalEntry<Hash> {
+	/// The hash of the transaction.
+	pub(crate) hash: Hash,
+	/// The source the transaction was originally received from.
+	pub(crate) source: TransactionSource,
+	/// When the transaction entered the pool, in seconds since the UNIX epoch.
+	pub(crate) inserted_at: u64,
+	/// Priority of the transaction, if known at the time it was journaled.
+	pub(crate) priority: Option<TransactionPriority>,
+	/// SCALE-encoded transaction.
+	pub(crate) tx: Vec<u8>,
+}
+
+/// A single record appended to the journal.
+#[derive(Debug, Encode, Decode)]
+enum JournalRecord<Hash> {
+	/// The transaction was inserted into the mempool.
+	Insert(JournalEntry<Hash>),
+	/// The transactions were removed from the mempool.
+	Remove(Vec<Hash>),
+}
+
+/// Requests processed by the journal writer task.
+enum JournalCommand<Hash> {
+	/// Append the record to the journal.
+	Append(JournalRecord<Hash>),
+	/// Replace the content of the journal with the given entries.
+	Compact(Vec<JournalEntry<Hash>>),
+}
+
+/// Handle to the mempool journal.
+///
+/// Records are sent to the writer task, which appends them to the file in the order they were
+/// sent.
+pub(crate) struct Journal<Hash> {
+	/// Channel to the writer task.
+	sender: Sender<JournalCommand<Hash>>,
+	/// Number of bytes in the journal file, as written by the writer task.
+	file_bytes: Arc<AtomicU64>,
+}
+
+impl<Hash> Journal<Hash>
+where
+	Hash: Encode + Decode + Eq + std::hash::Hash + Clone + Send + 'static,
+{
+	/// Opens the journal at the configured path.
+	///
+	/// Returns the journal handle, the entries that shall be restored and the writer task that
+	/// needs to be spawned. The journal file is rewritten with the entries to restore only, they
+	/// are kept until the journal gets compacted.
+	pub(crate) fn open(
+		config: &MempoolJournalConfig,
+	) -> io::Result<(Self, Vec<JournalEntry<Hash>>, TxMemPoolBlockingTask)> {
+		let entries = match fs::read(&config.path) {
+			Ok(content) => decode_journal(&content),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(error) => return Err(error),
+		};
+		let total = entries.len();
+
+		let now = unix_now();
+		let max_age = config.max_age.as_secs();
+		let entries = entries
+			.into_iter()
+			.filter(|entry| now.saturating_sub(entry.inserted_at) <= max_age)
+			.collect::<Vec<_>>();
+
+		debug!(
+			target: LOG_TARGET,
+			path = ?config.path,
+			total,
+			restored = entries.len(),
+			"mempool journal opened"
+		);
+
+		if let Some(parent) = config.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let (file, written) = compact_journal_file(&config.path, entries.clone())?;
+
+		let file_bytes = Arc::new(AtomicU64::new(written));
+		let (sender, receiver) = channel();
+		let task =
+			Box::pin(Self::writer_task(config.path.clone(), file, receiver, file_bytes.clone()));
+
+		Ok((Self { sender, file_bytes }, entries, task))
+	}
+
+	/// Journals the insertion of a transaction.
+	pub(crate) fn note_inserted(&self, entry: JournalEntry<Hash>) {
+		self.send(JournalCommand::Append(JournalRecord::Insert(entry)));
+	}
+
+	/// Journals the removal of transactions.
+	pub(crate) fn note_removed(&self, hashes: Vec<Hash>) {
+		if !hashes.is_empty() {
+			self.send(JournalCommand::Append(JournalRecord::Remove(hashes)));
+		}
+	}
+
+	/// Returns `true` if the journal file grew large enough compared to the total size of the
+	/// transactions kept in the mempool to be worth compacting.
+	pub(crate) fn needs_compaction(&self, mempool_bytes: usize) -> bool {
+		self.file_bytes.load(Ordering::Relaxed) >
+			(mempool_bytes as u64)
+				.saturating_mul(2)
+				.saturating_add(JOURNAL_COMPACTION_SLACK)
+	}
+
+	/// Replaces the content of the journal with the given entries.
+	pub(crate) fn compact(&self, entries: Vec<JournalEntry<Hash>>) {
+		self.send(JournalCommand::Compact(entries));
+	}
+
+	fn send(&self, command: JournalCommand<Hash>) {
+		if self.sender.send(command).is_err() {
+			debug!(target: LOG_TARGET, "mempool journal writer task is gone");
+		}
+	}
+
+	async fn writer_task(
+		path: PathBuf,
+		mut file: File,
+		receiver: Receiver<JournalCommand<Hash>>,
+		file_bytes: Arc<AtomicU64>,
+	) {
+		for command in receiver {
+			let result = match command {
+				JournalCommand::Append(record) => {
+					let record = record.encode();
+					file.write_all(&record).map(|_| {
+						file_bytes.fetch_add(record.len() as u64, Ordering::Relaxed);
+					})
+				},
+				JournalCommand::Compact(entries) => {
+					compact_journal_file(&path, entries).map(|(new_file, written)| {
+						file = new_file;
+						file_bytes.store(written, Ordering::Relaxed);
+					})
+				},
+			};
+
+			if let Err(error) = result {
+				warn!(target: LOG_TARGET, ?path, %error, "writing the mempool journal failed");
+			}
+		}
+	}
+}
+
+/// Decodes the live entries from the content of the journal file.
+///
+/// Decoding stops at the first malformed record, which is expected to be a partially written
+/// record at the end of the file.
+fn decode_journal<Hash>(content: &[u8]) -> Vec<JournalEntry<Hash>>
+where
+	Hash: Decode + Eq + std::hash::Hash + Clone,
+{
+	let Some((&version, mut input)) = content.split_first() else { return Vec::new() };
+	if version != JOURNAL_VERSION {
+		warn!(target: LOG_TARGET, version, "unsupported mempool journal version, ignoring");
+		return Vec::new();
+	}
+
+	let mut order = Vec::new();
+	let mut entries = HashMap::new();
+	while !input.is_empty() {
+		match JournalRecord::<Hash>::decode(&mut input) {
+			Ok(JournalRecord::Insert(entry)) => {
+				if entries.insert(entry.hash.clone(), entry.clone()).is_none() {
+					order.push(entry.hash);
+				}
+			},
+			Ok(JournalRecord::Remove(hashes)) => {
+				hashes.iter().for_each(|hash| {
+					entries.remove(hash);
+				});
+			},
+			Err(error) => {
+				debug!(
+					target: LOG_TARGET,
+					%error,
+					remaining = input.len(),
+					"malformed mempool journal record, ignoring the rest"
+				);
+				break;
+			},
+		}
+	}
+
+	order.into_iter().filter_map(|hash| entries.remove(&hash)).collect()
+}
+
+/// Creates an empty journal file at the given path, overwriting an existing one.
+fn create_journal_file(path: &Path) -> io::Result<File> {
+	let mut file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+	file.write_all(&[JOURNAL_VERSION])?;
+	Ok(file)
+}
+
+/// Atomically replaces the journal with the one containing given entries only.
+///
+/// Returns the new journal file opened for appending and its size.
+fn compact_journal_file<Hash: Encode>(
+	path: &Path,
+	entries: Vec<JournalEntry<Hash>>,
+) -> io::Result<(File, u64)> {
+	let tmp_path = path.with_extension("tmp");
+	let mut tmp = create_journal_file(&tmp_path)?;
+	let mut written = 1u64;
+	for entry in entries {
+		let record = JournalRecord::Insert(entry).encode();
+		tmp.write_all(&record)?;
+		written += record.len() as u64;
+	}
+	tmp.sync_all()?;
+	drop(tmp);
+	fs::rename(&tmp_path, path)?;
+
+	let file = OpenOptions::new().append(true).open(path)?;
+	Ok((file, written))
+}
+
+/// Returns the current time in seconds since the UNIX epoch.
+pub(crate) fn unix_now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+
+	fn entry(byte: u8, inserted_at: u64) -> JournalEntry<H256> {
+		JournalEntry {
+			hash: H256::repeat_byte(byte),
+			source: TransactionSource::External,
+			inserted_at,
+			priority: Some(byte as u64),
+			tx: vec![byte; 16],
+		}
+	}
+
+	fn encode_journal(records: Vec<JournalRecord<H256>>) -> Vec<u8> {
+		let mut content = vec![JOURNAL_VERSION];
+		records.into_iter().for_each(|record| record.encode_to(&mut content));
+		content
+	}
+
+	#[test]
+	fn removed_entries_are_not_restored() {
+		let content = encode_journal(vec![
+			JournalRecord::Insert(entry(1, 10)),
+			JournalRecord::Insert(entry(2, 11)),
+			JournalRecord::Insert(entry(3, 12)),
+			JournalRecord::Remove(vec![H256::repeat_byte(2)]),
+		]);
+
+		assert_eq!(decode_journal::<H256>(&content), vec![entry(1, 10), entry(3, 12)]);
+	}
+
+	#[test]
+	fn truncated_record_is_ignored() {
+		let mut content = encode_journal(vec![
+			JournalRecord::Insert(entry(1, 10)),
+			JournalRecord::Insert(entry(2, 11)),
+		]);
+		content.truncate(content.len() - 3);
+
+		assert_eq!(decode_journal::<H256>(&content), vec![entry(1, 10)]);
+	}
+
+	#[test]
+	fn unknown_version_is_ignored() {
+		let mut content = encode_journal(vec![JournalRecord::Insert(entry(1, 10))]);
+		content[0] = JOURNAL_VERSION + 1;
+
+		assert!(decode_journal::<H256>(&content).is_empty());
+	}
+
+	#[test]
+	fn stale_entries_are_dropped_on_open() {
+		let dir = tempfile::tempdir().unwrap();
+		let config = MempoolJournalConfig::new(dir.path().join("journal"))
+			.with_max_age(Duration::from_secs(60));
+		let now = unix_now();
+		fs::write(
+			&config.path,
+			encode_journal(vec![
+				JournalRecord::Insert(entry(1, now - 120)),
+				JournalRecord::Insert(entry(2, now - 30)),
+			]),
+		)
+		.unwrap();
+
+		let (_journal, entries, _task) = Journal::<H256>::open(&config).unwrap();
+		assert_eq!(entries, vec![entry(2, now - 30)]);
+
+		// The stale entries are dropped from the journal, the others are kept until compacted.
+		assert_eq!(
+			fs::read(&config.path).unwrap(),
+			encode_journal(vec![JournalRecord::Insert(entry(2, now - 30))])
+		);
+		let (_journal, entries, _task) = Journal::<H256>::open(&config).unwrap();
+		assert_eq!(entries, vec![entry(2, now - 30)]);
+	}
+
+	#[test]
+	fn journal_is_written_and_compacted() {
+		let dir = tempfile::tempdir().unwrap();
+		let config = MempoolJournalConfig::new(dir.path().join("journal"));
+		let now = unix_now();
+
+		let (journal, entries, task) = Journal::<H256>::open(&config).unwrap();
+		assert!(entries.is_empty());
+
+		journal.note_inserted(entry(1, now));
+		journal.note_inserted(entry(2, now));
+		journal.note_removed(vec![H256::repeat_byte(1)]);
+		journal.note_inserted(entry(3, now));
+		drop(journal);
+		futures::executor::block_on(task);
+
+		let content = fs::read(&config.path).unwrap();
+		assert_eq!(decode_journal::<H256>(&content), vec![entry(2, now), entry(3, now)]);
+
+		let (journal, entries, task) = Journal::<H256>::open(&config).unwrap();
+		assert_eq!(entries, vec![entry(2, now), entry(3, now)]);
+
+		journal.note_inserted(entry(4, now));
+		journal.compact(vec![entry(3, now)]);
+		journal.note_inserted(entry(5, now));
+		drop(journal);
+		futures::executor::block_on(task);
+
+		let (_journal, entries, _task) = Journal::<H256>::open(&config).unwrap();
+		assert_eq!(entries, vec![entry(3, now), entry(5, now)]);
+	}
+}
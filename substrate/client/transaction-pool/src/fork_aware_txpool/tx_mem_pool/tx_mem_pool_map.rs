@@ -224,18 +224,24 @@ where
 	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size,
 	V: std::cmp::PartialEq + std::fmt::Debug,
 {
-	/// Attempts to insert an item with replacement based on free space and priority.
-	/// Returns the total size in bytes of removed items, and their keys.
+	/// Attempts to insert an item with replacement based on free space.
 	///
-	/// Insertion always results with other item's removal, the len bound is kept elsewhere
+	/// Items are visited starting from the worst one, and removed as long as `can_evict` allows
+	/// it. The first item that cannot be evicted stops the search. Insertion always results with
+	/// other item's removal, the len bound is kept elsewhere.
 	///
-	/// If nothing was inserted `(None,0)` is returned.
-	pub fn try_insert_with_replacement(
+	/// Returns the removed items with their keys, and their total size in bytes. If nothing was
+	/// inserted `(None,0)` is returned.
+	pub fn try_insert_with_replacement_by<F>(
 		&mut self,
 		free_bytes: usize,
 		key: K,
 		item: V,
-	) -> (Option<Vec<K>>, usize) {
+		can_evict: F,
+	) -> (Option<Vec<(K, V)>>, usize)
+	where
+		F: Fn(&K, &V) -> bool,
+	{
 		let mut total_size_removed = 0usize;
 		let mut to_be_removed = vec![];
 
@@ -247,13 +253,8 @@ where
 			return (None, 0);
 		}
 
-		for (SortKey(PriorityKey(worst_priority, worst_timestamp), worst_key), worst_item) in
-			&self.items_by_priority
-		{
-			if *worst_priority > item.priority() {
-				return (None, 0);
-			}
-			if *worst_priority == item.priority() && *worst_timestamp < item.timestamp() {
+		for (SortKey(_, worst_key), worst_item) in &self.items_by_priority {
+			if !can_evict(worst_key, worst_item) {
 				return (None, 0);
 			}
 
@@ -271,11 +272,14 @@ where
 
 		self.insert(key, item);
 
-		for worst_key in &to_be_removed {
-			self.remove(worst_key);
-		}
+		let removed = to_be_removed
+			.into_iter()
+			.filter_map(|worst_key| {
+				self.remove(&worst_key).map(|worst_item| (worst_key, worst_item))
+			})
+			.collect();
 
-		(Some(to_be_removed), total_size_removed)
+		(Some(removed), total_size_removed)
 	}
 }
 
@@ -403,26 +407,50 @@ where
 		val
 	}
 
-	/// Refer to [`IndexedStorage::try_insert_with_replacement`]
+	/// Attempts to insert an item evicting the items with lower priority, or the newer items with
+	/// the same priority.
+	///
+	/// Refer to [`IndexedStorage::try_insert_with_replacement_by`]
+	#[cfg(test)]
 	pub fn try_insert_with_replacement(
 		&mut self,
 		max_total_bytes: usize,
 		key: K,
 		item: V,
 	) -> Option<Vec<K>> {
+		let (priority, timestamp) = (item.priority(), item.timestamp());
+		self.try_insert_with_replacement_by(max_total_bytes, key, item, |_, worst_item| {
+			worst_item.priority() < priority ||
+				(worst_item.priority() == priority && worst_item.timestamp() >= timestamp)
+		})
+		.map(|removed| removed.into_iter().map(|(key, _)| key).collect())
+	}
+
+	/// Refer to [`IndexedStorage::try_insert_with_replacement_by`]
+	pub fn try_insert_with_replacement_by<F>(
+		&mut self,
+		max_total_bytes: usize,
+		key: K,
+		item: V,
+		can_evict: F,
+	) -> Option<Vec<(K, V)>>
+	where
+		F: Fn(&K, &V) -> bool,
+	{
 		let item_size = item.size();
 		let current_bytes = std::cmp::max(self.bytes.load(AtomicOrdering::Relaxed), 0) as usize;
 		let free_bytes = max_total_bytes - current_bytes;
-		let (removed_keys, removed_bytes) =
-			self.inner_guard.try_insert_with_replacement(free_bytes, key, item);
+		let (removed, removed_bytes) = self
+			.inner_guard
+			.try_insert_with_replacement_by(free_bytes, key, item, can_evict);
 
-		if let Some(ref removed_keys) = removed_keys {
+		if let Some(ref removed) = removed {
 			let delta = item_size as isize - removed_bytes as isize;
 			self.bytes.fetch_add(delta, AtomicOrdering::Relaxed);
-			self.length.fetch_sub(removed_keys.len() as isize, AtomicOrdering::Relaxed);
+			self.length.fetch_sub(removed.len() as isize, AtomicOrdering::Relaxed);
 			self.length.fetch_add(1, AtomicOrdering::Relaxed);
 		}
-		removed_keys
+		removed
 	}
 
 	/// Allows to mutate item for given key, if exists.
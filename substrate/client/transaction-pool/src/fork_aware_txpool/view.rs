@@ -264,6 +264,7 @@ where
 		options: graph::Options,
 		metrics: PrometheusMetrics,
 		is_validator: IsValidator,
+		eviction_rank: Option<graph::EvictionRank<ChainApi>>,
 	) -> (
 		Self,
 		DroppedMonitoringStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
@@ -278,6 +279,7 @@ where
 					is_validator,
 					api,
 					event_handler,
+					eviction_rank,
 				),
 				at,
 				revalidation_worker_channels: Mutex::from(None),
@@ -22,7 +22,7 @@
 
 use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc, time::Instant};
 
-use crate::LOG_TARGET;
+use crate::{fork_aware_txpool::TransactionRank, LOG_TARGET};
 use sc_transaction_pool_api::{error, InPoolTransaction, PoolStatus};
 use serde::Serialize;
 use sp_core::hexdisplay::HexDisplay;
@@ -458,12 +458,14 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
 	/// them. Technically the worst transaction should be evaluated by computing the entire pending
-	/// set. We use a simplified approach to remove transactions with the lowest priority first or
-	/// those that occupy the pool for the longest time in case priority is the same.
+	/// set. We use a simplified approach to remove the ready transactions with the lowest `rank`
+	/// first or those that occupy the pool for the longest time in case the rank is the same. The
+	/// future transactions of the lowest lane are removed first, oldest first within the lane.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
 		future: &Limit,
+		rank: impl Fn(&Transaction<Hash, Ex>) -> TransactionRank,
 	) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = vec![];
 
@@ -478,8 +480,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 							// while it prefers priority like need here, it also prefers older
 							// transactions for inclusion purposes and limit enforcement needs to
 							// prefer newer transactions instead and drop the older ones.
-							match worst.transaction.priority.cmp(&transaction.transaction.priority)
-							{
+							match rank(&worst.transaction).cmp(&rank(&transaction.transaction)) {
 								Ordering::Less => worst,
 								Ordering::Equal => {
 									if worst.insertion_id > transaction.insertion_id {
@@ -506,21 +507,27 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			let worst = self.future.fold(|worst, current| match worst {
 				None => Some(current.clone()),
 				Some(worst) => Some(
-					match (worst.transaction.source.timestamp, current.transaction.source.timestamp)
-					{
-						(Some(worst_timestamp), Some(current_timestamp)) => {
-							if worst_timestamp > current_timestamp {
-								current.clone()
-							} else {
-								worst
-							}
-						},
-						_ => {
-							if worst.imported_at > current.imported_at {
-								current.clone()
-							} else {
-								worst
-							}
+					match rank(&worst.transaction).lane.cmp(&rank(&current.transaction).lane) {
+						Ordering::Less => worst,
+						Ordering::Greater => current.clone(),
+						Ordering::Equal => match (
+							worst.transaction.source.timestamp,
+							current.transaction.source.timestamp,
+						) {
+							(Some(worst_timestamp), Some(current_timestamp)) => {
+								if worst_timestamp > current_timestamp {
+									current.clone()
+								} else {
+									worst
+								}
+							},
+							_ => {
+								if worst.imported_at > current.imported_at {
+									current.clone()
+								} else {
+									worst
+								}
+							},
 						},
 					},
 				),
@@ -1276,4 +1283,46 @@ source: TimedTransactionSource { source: External, timestamp: None }, requires:
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn enforce_limits_should_evict_by_rank() {
+		// given
+		let mut pool = pool();
+		// transactions with `data` starting with `1` are put in the higher lane
+		let rank = |tx: &Transaction<Hash, Vec<u8>>| {
+			TransactionRank::new((tx.data[0] == 1).into(), Some(tx.priority))
+		};
+		let ready = Limit { count: 2, total_bytes: 100 };
+		let future = Limit { count: 1, total_bytes: 100 };
+		for (data, hash, priority) in [(1u8, 1u64, 1u64), (2, 2, 10), (3, 3, 20)] {
+			pool.import(Transaction {
+				data: vec![data],
+				hash,
+				priority,
+				provides: vec![vec![data]],
+				..default_tx().clone()
+			})
+			.unwrap();
+		}
+		for (data, hash) in [(1u8, 4u64), (2, 5)] {
+			pool.import(Transaction {
+				data: vec![data],
+				hash,
+				requires: vec![vec![10 + data]],
+				provides: vec![vec![20 + data]],
+				..default_tx().clone()
+			})
+			.unwrap();
+		}
+
+		// when
+		let removed = pool.enforce_limits(&ready, &future, rank);
+
+		// then
+		// the lowest priority transaction is kept, because of its lane
+		let removed = removed.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+		assert_eq!(removed, vec![2, 5]);
+		assert_eq!(pool.ready().map(|tx| tx.hash).collect::<Vec<_>>(), vec![3, 1]);
+		assert_eq!(pool.futures().map(|tx| tx.hash).collect::<Vec<_>>(), vec![4]);
+	}
 }
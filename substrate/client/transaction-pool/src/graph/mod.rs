@@ -42,7 +42,7 @@ pub use self::pool::{
 	TransactionFor, ValidateTransactionPriority, ValidatedTransactionFor,
 };
 pub use validated_pool::{
-	BaseSubmitOutcome, EventDispatcher, IsValidator, ValidatedPoolSubmitOutcome,
+	BaseSubmitOutcome, EventDispatcher, EvictionRank, IsValidator, ValidatedPoolSubmitOutcome,
 	ValidatedTransaction,
 };
 
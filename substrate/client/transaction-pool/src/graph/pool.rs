@@ -38,7 +38,7 @@ use tracing::{debug, instrument, trace, Level};
 
 use super::{
 	base_pool as base,
-	validated_pool::{IsValidator, ValidatedPool, ValidatedTransaction},
+	validated_pool::{EvictionRank, IsValidator, ValidatedPool, ValidatedTransaction},
 	EventHandler, ValidatedPoolSubmitOutcome,
 };
 
@@ -213,11 +213,15 @@ impl<B: ChainApi, L: EventHandler<B>> Pool<B, L> {
 	}
 
 	/// Create a new transaction pool.
+	///
+	/// If provided, `eviction_rank` decides which transactions are removed first when the limits
+	/// of the pool are exceeded.
 	pub fn new_with_event_handler(
 		options: Options,
 		is_validator: IsValidator,
 		api: Arc<B>,
 		event_handler: L,
+		eviction_rank: Option<EvictionRank<B>>,
 	) -> Self {
 		Self {
 			validated_pool: Arc::new(ValidatedPool::new_with_event_handler(
@@ -225,6 +229,7 @@ impl<B: ChainApi, L: EventHandler<B>> Pool<B, L> {
 				is_validator,
 				api,
 				event_handler,
+				eviction_rank,
 			)),
 		}
 	}
@@ -20,6 +20,7 @@ use crate::{
 	common::{
 		sliding_stat::SyncDurationSlidingStats, tracing_log_xt::log_xt_trace, STAT_SLIDING_WINDOW,
 	},
+	fork_aware_txpool::TransactionRank,
 	insert_and_log_throttled_sync, LOG_TARGET,
 };
 use futures::channel::mpsc::{channel, Sender};
@@ -160,11 +161,18 @@ impl<B: ChainApi, W> BaseSubmitOutcome<B, W> {
 	}
 }
 
+/// Ranks the transactions of the pool when its limits are enforced.
+pub type EvictionRank<B> = Arc<
+	dyn Fn(&base::Transaction<ExtrinsicHash<B>, ExtrinsicFor<B>>) -> TransactionRank + Send + Sync,
+>;
+
 /// Pool that deals with validated transactions.
 pub struct ValidatedPool<B: ChainApi, L: EventHandler<B>> {
 	api: Arc<B>,
 	is_validator: IsValidator,
 	options: Options,
+	/// Ranks the transactions on eviction, by priority if not set.
+	eviction_rank: Option<EvictionRank<B>>,
 	event_dispatcher: RwLock<EventDispatcher<B, L>>,
 	pub(crate) pool: RwLock<base::BasePool<ExtrinsicHash<B>, ExtrinsicFor<B>>>,
 	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
@@ -178,6 +186,7 @@ impl<B: ChainApi, L: EventHandler<B>> Clone for ValidatedPool<B, L> {
 			api: self.api.clone(),
 			is_validator: self.is_validator.clone(),
 			options: self.options.clone(),
+			eviction_rank: self.eviction_rank.clone(),
 			event_dispatcher: Default::default(),
 			pool: RwLock::from(self.pool.read().clone()),
 			import_notification_sinks: Default::default(),
@@ -204,7 +213,7 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		api: Arc<B>,
 	) -> Self {
 		let ban_time = options.ban_time;
-		Self::new_with_rotator(options, is_validator, api, PoolRotator::new(ban_time), None)
+		Self::new_with_rotator(options, is_validator, api, PoolRotator::new(ban_time), None, None)
 	}
 
 	/// Create a new transaction pool.
@@ -217,15 +226,20 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			api,
 			PoolRotator::new_with_expected_size(ban_time, total_count),
 			None,
+			None,
 		)
 	}
 
 	/// Create a new transaction pool with given event handler.
+	///
+	/// If provided, `eviction_rank` decides which transactions are removed first when the limits
+	/// of the pool are exceeded.
 	pub fn new_with_event_handler(
 		options: Options,
 		is_validator: IsValidator,
 		api: Arc<B>,
 		event_handler: L,
+		eviction_rank: Option<EvictionRank<B>>,
 	) -> Self {
 		let ban_time = options.ban_time;
 		let total_count = options.total_count();
@@ -235,6 +249,7 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			api,
 			PoolRotator::new_with_expected_size(ban_time, total_count),
 			Some(event_handler),
+			eviction_rank,
 		)
 	}
 
@@ -244,11 +259,13 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		api: Arc<B>,
 		rotator: PoolRotator<ExtrinsicHash<B>>,
 		event_handler: Option<L>,
+		eviction_rank: Option<EvictionRank<B>>,
 	) -> Self {
 		let base_pool = base::BasePool::new(options.reject_future_transactions);
 		Self {
 			is_validator,
 			options,
+			eviction_rank,
 			event_dispatcher: RwLock::new(EventDispatcher::new_with_event_handler(event_handler)),
 			api,
 			pool: RwLock::new(base_pool),
@@ -412,7 +429,10 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			let removed = {
 				let mut pool = self.pool.write();
 				let removed = pool
-					.enforce_limits(ready_limit, future_limit)
+					.enforce_limits(ready_limit, future_limit, |tx| match &self.eviction_rank {
+						Some(rank) => rank(tx),
+						None => Some(tx.priority).into(),
+					})
 					.into_iter()
 					.map(|x| x.hash)
 					.collect::<HashSet<_>>();
@@ -53,6 +53,7 @@ sp_api::decl_runtime_apis! {
 	/// - `ProviderId`: Identifies the oracle provider or data source
 	/// - `Key`: The oracle key identifying the specific data feed
 	/// - `Value`: The oracle data value type
+	#[api_version(2)]
 	pub trait OracleApi<ProviderId, Key, Value> where
 		ProviderId: Codec,
 		Key: Codec,
@@ -88,5 +89,22 @@ sp_api::decl_runtime_apis! {
 		/// Returns a vector of `(Key, Option<Value>)` pairs representing all oracle
 		/// data available from the specified provider.
 		fn get_all_values(provider_id: ProviderId) -> Vec<(Key, Option<Value>)>;
+
+		/// Retrieves the recorded history of aggregated values for a given provider and key.
+		///
+		/// The history is only recorded by runtimes using a history-aware aggregation
+		/// strategy, and is bounded by the runtime configuration.
+		///
+		/// # Parameters
+		///
+		/// * `provider_id`: The oracle provider identifier
+		/// * `key`: The oracle key identifying the data feed
+		///
+		/// # Returns
+		///
+		/// Returns the aggregated values, oldest first, or an empty vector if no history
+		/// was recorded.
+		#[api_version(2)]
+		fn get_history(provider_id: ProviderId, key: Key) -> Vec<Value>;
 	}
 }
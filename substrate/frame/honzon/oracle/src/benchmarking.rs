@@ -35,11 +35,37 @@ mod benchmarks {
 		let caller: T::AccountId = whitelisted_caller();
 		T::Members::add(&caller);
 
-		let values = T::BenchmarkHelper::get_currency_id_value_pairs()[..x as usize]
+		let values: BoundedVec<_, _> = T::BenchmarkHelper::get_currency_id_value_pairs()
+			[..x as usize]
 			.to_vec()
 			.try_into()
 			.expect("Must succeed since at worst the length remained the same.");
 
+		// The other operators fed in the same block, so that enough raw values are combined.
+		for i in 1..T::MaxHasDispatchedSize::get() {
+			let member: T::AccountId = account("member", i, 0);
+			T::Members::add(&member);
+			assert_ok!(Oracle::<T, I>::feed_values(
+				RawOrigin::Signed(member).into(),
+				values.clone()
+			));
+		}
+
+		// A new spot value is combined and recorded in a full history.
+		for (key, value) in values.iter() {
+			Values::<T, I>::remove(key);
+			let history = (0..T::MaxHistoryLength::get())
+				.map(|timestamp| TimestampedValue {
+					value: value.clone(),
+					timestamp: timestamp.into(),
+				})
+				.collect::<Vec<_>>();
+			History::<T, I>::insert(
+				key,
+				BoundedVec::try_from(history).expect("Has exactly `MaxHistoryLength` entries."),
+			);
+		}
+
 		#[extrinsic_call]
 		_(RawOrigin::Signed(caller.clone()), values);
 
@@ -0,0 +1,132 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{CombineData, Config, History, MomentOf, Pallet, TimestampedValue, TimestampedValueOf};
+use frame_support::traits::{Get, Time};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AtLeast32BitUnsigned, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto},
+	Rounding,
+};
+use sp_std::{marker, prelude::*};
+
+/// Combines values with `Inner` and records every spot value in [`History`].
+///
+/// Like [`TwapCombineData`], a spot value is recorded at the current time whenever `Inner`
+/// combines one from the given values, and `Inner` is never given the previous value so that it
+/// can't be mistaken for a new spot value. Returns prev_value if `Inner` can't combine the values.
+pub struct HistoryCombineData<T, Inner, I = ()>(marker::PhantomData<(T, I, Inner)>);
+
+impl<T, I, Inner> CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>
+	for HistoryCombineData<T, Inner, I>
+where
+	T: Config<I>,
+	I: 'static,
+	Inner: CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>,
+{
+	fn combine_data(
+		key: &<T as Config<I>>::OracleKey,
+		values: Vec<TimestampedValueOf<T, I>>,
+		prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let Some(spot) = Inner::combine_data(key, values, None) else { return prev_value };
+		Pallet::<T, I>::record_history(
+			key,
+			TimestampedValue { value: spot.value.clone(), timestamp: T::Time::now() },
+		);
+		Some(spot)
+	}
+}
+
+/// Returns the time-weighted average of the spot values combined by `Inner` over the last
+/// `Window`.
+///
+/// Every spot value is recorded in [`History`] at the current time and holds until the next one,
+/// the latest one until now. The window therefore needs `MaxHistoryLength` to be large enough to
+/// cover it. A spot value only starts weighing in once time has passed, which makes the average
+/// resistant to a single manipulated update.
+///
+/// `Inner` is never given the previous value, as it is an average rather than a spot value.
+/// Returns prev_value if no spot value was ever recorded.
+pub struct TwapCombineData<T, Inner, Window, I = ()>(marker::PhantomData<(T, I, Inner, Window)>);
+
+impl<T, I, Inner, Window> CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>
+	for TwapCombineData<T, Inner, Window, I>
+where
+	T: Config<I>,
+	T::OracleValue: AtLeast32BitUnsigned,
+	I: 'static,
+	Inner: CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>,
+	Window: Get<MomentOf<T, I>>,
+{
+	fn combine_data(
+		key: &<T as Config<I>>::OracleKey,
+		values: Vec<TimestampedValueOf<T, I>>,
+		prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let now = T::Time::now();
+
+		if let Some(spot) = Inner::combine_data(key, values, None) {
+			Pallet::<T, I>::record_history(
+				key,
+				TimestampedValue { value: spot.value, timestamp: now },
+			);
+		}
+
+		let history = History::<T, I>::get(key);
+		let window_start = now.saturating_sub(Window::get());
+
+		let mut samples = Vec::with_capacity(history.len());
+		let mut total_duration = 0u128;
+		for (index, entry) in history.iter().enumerate() {
+			let end = history.get(index + 1).map_or(now, |next| next.timestamp);
+			let start = entry.timestamp.max(window_start);
+			if end <= start {
+				continue;
+			}
+
+			let duration: u128 = (end - start).unique_saturated_into();
+			total_duration = total_duration.saturating_add(duration);
+			samples.push((entry.value.clone(), duration));
+		}
+
+		// Only the latest spot value is in the window, but no time passed since it was recorded.
+		if total_duration == 0 {
+			return history
+				.last()
+				.map(|latest| TimestampedValue { value: latest.value.clone(), timestamp: now })
+				.or(prev_value);
+		}
+
+		let average = samples.into_iter().fold(0u128, |acc, (value, duration)| {
+			// Can't overflow as `duration <= total_duration`.
+			let weighted = multiply_by_rational_with_rounding(
+				value.unique_saturated_into(),
+				duration,
+				total_duration,
+				Rounding::NearestPrefDown,
+			)
+			.unwrap_or_default();
+			acc.saturating_add(weighted)
+		});
+
+		Some(TimestampedValue {
+			value: <T::OracleValue as UniqueSaturatedFrom<u128>>::unique_saturated_from(average),
+			timestamp: now,
+		})
+	}
+}
@@ -0,0 +1,98 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::incentives::Pallet as OracleIncentives;
+
+use frame_benchmarking::v2::*;
+
+use frame_support::{assert_ok, traits::fungible::Mutate};
+use frame_system::RawOrigin;
+
+fn funded_member<T: Config<I>, I: 'static>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	T::Members::add(&who);
+	let balance = T::Currency::minimum_balance()
+		.saturating_add(T::FeederBond::get().saturating_mul(10u32.into()));
+	T::Currency::set_balance(&who, balance);
+	who
+}
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn bond() {
+		let caller = funded_member::<T, I>("caller", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(OracleIncentives::<T, I>::bonded(&caller), T::FeederBond::get());
+	}
+
+	#[benchmark]
+	fn unbond() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let balance = T::Currency::minimum_balance()
+			.saturating_add(T::FeederBond::get().saturating_mul(10u32.into()));
+		T::Currency::set_balance(&caller, balance);
+		T::Currency::hold(&HoldReason::<I>::FeederBond.into(), &caller, T::FeederBond::get())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(OracleIncentives::<T, I>::bonded(&caller).is_zero());
+
+		Ok(())
+	}
+
+	// Worst case: every member is bonded, had stats and gets slashed, and the stats of `n`
+	// non-members are dropped on top of the ones of the members. At most `MaxMembers` stats of
+	// non-members are dropped by a round.
+	#[benchmark]
+	fn end_round(m: Linear<1, { T::MaxMembers::get() }>, n: Linear<0, { T::MaxMembers::get() }>) {
+		let pot = OracleIncentives::<T, I>::reward_pot();
+		T::Currency::set_balance(&pot, T::Currency::minimum_balance());
+
+		let members = (0..m).map(|i| funded_member::<T, I>("member", i)).collect::<Vec<_>>();
+		for who in &members {
+			assert_ok!(OracleIncentives::<T, I>::bond(RawOrigin::Signed(who.clone()).into()));
+			Strikes::<T, I>::insert(who, T::MaxStrikes::get().saturating_sub(1));
+			RoundStats::<T, I>::insert(who, FeederStats { submissions: 1, deviations: 1 });
+		}
+		for i in 0..n {
+			let who: T::AccountId = account("non-member", i, 0);
+			RoundStats::<T, I>::insert(who, FeederStats { submissions: 1, deviations: 0 });
+		}
+
+		#[block]
+		{
+			OracleIncentives::<T, I>::end_round();
+		}
+
+		assert_eq!(CurrentRound::<T, I>::get(), 1);
+		assert_eq!(RoundStats::<T, I>::iter().count(), 0);
+	}
+
+	impl_benchmark_test_suite! {
+		OracleIncentives,
+		crate::incentives::mock::new_test_ext(),
+		crate::incentives::mock::Test,
+	}
+}
@@ -0,0 +1,122 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as pallet_oracle;
+
+use crate::{
+	incentives,
+	mock::{Members, Timestamp},
+	DefaultCombineData,
+};
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+	PalletId,
+};
+use sp_runtime::{traits::IdentityLookup, BuildStorage, Perbill, Permill};
+
+pub use crate::mock::{set_members, AccountId};
+
+pub type Balance = u64;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+parameter_types! {
+	pub const OraclePalletId: PalletId = PalletId(*b"py/oracl");
+	pub const RewardPotId: PalletId = PalletId(*b"py/orinc");
+	pub const MaxDeviation: Permill = Permill::from_percent(10);
+	pub const SlashFraction: Perbill = Perbill::from_percent(50);
+}
+
+impl pallet_oracle::Config for Test {
+	type OnNewData = OracleIncentives;
+	type CombineData = DefaultCombineData<Self, ConstU32<1>, ConstU32<600>>;
+	type Time = Timestamp;
+	type OracleKey = u32;
+	type OracleValue = u32;
+	type PalletId = OraclePalletId;
+	type Members = Members;
+	type WeightInfo = ();
+	type MaxHasDispatchedSize = ConstU32<100>;
+	type MaxFeedValues = ConstU32<5>;
+	type MaxHistoryLength = ConstU32<10>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+impl incentives::Config for Test {
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type FeederBond = ConstU64<100>;
+	type RewardPerRound = ConstU64<10>;
+	type RoundLength = ConstU64<10>;
+	type MaxDeviation = MaxDeviation;
+	type MaxStrikes = ConstU32<2>;
+	type SlashFraction = SlashFraction;
+	type RewardPotId = RewardPotId;
+	type MaxMembers = ConstU32<4>;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Oracle: pallet_oracle,
+		OracleIncentives: incentives,
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(1, 1_000),
+			(2, 1_000),
+			(3, 1_000),
+			(4, 1_000),
+			(incentives::Pallet::<Test>::reward_pot(), 1_000),
+		],
+		..Default::default()
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut t: sp_io::TestExternalities = storage.into();
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		Timestamp::set_timestamp(12345);
+		set_members(vec![1, 2, 3]);
+	});
+
+	t
+}
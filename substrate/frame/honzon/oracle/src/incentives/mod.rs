@@ -0,0 +1,438 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Oracle Incentives
+//!
+//! An optional companion pallet that makes the oracle operators economically accountable.
+//!
+//! ## Overview
+//!
+//! Operators bond funds, held through [`fungible::MutateHold`] with [`HoldReason::FeederBond`].
+//! Every value they feed is compared to the aggregated value of its key, and the pallet keeps
+//! track of how many of their values deviated by more than [`Config::MaxDeviation`] during the
+//! current round.
+//!
+//! Every [`Config::RoundLength`] blocks, the round ends and a [`FeederStatus`] is reported for
+//! each operator:
+//! - [`FeederStatus::Accurate`]: the operator fed values, most of them close to the aggregate.
+//!   Bonded operators are paid [`Config::RewardPerRound`] from the reward pot, added to their bond.
+//! - [`FeederStatus::Deviated`]: most of the values fed by the operator deviated.
+//! - [`FeederStatus::Missed`]: the operator didn't feed any value.
+//!
+//! Deviating and missing rounds are counted as strikes, reset by an accurate round. Reaching
+//! [`Config::MaxStrikes`] consecutive strikes slashes [`Config::SlashFraction`] of the bond of
+//! the operator into the reward pot, which therefore funds the rewards together with any external
+//! top-up. The reward pot needs to hold at least the existential deposit to receive slashes.
+//!
+//! ## Usage
+//!
+//! Add this pallet to the runtime with the same instance as the oracle it accounts for, and set
+//! it as (part of) the oracle's [`crate::Config::OnNewData`] hook.
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use crate::{OnNewData, Values};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		fungible::{self, Inspect, InspectHold, MutateHold},
+		tokens::{Fortitude, Precision, Preservation, Restriction},
+		SortedMembers,
+	},
+	PalletId,
+};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, AtLeast32BitUnsigned, Saturating, UniqueSaturatedInto, Zero},
+	Perbill, Permill,
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+type BalanceOf<T, I = ()> =
+	<<T as Config<I>>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The values fed by an operator during the current round.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Debug,
+	Default,
+	Eq,
+	PartialEq,
+	Clone,
+	Copy,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct FeederStats {
+	/// The number of values fed.
+	pub submissions: u32,
+	/// The number of values that deviated from the aggregate.
+	pub deviations: u32,
+}
+
+impl FeederStats {
+	/// The status of an operator at the end of a round with these stats.
+	pub fn status(&self) -> FeederStatus {
+		if self.submissions == 0 {
+			FeederStatus::Missed
+		} else if self.deviations.saturating_mul(2) > self.submissions {
+			FeederStatus::Deviated
+		} else {
+			FeederStatus::Accurate
+		}
+	}
+}
+
+/// The status of an operator at the end of a round.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Debug,
+	Eq,
+	PartialEq,
+	Clone,
+	Copy,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum FeederStatus {
+	/// Most of the values fed were close to the aggregate.
+	Accurate,
+	/// Most of the values fed deviated from the aggregate.
+	Deviated,
+	/// No value was fed.
+	Missed,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>:
+		frame_system::Config + crate::Config<I, OracleValue: AtLeast32BitUnsigned>
+	{
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason<I>>;
+
+		/// The currency used for bonds, rewards and slashes.
+		type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+			+ fungible::Mutate<Self::AccountId>;
+
+		/// The amount held from an operator when it bonds.
+		#[pallet::constant]
+		type FeederBond: Get<BalanceOf<Self, I>>;
+
+		/// The reward paid to a bonded operator for an accurate round.
+		#[pallet::constant]
+		type RewardPerRound: Get<BalanceOf<Self, I>>;
+
+		/// The number of blocks in a round. Rounds are disabled if zero.
+		#[pallet::constant]
+		type RoundLength: Get<BlockNumberFor<Self>>;
+
+		/// The maximum deviation from the aggregate for a value to count as accurate.
+		#[pallet::constant]
+		type MaxDeviation: Get<Permill>;
+
+		/// The number of consecutive deviating or missed rounds after which an operator is
+		/// slashed.
+		#[pallet::constant]
+		type MaxStrikes: Get<u32>;
+
+		/// The fraction of the bond slashed once an operator reaches [`Config::MaxStrikes`].
+		#[pallet::constant]
+		type SlashFraction: Get<Perbill>;
+
+		/// The pallet ID, used to derive the account of the reward pot.
+		#[pallet::constant]
+		type RewardPotId: Get<PalletId>;
+
+		/// The maximum number of members of the oracle.
+		///
+		/// Also bounds the number of round stats of non-members, like former members or the
+		/// oracle's root account, dropped at the end of a round.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The sender is not a member of the oracle.
+		NotMember,
+		/// The sender is still a member of the oracle and can't unbond.
+		StillMember,
+		/// The sender has already bonded.
+		AlreadyBonded,
+		/// The sender has not bonded.
+		NotBonded,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// An operator bonded funds.
+		Bonded { who: T::AccountId, amount: BalanceOf<T, I> },
+		/// An operator got its bond back.
+		Unbonded { who: T::AccountId, amount: BalanceOf<T, I> },
+		/// The activity of an operator during a round that just ended.
+		FeederReported {
+			who: T::AccountId,
+			round: u32,
+			stats: FeederStats,
+			status: FeederStatus,
+			/// The consecutive deviating or missed rounds of the operator, including this one.
+			strikes: u32,
+		},
+		/// An operator was rewarded for an accurate round.
+		FeederRewarded { who: T::AccountId, amount: BalanceOf<T, I> },
+		/// An operator was slashed for reaching [`Config::MaxStrikes`].
+		FeederSlashed { who: T::AccountId, amount: BalanceOf<T, I> },
+	}
+
+	/// A reason for this pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason<I: 'static = ()> {
+		/// The funds are bonded by an oracle operator.
+		#[codec(index = 0)]
+		FeederBond,
+	}
+
+	/// The index of the current round.
+	#[pallet::storage]
+	pub type CurrentRound<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// The values fed by each operator during the current round.
+	#[pallet::storage]
+	pub type RoundStats<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, FeederStats, ValueQuery>;
+
+	/// The consecutive deviating or missed rounds of each operator.
+	#[pallet::storage]
+	pub type Strikes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let round_length = T::RoundLength::get();
+			if round_length.is_zero() || !(n % round_length).is_zero() {
+				return Weight::zero();
+			}
+
+			let (members, cleared) = Self::end_round();
+			<T as Config<I>>::WeightInfo::end_round(members, cleared)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Bond [`Config::FeederBond`] as an oracle operator.
+		///
+		/// The dispatch origin of this call must be signed by a member of the oracle. The bond
+		/// is held until the operator leaves the oracle and calls [`Pallet::unbond`].
+		///
+		/// Emits [`Event::Bonded`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::bond())]
+		pub fn bond(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::Members::contains(&who), Error::<T, I>::NotMember);
+			ensure!(Self::bonded(&who).is_zero(), Error::<T, I>::AlreadyBonded);
+
+			let amount = T::FeederBond::get();
+			T::Currency::hold(&HoldReason::<I>::FeederBond.into(), &who, amount)?;
+			Self::deposit_event(Event::Bonded { who, amount });
+			Ok(())
+		}
+
+		/// Release the bond of a former oracle operator, including its unslashed rewards.
+		///
+		/// The dispatch origin of this call must be signed by an account that bonded and is no
+		/// longer a member of the oracle.
+		///
+		/// Emits [`Event::Unbonded`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::unbond())]
+		pub fn unbond(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::Members::contains(&who), Error::<T, I>::StillMember);
+			ensure!(!Self::bonded(&who).is_zero(), Error::<T, I>::NotBonded);
+
+			let amount = T::Currency::release_all(
+				&HoldReason::<I>::FeederBond.into(),
+				&who,
+				Precision::Exact,
+			)?;
+			Strikes::<T, I>::remove(&who);
+			Self::deposit_event(Event::Unbonded { who, amount });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The account of the reward pot.
+	pub fn reward_pot() -> T::AccountId {
+		T::RewardPotId::get().into_account_truncating()
+	}
+
+	/// The bond currently held from an operator, including its rewards.
+	pub fn bonded(who: &T::AccountId) -> BalanceOf<T, I> {
+		T::Currency::balance_on_hold(&HoldReason::<I>::FeederBond.into(), who)
+	}
+
+	/// Ends the current round, reporting, rewarding and slashing the oracle members.
+	///
+	/// Returns the number of members and the number of round stats of non-members dropped.
+	pub(crate) fn end_round() -> (u32, u32) {
+		let round = CurrentRound::<T, I>::mutate(|round| {
+			let current = *round;
+			*round = round.saturating_add(1);
+			current
+		});
+
+		let members = T::Members::sorted_members();
+		let mut taken = 0u32;
+		for who in &members {
+			let stats = RoundStats::<T, I>::take(who);
+			// Stats are only stored once a value was fed.
+			if stats.submissions > 0 {
+				taken.saturating_inc();
+			}
+			let status = stats.status();
+			let strikes = if status == FeederStatus::Accurate {
+				Strikes::<T, I>::remove(who);
+				0
+			} else {
+				Strikes::<T, I>::mutate(who, |strikes| {
+					strikes.saturating_inc();
+					*strikes
+				})
+			};
+
+			Self::deposit_event(Event::FeederReported {
+				who: who.clone(),
+				round,
+				stats,
+				status,
+				strikes,
+			});
+
+			if status == FeederStatus::Accurate {
+				Self::reward(who);
+			} else if strikes >= T::MaxStrikes::get() {
+				Strikes::<T, I>::remove(who);
+				Self::slash(who);
+			}
+		}
+
+		// Drop the stats of the accounts that aren't members, such as the oracle's root account.
+		// The keys removed here are gone by the end of the next round, which drops the remaining
+		// ones, so no cursor needs to be kept. The keys of the members taken above are still
+		// iterated over and counted, so they are added to the limit and removed from the count.
+		// Only the members that had stats are added, so that at most `MaxMembers` stats of
+		// non-members are dropped, as benchmarked.
+		let limit = T::MaxMembers::get().saturating_add(taken);
+		let cleared = RoundStats::<T, I>::clear(limit, None).unique.saturating_sub(taken);
+
+		(members.len() as u32, cleared)
+	}
+
+	fn reward(who: &T::AccountId) {
+		if Self::bonded(who).is_zero() {
+			return;
+		}
+
+		// The pot may be short of funds, in which case it pays what it can.
+		let rewarded = T::Currency::transfer_and_hold(
+			&HoldReason::<I>::FeederBond.into(),
+			&Self::reward_pot(),
+			who,
+			T::RewardPerRound::get(),
+			Precision::BestEffort,
+			Preservation::Preserve,
+			Fortitude::Polite,
+		);
+		match rewarded {
+			Ok(amount) if !amount.is_zero() => {
+				Self::deposit_event(Event::FeederRewarded { who: who.clone(), amount })
+			},
+			_ => {},
+		}
+	}
+
+	fn slash(who: &T::AccountId) {
+		let amount = T::SlashFraction::get().mul_floor(Self::bonded(who));
+		if amount.is_zero() {
+			return;
+		}
+
+		let slashed = T::Currency::transfer_on_hold(
+			&HoldReason::<I>::FeederBond.into(),
+			who,
+			&Self::reward_pot(),
+			amount,
+			Precision::BestEffort,
+			Restriction::Free,
+			Fortitude::Force,
+		);
+		if let Ok(amount) = slashed {
+			Self::deposit_event(Event::FeederSlashed { who: who.clone(), amount });
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnNewData<T::AccountId, T::OracleKey, T::OracleValue>
+	for Pallet<T, I>
+{
+	fn on_new_data(who: &T::AccountId, key: &T::OracleKey, value: &T::OracleValue) {
+		// The aggregate already accounts for `value`, the oracle combines it before calling us.
+		let deviated = Values::<T, I>::get(key).is_some_and(|aggregate| {
+			let reference: u128 = aggregate.value.unique_saturated_into();
+			let value: u128 = value.clone().unique_saturated_into();
+			value.abs_diff(reference) > T::MaxDeviation::get().mul_floor(reference)
+		});
+
+		RoundStats::<T, I>::mutate(who, |stats| {
+			stats.submissions.saturating_inc();
+			if deviated {
+				stats.deviations.saturating_inc();
+			}
+		});
+	}
+}
@@ -0,0 +1,187 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::DataFeeder;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::*;
+
+fn feed(values: &[(AccountId, u32)]) {
+	for (who, value) in values {
+		assert_ok!(Oracle::feed_value(Some(*who), 50, *value));
+	}
+}
+
+fn reported(who: AccountId, round: u32, stats: (u32, u32), strikes: u32) {
+	let stats = FeederStats { submissions: stats.0, deviations: stats.1 };
+	System::assert_has_event(
+		Event::<Test>::FeederReported { who, round, stats, status: stats.status(), strikes }.into(),
+	);
+}
+
+#[test]
+fn bond_and_unbond_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(OracleIncentives::bond(RuntimeOrigin::signed(4)), Error::<Test>::NotMember);
+
+		assert_ok!(OracleIncentives::bond(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::<Test>::Bonded { who: 1, amount: 100 }.into());
+		assert_eq!(OracleIncentives::bonded(&1), 100);
+		assert_eq!(Balances::free_balance(1), 900);
+
+		assert_noop!(
+			OracleIncentives::bond(RuntimeOrigin::signed(1)),
+			Error::<Test>::AlreadyBonded
+		);
+		assert_noop!(
+			OracleIncentives::unbond(RuntimeOrigin::signed(1)),
+			Error::<Test>::StillMember
+		);
+
+		set_members(vec![2, 3]);
+		assert_ok!(OracleIncentives::unbond(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::<Test>::Unbonded { who: 1, amount: 100 }.into());
+		assert_eq!(OracleIncentives::bonded(&1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+
+		assert_noop!(OracleIncentives::unbond(RuntimeOrigin::signed(1)), Error::<Test>::NotBonded);
+	});
+}
+
+#[test]
+fn rounds_end_every_round_length_blocks() {
+	new_test_ext().execute_with(|| {
+		feed(&[(1, 1000)]);
+
+		assert_eq!(OracleIncentives::on_initialize(5), Weight::zero());
+		assert_eq!(RoundStats::<Test>::get(1), FeederStats { submissions: 1, deviations: 0 });
+		assert_eq!(CurrentRound::<Test>::get(), 0);
+
+		assert_eq!(OracleIncentives::on_initialize(10), <() as WeightInfo>::end_round(3, 0));
+		assert!(!RoundStats::<Test>::contains_key(1));
+		assert_eq!(CurrentRound::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn stats_of_non_members_are_dropped_up_to_max_members() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		for who in 10..20 {
+			RoundStats::<Test>::insert(who, FeederStats { submissions: 1, deviations: 0 });
+		}
+	});
+	// Only the removal of committed keys is limited and counted, as the rounds end in different
+	// blocks.
+	ext.commit_all().unwrap();
+
+	// up to `MaxMembers`, even though the members had no stats
+	ext.execute_with(|| {
+		assert_eq!(OracleIncentives::on_initialize(10), <() as WeightInfo>::end_round(3, 4));
+		assert_eq!(RoundStats::<Test>::iter().count(), 6);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		assert_eq!(OracleIncentives::on_initialize(20), <() as WeightInfo>::end_round(3, 4));
+		assert_eq!(RoundStats::<Test>::iter().count(), 2);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		assert_eq!(OracleIncentives::on_initialize(30), <() as WeightInfo>::end_round(3, 2));
+		assert_eq!(RoundStats::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn stats_of_members_do_not_count_against_the_limit() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		for who in (1..4).chain(10..14) {
+			RoundStats::<Test>::insert(who, FeederStats { submissions: 1, deviations: 0 });
+		}
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		assert_eq!(OracleIncentives::end_round(), (3, 4));
+		assert_eq!(RoundStats::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn accurate_feeders_are_rewarded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OracleIncentives::bond(RuntimeOrigin::signed(1)));
+		assert_ok!(OracleIncentives::bond(RuntimeOrigin::signed(3)));
+
+		// 3 deviates from the median of 1010
+		feed(&[(1, 1000), (2, 1010), (3, 2000)]);
+		OracleIncentives::on_initialize(10);
+
+		reported(1, 0, (1, 0), 0);
+		reported(2, 0, (1, 0), 0);
+		reported(3, 0, (1, 1), 1);
+
+		// 2 is accurate but hasn't bonded
+		System::assert_has_event(Event::<Test>::FeederRewarded { who: 1, amount: 10 }.into());
+		assert_eq!(OracleIncentives::bonded(&1), 110);
+		assert_eq!(OracleIncentives::bonded(&2), 0);
+		assert_eq!(OracleIncentives::bonded(&3), 100);
+		assert_eq!(Balances::free_balance(OracleIncentives::reward_pot()), 990);
+	});
+}
+
+#[test]
+fn persistent_deviators_and_no_shows_are_slashed() {
+	new_test_ext().execute_with(|| {
+		set_members(vec![1, 2, 3, 4]);
+		for who in 1..=4 {
+			assert_ok!(OracleIncentives::bond(RuntimeOrigin::signed(who)));
+		}
+
+		// 3 deviates and 4 doesn't show up, twice in a row
+		feed(&[(1, 1000), (2, 1010), (3, 2000)]);
+		OracleIncentives::on_initialize(10);
+		reported(3, 0, (1, 1), 1);
+		reported(4, 0, (0, 0), 1);
+
+		feed(&[(1, 1000), (2, 1010), (3, 2000)]);
+		OracleIncentives::on_initialize(20);
+		reported(3, 1, (1, 1), 2);
+		reported(4, 1, (0, 0), 2);
+
+		System::assert_has_event(Event::<Test>::FeederSlashed { who: 3, amount: 50 }.into());
+		System::assert_has_event(Event::<Test>::FeederSlashed { who: 4, amount: 50 }.into());
+		assert_eq!(OracleIncentives::bonded(&3), 50);
+		assert_eq!(OracleIncentives::bonded(&4), 50);
+		assert_eq!(Strikes::<Test>::get(3), 0);
+		// Two rewards of 10 per round, and two slashes of 50.
+		assert_eq!(Balances::free_balance(OracleIncentives::reward_pot()), 1_060);
+
+		// an accurate round resets the strikes
+		feed(&[(1, 1000), (2, 1010), (3, 1020)]);
+		OracleIncentives::on_initialize(30);
+		reported(3, 2, (1, 0), 0);
+		reported(4, 2, (0, 0), 1);
+		feed(&[(1, 1000), (2, 1010), (3, 2000)]);
+		OracleIncentives::on_initialize(40);
+		reported(3, 3, (1, 1), 1);
+		assert_eq!(OracleIncentives::bonded(&3), 60);
+	});
+}
@@ -65,6 +65,10 @@
 //! - [`RawValues`]: Stores individual operator submissions with timestamps
 //! - [`Values`]: Stores the final aggregated values after processing
 //!
+//! The history-aware aggregation strategies additionally record the spot values combined from
+//! the operator submissions in [`History`], which allows time-weighted averages and exposes past
+//! values to clients.
+//!
 //! This design allows for:
 //! - Historical tracking of individual operator submissions
 //! - Efficient access to final aggregated values
@@ -98,8 +102,17 @@ mod benchmarking;
 
 mod default_combine_data;
 pub use default_combine_data::DefaultCombineData;
+mod history_combine_data;
+pub use history_combine_data::{HistoryCombineData, TwapCombineData};
+mod outlier_rejection_combine_data;
+pub use outlier_rejection_combine_data::OutlierRejectionCombineData;
+mod weighted_median_combine_data;
+pub use weighted_median_combine_data::WeightedMedianCombineData;
 pub mod traits;
-pub use traits::{CombineData, DataFeeder, DataProvider, DataProviderExtended, OnNewData};
+pub use traits::{
+	CombineData, DataFeeder, DataProvider, DataProviderExtended, OnNewData, OperatorWeight,
+};
+pub mod incentives;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -217,6 +230,13 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxFeedValues: Get<u32>;
 
+		/// The maximum number of spot values kept in [`History`] for each key.
+		///
+		/// Only used by the [`CombineData`] implementations that record history, such as
+		/// [`TwapCombineData`] and [`HistoryCombineData`]. Once full, the oldest entry is dropped.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+
 		/// A helper trait for benchmarking oracle operations.
 		///
 		/// Provides sample data for benchmarking the oracle pallet, allowing accurate
@@ -282,6 +302,23 @@ pub mod pallet {
 	pub type Values<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>;
 
+	/// The history of spot values for each oracle key, oldest first.
+	///
+	/// A spot value is the value combined from the raw values of the operators, timestamped with
+	/// the time it was combined at. Both history-aware [`CombineData`] implementations record one
+	/// every time the raw values are combined, i.e. on every feed of a key that has enough valid
+	/// raw values, so the history is the same whichever of them is used: [`HistoryCombineData`]
+	/// stores the latest spot value in [`Values`] while [`TwapCombineData`] stores their
+	/// time-weighted average. Bounded by [`Config::MaxHistoryLength`].
+	#[pallet::storage]
+	pub type History<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		<T as Config<I>>::OracleKey,
+		BoundedVec<TimestampedValueOf<T, I>, T::MaxHistoryLength>,
+		ValueQuery,
+	>;
+
 	/// A set of accounts that have already fed data in the current block.
 	///
 	/// This storage item tracks which oracle operators have already submitted data in the
@@ -321,6 +358,11 @@ pub mod pallet {
 		pub fn all_values() -> Vec<(T::OracleKey, TimestampedValueOf<T, I>)> {
 			<Values<T, I>>::iter().collect()
 		}
+
+		/// Retrieve the recorded history of spot values for a specific key, oldest first.
+		pub fn history(key: T::OracleKey) -> Vec<TimestampedValueOf<T, I>> {
+			<History<T, I>>::get(&key).into_inner()
+		}
 	}
 
 	#[pallet::call]
@@ -392,6 +434,34 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			.collect()
 	}
 
+	/// Reads the raw values for a given key from all oracle members, along with the account
+	/// that fed each of them.
+	pub fn read_raw_values_by_operator(
+		key: &T::OracleKey,
+	) -> Vec<(T::AccountId, TimestampedValueOf<T, I>)> {
+		T::Members::sorted_members()
+			.into_iter()
+			.chain([Self::get_pallet_account()])
+			.filter_map(|who| Self::raw_values(&who, key).map(|value| (who, value)))
+			.collect()
+	}
+
+	/// Appends a spot value to the history of a given key.
+	///
+	/// A value with the same timestamp as the latest entry replaces it, so the history holds at
+	/// most one entry per timestamp. The oldest entry is dropped when the history is full.
+	pub fn record_history(key: &T::OracleKey, value: TimestampedValueOf<T, I>) {
+		<History<T, I>>::mutate(key, |history| {
+			if history.last().is_some_and(|last| last.timestamp == value.timestamp) {
+				history.pop();
+			} else if history.is_full() && !history.is_empty() {
+				history.remove(0);
+			}
+			// Only fails if `MaxHistoryLength` is zero, in which case nothing is recorded.
+			let _ = history.try_push(value);
+		});
+	}
+
 	/// Returns the aggregated and timestamped value for a given key.
 	pub fn get(key: &T::OracleKey) -> Option<TimestampedValueOf<T, I>> {
 		Self::values(key)
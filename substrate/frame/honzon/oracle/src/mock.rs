@@ -87,6 +87,7 @@ impl Config for Test {
 	type WeightInfo = ();
 	type MaxHasDispatchedSize = ConstU32<100>;
 	type MaxFeedValues = MaxFeedValues;
+	type MaxHistoryLength = ConstU32<10>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
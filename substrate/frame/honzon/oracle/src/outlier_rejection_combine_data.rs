@@ -0,0 +1,67 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{traits::median, CombineData, Config, MomentOf, TimestampedValueOf};
+use frame_support::traits::{Get, Time};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Saturating, UniqueSaturatedInto},
+	Permill,
+};
+use sp_std::{marker, prelude::*};
+
+/// Drops the values deviating from the median of the fresh values by more than `MaxDeviation`,
+/// then combines the remaining ones with `Inner`.
+///
+/// Expired values are dropped as well, so they don't skew the reference median after a genuine
+/// move of the underlying data.
+pub struct OutlierRejectionCombineData<T, Inner, MaxDeviation, ExpiresIn, I = ()>(
+	marker::PhantomData<(T, I, Inner, MaxDeviation, ExpiresIn)>,
+);
+
+impl<T, I, Inner, MaxDeviation, ExpiresIn>
+	CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>
+	for OutlierRejectionCombineData<T, Inner, MaxDeviation, ExpiresIn, I>
+where
+	T: Config<I>,
+	T::OracleValue: AtLeast32BitUnsigned,
+	I: 'static,
+	Inner: CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>,
+	MaxDeviation: Get<Permill>,
+	ExpiresIn: Get<MomentOf<T, I>>,
+{
+	fn combine_data(
+		key: &<T as Config<I>>::OracleKey,
+		mut values: Vec<TimestampedValueOf<T, I>>,
+		prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let expires_in = ExpiresIn::get();
+		let now = T::Time::now();
+
+		values.retain(|x| x.timestamp.saturating_add(expires_in) > now);
+
+		if let Some(reference) = median(values.iter().map(|x| x.value.clone()).collect()) {
+			let reference: u128 = reference.unique_saturated_into();
+			let max_deviation = MaxDeviation::get().mul_floor(reference);
+			values.retain(|x| {
+				let value: u128 = x.value.clone().unique_saturated_into();
+				value.abs_diff(reference) <= max_deviation
+			});
+		}
+
+		Inner::combine_data(key, values, prev_value)
+	}
+}
@@ -16,8 +16,9 @@
 // limitations under the License.
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, parameter_types, traits::ConstU32};
 use mock::*;
+use sp_runtime::Permill;
 
 #[test]
 fn should_feed_values_from_member() {
@@ -339,3 +340,199 @@ fn values_are_updated_on_feed() {
 		);
 	});
 }
+
+parameter_types! {
+	pub const MaxDeviation: Permill = Permill::from_percent(10);
+}
+
+type MedianOfOne = DefaultCombineData<Test, ConstU32<1>, ConstU32<600>>;
+
+pub struct AccountIdAsWeight;
+
+impl OperatorWeight<AccountId> for AccountIdAsWeight {
+	fn weight(who: &AccountId) -> u128 {
+		*who
+	}
+}
+
+#[test]
+fn record_history_should_work() {
+	new_test_ext().execute_with(|| {
+		let value = |value, timestamp| TimestampedValue { value, timestamp };
+
+		ModuleOracle::record_history(&50, value(1000, 1));
+		ModuleOracle::record_history(&50, value(1100, 1));
+		assert_eq!(ModuleOracle::history(50), vec![value(1100, 1)]);
+
+		for timestamp in 2..=11 {
+			ModuleOracle::record_history(&50, value(1000 + timestamp, timestamp));
+		}
+		let history = ModuleOracle::history(50);
+		assert_eq!(history.len(), 10);
+		assert_eq!(history.first(), Some(&value(1002, 2)));
+		assert_eq!(history.last(), Some(&value(1011, 11)));
+
+		assert_eq!(ModuleOracle::history(51), vec![]);
+	});
+}
+
+#[test]
+fn history_combine_data_should_work() {
+	new_test_ext().execute_with(|| {
+		type Combine = HistoryCombineData<Test, MedianOfOne>;
+
+		assert_eq!(Combine::combine_data(&50, vec![], None), None);
+		assert_eq!(ModuleOracle::history(50), vec![]);
+
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 1000));
+		let combined = Combine::combine_data(&50, ModuleOracle::read_raw_values(&50), None);
+		assert_eq!(combined, Some(TimestampedValue { value: 1000, timestamp: 12345 }));
+		assert_eq!(ModuleOracle::history(50), vec![combined.unwrap()]);
+
+		// the previous value isn't a new spot value
+		Timestamp::set_timestamp(12395);
+		assert_eq!(Combine::combine_data(&50, vec![], combined), combined);
+		assert_eq!(ModuleOracle::history(50), vec![combined.unwrap()]);
+
+		// spot values are recorded at the time they are combined, like with TWAP
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 2000));
+		Timestamp::set_timestamp(12445);
+		assert_eq!(
+			Combine::combine_data(&50, ModuleOracle::read_raw_values(&50), combined),
+			Some(TimestampedValue { value: 2000, timestamp: 12395 })
+		);
+		assert_eq!(
+			ModuleOracle::history(50),
+			vec![combined.unwrap(), TimestampedValue { value: 2000, timestamp: 12445 }]
+		);
+	});
+}
+
+#[test]
+fn twap_combine_data_should_work() {
+	new_test_ext().execute_with(|| {
+		type Twap = TwapCombineData<Test, MedianOfOne, ConstU32<100>>;
+		let twap = || Twap::combine_data(&50, ModuleOracle::read_raw_values(&50), None);
+
+		assert_eq!(twap(), None);
+
+		// a single spot value is returned as is
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 1000));
+		assert_eq!(twap(), Some(TimestampedValue { value: 1000, timestamp: 12345 }));
+
+		// a new spot value doesn't weigh in until time passes
+		Timestamp::set_timestamp(12395);
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 2000));
+		assert_eq!(twap(), Some(TimestampedValue { value: 1000, timestamp: 12395 }));
+
+		Timestamp::set_timestamp(12445);
+		assert_eq!(twap(), Some(TimestampedValue { value: 1500, timestamp: 12445 }));
+
+		// samples older than the window are ignored
+		Timestamp::set_timestamp(12545);
+		assert_eq!(twap(), Some(TimestampedValue { value: 2000, timestamp: 12545 }));
+
+		assert_eq!(
+			ModuleOracle::history(50)
+				.into_iter()
+				.map(|x| (x.value, x.timestamp))
+				.collect::<Vec<_>>(),
+			vec![(1000, 12345), (2000, 12395), (2000, 12445), (2000, 12545)]
+		);
+	});
+}
+
+#[test]
+fn history_is_the_same_with_both_strategies() {
+	fn history<C>() -> Vec<TimestampedValueOf<Test>>
+	where
+		C: CombineData<u32, TimestampedValueOf<Test>>,
+	{
+		new_test_ext().execute_with(|| {
+			let mut prev_value = None;
+			for (timestamp, value) in [(12345, Some(1000)), (12395, None), (12445, Some(2000))] {
+				Timestamp::set_timestamp(timestamp);
+				if let Some(value) = value {
+					assert_ok!(ModuleOracle::feed_value(Some(1), 50, value));
+				}
+				// combined twice, with and without the raw values
+				C::combine_data(&50, vec![], prev_value);
+				prev_value = C::combine_data(&50, ModuleOracle::read_raw_values(&50), prev_value);
+			}
+			ModuleOracle::history(50)
+		})
+	}
+
+	assert_eq!(
+		history::<HistoryCombineData<Test, MedianOfOne>>(),
+		history::<TwapCombineData<Test, MedianOfOne, ConstU32<100>>>()
+	);
+	assert_eq!(history::<HistoryCombineData<Test, MedianOfOne>>().len(), 3);
+}
+
+#[test]
+fn weighted_median_combine_data_should_work() {
+	new_test_ext().execute_with(|| {
+		type Median = WeightedMedianCombineData<Test, (), ConstU32<3>, ConstU32<600>>;
+		type WeightedMedian =
+			WeightedMedianCombineData<Test, AccountIdAsWeight, ConstU32<3>, ConstU32<600>>;
+
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 1000));
+		assert_ok!(ModuleOracle::feed_value(Some(2), 50, 1100));
+		assert_ok!(ModuleOracle::feed_value(Some(3), 50, 2000));
+		let values = ModuleOracle::read_raw_values(&50);
+
+		assert_eq!(
+			Median::combine_data(&50, values.clone(), None),
+			Some(TimestampedValue { value: 1100, timestamp: 12345 })
+		);
+		assert_eq!(
+			WeightedMedian::combine_data(&50, values, None),
+			Some(TimestampedValue { value: 2000, timestamp: 12345 })
+		);
+
+		// operators with a zero weight are ignored
+		set_members(vec![0, 1, 2]);
+		assert_ok!(ModuleOracle::feed_value(Some(0), 51, 5000));
+		assert_ok!(ModuleOracle::feed_value(Some(1), 51, 1000));
+		assert_ok!(ModuleOracle::feed_value(Some(2), 51, 1100));
+		let values = ModuleOracle::read_raw_values(&51);
+		let prev_value = Some(TimestampedValue { value: 900, timestamp: 0 });
+
+		assert_eq!(WeightedMedian::combine_data(&51, values.clone(), prev_value), prev_value);
+		assert_eq!(
+			WeightedMedianCombineData::<Test, AccountIdAsWeight, ConstU32<2>, ConstU32<600>>::combine_data(
+				&51, values, None
+			),
+			Some(TimestampedValue { value: 1100, timestamp: 12345 })
+		);
+	});
+}
+
+#[test]
+fn outlier_rejection_combine_data_should_work() {
+	new_test_ext().execute_with(|| {
+		type Combine = OutlierRejectionCombineData<
+			Test,
+			DefaultCombineData<Test, ConstU32<2>, ConstU32<600>>,
+			MaxDeviation,
+			ConstU32<600>,
+		>;
+		let combine = || Combine::combine_data(&50, ModuleOracle::read_raw_values(&50), None);
+
+		set_members(vec![1, 2, 3, 4, 5]);
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 1000));
+		assert_ok!(ModuleOracle::feed_value(Some(2), 50, 1050));
+		assert_ok!(ModuleOracle::feed_value(Some(3), 50, 5000));
+		assert_eq!(combine(), Some(TimestampedValue { value: 1050, timestamp: 12345 }));
+
+		assert_ok!(ModuleOracle::feed_value(Some(5), 50, 990));
+		assert_eq!(combine(), Some(TimestampedValue { value: 1000, timestamp: 12345 }));
+
+		// expired values don't drag the reference median away from the fresh ones
+		Timestamp::set_timestamp(13000);
+		assert_ok!(ModuleOracle::feed_value(Some(3), 50, 2000));
+		assert_ok!(ModuleOracle::feed_value(Some(4), 50, 2100));
+		assert_eq!(combine(), Some(TimestampedValue { value: 2100, timestamp: 13000 }));
+	});
+}
@@ -91,6 +91,21 @@ pub trait CombineData<Key, TimestampedValue> {
 	) -> Option<TimestampedValue>;
 }
 
+/// Provides the weight of an oracle operator, e.g. derived from its stake or reputation.
+///
+/// Used by [`crate::WeightedMedianCombineData`]. The unit implementation gives every operator
+/// the same weight.
+pub trait OperatorWeight<AccountId> {
+	/// Returns the weight of the given operator. Operators with a zero weight are ignored.
+	fn weight(who: &AccountId) -> u128;
+}
+
+impl<AccountId> OperatorWeight<AccountId> for () {
+	fn weight(_who: &AccountId) -> u128 {
+		1
+	}
+}
+
 /// A handler for new data events.
 #[impl_trait_for_tuples::impl_for_tuples(30)]
 pub trait OnNewData<AccountId, Key, Value> {
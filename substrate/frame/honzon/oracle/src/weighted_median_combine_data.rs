@@ -0,0 +1,87 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{CombineData, Config, MomentOf, OperatorWeight, Pallet, TimestampedValueOf};
+use frame_support::traits::{Get, Time};
+use sp_runtime::traits::Saturating;
+use sp_std::{marker, prelude::*};
+
+/// Sort by value and returns the weighted median timestamped value, where each value is weighted
+/// by the [`OperatorWeight`] of the operator that fed it.
+///
+/// Values are matched back to their operators through [`Pallet::read_raw_values_by_operator`],
+/// so this can be wrapped by filters such as [`crate::OutlierRejectionCombineData`]. With equal
+/// weights, the result is the same as [`crate::DefaultCombineData`].
+///
+/// Returns prev_value if not enough valid values or if all of them have a zero weight.
+pub struct WeightedMedianCombineData<T, Weights, MinimumCount, ExpiresIn, I = ()>(
+	marker::PhantomData<(T, I, Weights, MinimumCount, ExpiresIn)>,
+);
+
+impl<T, I, Weights, MinimumCount, ExpiresIn>
+	CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>
+	for WeightedMedianCombineData<T, Weights, MinimumCount, ExpiresIn, I>
+where
+	T: Config<I>,
+	I: 'static,
+	Weights: OperatorWeight<T::AccountId>,
+	MinimumCount: Get<u32>,
+	ExpiresIn: Get<MomentOf<T, I>>,
+{
+	fn combine_data(
+		key: &<T as Config<I>>::OracleKey,
+		mut values: Vec<TimestampedValueOf<T, I>>,
+		prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let expires_in = ExpiresIn::get();
+		let now = T::Time::now();
+
+		values.retain(|x| x.timestamp.saturating_add(expires_in) > now);
+
+		// Each value is consumed by at most one operator, so duplicated values keep their own
+		// weight.
+		let mut weighted = Vec::with_capacity(values.len());
+		for (who, value) in Pallet::<T, I>::read_raw_values_by_operator(key) {
+			if let Some(index) = values.iter().position(|x| *x == value) {
+				values.swap_remove(index);
+				let weight = Weights::weight(&who);
+				if weight > 0 {
+					weighted.push((value, weight));
+				}
+			}
+		}
+
+		let count = weighted.len() as u32;
+		let minimum_count = MinimumCount::get();
+		if count < minimum_count || count == 0 {
+			return prev_value;
+		}
+
+		weighted.sort_by(|(a, _), (b, _)| a.value.cmp(&b.value));
+		let total = weighted.iter().fold(0u128, |acc, (_, weight)| acc.saturating_add(*weight));
+
+		let mut accumulated = 0u128;
+		for (value, weight) in weighted {
+			accumulated = accumulated.saturating_add(weight);
+			if accumulated > total.saturating_sub(accumulated) {
+				return Some(value);
+			}
+		}
+
+		prev_value
+	}
+}
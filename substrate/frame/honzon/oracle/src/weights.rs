@@ -17,10 +17,10 @@
 
 //! Autogenerated weights for `pallet_oracle`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 50.0.0
-//! DATE: 2025-09-23, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
+//! DATE: 2026-10-18, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Mac.lan`, CPU: `<UNKNOWN>`
+//! HOSTNAME: `vm`, CPU: `Intel(R) Xeon(R) Processor`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: `1024`
 
 // Executed Command:
@@ -28,7 +28,7 @@
 // v1
 // benchmark
 // pallet
-// --extrinsic=all
+// --extrinsic=*
 // --runtime=target/production/wbuild/kitchensink-runtime/kitchensink_runtime.wasm
 // --pallet=pallet_oracle
 // --header=substrate/HEADER-APACHE2
@@ -41,8 +41,7 @@
 // --no-storage-info
 // --no-min-squares
 // --no-median-slopes
-// --genesis-builder-policy=none
-// --exclude-pallets=pallet_xcm,pallet_xcm_benchmarks::fungible,pallet_xcm_benchmarks::generic,pallet_nomination_pools,pallet_remark,pallet_transaction_storage,pallet_election_provider_multi_block,pallet_election_provider_multi_block::signed,pallet_election_provider_multi_block::unsigned,pallet_election_provider_multi_block::verifier
+// --exclude-pallets=pallet_xcm,pallet_xcm_benchmarks::fungible,pallet_xcm_benchmarks::generic,pallet_nomination_pools,pallet_remark,pallet_transaction_storage
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -68,24 +67,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Oracle::HasDispatched` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
 	/// Storage: `Timestamp::Now` (r:1 w:0)
 	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	/// Storage: `Oracle::RawValues` (r:10 w:10)
+	/// Storage: `Oracle::RawValues` (r:200 w:10)
 	/// Proof: `Oracle::RawValues` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
-	/// Storage: `Oracle::Values` (r:10 w:0)
+	/// Storage: `Oracle::Values` (r:10 w:10)
 	/// Proof: `Oracle::Values` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Oracle::History` (r:10 w:10)
+	/// Proof: `Oracle::History` (`max_values`: None, `max_size`: Some(2414), added: 4889, mode: `MaxEncodedLen`)
 	/// The range of component `x` is `[0, 10]`.
 	fn feed_values(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `3`
-		//  Estimated: `4687 + x * (2551 ±0)`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(6_049_545, 4687)
-			// Standard Error: 9_666
-			.saturating_add(Weight::from_parts(5_167_799, 0).saturating_mul(x.into()))
+		//  Measured:  `2486 + x * (3196 ±0)`
+		//  Estimated: `4687 + x * (51020 ±0)`
+		// Minimum execution time: 30_253_000 picoseconds.
+		Weight::from_parts(104_352_291, 4687)
+			// Standard Error: 381_593
+			.saturating_add(Weight::from_parts(158_420_844, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().reads((22_u64).saturating_mul(x.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
-			.saturating_add(Weight::from_parts(0, 2551).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 51020).saturating_mul(x.into()))
 	}
 	/// Storage: `Oracle::HasDispatched` (r:0 w:1)
 	/// Proof: `Oracle::HasDispatched` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
@@ -93,8 +94,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
-		// Minimum execution time: 0_000 picoseconds.
-		Weight::from_parts(1_000_000, 0)
+		// Minimum execution time: 1_398_000 picoseconds.
+		Weight::from_parts(3_019_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 }
@@ -107,24 +108,26 @@ impl WeightInfo for () {
 	/// Proof: `Oracle::HasDispatched` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
 	/// Storage: `Timestamp::Now` (r:1 w:0)
 	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	/// Storage: `Oracle::RawValues` (r:10 w:10)
+	/// Storage: `Oracle::RawValues` (r:200 w:10)
 	/// Proof: `Oracle::RawValues` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
-	/// Storage: `Oracle::Values` (r:10 w:0)
+	/// Storage: `Oracle::Values` (r:10 w:10)
 	/// Proof: `Oracle::Values` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Oracle::History` (r:10 w:10)
+	/// Proof: `Oracle::History` (`max_values`: None, `max_size`: Some(2414), added: 4889, mode: `MaxEncodedLen`)
 	/// The range of component `x` is `[0, 10]`.
 	fn feed_values(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `3`
-		//  Estimated: `4687 + x * (2551 ±0)`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(6_049_545, 4687)
-			// Standard Error: 9_666
-			.saturating_add(Weight::from_parts(5_167_799, 0).saturating_mul(x.into()))
+		//  Measured:  `2486 + x * (3196 ±0)`
+		//  Estimated: `4687 + x * (51020 ±0)`
+		// Minimum execution time: 30_253_000 picoseconds.
+		Weight::from_parts(104_352_291, 4687)
+			// Standard Error: 381_593
+			.saturating_add(Weight::from_parts(158_420_844, 0).saturating_mul(x.into()))
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(x.into())))
+			.saturating_add(RocksDbWeight::get().reads((22_u64).saturating_mul(x.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
-			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x.into())))
-			.saturating_add(Weight::from_parts(0, 2551).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 51020).saturating_mul(x.into()))
 	}
 	/// Storage: `Oracle::HasDispatched` (r:0 w:1)
 	/// Proof: `Oracle::HasDispatched` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
@@ -132,8 +135,8 @@ impl WeightInfo for () {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
-		// Minimum execution time: 0_000 picoseconds.
-		Weight::from_parts(1_000_000, 0)
+		// Minimum execution time: 1_398_000 picoseconds.
+		Weight::from_parts(3_019_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 }
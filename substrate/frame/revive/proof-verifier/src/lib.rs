@@ -0,0 +1,339 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Offline verification of the [`AccountProof`] returned by the pallet-revive `eth_getProof`
+//! JSON-RPC method.
+//!
+//! The proofs are Substrate trie proofs, checked against the state root of the block header the
+//! proof was requested at. The verifier recomputes every storage key from the proven address and
+//! slots, so that a proof can only be accepted for the values it claims:
+//!
+//! - the `nonce` is read from the `System::Account` entry of the account id mapped to the address,
+//!   using the `OriginalAccount` entry of the pallet, or the fallback account id if there is none.
+//! - the `code_hash` and the contract child trie are read from the `AccountInfoOf` entry.
+//! - the `storage_hash` is the root of the contract child trie, read from the main trie.
+//! - each storage slot value is read from the contract child trie, rooted at `storage_hash`.
+//!
+//! The `balance` is not verified: it is derived by the runtime from the `System::Account` data,
+//! the existential deposit and the native to EVM denomination ratio, which are runtime specific.
+
+use codec::Decode;
+use pallet_revive::evm::{AccountProof, Bytes};
+use sp_core::{Blake2Hasher, H256, U256, storage::ChildInfo};
+use sp_crypto_hashing::{blake2_128, blake2_256, keccak_256, twox_128};
+use sp_trie::{LayoutV1, MemoryDB, StorageProof, read_trie_value};
+
+/// The name under which pallet-revive is declared in the runtime, in the polkadot-sdk runtimes.
+pub const DEFAULT_PALLET_NAME: &str = "Revive";
+
+/// The errors that can occur when verifying a proof.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum Error {
+	/// The proof does not contain the nodes needed to read a value.
+	#[error("incomplete or invalid proof for {0}")]
+	InvalidProof(&'static str),
+	/// A value read from the proof could not be decoded.
+	#[error("failed to decode {0}")]
+	Decode(&'static str),
+	/// A value claimed by the proof does not match the proven value.
+	#[error("{0} mismatch")]
+	Mismatch(&'static str),
+}
+
+/// Compute the storage key of a storage value or of a storage map entry, given the already hashed
+/// map key.
+fn storage_key(pallet_name: &str, storage_name: &str, hashed_key: &[u8]) -> Vec<u8> {
+	let mut key = twox_128(pallet_name.as_bytes()).to_vec();
+	key.extend_from_slice(&twox_128(storage_name.as_bytes()));
+	key.extend_from_slice(hashed_key);
+	key
+}
+
+/// Build an in-memory trie database from the nodes of a proof.
+fn memory_db(nodes: &[Bytes]) -> MemoryDB<Blake2Hasher> {
+	StorageProof::new(nodes.iter().map(|node| node.0.clone())).into_memory_db()
+}
+
+/// Read the value at `key` from the trie with the given `root`.
+fn read_value(
+	db: &MemoryDB<Blake2Hasher>,
+	root: &H256,
+	key: &[u8],
+	what: &'static str,
+) -> Result<Option<Vec<u8>>, Error> {
+	read_trie_value::<LayoutV1<Blake2Hasher>, _>(db, root, key, None, None)
+		.map_err(|_| Error::InvalidProof(what))
+}
+
+/// Convert a storage slot value to a [`U256`].
+fn slot_value(value: Option<Vec<u8>>) -> Result<U256, Error> {
+	match value {
+		Some(value) if value.len() > 32 => Err(Error::Decode("storage value")),
+		Some(value) => Ok(U256::from_big_endian(&value)),
+		None => Ok(U256::zero()),
+	}
+}
+
+/// Verify the given [`AccountProof`] against the `state_root` of the block it was requested at.
+///
+/// `pallet_name` is the name under which pallet-revive is declared in the runtime, see
+/// [`DEFAULT_PALLET_NAME`], and `Nonce` is the `frame_system::Config::Nonce` type of the runtime.
+pub fn verify_account_proof<Nonce: Decode + Into<U256>>(
+	state_root: &H256,
+	pallet_name: &str,
+	proof: &AccountProof,
+) -> Result<(), Error> {
+	let db = memory_db(&proof.account_proof);
+	let address = proof.address.as_bytes();
+
+	// `OriginalAccount` and `AccountInfoOf` are `Identity` hashed maps keyed by the address.
+	let original_account = read_value(
+		&db,
+		state_root,
+		&storage_key(pallet_name, "OriginalAccount", address),
+		"original account",
+	)?;
+	let account_id: [u8; 32] = match original_account {
+		Some(account_id) => account_id.try_into().map_err(|_| Error::Decode("original account"))?,
+		None => {
+			let mut account_id = [0xEE; 32];
+			account_id[..20].copy_from_slice(address);
+			account_id
+		},
+	};
+
+	// `System::Account` is a `Blake2_128Concat` hashed map keyed by the account id.
+	let hashed_account_id = [&blake2_128(&account_id)[..], &account_id[..]].concat();
+	let system_account = read_value(
+		&db,
+		state_root,
+		&storage_key("System", "Account", &hashed_account_id),
+		"system account",
+	)?;
+	let nonce = match &system_account {
+		Some(account) => {
+			Nonce::decode(&mut &account[..]).map_err(|_| Error::Decode("nonce"))?.into()
+		},
+		None => U256::zero(),
+	};
+	if nonce != proof.nonce {
+		return Err(Error::Mismatch("nonce"));
+	}
+
+	// The account info starts with the account type, whose `Contract` variant starts with the trie
+	// id and the code hash of the contract.
+	let account_info = read_value(
+		&db,
+		state_root,
+		&storage_key(pallet_name, "AccountInfoOf", address),
+		"account info",
+	)?;
+	let contract = match account_info.as_deref() {
+		Some([0, contract @ ..]) => Some(
+			<(Vec<u8>, H256)>::decode(&mut &contract[..])
+				.map_err(|_| Error::Decode("contract info"))?,
+		),
+		_ => None,
+	};
+
+	let code_hash = match (&contract, &system_account) {
+		(Some((_, code_hash)), _) => *code_hash,
+		(None, Some(_)) => H256(keccak_256(&[])),
+		(None, None) => H256::zero(),
+	};
+	if code_hash != proof.code_hash {
+		return Err(Error::Mismatch("code hash"));
+	}
+
+	let Some((trie_id, _)) = contract else {
+		if !proof.storage_hash.is_zero() {
+			return Err(Error::Mismatch("storage hash"));
+		}
+		if proof.storage_proof.iter().any(|slot| !slot.value.is_zero()) {
+			return Err(Error::Mismatch("storage value"));
+		}
+		return Ok(());
+	};
+
+	let child_trie_key = ChildInfo::new_default(&trie_id).prefixed_storage_key().into_inner();
+	let storage_hash = match read_value(&db, state_root, &child_trie_key, "storage hash")? {
+		Some(root) => H256::decode(&mut &root[..]).map_err(|_| Error::Decode("storage hash"))?,
+		// The child trie is empty.
+		None => H256::zero(),
+	};
+	if storage_hash != proof.storage_hash {
+		return Err(Error::Mismatch("storage hash"));
+	}
+
+	for slot in &proof.storage_proof {
+		let value = if storage_hash.is_zero() {
+			U256::zero()
+		} else {
+			let db = memory_db(&slot.proof);
+			let key = blake2_256(&slot.key.to_big_endian());
+			slot_value(read_value(&db, &storage_hash, &key, "storage value")?)?
+		};
+
+		if value != slot.value {
+			return Err(Error::Mismatch("storage value"));
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use pallet_revive::evm::StorageProof as SlotProof;
+	use sp_core::{H160, storage::StateVersion};
+	use sp_state_machine::{Backend, InMemoryBackend, prove_child_read, prove_read};
+
+	const TRIE_ID: &[u8] = b"trie_id";
+
+	fn address() -> H160 {
+		H160::repeat_byte(0x42)
+	}
+
+	fn fallback_account_id() -> Vec<u8> {
+		let mut account_id = vec![0x42; 20];
+		account_id.extend_from_slice(&[0xEE; 12]);
+		account_id
+	}
+
+	fn account_keys() -> Vec<Vec<u8>> {
+		let account_id = fallback_account_id();
+		let hashed_account_id = [&blake2_128(&account_id)[..], &account_id[..]].concat();
+		vec![
+			storage_key(DEFAULT_PALLET_NAME, "OriginalAccount", address().as_bytes()),
+			storage_key("System", "Account", &hashed_account_id),
+			storage_key(DEFAULT_PALLET_NAME, "AccountInfoOf", address().as_bytes()),
+		]
+	}
+
+	/// Build a state with a contract holding `slot` in its storage, and return the state root and
+	/// the proof of the contract and of `slot`.
+	fn contract_proof(slot: U256) -> (H256, AccountProof) {
+		let child_info = ChildInfo::new_default(TRIE_ID);
+		let code_hash = H256::repeat_byte(0x01);
+		let keys = account_keys();
+
+		let top = vec![
+			// `frame_system::AccountInfo` starting with a nonce of 3.
+			(keys[1].clone(), Some((3u32, 0u32, 1u32, 0u32, 100u128).encode())),
+			// `AccountInfo` with a `Contract` account type, followed by the rest of the contract
+			// info.
+			(keys[2].clone(), Some((0u8, TRIE_ID.to_vec(), code_hash, 0u32, 0u32).encode())),
+		];
+		let child = vec![(blake2_256(&slot.to_big_endian()).to_vec(), Some(vec![0x2a]))];
+		let backend = InMemoryBackend::<Blake2Hasher>::from((
+			vec![(None, top), (Some(child_info.clone()), child)],
+			StateVersion::V1,
+		));
+		let state_root = *backend.root();
+
+		let child_trie_key = child_info.prefixed_storage_key().into_inner();
+		let account_proof =
+			prove_read(backend.clone(), keys.iter().chain(std::iter::once(&child_trie_key)))
+				.unwrap();
+		let storage_hash = backend.storage(&child_trie_key).unwrap().unwrap();
+		let storage_proof =
+			prove_child_read(backend.clone(), &child_info, [blake2_256(&slot.to_big_endian())])
+				.unwrap();
+
+		let proof = AccountProof {
+			address: address(),
+			account_proof: account_proof.into_iter_nodes().map(Bytes).collect(),
+			code_hash,
+			nonce: U256::from(3),
+			storage_hash: H256::from_slice(&storage_hash),
+			storage_proof: vec![SlotProof {
+				key: slot,
+				value: U256::from(0x2a),
+				proof: storage_proof.into_iter_nodes().map(Bytes).collect(),
+			}],
+			..Default::default()
+		};
+
+		(state_root, proof)
+	}
+
+	#[test]
+	fn contract_proof_works() {
+		let (state_root, proof) = contract_proof(U256::from(1));
+		assert_eq!(verify_account_proof::<u32>(&state_root, DEFAULT_PALLET_NAME, &proof), Ok(()));
+	}
+
+	#[test]
+	fn tampered_contract_proof_fails() {
+		let (state_root, proof) = contract_proof(U256::from(1));
+
+		let mut tampered = proof.clone();
+		tampered.storage_proof[0].value = U256::from(0x2b);
+		assert_eq!(
+			verify_account_proof::<u32>(&state_root, DEFAULT_PALLET_NAME, &tampered),
+			Err(Error::Mismatch("storage value"))
+		);
+
+		let mut tampered = proof.clone();
+		tampered.nonce = U256::from(4);
+		assert_eq!(
+			verify_account_proof::<u32>(&state_root, DEFAULT_PALLET_NAME, &tampered),
+			Err(Error::Mismatch("nonce"))
+		);
+
+		let mut tampered = proof.clone();
+		tampered.code_hash = H256::zero();
+		assert_eq!(
+			verify_account_proof::<u32>(&state_root, DEFAULT_PALLET_NAME, &tampered),
+			Err(Error::Mismatch("code hash"))
+		);
+
+		assert_eq!(
+			verify_account_proof::<u32>(&H256::zero(), DEFAULT_PALLET_NAME, &proof),
+			Err(Error::InvalidProof("original account"))
+		);
+	}
+
+	#[test]
+	fn missing_account_proof_works() {
+		let keys = account_keys();
+		let backend = InMemoryBackend::<Blake2Hasher>::from((
+			vec![(None, vec![(b"other".to_vec(), Some(vec![1]))])],
+			StateVersion::V1,
+		));
+		let account_proof = prove_read(backend.clone(), &keys).unwrap();
+
+		let mut proof = AccountProof {
+			address: address(),
+			account_proof: account_proof.into_iter_nodes().map(Bytes).collect(),
+			storage_proof: vec![SlotProof { key: U256::from(1), ..Default::default() }],
+			..Default::default()
+		};
+		assert_eq!(
+			verify_account_proof::<u32>(backend.root(), DEFAULT_PALLET_NAME, &proof),
+			Ok(())
+		);
+
+		proof.storage_proof[0].value = U256::from(1);
+		assert_eq!(
+			verify_account_proof::<u32>(backend.root(), DEFAULT_PALLET_NAME, &proof),
+			Err(Error::Mismatch("storage value"))
+		);
+	}
+}
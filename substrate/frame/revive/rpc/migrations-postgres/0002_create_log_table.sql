@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS logs (
+	block_hash BYTEA NOT NULL,
+	transaction_index BIGINT NOT NULL,
+	log_index BIGINT NOT NULL,
+	address BYTEA NOT NULL,
+	block_number BIGINT NOT NULL,
+	transaction_hash BYTEA NOT NULL,
+	topic_0 BYTEA,
+	topic_1 BYTEA,
+	topic_2 BYTEA,
+	topic_3 BYTEA,
+	data BYTEA,
+	PRIMARY KEY (block_hash, transaction_index, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_block_number_address_topics ON logs (
+	block_number,
+	address,
+	topic_0,
+	topic_1,
+	topic_2,
+	topic_3
+);
+
+CREATE INDEX IF NOT EXISTS idx_logs_block_hash ON logs (
+	block_hash
+);
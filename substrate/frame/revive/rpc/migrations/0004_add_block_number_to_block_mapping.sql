@@ -0,0 +1,7 @@
+-- Record the Substrate block number of each block mapping, so that blocks re-orged out of the
+-- canonical chain can be found and removed when re-indexing.
+ALTER TABLE eth_to_substrate_blocks ADD COLUMN block_number INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_eth_to_substrate_blocks_block_number ON eth_to_substrate_blocks (
+	block_number
+);
@@ -61,6 +61,34 @@ pub trait DebugRpc {
 	async fn get_automine(&self) -> RpcResult<bool>;
 }
 
+/// Parity-style trace JSON-RPC apis.
+#[rpc(server, client)]
+pub trait TraceRpc {
+	/// Returns the flat call traces of all the transactions of a block.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_block>
+	#[method(name = "trace_block")]
+	async fn trace_block(&self, block: BlockNumberOrTag) -> RpcResult<Vec<FlatCallTrace>>;
+
+	/// Returns the flat call traces of a transaction.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_transaction>
+	#[method(name = "trace_transaction")]
+	async fn trace_transaction(&self, transaction_hash: H256) -> RpcResult<Vec<FlatCallTrace>>;
+
+	/// Returns the flat call traces of a range of blocks matching the given filter.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_filter>
+	#[method(name = "trace_filter")]
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<FlatCallTrace>>;
+}
+
 pub struct DebugRpcServerImpl {
 	client: client::Client,
 }
@@ -123,3 +151,45 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		sc_service::Result::Ok(self.client.get_automine().await)
 	}
 }
+
+pub struct TraceRpcServerImpl {
+	client: client::Client,
+}
+
+impl TraceRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl TraceRpcServer for TraceRpcServerImpl {
+	async fn trace_block(&self, block: BlockNumberOrTag) -> RpcResult<Vec<FlatCallTrace>> {
+		let traces = self
+			.client
+			.trace_block_by_number(block, TracerType::FlatCallTracer(None))
+			.await?;
+		Ok(traces
+			.into_iter()
+			.flat_map(|TransactionTrace { trace, .. }| match trace {
+				Trace::Flat(traces) => traces,
+				_ => Vec::new(),
+			})
+			.collect())
+	}
+
+	async fn trace_transaction(&self, transaction_hash: H256) -> RpcResult<Vec<FlatCallTrace>> {
+		let trace = self
+			.client
+			.trace_transaction(transaction_hash, TracerType::FlatCallTracer(None))
+			.await?;
+		match trace {
+			Trace::Flat(traces) => Ok(traces),
+			_ => Ok(Vec::new()),
+		}
+	}
+
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<FlatCallTrace>> {
+		Ok(self.client.trace_filter(filter).await?)
+	}
+}
@@ -43,6 +43,14 @@ pub trait EthRpc {
 	#[method(name = "eth_chainId")]
 	async fn chain_id(&self) -> RpcResult<U256>;
 
+	/// Generates an access list for a transaction, and the gas used with that access list.
+	#[method(name = "eth_createAccessList")]
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult>;
+
 	/// Generates and returns an estimate of how much gas is necessary to allow the transaction to
 	/// complete.
 	#[method(name = "eth_estimateGas")]
@@ -95,10 +103,29 @@ pub trait EthRpc {
 	#[method(name = "eth_getCode")]
 	async fn get_code(&self, address: Address, block: BlockNumberOrTagOrHash) -> RpcResult<Bytes>;
 
+	/// Polling method for a filter, which returns an array of logs which occurred since last poll.
+	#[method(name = "eth_getFilterChanges")]
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
+	/// Returns an array of all logs matching filter with given id.
+	#[method(name = "eth_getFilterLogs")]
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
 	/// Returns an array of all logs matching filter with given id.
 	#[method(name = "eth_getLogs")]
 	async fn get_logs(&self, filter: Option<Filter>) -> RpcResult<FilterResults>;
 
+	/// Returns the account and storage values of the specified account including the Merkle-proof.
+	///
+	/// See [`AccountProof`] for the format of the proof.
+	#[method(name = "eth_getProof")]
+	async fn get_proof(
+		&self,
+		address: Address,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof>;
+
 	/// Returns the value from a storage position at a given address.
 	#[method(name = "eth_getStorageAt")]
 	async fn get_storage_at(
@@ -150,6 +177,19 @@ pub trait EthRpc {
 	#[method(name = "eth_maxPriorityFeePerGas")]
 	async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
 
+	/// Creates a filter in the node, to notify when a new block arrives.
+	#[method(name = "eth_newBlockFilter")]
+	async fn new_block_filter(&self) -> RpcResult<U256>;
+
+	/// Install a log filter in the server, allowing for later polling. Registers client interest in
+	/// logs matching the filter, and returns an identifier.
+	#[method(name = "eth_newFilter")]
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
+
+	/// Creates a filter in the node, to notify when new pending transactions arrive.
+	#[method(name = "eth_newPendingTransactionFilter")]
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256>;
+
 	/// Submits a raw transaction. For EIP-4844 transactions, the raw form must be the network form.
 	/// This means it includes the blobs, KZG commitments, and KZG proofs.
 	#[method(name = "eth_sendRawTransaction")]
@@ -159,10 +199,23 @@ pub trait EthRpc {
 	#[method(name = "eth_sendTransaction")]
 	async fn send_transaction(&self, transaction: GenericTransaction) -> RpcResult<H256>;
 
+	/// Executes a sequence of blocks of calls, with optional block and state overrides, without
+	/// creating transactions on the block chain.
+	#[method(name = "eth_simulateV1")]
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>>;
+
 	/// Returns an object with data about the sync status or false.
 	#[method(name = "eth_syncing")]
 	async fn syncing(&self) -> RpcResult<SyncingStatus>;
 
+	/// Uninstalls a filter with given id.
+	#[method(name = "eth_uninstallFilter")]
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool>;
+
 	/// Returns true when the client is actively listening for network connections, otherwise false
 	#[method(name = "net_listening")]
 	async fn net_listening(&self) -> RpcResult<bool>;
@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Ethereum pubsub JSON-RPC methods.
+
+use crate::*;
+use jsonrpsee::{
+	PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink, core::SubscriptionResult,
+	proc_macros::rpc,
+};
+use std::future::Future;
+use tokio::sync::broadcast::{Receiver, error::RecvError};
+
+/// Ethereum pubsub JSON-RPC apis.
+#[rpc(server, client)]
+pub trait EthPubSubRpc {
+	/// Create a subscription to the given kind of events.
+	///
+	/// The `params` argument is only accepted for `logs` subscriptions, where it is used to filter
+	/// the logs by address and topics. The block range of the filter is ignored.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn subscribe_events(
+		&self,
+		kind: SubscriptionKind,
+		params: Option<Filter>,
+	) -> SubscriptionResult;
+}
+
+pub struct EthPubSubRpcServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+/// Forward the values received on `receiver` to the subscription `sink`, until either side is
+/// closed.
+///
+/// Each received value is mapped to a list of subscription items with `f`.
+async fn forward<T, F, Fut>(
+	sink: SubscriptionSink,
+	mut receiver: Receiver<T>,
+	f: F,
+) -> SubscriptionResult
+where
+	T: Clone,
+	F: Fn(T) -> Fut,
+	Fut: Future<Output = Vec<SubscriptionItem>>,
+{
+	loop {
+		let value = tokio::select! {
+			_ = sink.closed() => return Ok(()),
+			value = receiver.recv() => value,
+		};
+
+		match value {
+			Ok(value) => {
+				for item in f(value).await {
+					sink.send(SubscriptionMessage::from_json(&item)?).await?;
+				}
+			},
+			Err(RecvError::Lagged(skipped)) => {
+				log::warn!(target: LOG_TARGET, "Subscription {:?} lagged behind, skipped {skipped} notifications", sink.subscription_id());
+			},
+			Err(RecvError::Closed) => return Ok(()),
+		}
+	}
+}
+
+#[async_trait]
+impl EthPubSubRpcServer for EthPubSubRpcServerImpl {
+	async fn subscribe_events(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		params: Option<Filter>,
+	) -> SubscriptionResult {
+		if kind != SubscriptionKind::Logs && params.is_some() {
+			pending
+				.reject(ErrorObjectOwned::owned::<String>(
+					ErrorCode::InvalidParams.code(),
+					format!("Unexpected params for {kind:?} subscription"),
+					None,
+				))
+				.await;
+			return Ok(());
+		}
+
+		match kind {
+			SubscriptionKind::NewHeads => {
+				let receiver = self.client.subscribe_new_heads();
+				let sink = pending.accept().await?;
+				forward(sink, receiver, |notification| async move {
+					let mut header = notification.ethereum_block;
+					header.transactions = HashesOrTransactionInfos::Hashes(vec![]);
+					vec![SubscriptionItem::from(Box::new(header))]
+				})
+				.await
+			},
+			SubscriptionKind::Logs => {
+				let Filter { address, topics, .. } = params.unwrap_or_default();
+				let receiver = self.client.subscribe_new_heads();
+				let sink = pending.accept().await?;
+				forward(sink, receiver, |notification| {
+					let filter = Filter {
+						address: address.clone(),
+						topics: topics.clone(),
+						block_hash: Some(notification.ethereum_block.hash),
+						..Default::default()
+					};
+					async move {
+						self.client
+							.logs(Some(filter))
+							.await
+							.inspect_err(|err| {
+								log::debug!(target: LOG_TARGET, "Failed to fetch logs for block {:?}: {err:?}", notification.ethereum_block.hash);
+							})
+							.unwrap_or_default()
+							.into_iter()
+							.map(SubscriptionItem::from)
+							.collect()
+					}
+				})
+				.await
+			},
+			SubscriptionKind::NewPendingTransactions => {
+				let receiver = self.client.subscribe_pending_transactions();
+				let sink = pending.accept().await?;
+				forward(sink, receiver, |hash| async move { vec![SubscriptionItem::from(hash)] })
+					.await
+			},
+		}
+	}
+}
@@ -16,12 +16,14 @@
 // limitations under the License.
 //! The Ethereum JSON-RPC server.
 use crate::{
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, LOG_TARGET,
-	PolkadotRpcServer, PolkadotRpcServerImpl, ReceiptExtractor, ReceiptProvider,
-	SubxtBlockInfoProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl,
+	DebugRpcServer, DebugRpcServerImpl, EthPubSubRpcServer, EthPubSubRpcServerImpl, EthRpcServer,
+	EthRpcServerImpl, LOG_TARGET, PolkadotRpcServer, PolkadotRpcServerImpl, PostgresReceiptStorage,
+	ReceiptExtractor, ReceiptProvider, ReceiptStorage, SqliteReceiptStorage,
+	SubxtBlockInfoProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl, TraceRpcServer,
+	TraceRpcServerImpl,
 	client::{Client, SubscriptionType, SubstrateBlockNumber, connect},
 };
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use futures::{FutureExt, future::BoxFuture, pin_mut};
 use jsonrpsee::server::RpcModule;
 use sc_cli::{PrometheusParams, RpcParams, SharedParams, Signals};
@@ -30,7 +32,8 @@ use sc_service::{
 	config::{PrometheusConfig, RpcConfiguration},
 	start_rpc_servers,
 };
-use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::{postgres::PgPoolOptions, sqlite::SqlitePoolOptions};
+use std::sync::Arc;
 
 // Default port if --prometheus-port is not specified
 const DEFAULT_PROMETHEUS_PORT: u16 = 9616;
@@ -59,13 +62,29 @@ pub struct CliCommand {
 	/// The database used to store Ethereum transaction hashes.
 	/// This is only useful if the node needs to act as an archive node and respond to Ethereum RPC
 	/// queries for transactions that are not in the in memory cache.
+	///
+	/// Both SQLite (`sqlite://...`) and Postgres (`postgres://...`) URLs are supported. A Postgres
+	/// database can be shared by several eth-rpc instances.
 	#[clap(long, env = "DATABASE_URL", default_value = IN_MEMORY_DB)]
 	pub database_url: String,
 
+	/// The number of latest blocks kept in a persistent (file or Postgres) database, the older
+	/// blocks are pruned. All the blocks are kept by default, while the in-memory database always
+	/// keeps `--cache-size` blocks.
+	///
+	/// Blocks are pruned by block number, so all the instances sharing a Postgres database should
+	/// use the same value.
+	#[clap(long)]
+	pub database_pruning: Option<usize>,
+
 	/// If provided, index the last n blocks
 	#[clap(long)]
 	pub index_last_n_blocks: Option<SubstrateBlockNumber>,
 
+	#[allow(missing_docs)]
+	#[clap(subcommand)]
+	pub subcommand: Option<EthRpcSubcommand>,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -85,6 +104,31 @@ pub struct CliCommand {
 	pub allow_unprotected_txs: bool,
 }
 
+/// Subcommands of the eth-rpc binary.
+#[derive(Subcommand, Debug)]
+pub enum EthRpcSubcommand {
+	/// Index a range of blocks into the database and exit, without starting the RPC server.
+	///
+	/// Entries left by blocks that were re-orged out of the canonical chain are removed.
+	Index(IndexCmd),
+}
+
+/// The `index` subcommand.
+#[derive(Args, Debug)]
+pub struct IndexCmd {
+	/// The first block number to index.
+	#[clap(long)]
+	pub from: SubstrateBlockNumber,
+
+	/// The last block number to index, defaults to the latest finalized block.
+	#[clap(long)]
+	pub to: Option<SubstrateBlockNumber>,
+
+	/// The maximum number of blocks indexed in parallel.
+	#[clap(long, default_value = "16")]
+	pub concurrency: usize,
+}
+
 /// Initialize the logger
 #[cfg(not(test))]
 fn init_logger(params: &SharedParams) -> anyhow::Result<()> {
@@ -106,9 +150,15 @@ fn init_logger(params: &SharedParams) -> anyhow::Result<()> {
 	Ok(())
 }
 
+/// Returns `true` if the given database URL points to a Postgres database.
+fn is_postgres_url(database_url: &str) -> bool {
+	database_url.starts_with("postgres://") || database_url.starts_with("postgresql://")
+}
+
 fn build_client(
 	tokio_handle: &tokio::runtime::Handle,
 	cache_size: usize,
+	database_pruning: Option<usize>,
 	earliest_receipt_block: Option<SubstrateBlockNumber>,
 	node_rpc_url: &str,
 	database_url: &str,
@@ -120,7 +170,7 @@ fn build_client(
 		let (api, rpc_client, rpc) = connect(node_rpc_url, max_request_size, max_response_size).await?;
 		let block_provider = SubxtBlockInfoProvider::new( api.clone(), rpc.clone()).await?;
 
-		let (pool, keep_latest_n_blocks) = if database_url == IN_MEMORY_DB {
+		let (storage, keep_latest_n_blocks): (Arc<dyn ReceiptStorage>, _) = if database_url == IN_MEMORY_DB {
 			log::warn!( target: LOG_TARGET, "💾 Using in-memory database, keeping only {cache_size} blocks in memory");
 			// see sqlite in-memory issue: https://github.com/launchbadge/sqlx/issues/2510
 			let pool = SqlitePoolOptions::new()
@@ -129,9 +179,14 @@ fn build_client(
 					.max_lifetime(None)
 					.connect(database_url).await?;
 
-			(pool, Some(cache_size))
+			(Arc::new(SqliteReceiptStorage::new(pool).await?), Some(cache_size))
+		} else if is_postgres_url(database_url) {
+			log::info!( target: LOG_TARGET, "💾 Using Postgres database");
+			let pool = PgPoolOptions::new().connect(database_url).await?;
+			(Arc::new(PostgresReceiptStorage::new(pool).await?), database_pruning)
 		} else {
-			(SqlitePoolOptions::new().connect(database_url).await?, None)
+			let pool = SqlitePoolOptions::new().connect(database_url).await?;
+			(Arc::new(SqliteReceiptStorage::new(pool).await?), database_pruning)
 		};
 
 		let receipt_extractor = ReceiptExtractor::new(
@@ -140,12 +195,11 @@ fn build_client(
 		).await?;
 
 		let receipt_provider = ReceiptProvider::new(
-				pool,
+				storage,
 				block_provider.clone(),
 				receipt_extractor.clone(),
 				keep_latest_n_blocks,
-			)
-			.await?;
+			);
 
 		let client =
 			Client::new(api, rpc_client, rpc, block_provider, receipt_provider).await?;
@@ -170,10 +224,12 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		node_rpc_url,
 		cache_size,
 		database_url,
+		database_pruning,
 		earliest_receipt_block,
 		index_last_n_blocks,
 		shared_params,
 		allow_unprotected_txs,
+		subcommand,
 		..
 	} = cmd;
 
@@ -206,6 +262,10 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		prometheus_params.prometheus_config(DEFAULT_PROMETHEUS_PORT, "eth-rpc".into());
 	let prometheus_registry = prometheus_config.as_ref().map(|config| &config.registry);
 
+	if subcommand.is_some() && database_url == IN_MEMORY_DB {
+		anyhow::bail!("The index subcommand requires a persistent --database-url");
+	}
+
 	let tokio_runtime = sc_cli::build_runtime()?;
 	let tokio_handle = tokio_runtime.handle();
 	let mut task_manager = TaskManager::new(tokio_handle.clone(), prometheus_registry)?;
@@ -213,6 +273,7 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 	let client = build_client(
 		tokio_handle,
 		cache_size,
+		database_pruning,
 		earliest_receipt_block,
 		&node_rpc_url,
 		&database_url,
@@ -221,6 +282,17 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		tokio_runtime.block_on(async { Signals::capture() })?,
 	)?;
 
+	let signals = tokio_runtime.block_on(async { Signals::capture() })?;
+	if let Some(EthRpcSubcommand::Index(index_cmd)) = subcommand {
+		let fut = run_index(&client, index_cmd).fuse();
+		pin_mut!(fut);
+
+		return match tokio_runtime.block_on(signals.try_until_signal(fut)) {
+			Ok(res) => res,
+			Err(_) => anyhow::bail!("Process interrupted"),
+		};
+	}
+
 	// Prometheus metrics.
 	if let Some(PrometheusConfig { port, registry }) = prometheus_config.clone() {
 		task_manager.spawn_handle().spawn(
@@ -256,11 +328,29 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		});
 
 	task_manager.keep_alive(rpc_server_handle);
-	let signals = tokio_runtime.block_on(async { Signals::capture() })?;
 	tokio_runtime.block_on(signals.run_until_signal(task_manager.future().fuse()))?;
 	Ok(())
 }
 
+/// Run the `index` subcommand.
+async fn run_index(client: &Client, cmd: IndexCmd) -> anyhow::Result<()> {
+	let IndexCmd { from, to, concurrency } = cmd;
+	let finalized = client.finalized_block_number().await?;
+	let to = to.unwrap_or(finalized);
+
+	if to > finalized {
+		anyhow::bail!(
+			"--to #{to} is above the latest finalized block #{finalized} and could still be re-orged"
+		);
+	}
+	if from > to {
+		anyhow::bail!("--from #{from} is greater than --to #{to}");
+	}
+
+	client.index_blocks(from..=to, concurrency).await?;
+	Ok(())
+}
+
 /// Create the JSON-RPC module.
 fn rpc_module(
 	is_dev: bool,
@@ -285,12 +375,16 @@ fn rpc_module(
 
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
 	let debug_api = DebugRpcServerImpl::new(client.clone()).into_rpc();
+	let trace_api = TraceRpcServerImpl::new(client.clone()).into_rpc();
+	let pubsub_api = EthPubSubRpcServerImpl::new(client.clone()).into_rpc();
 	let polkadot_api = PolkadotRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(trace_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(pubsub_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module
 		.merge(polkadot_api)
 		.map_err(|e| sc_service::Error::Application(e.into()))?;
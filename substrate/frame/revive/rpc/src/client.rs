@@ -20,24 +20,33 @@
 pub(crate) mod runtime_api;
 pub(crate) mod storage_api;
 use crate::{
-	BlockInfoProvider, BlockTag, FeeHistoryProvider, ReceiptProvider, SubxtBlockInfoProvider,
-	TracerType, TransactionInfo,
+	BlockInfoProvider, BlockTag, FeeHistoryProvider, FilterChanges, FilterKind, FilterProvider,
+	ReceiptProvider, SubxtBlockInfoProvider, TracerType, TransactionInfo,
 	subxt_client::{self, SrcChainConfig, revive::calls::types::EthTransact},
 };
 use futures::TryStreamExt;
 use jsonrpsee::types::{ErrorObjectOwned, error::CALL_EXECUTION_FAILED_CODE};
 use pallet_revive::{
-	EthTransactError,
+	AccountProofKeys, EthTransactError, SimulatedBlockInfo,
 	evm::{
-		Block, BlockNumberOrTag, BlockNumberOrTagOrHash, FeeHistoryResult, Filter,
-		GenericTransaction, H256, HashesOrTransactionInfos, Log, ReceiptInfo, SyncingProgress,
-		SyncingStatus, Trace, TransactionSigned, TransactionTrace, decode_revert_reason,
+		AccessList, AccessListEntry, AccessListResult, AccountProof, Block, BlockNumberOrTag,
+		BlockNumberOrTagOrHash, FeeHistoryResult, Filter, FilterResults, FlatCallTrace,
+		GenericTransaction, H160, H256, HashesOrTransactionInfos, Log, PrestateTrace,
+		PrestateTracerConfig, ReceiptInfo, SimulateCallError, SimulateCallResult, SimulatePayload,
+		SimulatedBlock, StorageProof, SyncingProgress, SyncingStatus, Trace, TraceFilter,
+		TransactionSigned, TransactionTrace, U256, decode_revert_reason,
 	},
 };
 use runtime_api::RuntimeApi;
+use sc_rpc_api::state::ReadProof;
+use sp_core::keccak_256;
 use sp_runtime::traits::Block as BlockT;
 use sp_weights::Weight;
-use std::{ops::Range, sync::Arc, time::Duration};
+use std::{
+	ops::{Range, RangeInclusive},
+	sync::Arc,
+	time::Duration,
+};
 use storage_api::StorageApi;
 use subxt::{
 	Config, OnlineClient,
@@ -82,6 +91,15 @@ pub enum SubscriptionType {
 	FinalizedBlocks,
 }
 
+/// A notification sent to the subscribers of new best blocks, once the block has been indexed.
+#[derive(Debug, Clone)]
+pub struct BlockNotification {
+	/// The Substrate block hash.
+	pub substrate_hash: SubstrateBlockHash,
+	/// The Ethereum block, with transaction hashes only.
+	pub ethereum_block: Block,
+}
+
 /// Submit Error reason.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
 pub enum SubmitError {
@@ -171,12 +189,44 @@ pub enum ClientError {
 	/// Transaction submission timeout.
 	#[error("Transaction submission timeout")]
 	Timeout,
+	/// The filter was not found, or has expired.
+	#[error("filter not found")]
+	FilterNotFound,
+	/// The filter parameters are invalid.
+	#[error("invalid filter: {0}")]
+	InvalidFilter(&'static str),
+	/// The requested option is not supported.
+	#[error("unsupported option: {0}")]
+	Unsupported(&'static str),
 }
 const LOG_TARGET: &str = "eth-rpc::client";
 
 const REVERT_CODE: i32 = 3;
 
 const NOTIFIER_CAPACITY: usize = 16;
+
+/// The maximum number of blocks traced by a `trace_filter` request.
+const MAX_TRACE_FILTER_BLOCK_RANGE: SubstrateBlockNumber = 100;
+
+/// Number of indexed blocks between two progress reports of [`Client::index_blocks`].
+const INDEX_PROGRESS_INTERVAL: u32 = 1_000;
+
+/// Fill the block and transaction fields of the flat call traces of `trace`.
+fn fill_flat_traces(
+	trace: &mut Trace,
+	block_hash: H256,
+	block_number: SubstrateBlockNumber,
+	transaction_hash: H256,
+	transaction_position: u32,
+) {
+	let Trace::Flat(traces) = trace else { return };
+	for trace in traces {
+		trace.block_hash = Some(block_hash);
+		trace.block_number = Some(block_number.into());
+		trace.transaction_hash = Some(transaction_hash);
+		trace.transaction_position = Some(transaction_position);
+	}
+}
 impl From<ClientError> for ErrorObjectOwned {
 	fn from(err: ClientError) -> Self {
 		match err {
@@ -187,11 +237,7 @@ impl From<ClientError> for ErrorObjectOwned {
 				ErrorObjectOwned::owned::<Vec<u8>>(err.code, err.message, None)
 			},
 			ClientError::TransactError(EthTransactError::Data(data)) => {
-				let msg = match decode_revert_reason(&data) {
-					Some(reason) => format!("execution reverted: {reason}"),
-					None => "execution reverted".to_string(),
-				};
-
+				let msg = revert_message(&data);
 				let data = format!("0x{}", hex::encode(data));
 				ErrorObjectOwned::owned::<String>(REVERT_CODE, msg, Some(data))
 			},
@@ -205,6 +251,104 @@ impl From<ClientError> for ErrorObjectOwned {
 	}
 }
 
+/// The error message of a call that reverted with the given data.
+fn revert_message(data: &[u8]) -> String {
+	match decode_revert_reason(data) {
+		Some(reason) => format!("execution reverted: {reason}"),
+		None => "execution reverted".to_string(),
+	}
+}
+
+/// The hash of a call simulated with `eth_simulateV1`, computed from its unsigned payload.
+fn simulated_transaction_hash(tx: GenericTransaction) -> H256 {
+	let payload = tx.try_into_unsigned().map(|tx| tx.unsigned_payload()).unwrap_or_default();
+	H256(keccak_256(&payload))
+}
+
+/// Convert a block simulated by the runtime into its `eth_simulateV1` representation.
+///
+/// `transaction_hashes` are the hashes of the calls of the block, see
+/// [`simulated_transaction_hash`].
+fn simulated_block(
+	block: SimulatedBlockInfo,
+	parent_hash: H256,
+	transaction_hashes: Vec<H256>,
+	gas_limit: U256,
+	base_fee_per_gas: U256,
+) -> SimulatedBlock {
+	let hash = {
+		let mut bytes = [
+			parent_hash.as_bytes(),
+			&block.number.to_big_endian(),
+			&block.timestamp.to_big_endian(),
+		]
+		.concat();
+		transaction_hashes
+			.iter()
+			.for_each(|hash| bytes.extend_from_slice(hash.as_bytes()));
+		H256(keccak_256(&bytes))
+	};
+
+	let mut log_index = 0u64;
+	let mut gas_used = U256::zero();
+	let mut calls = Vec::with_capacity(block.calls.len());
+
+	for (index, call) in block.calls.into_iter().enumerate() {
+		let transaction_hash = transaction_hashes.get(index).copied().unwrap_or_default();
+		let mut logs = Vec::with_capacity(call.logs.len());
+		for log in call.logs {
+			logs.push(Log {
+				address: log.address,
+				block_hash: hash,
+				block_number: block.number,
+				data: Some(log.data),
+				log_index: log_index.into(),
+				topics: log.topics,
+				transaction_hash,
+				transaction_index: index.into(),
+				..Default::default()
+			});
+			log_index += 1;
+		}
+
+		gas_used = gas_used.saturating_add(call.gas_used);
+		let (return_data, error) = match call.result {
+			Ok(data) => (data, None),
+			Err(EthTransactError::Data(data)) => {
+				let error = SimulateCallError {
+					code: REVERT_CODE,
+					message: revert_message(&data),
+					data: Some(data.clone().into()),
+				};
+				(data, Some(error))
+			},
+			Err(EthTransactError::Message(message)) => {
+				let error =
+					SimulateCallError { code: CALL_EXECUTION_FAILED_CODE, message, data: None };
+				(Vec::new(), Some(error))
+			},
+		};
+
+		calls.push(SimulateCallResult {
+			return_data: return_data.into(),
+			logs,
+			gas_used: call.gas_used,
+			status: if error.is_none() { U256::one() } else { U256::zero() },
+			error,
+		});
+	}
+
+	SimulatedBlock {
+		hash,
+		number: block.number,
+		timestamp: block.timestamp,
+		gas_limit,
+		gas_used,
+		base_fee_per_gas,
+		calls,
+	}
+}
+
 /// A client connect to a node and maintains a cache of the last `CACHE_SIZE` blocks.
 #[derive(Clone)]
 pub struct Client {
@@ -214,12 +358,17 @@ pub struct Client {
 	receipt_provider: ReceiptProvider,
 	block_provider: SubxtBlockInfoProvider,
 	fee_history_provider: FeeHistoryProvider,
+	filter_provider: FilterProvider,
 	chain_id: u64,
 	max_block_weight: Weight,
 	/// Whether the node has automine enabled.
 	automine: bool,
 	/// A notifier, that informs subscribers of new best blocks.
 	block_notifier: Option<tokio::sync::broadcast::Sender<H256>>,
+	/// A notifier, that informs pubsub subscribers of new indexed best blocks.
+	new_heads_notifier: tokio::sync::broadcast::Sender<BlockNotification>,
+	/// A notifier, that informs pubsub subscribers of transactions submitted through this server.
+	pending_transactions_notifier: tokio::sync::broadcast::Sender<H256>,
 	/// A lock to ensure only one subscription can perform write operations at a time.
 	subscription_lock: Arc<Mutex<()>>,
 }
@@ -293,11 +442,14 @@ impl Client {
 			receipt_provider,
 			block_provider,
 			fee_history_provider: FeeHistoryProvider::default(),
+			filter_provider: FilterProvider::default(),
 			chain_id,
 			max_block_weight,
 			automine,
 			block_notifier: automine
 				.then(|| tokio::sync::broadcast::channel::<H256>(NOTIFIER_CAPACITY).0),
+			new_heads_notifier: tokio::sync::broadcast::channel(NOTIFIER_CAPACITY).0,
+			pending_transactions_notifier: tokio::sync::broadcast::channel(NOTIFIER_CAPACITY).0,
 			subscription_lock: Arc::new(Mutex::new(())),
 		};
 
@@ -428,6 +580,18 @@ impl Client {
 				},
 				_ => {},
 			}
+
+			if subscription_type == SubscriptionType::BestBlocks {
+				self.filter_provider.on_new_block(evm_block.hash).await;
+			}
+
+			if subscription_type == SubscriptionType::BestBlocks &&
+				self.new_heads_notifier.receiver_count() > 0
+			{
+				let _ = self
+					.new_heads_notifier
+					.send(BlockNotification { substrate_hash: hash, ethereum_block: evm_block });
+			}
 			Ok(())
 		})
 		.await
@@ -457,6 +621,58 @@ impl Client {
 		Ok(())
 	}
 
+	/// Index the canonical blocks in `range`, processing up to `concurrency` blocks in parallel.
+	///
+	/// Entries left by blocks that were re-orged out of the canonical chain are removed.
+	pub async fn index_blocks(
+		&self,
+		range: RangeInclusive<SubstrateBlockNumber>,
+		concurrency: usize,
+	) -> Result<(), ClientError> {
+		use futures::StreamExt;
+
+		log::info!(target: LOG_TARGET, "🗄️ Indexing blocks in range {range:?}");
+		let total = range.end().saturating_sub(*range.start()).saturating_add(1);
+
+		let mut blocks = futures::stream::iter(range)
+			.map(|block_number| self.index_block(block_number))
+			.buffer_unordered(concurrency.max(1));
+
+		let (mut indexed, mut removed) = (0u32, 0usize);
+		while let Some(removed_at_height) = blocks.try_next().await? {
+			indexed += 1;
+			removed += removed_at_height;
+			if indexed % INDEX_PROGRESS_INTERVAL == 0 {
+				log::info!(target: LOG_TARGET, "🗄️ Indexed {indexed}/{total} blocks");
+			}
+		}
+
+		log::info!(target: LOG_TARGET, "🗄️ Finished indexing {indexed} blocks, removed {removed} re-orged blocks");
+		Ok(())
+	}
+
+	/// Index the canonical block at the given height, returning the number of re-orged blocks
+	/// removed.
+	async fn index_block(&self, block_number: SubstrateBlockNumber) -> Result<usize, ClientError> {
+		let block = self
+			.block_provider
+			.block_by_number(block_number)
+			.await?
+			.ok_or(ClientError::BlockNotFound)?;
+		let ethereum_hash = self
+			.runtime_api(block.hash())
+			.eth_block_hash(U256::from(block_number))
+			.await?
+			.ok_or(ClientError::EthereumBlockNotFound)?;
+
+		self.receipt_provider
+			.reindex_block_receipts(&block, &ethereum_hash)
+			.await
+			.inspect_err(|err| {
+				log::error!(target: LOG_TARGET, "Failed to index block #{block_number}: {err:?}");
+			})
+	}
+
 	/// Get the block hash for the given block number or tag.
 	pub async fn block_hash_for_tag(
 		&self,
@@ -504,6 +720,19 @@ impl Client {
 		self.block_provider.latest_block().await
 	}
 
+	/// Fetch the number of the latest finalized block from the node.
+	///
+	/// Unlike [`Self::latest_finalized_block`], this does not rely on the block subscriptions.
+	pub async fn finalized_block_number(&self) -> Result<SubstrateBlockNumber, ClientError> {
+		let hash = self.rpc.chain_get_finalized_head().await?;
+		let block = self
+			.block_provider
+			.block_by_hash(&hash)
+			.await?
+			.ok_or(ClientError::BlockNotFound)?;
+		Ok(block.number())
+	}
+
 	/// Submit an ethereum transaction and return a stream of transaction status updates.
 	async fn submit_transaction(
 		&self,
@@ -764,7 +993,17 @@ impl Client {
 		}
 
 		let block_hash = self.block_hash_for_tag(at.into()).await?;
+		self.trace_block_by_hash(block_hash, config).await
+	}
+
+	/// Get the transaction traces for the given Substrate block hash.
+	async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		config: TracerType,
+	) -> Result<Vec<TransactionTrace>, ClientError> {
 		let block = self.tracing_block(block_hash).await?;
+		let block_number = block.header().number;
 		let parent_hash = block.header().parent_hash;
 		let runtime_api = RuntimeApi::new(self.api.runtime_api().at(parent_hash));
 		let traces = runtime_api.trace_block(block, config.clone()).await?;
@@ -774,9 +1013,12 @@ impl Client {
 			.block_transaction_hashes(&block_hash)
 			.await
 			.ok_or(ClientError::EthExtrinsicNotFound)?;
+		let ethereum_hash = self.resolve_ethereum_hash(&block_hash).await.unwrap_or(block_hash);
 
-		let traces = traces.into_iter().filter_map(|(index, trace)| {
-			Some(TransactionTrace { tx_hash: hashes.remove(&(index as usize))?, trace })
+		let traces = traces.into_iter().filter_map(|(index, mut trace)| {
+			let tx_hash = hashes.remove(&(index as usize))?;
+			fill_flat_traces(&mut trace, ethereum_hash, block_number, tx_hash, index);
+			Some(TransactionTrace { tx_hash, trace })
 		});
 
 		Ok(traces.collect())
@@ -795,10 +1037,73 @@ impl Client {
 			.ok_or(ClientError::EthExtrinsicNotFound)?;
 
 		let block = self.tracing_block(block_hash).await?;
+		let block_number = block.header.number;
 		let parent_hash = block.header.parent_hash;
 		let runtime_api = self.runtime_api(parent_hash);
 
-		runtime_api.trace_tx(block, transaction_index as u32, config).await
+		let mut trace = runtime_api.trace_tx(block, transaction_index as u32, config).await?;
+		let ethereum_hash = self.resolve_ethereum_hash(&block_hash).await.unwrap_or(block_hash);
+		fill_flat_traces(
+			&mut trace,
+			ethereum_hash,
+			block_number,
+			transaction_hash,
+			transaction_index as u32,
+		);
+		Ok(trace)
+	}
+
+	/// Get the flat call traces of the blocks in the range of the given filter, matching its
+	/// addresses.
+	///
+	/// See [`TraceFilter`] for the matching rules.
+	pub async fn trace_filter(
+		&self,
+		filter: TraceFilter,
+	) -> Result<Vec<FlatCallTrace>, ClientError> {
+		let latest = BlockNumberOrTag::BlockTag(BlockTag::Latest);
+		let from_block = self.block_by_number_or_tag(filter.from_block.as_ref().unwrap_or(&latest));
+		let from_block = from_block.await?.ok_or(ClientError::BlockNotFound)?.number();
+		let to_block = self.block_by_number_or_tag(filter.to_block.as_ref().unwrap_or(&latest));
+		let to_block = to_block.await?.ok_or(ClientError::BlockNotFound)?.number();
+
+		if from_block > to_block {
+			return Err(ClientError::InvalidFilter("fromBlock is greater than toBlock"));
+		}
+		if to_block - from_block >= MAX_TRACE_FILTER_BLOCK_RANGE {
+			return Err(ClientError::InvalidFilter("block range is too large"));
+		}
+
+		let after = filter.after.unwrap_or_default() as usize;
+		let count = filter.count.map_or(usize::MAX, |count| count as usize);
+		if count == 0 {
+			return Ok(Vec::new());
+		}
+		let config = TracerType::FlatCallTracer(None);
+
+		let mut traces = Vec::new();
+		let mut skipped = 0;
+		for number in from_block..=to_block {
+			let block_hash =
+				self.get_block_hash(number).await?.ok_or(ClientError::BlockNotFound)?;
+			for TransactionTrace { trace, .. } in
+				self.trace_block_by_hash(block_hash, config.clone()).await?
+			{
+				let Trace::Flat(flat_traces) = trace else { continue };
+				for trace in flat_traces.into_iter().filter(|trace| filter.matches(trace)) {
+					if skipped < after {
+						skipped += 1;
+						continue;
+					}
+					traces.push(trace);
+					if traces.len() >= count {
+						return Ok(traces);
+					}
+				}
+			}
+		}
+
+		Ok(traces)
 	}
 
 	/// Get the transaction traces for the given block.
@@ -813,6 +1118,175 @@ impl Client {
 		runtime_api.trace_call(transaction, config).await
 	}
 
+	/// Get the proof of the given account and storage slots, at the given block.
+	///
+	/// See [`AccountProof`] for the format of the proof.
+	pub async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccountProof, ClientError> {
+		let block_hash = self.block_hash_for_tag(block).await?;
+		let runtime_api = self.runtime_api(block_hash);
+		let slots = storage_keys.iter().map(|key| key.to_big_endian()).collect::<Vec<_>>();
+		let AccountProofKeys { account_keys, child_trie_key, storage_keys: hashed_keys, code_hash } =
+			runtime_api.account_proof_keys(address, slots).await?;
+
+		let keys = account_keys.iter().chain(child_trie_key.iter()).map(to_hex).collect::<Vec<_>>();
+		let account_proof: ReadProof<SubstrateBlockHash> = self
+			.rpc_client
+			.request("state_getReadProof", rpc_params![keys, block_hash])
+			.await?;
+
+		let storage_hash: Option<H256> = match &child_trie_key {
+			Some(child_trie_key) => {
+				self.rpc_client
+					.request("state_getStorage", rpc_params![to_hex(child_trie_key), block_hash])
+					.await?
+			},
+			None => None,
+		};
+
+		let mut storage_proof = Vec::with_capacity(storage_keys.len());
+		for (index, key) in storage_keys.into_iter().enumerate() {
+			let (Some(child_trie_key), Some(hashed_key)) =
+				(&child_trie_key, hashed_keys.get(index))
+			else {
+				// Not a contract, the storage is empty.
+				storage_proof.push(StorageProof { key, ..Default::default() });
+				continue;
+			};
+
+			let proof: ReadProof<SubstrateBlockHash> = self
+				.rpc_client
+				.request(
+					"state_getChildReadProof",
+					rpc_params![to_hex(child_trie_key), vec![to_hex(hashed_key)], block_hash],
+				)
+				.await?;
+			let value = match runtime_api.get_storage(address, key.to_big_endian()).await? {
+				Some(value) if value.len() > 32 => return Err(ClientError::ConversionFailed),
+				Some(value) => U256::from_big_endian(&value),
+				None => U256::zero(),
+			};
+
+			storage_proof.push(StorageProof {
+				key,
+				value,
+				proof: proof.proof.into_iter().map(|node| node.0.into()).collect(),
+			});
+		}
+
+		Ok(AccountProof {
+			address,
+			account_proof: account_proof.proof.into_iter().map(|node| node.0.into()).collect(),
+			balance: runtime_api.balance(address).await?,
+			code_hash,
+			nonce: runtime_api.nonce(address).await?,
+			storage_hash: storage_hash.unwrap_or_default(),
+			storage_proof,
+		})
+	}
+
+	/// Simulate the given blocks of calls on top of the given block.
+	///
+	/// See [`SimulatePayload`] for the supported options.
+	pub async fn simulate(
+		&self,
+		payload: SimulatePayload,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<Vec<SimulatedBlock>, ClientError> {
+		let SimulatePayload { block_state_calls, trace_transfers, validation } = payload;
+		if trace_transfers {
+			return Err(ClientError::Unsupported("traceTransfers"));
+		}
+
+		let block_hash = self.block_hash_for_tag(block).await?;
+		let runtime_api = self.runtime_api(block_hash);
+		let gas_limit = runtime_api.block_gas_limit().await?;
+		let base_fee_per_gas = runtime_api.gas_price().await?;
+
+		let transaction_hashes = block_state_calls
+			.iter()
+			.map(|block| {
+				block.calls.iter().cloned().map(simulated_transaction_hash).collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>();
+		let blocks = runtime_api.eth_simulate(block_state_calls, validation).await?;
+
+		let mut parent_hash = self.resolve_ethereum_hash(&block_hash).await.unwrap_or(block_hash);
+		Ok(blocks
+			.into_iter()
+			.zip(transaction_hashes)
+			.map(|(block, transaction_hashes)| {
+				let block = simulated_block(
+					block,
+					parent_hash,
+					transaction_hashes,
+					gas_limit,
+					base_fee_per_gas,
+				);
+				parent_hash = block.hash;
+				block
+			})
+			.collect())
+	}
+
+	/// Create the access list of the given transaction, at the given block.
+	///
+	/// The access list holds the accounts and storage slots accessed by the transaction, as
+	/// reported by the prestate tracer. The sender, the recipient and the block author are only
+	/// listed when some of their storage slots are accessed.
+	pub async fn create_access_list(
+		&self,
+		mut transaction: GenericTransaction,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccessListResult, ClientError> {
+		let block_hash = self.block_hash_for_tag(block.clone()).await?;
+		let runtime_api = self.runtime_api(block_hash);
+
+		let config =
+			PrestateTracerConfig { diff_mode: false, disable_storage: false, disable_code: true };
+		let trace = runtime_api
+			.trace_call(transaction.clone(), TracerType::PrestateTracer(Some(config)))
+			.await?;
+		let Trace::Prestate(PrestateTrace::Prestate(accounts)) = trace else {
+			return Err(ClientError::ConversionFailed);
+		};
+
+		let excluded = [transaction.from, transaction.to, Some(runtime_api.block_author().await?)];
+		let access_list = accounts
+			.into_iter()
+			.filter_map(|(address, info)| {
+				let storage_keys = info
+					.storage
+					.into_keys()
+					.filter(|key| key.0.len() == 32)
+					.map(|key| H256::from_slice(&key.0))
+					.collect::<Vec<_>>();
+				if storage_keys.is_empty() && excluded.contains(&Some(address)) {
+					return None;
+				}
+				Some(AccessListEntry { address, storage_keys })
+			})
+			.collect::<AccessList>();
+
+		transaction.access_list = Some(access_list.clone());
+		match runtime_api.dry_run(transaction, block).await {
+			Ok(info) => Ok(AccessListResult { access_list, gas_used: info.eth_gas, error: None }),
+			Err(ClientError::TransactError(err)) => {
+				let error = ErrorObjectOwned::from(ClientError::TransactError(err));
+				Ok(AccessListResult {
+					access_list,
+					gas_used: U256::zero(),
+					error: Some(error.message().to_string()),
+				})
+			},
+			Err(err) => Err(err),
+		}
+	}
+
 	/// Get the EVM block for the given Substrate block.
 	pub async fn evm_block(
 		&self,
@@ -868,6 +1342,53 @@ impl Client {
 		self.block_notifier.clone()
 	}
 
+	/// Subscribe to the new best blocks indexed by the client.
+	pub fn subscribe_new_heads(&self) -> tokio::sync::broadcast::Receiver<BlockNotification> {
+		self.new_heads_notifier.subscribe()
+	}
+
+	/// Subscribe to the hashes of the transactions submitted through this client.
+	pub fn subscribe_pending_transactions(&self) -> tokio::sync::broadcast::Receiver<H256> {
+		self.pending_transactions_notifier.subscribe()
+	}
+
+	/// Notify the pending transactions subscribers and filters of a newly submitted transaction.
+	pub async fn notify_pending_transaction(&self, hash: H256) {
+		self.filter_provider.on_pending_transaction(hash).await;
+		if self.pending_transactions_notifier.receiver_count() > 0 {
+			let _ = self.pending_transactions_notifier.send(hash);
+		}
+	}
+
+	/// Install a new filter and return its id.
+	pub async fn new_filter(&self, kind: FilterKind) -> Result<U256, ClientError> {
+		let latest_block = self.block_number().await?;
+		self.filter_provider.install(kind, latest_block).await
+	}
+
+	/// Uninstall the filter with the given id, returning whether it was installed.
+	pub async fn uninstall_filter(&self, id: &U256) -> bool {
+		self.filter_provider.uninstall(id).await
+	}
+
+	/// Get the changes of the filter with the given id, since it was last polled.
+	pub async fn filter_changes(&self, id: &U256) -> Result<FilterResults, ClientError> {
+		let latest_block = self.block_number().await?;
+		match self.filter_provider.poll(id, latest_block).await? {
+			FilterChanges::Hashes(hashes) => Ok(FilterResults::Hashes(hashes)),
+			FilterChanges::Logs(None) => Ok(FilterResults::Logs(vec![])),
+			FilterChanges::Logs(Some(filter)) => {
+				Ok(FilterResults::Logs(self.logs(Some(filter)).await?))
+			},
+		}
+	}
+
+	/// Get all the logs matching the log filter with the given id.
+	pub async fn filter_logs(&self, id: &U256) -> Result<FilterResults, ClientError> {
+		let filter = self.filter_provider.log_filter(id).await?;
+		Ok(FilterResults::Logs(self.logs(Some(filter)).await?))
+	}
+
 	/// Get the logs matching the given filter.
 	pub async fn logs(&self, filter: Option<Filter>) -> Result<Vec<Log>, ClientError> {
 		let logs =
@@ -22,10 +22,10 @@ use crate::{
 };
 use futures::TryFutureExt;
 use pallet_revive::{
-	DryRunConfig, EthTransactInfo,
+	AccountProofKeys, DryRunConfig, EthTransactInfo, SimulatedBlockInfo,
 	evm::{
 		Block as EthBlock, BlockNumberOrTagOrHash, BlockTag, GenericTransaction, H160,
-		ReceiptGasInfo, Trace, U256,
+		ReceiptGasInfo, SimulateBlock, Trace, U256,
 	},
 };
 use sp_core::H256;
@@ -66,6 +66,20 @@ impl RuntimeApi {
 		Ok(result)
 	}
 
+	/// Get the storage keys needed to prove the given account and storage slots.
+	pub async fn account_proof_keys(
+		&self,
+		address: H160,
+		keys: Vec<[u8; 32]>,
+	) -> Result<AccountProofKeys, ClientError> {
+		let payload = subxt_client::apis()
+			.revive_api()
+			.account_proof_keys(address, keys)
+			.unvalidated();
+		let proof_keys = self.0.call(payload).await?;
+		Ok(proof_keys.0)
+	}
+
 	/// Dry run a transaction and returns the [`EthTransactInfo`] for the transaction.
 	pub async fn dry_run(
 		&self,
@@ -122,6 +136,21 @@ impl RuntimeApi {
 		}
 	}
 
+	/// Simulate the given blocks of calls on top of the current block.
+	pub async fn eth_simulate(
+		&self,
+		blocks: Vec<SimulateBlock>,
+		validation: bool,
+	) -> Result<Vec<SimulatedBlockInfo>, ClientError> {
+		let blocks = blocks.into_iter().map(Into::into).collect();
+		let payload =
+			subxt_client::apis().revive_api().eth_simulate(blocks, validation).unvalidated();
+
+		let blocks =
+			self.0.call(payload).await?.map_err(|err| ClientError::TransactError(err.0))?;
+		Ok(blocks.into_iter().map(|block| block.0).collect())
+	}
+
 	/// Get the nonce of the given address.
 	pub async fn nonce(&self, address: H160) -> Result<U256, ClientError> {
 		let address = address.0.into();
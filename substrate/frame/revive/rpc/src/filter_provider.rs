@@ -0,0 +1,304 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{BlockNumberOrTag, BlockTag, ClientError, client::SubstrateBlockNumber};
+use pallet_revive::evm::Filter;
+use sp_core::{H256, U256};
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::{
+		Arc,
+		atomic::{AtomicU64, Ordering},
+	},
+	time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+const LOG_TARGET: &str = "eth-rpc::filter_provider";
+
+/// The default duration after which a filter that has not been polled is uninstalled.
+pub const DEFAULT_FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The maximum number of filters that can be installed at the same time.
+const MAX_FILTERS: usize = 1024;
+
+/// The maximum number of hashes buffered by a block or pending transaction filter between two
+/// polls. Older hashes are dropped first.
+const MAX_BUFFERED_HASHES: usize = 1024;
+
+/// The kind of an installed filter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterKind {
+	/// A log filter, created with `eth_newFilter`.
+	Logs(Filter),
+	/// A new block filter, created with `eth_newBlockFilter`.
+	Blocks,
+	/// A pending transaction filter, created with `eth_newPendingTransactionFilter`.
+	PendingTransactions,
+}
+
+/// The changes of a filter since it was last polled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterChanges {
+	/// The new block or transaction hashes.
+	Hashes(Vec<H256>),
+	/// The log filter to query, restricted to the blocks that have not been polled yet.
+	///
+	/// `None` when no new block matches the filter range.
+	Logs(Option<Filter>),
+}
+
+/// A filter installed by a client.
+struct InstalledFilter {
+	/// The kind of the filter.
+	kind: FilterKind,
+	/// The last time the filter was installed or polled.
+	last_poll: Instant,
+	/// The hashes received since the last poll, for block and pending transaction filters.
+	hashes: VecDeque<H256>,
+	/// The first block number that has not been polled yet, for log filters.
+	next_block: SubstrateBlockNumber,
+}
+
+/// Manages the filters installed with the `eth_new*Filter` methods.
+///
+/// Filters that are not polled within the configured timeout are uninstalled.
+#[derive(Clone)]
+pub struct FilterProvider {
+	/// The installed filters, keyed by id.
+	filters: Arc<Mutex<HashMap<U256, InstalledFilter>>>,
+	/// The id of the next installed filter.
+	next_id: Arc<AtomicU64>,
+	/// The duration after which a filter that has not been polled is uninstalled.
+	timeout: Duration,
+}
+
+impl Default for FilterProvider {
+	fn default() -> Self {
+		Self::new(DEFAULT_FILTER_TIMEOUT)
+	}
+}
+
+/// Resolve a filter block bound, `None` meaning that the bound follows the latest block.
+fn block_bound(
+	block: &Option<BlockNumberOrTag>,
+) -> Result<Option<SubstrateBlockNumber>, ClientError> {
+	match block {
+		Some(BlockNumberOrTag::U256(n)) => {
+			Ok(Some((*n).try_into().map_err(|_| ClientError::ConversionFailed)?))
+		},
+		Some(BlockNumberOrTag::BlockTag(BlockTag::Earliest)) => Ok(Some(0)),
+		Some(BlockNumberOrTag::BlockTag(_)) | None => Ok(None),
+	}
+}
+
+impl FilterProvider {
+	/// Create a new [`FilterProvider`] with the given filter timeout.
+	pub fn new(timeout: Duration) -> Self {
+		Self { filters: Default::default(), next_id: Arc::new(AtomicU64::new(1)), timeout }
+	}
+
+	/// Remove the filters that have not been polled within the timeout.
+	fn remove_expired(&self, filters: &mut HashMap<U256, InstalledFilter>) {
+		let timeout = self.timeout;
+		filters.retain(|id, filter| {
+			let keep = filter.last_poll.elapsed() < timeout;
+			if !keep {
+				log::debug!(target: LOG_TARGET, "Uninstalling expired filter {id:?}");
+			}
+			keep
+		});
+	}
+
+	/// Install a new filter and return its id.
+	///
+	/// `latest_block` is the latest block number at installation time. Log filters only report the
+	/// logs of the blocks that follow it.
+	pub async fn install(
+		&self,
+		kind: FilterKind,
+		latest_block: SubstrateBlockNumber,
+	) -> Result<U256, ClientError> {
+		if let FilterKind::Logs(filter) = &kind {
+			if filter.block_hash.is_some() {
+				return Err(ClientError::InvalidFilter("blockHash is not supported"));
+			}
+			if filter.topics.as_ref().is_some_and(|topics| topics.len() > 4) {
+				return Err(ClientError::InvalidFilter("exceed max topics"));
+			}
+			block_bound(&filter.from_block)?;
+			block_bound(&filter.to_block)?;
+		}
+
+		let mut filters = self.filters.lock().await;
+		self.remove_expired(&mut filters);
+		if filters.len() >= MAX_FILTERS {
+			return Err(ClientError::InvalidFilter("too many installed filters"));
+		}
+
+		let id = U256::from(self.next_id.fetch_add(1, Ordering::Relaxed));
+		log::debug!(target: LOG_TARGET, "Installing filter {id:?}: {kind:?}");
+		filters.insert(
+			id,
+			InstalledFilter {
+				kind,
+				last_poll: Instant::now(),
+				hashes: Default::default(),
+				next_block: latest_block.saturating_add(1),
+			},
+		);
+		Ok(id)
+	}
+
+	/// Uninstall the filter with the given id, returning whether it was installed.
+	pub async fn uninstall(&self, id: &U256) -> bool {
+		self.filters.lock().await.remove(id).is_some()
+	}
+
+	/// Push the given hash to all the installed filters of the given kind.
+	async fn push_hash(&self, kind: FilterKind, hash: H256) {
+		let mut filters = self.filters.lock().await;
+		self.remove_expired(&mut filters);
+		for filter in filters.values_mut().filter(|filter| filter.kind == kind) {
+			if filter.hashes.len() >= MAX_BUFFERED_HASHES {
+				filter.hashes.pop_front();
+			}
+			filter.hashes.push_back(hash);
+		}
+	}
+
+	/// Notify the block filters of a new best block.
+	pub async fn on_new_block(&self, ethereum_hash: H256) {
+		self.push_hash(FilterKind::Blocks, ethereum_hash).await
+	}
+
+	/// Notify the pending transaction filters of a newly submitted transaction.
+	pub async fn on_pending_transaction(&self, transaction_hash: H256) {
+		self.push_hash(FilterKind::PendingTransactions, transaction_hash).await
+	}
+
+	/// Poll the changes of the filter with the given id, since it was last polled.
+	pub async fn poll(
+		&self,
+		id: &U256,
+		latest_block: SubstrateBlockNumber,
+	) -> Result<FilterChanges, ClientError> {
+		let mut filters = self.filters.lock().await;
+		self.remove_expired(&mut filters);
+		let filter = filters.get_mut(id).ok_or(ClientError::FilterNotFound)?;
+		filter.last_poll = Instant::now();
+
+		let FilterKind::Logs(ref log_filter) = filter.kind else {
+			return Ok(FilterChanges::Hashes(filter.hashes.drain(..).collect()));
+		};
+
+		let from = block_bound(&log_filter.from_block)?.unwrap_or(0).max(filter.next_block);
+		let to = block_bound(&log_filter.to_block)?.unwrap_or(latest_block).min(latest_block);
+		filter.next_block = filter.next_block.max(latest_block.saturating_add(1));
+
+		if from > to {
+			return Ok(FilterChanges::Logs(None));
+		}
+
+		Ok(FilterChanges::Logs(Some(Filter {
+			from_block: Some(U256::from(from).into()),
+			to_block: Some(U256::from(to).into()),
+			..log_filter.clone()
+		})))
+	}
+
+	/// Get the log filter with the given id.
+	pub async fn log_filter(&self, id: &U256) -> Result<Filter, ClientError> {
+		let mut filters = self.filters.lock().await;
+		self.remove_expired(&mut filters);
+		let filter = filters.get_mut(id).ok_or(ClientError::FilterNotFound)?;
+		filter.last_poll = Instant::now();
+
+		match filter.kind {
+			FilterKind::Logs(ref filter) => Ok(filter.clone()),
+			_ => Err(ClientError::FilterNotFound),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[tokio::test]
+	async fn block_filter_returns_hashes_since_last_poll() {
+		let provider = FilterProvider::default();
+		let id = provider.install(FilterKind::Blocks, 1).await.unwrap();
+		let pending_id = provider.install(FilterKind::PendingTransactions, 1).await.unwrap();
+
+		provider.on_new_block(H256::from([1u8; 32])).await;
+		provider.on_new_block(H256::from([2u8; 32])).await;
+		provider.on_pending_transaction(H256::from([3u8; 32])).await;
+
+		assert_eq!(
+			provider.poll(&id, 3).await.unwrap(),
+			FilterChanges::Hashes(vec![H256::from([1u8; 32]), H256::from([2u8; 32])])
+		);
+		assert_eq!(provider.poll(&id, 3).await.unwrap(), FilterChanges::Hashes(vec![]));
+		assert_eq!(
+			provider.poll(&pending_id, 3).await.unwrap(),
+			FilterChanges::Hashes(vec![H256::from([3u8; 32])])
+		);
+
+		assert!(provider.uninstall(&id).await);
+		assert!(!provider.uninstall(&id).await);
+		assert!(matches!(provider.poll(&id, 3).await, Err(ClientError::FilterNotFound)));
+	}
+
+	#[tokio::test]
+	async fn log_filter_tracks_polled_blocks() {
+		let provider = FilterProvider::default();
+		let filter = Filter { to_block: Some(U256::from(7).into()), ..Default::default() };
+		let id = provider.install(FilterKind::Logs(filter), 2).await.unwrap();
+
+		// No new block since installation.
+		assert_eq!(provider.poll(&id, 2).await.unwrap(), FilterChanges::Logs(None));
+
+		let range = |from: u64, to: u64| {
+			FilterChanges::Logs(Some(Filter {
+				from_block: Some(U256::from(from).into()),
+				to_block: Some(U256::from(to).into()),
+				..Default::default()
+			}))
+		};
+		assert_eq!(provider.poll(&id, 5).await.unwrap(), range(3, 5));
+		assert_eq!(provider.poll(&id, 10).await.unwrap(), range(6, 7));
+		assert_eq!(provider.poll(&id, 12).await.unwrap(), FilterChanges::Logs(None));
+	}
+
+	#[tokio::test]
+	async fn invalid_log_filters_are_rejected() {
+		let provider = FilterProvider::default();
+		let filter = Filter { block_hash: Some(H256::zero()), ..Default::default() };
+		assert!(matches!(
+			provider.install(FilterKind::Logs(filter), 0).await,
+			Err(ClientError::InvalidFilter(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn expired_filters_are_uninstalled() {
+		let provider = FilterProvider::new(Duration::ZERO);
+		let id = provider.install(FilterKind::Blocks, 0).await.unwrap();
+		assert!(matches!(provider.poll(&id, 0).await, Err(ClientError::FilterNotFound)));
+	}
+}
@@ -44,6 +44,9 @@ pub use receipt_provider::*;
 mod fee_history_provider;
 pub use fee_history_provider::*;
 
+mod filter_provider;
+pub use filter_provider::*;
+
 mod receipt_extractor;
 pub use receipt_extractor::*;
 
@@ -194,6 +197,24 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(dry_run.data.into())
 	}
 
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult> {
+		let block = block.unwrap_or_default();
+		Ok(self.client.create_access_list(transaction, block).await?)
+	}
+
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>> {
+		let block = block.unwrap_or_default();
+		Ok(self.client.simulate(payload, block).await?)
+	}
+
 	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256> {
 		let hash = H256(keccak_256(&transaction.0));
 		log::trace!(target: LOG_TARGET, "send_raw_transaction transaction: {transaction:?} ethereum_hash: {hash:?}");
@@ -240,6 +261,8 @@ impl EthRpcServer for EthRpcServerImpl {
 			err
 		})?;
 
+		self.client.notify_pending_transaction(hash).await;
+
 		if matches!(tx_status, TransactionStatus::Future) {
 			return Ok(hash);
 		}
@@ -400,6 +423,39 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof> {
+		Ok(self.client.get_proof(address, storage_keys, block).await?)
+	}
+
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
+		Ok(self.client.new_filter(FilterKind::Logs(filter)).await?)
+	}
+
+	async fn new_block_filter(&self) -> RpcResult<U256> {
+		Ok(self.client.new_filter(FilterKind::Blocks).await?)
+	}
+
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
+		Ok(self.client.new_filter(FilterKind::PendingTransactions).await?)
+	}
+
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.client.filter_changes(&filter_id).await?)
+	}
+
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.client.filter_logs(&filter_id).await?)
+	}
+
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.client.uninstall_filter(&filter_id).await)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
@@ -0,0 +1,326 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Postgres implementation of the [`ReceiptStorage`].
+//!
+//! Unlike SQLite, a Postgres database can be shared by several eth-rpc instances, so writes use
+//! upserts and the receipts of a block are inserted in a single transaction.
+//!
+//! Blocks are pruned by block number rather than by count, so all the instances sharing a database
+//! should be configured with the same pruning depth. Every instance removes the blocks it sees
+//! re-orged out of its own view of the chain: the entries of a re-orged block indexed by a lagging
+//! instance are only removed once that instance catches up with the re-org, and the block
+//! replacing it is then indexed again to restore the transaction hashes they share. The entries
+//! left by an instance stopped before it caught up are repaired by the `index` subcommand.
+//!
+//! The offline query data in `.sqlx` is keyed by the query text, so the queries must not be
+//! written exactly like the SQLite ones, or they would be checked against the SQLite schema.
+use super::storage::{
+	BLOCK_MAPPINGS_AT_QUERY, BlockHashMap, LogFilter, LogRow, MAX_LOGS_PER_QUERY, ReceiptStorage,
+	logs_query,
+};
+use crate::client::SubstrateBlockNumber;
+use jsonrpsee::core::async_trait;
+use pallet_revive::evm::{Log, ReceiptInfo, TransactionSigned};
+use sp_core::H256;
+use sqlx::{PgPool, Postgres, query};
+use std::collections::HashMap;
+
+/// Stores transaction hashes, logs and block mappings in a Postgres database.
+#[derive(Clone)]
+pub struct PostgresReceiptStorage {
+	/// The database pool.
+	pool: PgPool,
+}
+
+impl PostgresReceiptStorage {
+	/// Create a new `PostgresReceiptStorage`, running the pending migrations.
+	pub async fn new(pool: PgPool) -> Result<Self, sqlx::Error> {
+		sqlx::migrate!("./migrations-postgres").run(&pool).await?;
+		Ok(Self { pool })
+	}
+
+	/// Create a new `PostgresReceiptStorage` from an already migrated pool.
+	#[cfg(test)]
+	pub(crate) fn from_pool(pool: PgPool) -> Self {
+		Self { pool }
+	}
+}
+
+#[async_trait]
+impl ReceiptStorage for PostgresReceiptStorage {
+	async fn find_transaction(
+		&self,
+		transaction_hash: &H256,
+	) -> Result<Option<(H256, usize)>, sqlx::Error> {
+		let result = query!(
+			"SELECT block_hash, transaction_index FROM transaction_hashes WHERE transaction_hash = $1",
+			transaction_hash.as_bytes()
+		)
+		.fetch_optional(&self.pool)
+		.await?;
+
+		Ok(result.and_then(|result| {
+			let block_hash = H256::from_slice(&result.block_hash);
+			let transaction_index = result.transaction_index.try_into().ok()?;
+			Some((block_hash, transaction_index))
+		}))
+	}
+
+	async fn insert_block_mapping(
+		&self,
+		block_map: &BlockHashMap,
+		block_number: SubstrateBlockNumber,
+	) -> Result<(), sqlx::Error> {
+		query!(
+			r#"
+			INSERT INTO eth_to_substrate_blocks (ethereum_block_hash, substrate_block_hash, block_number)
+			VALUES ($1, $2, $3)
+			ON CONFLICT (ethereum_block_hash)
+			DO UPDATE SET
+				substrate_block_hash = EXCLUDED.substrate_block_hash,
+				block_number = EXCLUDED.block_number
+			"#,
+			block_map.ethereum_hash.as_bytes(),
+			block_map.substrate_hash.as_bytes(),
+			block_number as i64,
+		)
+		.execute(&self.pool)
+		.await?;
+
+		Ok(())
+	}
+
+	async fn block_mappings_at(
+		&self,
+		block_number: SubstrateBlockNumber,
+	) -> Result<Vec<BlockHashMap>, sqlx::Error> {
+		let rows = sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(BLOCK_MAPPINGS_AT_QUERY)
+			.bind(block_number as i64)
+			.fetch_all(&self.pool)
+			.await?;
+
+		Ok(rows
+			.into_iter()
+			.map(|(ethereum_hash, substrate_hash)| {
+				BlockHashMap::new(
+					H256::from_slice(&substrate_hash),
+					H256::from_slice(&ethereum_hash),
+				)
+			})
+			.collect())
+	}
+
+	async fn get_substrate_hash(&self, ethereum_hash: &H256) -> Result<Option<H256>, sqlx::Error> {
+		let result = query!(
+			"SELECT substrate_block_hash FROM eth_to_substrate_blocks WHERE ethereum_block_hash = $1",
+			ethereum_hash.as_bytes()
+		)
+		.fetch_optional(&self.pool)
+		.await?;
+
+		Ok(result.map(|result| H256::from_slice(&result.substrate_block_hash)))
+	}
+
+	async fn get_ethereum_hash(&self, substrate_hash: &H256) -> Result<Option<H256>, sqlx::Error> {
+		let result = query!(
+			"SELECT ethereum_block_hash FROM eth_to_substrate_blocks WHERE substrate_block_hash = $1",
+			substrate_hash.as_bytes()
+		)
+		.fetch_optional(&self.pool)
+		.await?;
+
+		Ok(result.map(|result| H256::from_slice(&result.ethereum_block_hash)))
+	}
+
+	async fn has_block_mapping(&self, substrate_hash: &H256) -> Result<bool, sqlx::Error> {
+		let result = query!(
+			r#"SELECT EXISTS(SELECT 1 FROM eth_to_substrate_blocks WHERE substrate_block_hash = $1) AS "exists!""#,
+			substrate_hash.as_bytes()
+		)
+		.fetch_one(&self.pool)
+		.await?;
+
+		Ok(result.exists)
+	}
+
+	async fn insert_receipts(
+		&self,
+		block_map: &BlockHashMap,
+		block_number: SubstrateBlockNumber,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), sqlx::Error> {
+		let mut tx = self.pool.begin().await?;
+
+		for (_, receipt) in receipts {
+			let transaction_hash = receipt.transaction_hash.as_bytes();
+			let transaction_index = receipt.transaction_index.as_u64() as i64;
+
+			query!(
+				r#"
+				INSERT INTO transaction_hashes (transaction_hash, block_hash, transaction_index)
+				VALUES ($1, $2, $3)
+				ON CONFLICT (transaction_hash)
+				DO UPDATE SET
+					block_hash = EXCLUDED.block_hash,
+					transaction_index = EXCLUDED.transaction_index
+				"#,
+				transaction_hash,
+				block_map.substrate_hash.as_bytes(),
+				transaction_index,
+			)
+			.execute(&mut *tx)
+			.await?;
+
+			for log in &receipt.logs {
+				query!(
+					r#"
+					INSERT INTO logs(
+						block_hash,
+						transaction_index,
+						log_index,
+						address,
+						block_number,
+						transaction_hash,
+						topic_0, topic_1, topic_2, topic_3,
+						data)
+					VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+					ON CONFLICT DO NOTHING
+					"#,
+					block_map.ethereum_hash.as_bytes(),
+					transaction_index,
+					log.log_index.as_u64() as i64,
+					log.address.as_bytes(),
+					block_number as i64,
+					transaction_hash,
+					log.topics.first().map(|v| v.as_bytes()),
+					log.topics.get(1).map(|v| v.as_bytes()),
+					log.topics.get(2).map(|v| v.as_bytes()),
+					log.topics.get(3).map(|v| v.as_bytes()),
+					log.data.as_ref().map(|v| &v.0[..]),
+				)
+				.execute(&mut *tx)
+				.await?;
+			}
+		}
+
+		tx.commit().await
+	}
+
+	async fn remove(&self, block_mappings: &[BlockHashMap]) -> Result<(), sqlx::Error> {
+		if block_mappings.is_empty() {
+			return Ok(());
+		}
+
+		let substrate_hashes =
+			block_mappings.iter().map(|m| m.substrate_hash.0.to_vec()).collect::<Vec<_>>();
+		// logs table uses ethereum block hash
+		let ethereum_hashes =
+			block_mappings.iter().map(|m| m.ethereum_hash.0.to_vec()).collect::<Vec<_>>();
+
+		let mut tx = self.pool.begin().await?;
+		query!("DELETE FROM transaction_hashes WHERE block_hash = ANY($1)", &substrate_hashes)
+			.execute(&mut *tx)
+			.await?;
+		query!("DELETE FROM logs WHERE block_hash = ANY($1)", &ethereum_hashes)
+			.execute(&mut *tx)
+			.await?;
+		query!(
+			"DELETE FROM eth_to_substrate_blocks WHERE substrate_block_hash = ANY($1)",
+			&substrate_hashes
+		)
+		.execute(&mut *tx)
+		.await?;
+		tx.commit().await
+	}
+
+	async fn prune(&self, before: SubstrateBlockNumber) -> Result<(), sqlx::Error> {
+		let before = before as i64;
+
+		let mut tx = self.pool.begin().await?;
+		query!(
+			r#"
+			DELETE FROM transaction_hashes
+			WHERE block_hash = ANY(ARRAY(
+				SELECT substrate_block_hash FROM eth_to_substrate_blocks WHERE block_number < $1
+				UNION
+				SELECT m.substrate_block_hash
+				FROM logs l
+				JOIN eth_to_substrate_blocks m ON m.ethereum_block_hash = l.block_hash
+				WHERE l.block_number < $1
+			))
+			"#,
+			before
+		)
+		.execute(&mut *tx)
+		.await?;
+		query!(
+			r#"
+			DELETE FROM eth_to_substrate_blocks
+			WHERE block_number < $1
+				OR ethereum_block_hash = ANY(ARRAY(SELECT block_hash FROM logs WHERE block_number < $1))
+			"#,
+			before
+		)
+		.execute(&mut *tx)
+		.await?;
+		query!("DELETE FROM logs WHERE block_number < $1::BIGINT", before)
+			.execute(&mut *tx)
+			.await?;
+		tx.commit().await
+	}
+
+	async fn logs(&self, filter: &LogFilter) -> Result<Vec<Log>, sqlx::Error> {
+		let mut qb = logs_query::<Postgres>(filter);
+		qb.push(" ORDER BY block_number, transaction_index, log_index LIMIT ")
+			.push_bind(MAX_LOGS_PER_QUERY);
+
+		let rows = qb.build_query_as::<LogRow>().fetch_all(&self.pool).await?;
+		Ok(rows.into_iter().map(Log::from).collect())
+	}
+
+	async fn receipts_count_per_block(&self, block_hash: &H256) -> Result<usize, sqlx::Error> {
+		let result = query!(
+			r#"SELECT COUNT(*) AS "count!" FROM transaction_hashes WHERE block_hash = $1"#,
+			block_hash.as_bytes()
+		)
+		.fetch_one(&self.pool)
+		.await?;
+
+		Ok(result.count as usize)
+	}
+
+	async fn block_transaction_hashes(
+		&self,
+		block_hash: &H256,
+	) -> Result<HashMap<usize, H256>, sqlx::Error> {
+		let rows = query!(
+			r#"
+			SELECT transaction_index, transaction_hash
+			FROM transaction_hashes
+			WHERE block_hash = $1
+			"#,
+			block_hash.as_bytes()
+		)
+		.fetch_all(&self.pool)
+		.await?;
+
+		Ok(rows
+			.into_iter()
+			.map(|row| (row.transaction_index as usize, H256::from_slice(&row.transaction_hash)))
+			.collect())
+	}
+}
@@ -0,0 +1,350 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! SQLite implementation of the [`ReceiptStorage`].
+use super::storage::{
+	BLOCK_MAPPINGS_AT_QUERY, BlockHashMap, LogFilter, LogRow, MAX_LOGS_PER_QUERY, ReceiptStorage,
+	logs_query,
+};
+use crate::client::SubstrateBlockNumber;
+use jsonrpsee::core::async_trait;
+use pallet_revive::evm::{Log, ReceiptInfo, TransactionSigned};
+use sp_core::H256;
+use sqlx::{Sqlite, SqlitePool, query};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Stores transaction hashes, logs and block mappings in a SQLite database.
+#[derive(Clone)]
+pub struct SqliteReceiptStorage {
+	/// The database pool.
+	pool: SqlitePool,
+	/// Held while writing, so that concurrent writers, e.g. the blocks indexed in parallel, wait
+	/// for each other instead of failing with "database is locked".
+	write_lock: Arc<Mutex<()>>,
+}
+
+impl SqliteReceiptStorage {
+	/// Create a new `SqliteReceiptStorage`, running the pending migrations.
+	pub async fn new(pool: SqlitePool) -> Result<Self, sqlx::Error> {
+		sqlx::migrate!().run(&pool).await?;
+		Ok(Self::from_pool(pool))
+	}
+
+	/// Create a new `SqliteReceiptStorage` from an already migrated pool.
+	pub(crate) fn from_pool(pool: SqlitePool) -> Self {
+		Self { pool, write_lock: Default::default() }
+	}
+}
+
+#[async_trait]
+impl ReceiptStorage for SqliteReceiptStorage {
+	async fn find_transaction(
+		&self,
+		transaction_hash: &H256,
+	) -> Result<Option<(H256, usize)>, sqlx::Error> {
+		let transaction_hash = transaction_hash.as_ref();
+		let result = query!(
+			r#"
+			SELECT block_hash, transaction_index
+			FROM transaction_hashes
+			WHERE transaction_hash = $1
+			"#,
+			transaction_hash
+		)
+		.fetch_optional(&self.pool)
+		.await?;
+
+		Ok(result.and_then(|result| {
+			let block_hash = H256::from_slice(&result.block_hash[..]);
+			let transaction_index = result.transaction_index.try_into().ok()?;
+			Some((block_hash, transaction_index))
+		}))
+	}
+
+	async fn insert_block_mapping(
+		&self,
+		block_map: &BlockHashMap,
+		block_number: SubstrateBlockNumber,
+	) -> Result<(), sqlx::Error> {
+		let ethereum_hash_ref = block_map.ethereum_hash.as_ref();
+		let substrate_hash_ref = block_map.substrate_hash.as_ref();
+		let block_number = block_number as i64;
+
+		let _write = self.write_lock.lock().await;
+		query!(
+			r#"
+			INSERT OR REPLACE INTO eth_to_substrate_blocks (ethereum_block_hash, substrate_block_hash, block_number)
+			VALUES ($1, $2, $3)
+			"#,
+			ethereum_hash_ref,
+			substrate_hash_ref,
+			block_number,
+		)
+		.execute(&self.pool)
+		.await?;
+
+		Ok(())
+	}
+
+	async fn block_mappings_at(
+		&self,
+		block_number: SubstrateBlockNumber,
+	) -> Result<Vec<BlockHashMap>, sqlx::Error> {
+		let rows = sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(BLOCK_MAPPINGS_AT_QUERY)
+			.bind(block_number as i64)
+			.fetch_all(&self.pool)
+			.await?;
+
+		Ok(rows
+			.into_iter()
+			.map(|(ethereum_hash, substrate_hash)| {
+				BlockHashMap::new(
+					H256::from_slice(&substrate_hash),
+					H256::from_slice(&ethereum_hash),
+				)
+			})
+			.collect())
+	}
+
+	async fn get_substrate_hash(&self, ethereum_hash: &H256) -> Result<Option<H256>, sqlx::Error> {
+		let ethereum_hash = ethereum_hash.as_ref();
+		let result = query!(
+			r#"
+			SELECT substrate_block_hash
+			FROM eth_to_substrate_blocks
+			WHERE ethereum_block_hash = $1
+			"#,
+			ethereum_hash
+		)
+		.fetch_optional(&self.pool)
+		.await?;
+
+		Ok(result.map(|result| H256::from_slice(&result.substrate_block_hash[..])))
+	}
+
+	async fn get_ethereum_hash(&self, substrate_hash: &H256) -> Result<Option<H256>, sqlx::Error> {
+		let substrate_hash = substrate_hash.as_ref();
+		let result = query!(
+			r#"
+			SELECT ethereum_block_hash
+			FROM eth_to_substrate_blocks
+			WHERE substrate_block_hash = $1
+			"#,
+			substrate_hash
+		)
+		.fetch_optional(&self.pool)
+		.await?;
+
+		Ok(result.map(|result| H256::from_slice(&result.ethereum_block_hash[..])))
+	}
+
+	async fn has_block_mapping(&self, substrate_hash: &H256) -> Result<bool, sqlx::Error> {
+		let substrate_hash_ref = substrate_hash.as_ref();
+		let result = sqlx::query!(
+			r#"SELECT EXISTS(SELECT 1 FROM eth_to_substrate_blocks WHERE substrate_block_hash = $1) AS "exists!:bool""#, substrate_hash_ref
+		)
+		.fetch_one(&self.pool)
+		.await?;
+
+		Ok(result.exists)
+	}
+
+	async fn insert_receipts(
+		&self,
+		block_map: &BlockHashMap,
+		block_number: SubstrateBlockNumber,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), sqlx::Error> {
+		let substrate_hash_ref = block_map.substrate_hash.as_ref();
+		let ethereum_hash_ref = block_map.ethereum_hash.as_ref();
+		let block_number = block_number as i64;
+
+		let _write = self.write_lock.lock().await;
+		for (_, receipt) in receipts {
+			let transaction_hash: &[u8] = receipt.transaction_hash.as_ref();
+			let transaction_index = receipt.transaction_index.as_u32() as i32;
+
+			query!(
+				r#"
+					INSERT OR REPLACE INTO transaction_hashes (transaction_hash, block_hash, transaction_index)
+					VALUES ($1, $2, $3)
+					"#,
+				transaction_hash,
+				substrate_hash_ref,
+				transaction_index
+			)
+			.execute(&self.pool)
+			.await?;
+
+			for log in &receipt.logs {
+				let log_index = log.log_index.as_u32() as i32;
+				let address: &[u8] = log.address.as_ref();
+
+				let topic_0 = log.topics.first().as_ref().map(|v| &v[..]);
+				let topic_1 = log.topics.get(1).as_ref().map(|v| &v[..]);
+				let topic_2 = log.topics.get(2).as_ref().map(|v| &v[..]);
+				let topic_3 = log.topics.get(3).as_ref().map(|v| &v[..]);
+				let data = log.data.as_ref().map(|v| &v.0[..]);
+
+				query!(
+					r#"
+						INSERT INTO logs(
+							block_hash,
+							transaction_index,
+							log_index,
+							address,
+							block_number,
+							transaction_hash,
+							topic_0, topic_1, topic_2, topic_3,
+							data)
+						VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+						"#,
+					ethereum_hash_ref,
+					transaction_index,
+					log_index,
+					address,
+					block_number,
+					transaction_hash,
+					topic_0,
+					topic_1,
+					topic_2,
+					topic_3,
+					data
+				)
+				.execute(&self.pool)
+				.await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn remove(&self, block_mappings: &[BlockHashMap]) -> Result<(), sqlx::Error> {
+		if block_mappings.is_empty() {
+			return Ok(());
+		}
+
+		let placeholders = vec!["?"; block_mappings.len()].join(", ");
+		let sql = format!("DELETE FROM transaction_hashes WHERE block_hash in ({placeholders})");
+
+		let mut delete_tx_query = sqlx::query(&sql);
+		let sql = format!(
+			"DELETE FROM eth_to_substrate_blocks WHERE substrate_block_hash in ({placeholders})"
+		);
+		let mut delete_mappings_query = sqlx::query(&sql);
+
+		let sql = format!("DELETE FROM logs WHERE block_hash in ({placeholders})");
+		let mut delete_logs_query = sqlx::query(&sql);
+
+		for block_map in block_mappings {
+			delete_tx_query = delete_tx_query.bind(block_map.substrate_hash.as_ref());
+			delete_mappings_query = delete_mappings_query.bind(block_map.substrate_hash.as_ref());
+			// logs table uses  ethereum block hash
+			delete_logs_query = delete_logs_query.bind(block_map.ethereum_hash.as_ref());
+		}
+
+		let _write = self.write_lock.lock().await;
+		let delete_transaction_hashes = delete_tx_query.execute(&self.pool);
+		let delete_logs = delete_logs_query.execute(&self.pool);
+		let delete_mappings = delete_mappings_query.execute(&self.pool);
+		tokio::try_join!(delete_transaction_hashes, delete_logs, delete_mappings)?;
+		Ok(())
+	}
+
+	async fn prune(&self, before: SubstrateBlockNumber) -> Result<(), sqlx::Error> {
+		let before = before as i64;
+
+		let _write = self.write_lock.lock().await;
+		let mut tx = self.pool.begin().await?;
+		query!(
+			r#"
+			DELETE FROM transaction_hashes
+			WHERE block_hash IN (
+				SELECT substrate_block_hash FROM eth_to_substrate_blocks WHERE block_number < $1
+				UNION
+				SELECT m.substrate_block_hash
+				FROM logs l
+				JOIN eth_to_substrate_blocks m ON m.ethereum_block_hash = l.block_hash
+				WHERE l.block_number < $1
+			)
+			"#,
+			before
+		)
+		.execute(&mut *tx)
+		.await?;
+		query!(
+			r#"
+			DELETE FROM eth_to_substrate_blocks
+			WHERE block_number < $1
+				OR ethereum_block_hash IN (SELECT block_hash FROM logs WHERE block_number < $1)
+			"#,
+			before
+		)
+		.execute(&mut *tx)
+		.await?;
+		query!("DELETE FROM logs WHERE block_number < $1", before).execute(&mut *tx).await?;
+		tx.commit().await
+	}
+
+	async fn logs(&self, filter: &LogFilter) -> Result<Vec<Log>, sqlx::Error> {
+		let mut qb = logs_query::<Sqlite>(filter);
+		qb.push(" LIMIT ").push_bind(MAX_LOGS_PER_QUERY);
+
+		let rows = qb.build_query_as::<LogRow>().fetch_all(&self.pool).await?;
+		Ok(rows.into_iter().map(Log::from).collect())
+	}
+
+	async fn receipts_count_per_block(&self, block_hash: &H256) -> Result<usize, sqlx::Error> {
+		let block_hash = block_hash.as_ref();
+		let row = query!(
+			r#"
+            SELECT COUNT(*) as count
+            FROM transaction_hashes
+            WHERE block_hash = $1
+            "#,
+			block_hash
+		)
+		.fetch_one(&self.pool)
+		.await?;
+
+		Ok(row.count as usize)
+	}
+
+	async fn block_transaction_hashes(
+		&self,
+		block_hash: &H256,
+	) -> Result<HashMap<usize, H256>, sqlx::Error> {
+		let block_hash = block_hash.as_ref();
+		let rows = query!(
+			r#"
+		      SELECT transaction_index, transaction_hash
+		      FROM transaction_hashes
+		      WHERE block_hash = $1
+		      "#,
+			block_hash
+		)
+		.map(|row| {
+			let transaction_index = row.transaction_index as usize;
+			let transaction_hash = H256::from_slice(&row.transaction_hash);
+			(transaction_index, transaction_hash)
+		})
+		.fetch_all(&self.pool)
+		.await?;
+
+		Ok(rows.into_iter().collect())
+	}
+}
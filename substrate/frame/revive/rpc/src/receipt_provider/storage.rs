@@ -0,0 +1,256 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The storage backend abstraction used by the [`crate::ReceiptProvider`].
+use crate::{Address, Bytes, client::SubstrateBlockNumber};
+use jsonrpsee::core::async_trait;
+use pallet_revive::evm::{Log, ReceiptInfo, TransactionSigned};
+use sp_core::{H256, U256};
+use sqlx::{Database, Encode, QueryBuilder, Type};
+use std::collections::HashMap;
+
+/// Maximum number of logs returned by a single log query.
+pub(crate) const MAX_LOGS_PER_QUERY: i64 = 10_000;
+
+/// Substrate block to Ethereum block mapping
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockHashMap {
+	/// The Substrate block hash.
+	pub substrate_hash: H256,
+	/// The Ethereum block hash.
+	pub ethereum_hash: H256,
+}
+
+impl BlockHashMap {
+	/// Create a new mapping between a Substrate and an Ethereum block hash.
+	pub fn new(substrate_hash: H256, ethereum_hash: H256) -> Self {
+		Self { substrate_hash, ethereum_hash }
+	}
+}
+
+/// The block constraint of a [`LogFilter`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogBlockFilter {
+	/// Logs in the given inclusive block number range.
+	Range { from: Option<u64>, to: Option<u64> },
+	/// Logs in the block with the given Ethereum block hash.
+	Hash(H256),
+}
+
+/// A log query, resolved from an Ethereum [`pallet_revive::evm::Filter`].
+///
+/// Block tags are already resolved to block numbers, so storage backends only have to translate
+/// it into SQL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogFilter {
+	/// The block constraint.
+	pub block: LogBlockFilter,
+	/// When `Some`, only logs emitted by one of these addresses match.
+	pub addresses: Option<Vec<Address>>,
+	/// Topic constraints, indexed by topic position. A log matches if its topic at each position
+	/// is one of the given hashes.
+	pub topics: Vec<Vec<H256>>,
+}
+
+/// Storage backend for transaction hashes, logs and block mappings.
+///
+/// The [`crate::ReceiptProvider`] owns the in-memory state (fork detection, pruning) and
+/// delegates persistence to an implementation of this trait.
+#[async_trait]
+pub trait ReceiptStorage: Send + Sync {
+	/// Get the block hash and transaction index of the given transaction hash.
+	async fn find_transaction(
+		&self,
+		transaction_hash: &H256,
+	) -> Result<Option<(H256, usize)>, sqlx::Error>;
+
+	/// Insert a block mapping from Ethereum block hash to Substrate block hash.
+	async fn insert_block_mapping(
+		&self,
+		block_map: &BlockHashMap,
+		block_number: SubstrateBlockNumber,
+	) -> Result<(), sqlx::Error>;
+
+	/// Get the mappings of all the blocks indexed at the given block number.
+	///
+	/// Blocks indexed before their block number was recorded are found through their logs.
+	async fn block_mappings_at(
+		&self,
+		block_number: SubstrateBlockNumber,
+	) -> Result<Vec<BlockHashMap>, sqlx::Error>;
+
+	/// Get the Substrate block hash for the given Ethereum block hash.
+	async fn get_substrate_hash(&self, ethereum_hash: &H256) -> Result<Option<H256>, sqlx::Error>;
+
+	/// Get the Ethereum block hash for the given Substrate block hash.
+	async fn get_ethereum_hash(&self, substrate_hash: &H256) -> Result<Option<H256>, sqlx::Error>;
+
+	/// Check whether a block mapping exists for the given Substrate block hash.
+	async fn has_block_mapping(&self, substrate_hash: &H256) -> Result<bool, sqlx::Error>;
+
+	/// Insert the transaction hashes and logs of a block.
+	///
+	/// The block mapping is inserted separately, once the receipts are stored, see
+	/// [`Self::has_block_mapping`].
+	async fn insert_receipts(
+		&self,
+		block_map: &BlockHashMap,
+		block_number: SubstrateBlockNumber,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), sqlx::Error>;
+
+	/// Delete all the records of the given blocks.
+	async fn remove(&self, block_mappings: &[BlockHashMap]) -> Result<(), sqlx::Error>;
+
+	/// Delete all the records of the blocks below the given block number.
+	///
+	/// Unlike [`Self::remove`], this also deletes the blocks indexed by other instances sharing
+	/// the database, or by this instance before it restarted. Blocks indexed before their block
+	/// number was recorded are found through their logs.
+	async fn prune(&self, before: SubstrateBlockNumber) -> Result<(), sqlx::Error>;
+
+	/// Get the logs that match the given filter.
+	async fn logs(&self, filter: &LogFilter) -> Result<Vec<Log>, sqlx::Error>;
+
+	/// Get the number of transactions stored for the given block hash.
+	async fn receipts_count_per_block(&self, block_hash: &H256) -> Result<usize, sqlx::Error>;
+
+	/// Get all transaction hashes for the given block hash, keyed by transaction index.
+	async fn block_transaction_hashes(
+		&self,
+		block_hash: &H256,
+	) -> Result<HashMap<usize, H256>, sqlx::Error>;
+}
+
+/// Query used by [`ReceiptStorage::block_mappings_at`], shared by all backends.
+pub(crate) const BLOCK_MAPPINGS_AT_QUERY: &str = r#"
+	SELECT ethereum_block_hash, substrate_block_hash
+	FROM eth_to_substrate_blocks
+	WHERE block_number = $1
+	UNION
+	SELECT m.ethereum_block_hash, m.substrate_block_hash
+	FROM logs l
+	JOIN eth_to_substrate_blocks m ON m.ethereum_block_hash = l.block_hash
+	WHERE l.block_number = $1
+"#;
+
+/// A row of the `logs` table.
+#[derive(sqlx::FromRow)]
+pub(crate) struct LogRow {
+	block_hash: Vec<u8>,
+	transaction_index: i64,
+	log_index: i64,
+	address: Vec<u8>,
+	block_number: i64,
+	transaction_hash: Vec<u8>,
+	topic_0: Option<Vec<u8>>,
+	topic_1: Option<Vec<u8>>,
+	topic_2: Option<Vec<u8>>,
+	topic_3: Option<Vec<u8>>,
+	data: Option<Vec<u8>>,
+}
+
+impl From<LogRow> for Log {
+	fn from(row: LogRow) -> Self {
+		let topics = [row.topic_0, row.topic_1, row.topic_2, row.topic_3]
+			.iter()
+			.filter_map(|t| t.as_ref().map(|t| H256::from_slice(t)))
+			.collect::<Vec<_>>();
+
+		Log {
+			address: Address::from_slice(&row.address),
+			block_hash: H256::from_slice(&row.block_hash),
+			block_number: U256::from(row.block_number as u64),
+			data: row.data.map(Bytes::from),
+			log_index: U256::from(row.log_index as u64),
+			topics,
+			transaction_hash: H256::from_slice(&row.transaction_hash),
+			transaction_index: U256::from(row.transaction_index as u64),
+			removed: false,
+		}
+	}
+}
+
+/// Build a `SELECT` statement on the `logs` table, constrained by the given filter.
+///
+/// The statement is left open so backends can append ordering and limits.
+pub(crate) fn logs_query<'args, DB>(filter: &LogFilter) -> QueryBuilder<'args, DB>
+where
+	DB: Database,
+	DB::Arguments<'args>: Default,
+	i64: Encode<'args, DB> + Type<DB>,
+	Vec<u8>: Encode<'args, DB> + Type<DB>,
+{
+	let mut qb = QueryBuilder::<DB>::new("SELECT logs.* FROM logs WHERE 1=1");
+
+	match filter.block {
+		LogBlockFilter::Range { from: Some(from), to: Some(to) } if from == to => {
+			qb.push(" AND block_number = ").push_bind(from as i64);
+		},
+		LogBlockFilter::Range { from: Some(from), to: Some(to) } => {
+			qb.push(" AND block_number BETWEEN ")
+				.push_bind(from as i64)
+				.push(" AND ")
+				.push_bind(to as i64);
+		},
+		LogBlockFilter::Range { from: Some(from), to: None } => {
+			qb.push(" AND block_number >= ").push_bind(from as i64);
+		},
+		LogBlockFilter::Range { from: None, to: Some(to) } => {
+			qb.push(" AND block_number <= ").push_bind(to as i64);
+		},
+		LogBlockFilter::Range { from: None, to: None } => {},
+		LogBlockFilter::Hash(hash) => {
+			qb.push(" AND block_hash = ").push_bind(hash.0.to_vec());
+		},
+	}
+
+	if let Some(addresses) = &filter.addresses {
+		push_in(&mut qb, "address", addresses.iter().map(|addr| addr.0.to_vec()).collect());
+	}
+
+	for (i, topics) in filter.topics.iter().enumerate() {
+		push_in(
+			&mut qb,
+			&format!("topic_{i}"),
+			topics.iter().map(|hash| hash.0.to_vec()).collect(),
+		);
+	}
+
+	qb
+}
+
+/// Push an `AND column IN (...)` clause, using `=` for a single value.
+///
+/// An empty list of values matches nothing.
+fn push_in<'args, DB>(qb: &mut QueryBuilder<'args, DB>, column: &str, mut values: Vec<Vec<u8>>)
+where
+	DB: Database,
+	Vec<u8>: Encode<'args, DB> + Type<DB>,
+{
+	if values.is_empty() {
+		qb.push(" AND 1 = 0");
+	} else if values.len() == 1 {
+		qb.push(format_args!(" AND {column} = ")).push_bind(values.swap_remove(0));
+	} else {
+		qb.push(format_args!(" AND {column} IN ("));
+		let mut separated = qb.separated(", ");
+		for value in values {
+			separated.push_bind(value);
+		}
+		separated.push_unseparated(")");
+	}
+}
@@ -63,6 +63,18 @@ pub use subxt::config::PolkadotConfig as SrcChainConfig;
 		path = "pallet_revive::primitives::ExecReturnValue",
 		with = "::subxt::utils::Static<::pallet_revive::ExecReturnValue>"
 	),
+	substitute_type(
+		path = "pallet_revive::primitives::AccountProofKeys",
+		with = "::subxt::utils::Static<::pallet_revive::AccountProofKeys>"
+	),
+	substitute_type(
+		path = "pallet_revive::evm::api::simulate_rpc_types::SimulateBlock",
+		with = "::subxt::utils::Static<::pallet_revive::evm::SimulateBlock>"
+	),
+	substitute_type(
+		path = "pallet_revive::primitives::SimulatedBlockInfo",
+		with = "::subxt::utils::Static<::pallet_revive::SimulatedBlockInfo>"
+	),
 	substitute_type(
 		path = "sp_weights::weight_v2::Weight",
 		with = "::subxt::utils::Static<::sp_weights::Weight>"
@@ -19,7 +19,7 @@
 //! [evm-test-suite](https://github.com/paritytech/evm-test-suite) repository.
 
 use crate::{
-	EthRpcClient,
+	EthPubSubRpcClient, EthRpcClient, TraceRpcClient,
 	cli::{self, CliCommand},
 	example::TransactionBuilder,
 	subxt_client::{
@@ -32,8 +32,10 @@ use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use pallet_revive::{
 	create1,
 	evm::{
-		Account, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockTag, H256,
-		HashesOrTransactionInfos, TransactionInfo, TransactionUnsigned, U256,
+		Account, AccountOverride, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockOverrides,
+		BlockTag, FilterResults, FlatCallTraceType, FlatCallType, GenericTransaction, H160, H256,
+		HashesOrTransactionInfos, SimulateBlock, SimulatePayload, SubscriptionItem,
+		SubscriptionKind, TraceFilter, TransactionInfo, TransactionUnsigned, U256,
 	},
 };
 use std::{sync::Arc, thread};
@@ -317,6 +319,12 @@ async fn run_all_eth_rpc_tests_inner() -> anyhow::Result<()> {
 		test_multiple_transactions_in_block,
 		test_mixed_evm_substrate_transactions,
 		test_runtime_pallets_address_upload_code,
+		test_subscribe_new_heads_and_pending_transactions,
+		test_block_and_pending_transaction_filters,
+		test_get_proof,
+		test_simulate_v1,
+		test_create_access_list,
+		test_trace_apis,
 	);
 
 	log::debug!(target: LOG_TARGET, "All tests completed successfully!");
@@ -732,6 +740,223 @@ async fn test_multiple_transactions_in_block() -> anyhow::Result<()> {
 	Ok(())
 }
 
+async fn test_subscribe_new_heads_and_pending_transactions() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let mut new_heads = client.subscribe_events(SubscriptionKind::NewHeads, None).await?;
+	let mut pending_txs =
+		client.subscribe_events(SubscriptionKind::NewPendingTransactions, None).await?;
+
+	let tx = TransactionBuilder::new(client.clone())
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let Some(SubscriptionItem::TransactionHash(hash)) = pending_txs.next().await.transpose()?
+	else {
+		return Err(anyhow!("Expected a pending transaction hash"));
+	};
+	assert_eq!(hash, tx.hash());
+
+	loop {
+		let Some(SubscriptionItem::Header(header)) = new_heads.next().await.transpose()? else {
+			return Err(anyhow!("Expected a new head"));
+		};
+		if header.number == receipt.block_number {
+			assert_eq!(header.hash, receipt.block_hash);
+			break;
+		}
+	}
+
+	Ok(())
+}
+
+async fn test_block_and_pending_transaction_filters() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let block_filter = client.new_block_filter().await?;
+	let pending_filter = client.new_pending_transaction_filter().await?;
+
+	let tx = TransactionBuilder::new(client.clone())
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let FilterResults::Hashes(hashes) = client.get_filter_changes(pending_filter).await? else {
+		return Err(anyhow!("Expected transaction hashes"));
+	};
+	assert_eq!(hashes, vec![tx.hash()]);
+
+	// The block filter is notified after the receipts of the block are indexed.
+	let mut block_hashes = vec![];
+	for _ in 0..10 {
+		let FilterResults::Hashes(hashes) = client.get_filter_changes(block_filter).await? else {
+			return Err(anyhow!("Expected block hashes"));
+		};
+		block_hashes.extend(hashes);
+		if block_hashes.contains(&receipt.block_hash) {
+			break;
+		}
+		tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+	}
+	assert!(block_hashes.contains(&receipt.block_hash));
+
+	assert!(client.uninstall_filter(block_filter).await?);
+	assert!(client.uninstall_filter(pending_filter).await?);
+	assert!(client.get_filter_changes(block_filter).await.is_err());
+	Ok(())
+}
+
+async fn test_get_proof() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+	let block: BlockNumberOrTagOrHash = client.block_number().await?.into();
+
+	let proof = client.get_proof(account.address(), vec![U256::zero()], block.clone()).await?;
+	assert_eq!(proof.address, account.address());
+	assert!(!proof.account_proof.is_empty());
+	assert_eq!(proof.balance, client.get_balance(account.address(), block.clone()).await?);
+	assert_eq!(proof.nonce, client.get_transaction_count(account.address(), block).await?);
+	assert_eq!(proof.storage_hash, H256::zero());
+	assert_eq!(proof.storage_proof.len(), 1);
+	assert_eq!(proof.storage_proof[0].value, U256::zero());
+	Ok(())
+}
+
+async fn test_simulate_v1() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let sender = H160::repeat_byte(0x42);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+	let block_number = client.block_number().await?;
+	let value = U256::from(1_000_000_000_000_000_000u128);
+
+	let transfer = GenericTransaction {
+		from: Some(sender),
+		to: Some(ethan.address()),
+		value: Some(value),
+		..Default::default()
+	};
+	let balance = AccountOverride { balance: Some(value * 10), ..Default::default() };
+	let payload = SimulatePayload {
+		block_state_calls: vec![
+			SimulateBlock {
+				state_overrides: Some([(sender, balance)].into()),
+				calls: vec![transfer.clone()],
+				..Default::default()
+			},
+			SimulateBlock {
+				block_overrides: Some(BlockOverrides {
+					number: Some(block_number + 10),
+					time: None,
+				}),
+				calls: vec![transfer],
+				..Default::default()
+			},
+		],
+		..Default::default()
+	};
+
+	let blocks = client.simulate_v1(payload, None).await?;
+	assert_eq!(blocks.len(), 2);
+	assert_eq!(blocks[0].number, block_number + 1);
+	assert_eq!(blocks[1].number, block_number + 10);
+	assert!(blocks[1].timestamp > blocks[0].timestamp);
+	assert_ne!(blocks[0].hash, H256::zero());
+	assert_ne!(blocks[0].hash, blocks[1].hash);
+	for call in blocks.iter().flat_map(|block| &block.calls) {
+		assert_eq!(call.status, U256::one(), "call failed: {:?}", call.error);
+	}
+
+	// The overrides are not persisted.
+	assert_eq!(client.get_balance(sender, BlockTag::Latest.into()).await?, U256::zero());
+	Ok(())
+}
+
+async fn test_create_access_list() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let transfer = GenericTransaction {
+		from: Some(account.address()),
+		to: Some(ethan.address()),
+		value: Some(U256::from(1_000_000_000_000_000u128)),
+		..Default::default()
+	};
+	let result = client.create_access_list(transfer, None).await?;
+	assert_eq!(result.error, None);
+	assert!(result.gas_used > U256::zero());
+	// A plain transfer does not access any storage slot.
+	assert!(result.access_list.iter().all(|entry| entry.storage_keys.is_empty()));
+	assert!(!result.access_list.iter().any(|entry| entry.address == account.address()));
+	Ok(())
+}
+
+async fn test_trace_apis() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let tx = TransactionBuilder::new(client.clone())
+		.value(U256::from(1_000_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let traces = TraceRpcClient::trace_transaction(&*client, receipt.transaction_hash).await?;
+	assert_eq!(traces.len(), 1);
+	let trace = &traces[0];
+	assert_eq!(trace.trace_type, FlatCallTraceType::Call);
+	assert_eq!(trace.action.call_type, Some(FlatCallType::Call));
+	assert_eq!(trace.action.from, Some(account.address()));
+	assert_eq!(trace.action.to, Some(ethan.address()));
+	assert_eq!(trace.block_hash, Some(receipt.block_hash));
+	assert_eq!(trace.transaction_hash, Some(receipt.transaction_hash));
+	assert!(trace.trace_address.is_empty());
+
+	let block = BlockNumberOrTag::U256(receipt.block_number);
+	let traces = client.trace_block(block.clone()).await?;
+	assert!(traces.contains(trace), "trace_block should include {trace:?}");
+
+	let filter = TraceFilter {
+		from_block: Some(block.clone()),
+		to_block: Some(block.clone()),
+		from_address: Some(vec![account.address()]),
+		to_address: Some(vec![ethan.address()]),
+		..Default::default()
+	};
+	let traces = client.trace_filter(filter.clone()).await?;
+	assert!(traces.contains(trace), "trace_filter should include {trace:?}");
+
+	let filter = TraceFilter { to_address: Some(vec![H160::repeat_byte(0x42)]), ..filter };
+	assert!(client.trace_filter(filter).await?.is_empty());
+
+	// Paginate over the traces of the block, one at a time.
+	let filter = TraceFilter {
+		from_block: Some(block.clone()),
+		to_block: Some(block),
+		..Default::default()
+	};
+	let traces = client.trace_filter(filter.clone()).await?;
+	assert!(traces.contains(trace), "trace_filter should include {trace:?}");
+	let page = TraceFilter { count: Some(0), ..filter.clone() };
+	assert!(client.trace_filter(page).await?.is_empty());
+	for (after, trace) in traces.iter().enumerate() {
+		let page = TraceFilter { after: Some(after as u32), count: Some(1), ..filter.clone() };
+		assert_eq!(client.trace_filter(page).await?, vec![trace.clone()]);
+	}
+	let page = TraceFilter { after: Some(traces.len() as u32), ..filter };
+	assert!(client.trace_filter(page).await?.is_empty());
+	Ok(())
+}
+
 async fn test_mixed_evm_substrate_transactions() -> anyhow::Result<()> {
 	let client = Arc::new(SharedResources::client().await);
 	let node_client = SharedResources::node_client().await;
@@ -2475,6 +2475,177 @@ mod benchmarks {
 		assert_ok!(result);
 	}
 
+	// Add two G1 points.
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_g1_add() {
+		use hex_literal::hex;
+		let input = hex!(
+			"0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+			"0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+			"00000000000000000000000000000000112b98340eee2777cc3c14163dea3ec97977ac3dc5c70da32e6e87578f44912e902ccef9efe28d4a78b8999dfbca9426"
+			"00000000000000000000000000000000186b28d92356c4dfec4b5201ad099dbdede3781f8998ddf929b4cd7756192185ca7b8f4ef7088f813270ac3d48868a21"
+		)
+		.to_vec();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result =
+				run_builtin_precompile(&mut ext, H160::from_low_u64_be(11).as_fixed_bytes(), input);
+		}
+		assert_ok!(result);
+	}
+
+	// `n`: number of (point, scalar) pairs
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_g1_msm(n: Linear<1, 64>) {
+		use hex_literal::hex;
+		// The generator, multiplied by the largest scalar.
+		let item = [
+			&hex!(
+				"0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+				"0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+			)[..],
+			&[0xff; 32],
+		]
+		.concat();
+		let input = item.repeat(n as usize);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result =
+				run_builtin_precompile(&mut ext, H160::from_low_u64_be(12).as_fixed_bytes(), input);
+		}
+		assert_ok!(result);
+	}
+
+	// Add two G2 points.
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_g2_add() {
+		use hex_literal::hex;
+		let input = hex!(
+			"00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+			"0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"
+			"000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"
+			"000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"
+			"00000000000000000000000000000000103121a2ceaae586d240843a398967325f8eb5a93e8fea99b62b9f88d8556c80dd726a4b30e84a36eeabaf3592937f27"
+			"00000000000000000000000000000000086b990f3da2aeac0a36143b7d7c824428215140db1bb859338764cb58458f081d92664f9053b50b3fbd2e4723121b68"
+			"000000000000000000000000000000000f9e7ba9a86a8f7624aa2b42dcc8772e1af4ae115685e60abc2c9b90242167acef3d0be4050bf935eed7c3b6fc7ba77e"
+			"000000000000000000000000000000000d22c3652d0dc6f0fc9316e14268477c2049ef772e852108d269d9c38dba1d4802e8dae479818184c08f9a569d878451"
+		)
+		.to_vec();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result =
+				run_builtin_precompile(&mut ext, H160::from_low_u64_be(13).as_fixed_bytes(), input);
+		}
+		assert_ok!(result);
+	}
+
+	// `n`: number of (point, scalar) pairs
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_g2_msm(n: Linear<1, 64>) {
+		use hex_literal::hex;
+		// The generator, multiplied by the largest scalar.
+		let item = [
+			&hex!(
+				"00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+				"0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"
+				"000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"
+				"000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"
+			)[..],
+			&[0xff; 32],
+		]
+		.concat();
+		let input = item.repeat(n as usize);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result =
+				run_builtin_precompile(&mut ext, H160::from_low_u64_be(14).as_fixed_bytes(), input);
+		}
+		assert_ok!(result);
+	}
+
+	// `n`: number of (G1, G2) pairs
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_pairing(n: Linear<1, 20>) {
+		use hex_literal::hex;
+		// The generators of G1 and G2.
+		let item = hex!(
+			"0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+			"0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+			"00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+			"0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"
+			"000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"
+			"000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"
+		);
+		let input = item.repeat(n as usize);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result =
+				run_builtin_precompile(&mut ext, H160::from_low_u64_be(15).as_fixed_bytes(), input);
+		}
+		assert_ok!(result);
+	}
+
+	// Map a field element to a G1 point.
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_map_fp_to_g1() {
+		use hex_literal::hex;
+		let input = hex!(
+			"00000000000000000000000000000000156c8a6a2c184569d69a76be144b5cdc5141d2d2ca4fe341f011e25e3969c55ad9e9b9ce2eb833c81a908e5fa4ac5f03"
+		)
+		.to_vec();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result =
+				run_builtin_precompile(&mut ext, H160::from_low_u64_be(16).as_fixed_bytes(), input);
+		}
+		assert_ok!(result);
+	}
+
+	// Map a quadratic extension field element to a G2 point.
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_map_fp2_to_g2() {
+		use hex_literal::hex;
+		let input = hex!(
+			"0000000000000000000000000000000007355d25caf6e7f2f0cb2812ca0e513bd026ed09dda65b177500fa31714e09ea0ded3a078b526bed3307f804d4b93b04"
+			"0000000000000000000000000000000002829ce3c021339ccb5caf3e187f6370e1e2a311dec9b75363117063ab2015603ff52c3d3b98f19c2f65575e99e8b78c"
+		)
+		.to_vec();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result =
+				run_builtin_precompile(&mut ext, H160::from_low_u64_be(17).as_fixed_bytes(), input);
+		}
+		assert_ok!(result);
+	}
+
 	// Only calling the function itself for the list of
 	// generated different ECDSA keys.
 	// This is a slow call: We reduce the number of runs.
@@ -23,6 +23,8 @@ mod call;
 pub(crate) use call::*;
 mod tracing;
 pub use tracing::*;
+mod simulate;
+pub use simulate::MAX_SIMULATED_BLOCKS;
 pub mod fees;
 pub mod runtime;
 pub mod tx_extension;
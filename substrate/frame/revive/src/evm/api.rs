@@ -30,12 +30,21 @@ pub use type_id::*;
 mod debug_rpc_types;
 pub use debug_rpc_types::*;
 
+mod proof_rpc_types;
+pub use proof_rpc_types::*;
+
+mod pubsub_rpc_types;
+pub use pubsub_rpc_types::*;
+
 mod rpc_types;
 pub use rpc_types::DryRunConfig;
 
 mod rpc_types_gen;
 pub use rpc_types_gen::*;
 
+mod simulate_rpc_types;
+pub use simulate_rpc_types::*;
+
 #[cfg(feature = "std")]
 mod account;
 
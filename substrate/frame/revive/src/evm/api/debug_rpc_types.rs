@@ -15,7 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Weight, evm::Bytes};
+use crate::{
+	Weight,
+	evm::{BlockNumberOrTag, Bytes},
+};
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use derive_more::From;
@@ -39,6 +42,13 @@ pub enum TracerType {
 
 	/// A tracer that traces opcodes and syscalls.
 	ExecutionTracer(Option<ExecutionTracerConfig>),
+
+	/// A tracer that counts the function selectors of the calls.
+	#[serde(rename = "4byteTracer")]
+	FourByteTracer,
+
+	/// A tracer that traces calls as a flat list, in the Parity `trace_*` format.
+	FlatCallTracer(Option<FlatCallTracerConfig>),
 }
 
 impl From<CallTracerConfig> for TracerType {
@@ -59,6 +69,12 @@ impl From<ExecutionTracerConfig> for TracerType {
 	}
 }
 
+impl From<FlatCallTracerConfig> for TracerType {
+	fn from(config: FlatCallTracerConfig) -> Self {
+		TracerType::FlatCallTracer(Some(config))
+	}
+}
+
 impl Default for TracerType {
 	fn default() -> Self {
 		TracerType::ExecutionTracer(Some(ExecutionTracerConfig::default()))
@@ -158,6 +174,17 @@ impl Default for PrestateTracerConfig {
 	}
 }
 
+/// The configuration for the flat call tracer.
+#[derive(Clone, Debug, Default, Decode, Serialize, Deserialize, Encode, PartialEq, TypeInfo)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FlatCallTracerConfig {
+	/// Whether to convert the error messages to the ones used by Parity.
+	pub convert_parity_errors: bool,
+
+	/// Whether to include the calls to precompiles in the trace.
+	pub include_precompiles: bool,
+}
+
 fn zero_to_none<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
 where
 	D: Deserializer<'de>,
@@ -295,6 +322,22 @@ fn test_tracer_config_serialization() {
 				timeout: None,
 			},
 		),
+		(
+			r#"{"tracer": "4byteTracer"}"#,
+			TracerConfig { config: TracerType::FourByteTracer, timeout: None },
+		),
+		(
+			r#"{"tracer": "flatCallTracer"}"#,
+			TracerConfig { config: TracerType::FlatCallTracer(None), timeout: None },
+		),
+		(
+			r#"{"tracer": "flatCallTracer", "tracerConfig": { "convertParityErrors": true }}"#,
+			TracerConfig {
+				config: FlatCallTracerConfig { convert_parity_errors: true, ..Default::default() }
+					.into(),
+				timeout: None,
+			},
+		),
 	];
 
 	for (json_data, expected) in tracers {
@@ -335,6 +378,10 @@ pub enum Trace {
 	Prestate(PrestateTrace),
 	/// An execution trace (opcodes and syscalls).
 	Execution(ExecutionTrace),
+	/// A 4byte trace.
+	FourByte(FourByteTrace),
+	/// A flat call trace.
+	Flat(Vec<FlatCallTrace>),
 }
 
 /// A prestate Trace
@@ -876,6 +923,205 @@ pub struct CallLog {
 	pub position: u32,
 }
 
+/// The number of calls made with each function selector and input size, keyed by
+/// `<selector>-<input size>`, e.g. `0x27dc297e-128`.
+///
+/// The input size does not include the 4 bytes of the selector.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(transparent)]
+pub struct FourByteTrace(pub BTreeMap<String, u32>);
+
+/// The type of a flat call trace.
+#[derive(
+	Default, TypeInfo, Encode, Decode, Serialize, Deserialize, Eq, PartialEq, Clone, Debug,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum FlatCallTraceType {
+	/// A call.
+	#[default]
+	Call,
+	/// A contract creation.
+	Create,
+	/// A selfdestruct.
+	Suicide,
+}
+
+/// The kind of call or contract creation of a flat call trace.
+#[derive(
+	Default, TypeInfo, Encode, Decode, Serialize, Deserialize, Eq, PartialEq, Clone, Debug,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum FlatCallType {
+	/// A regular call.
+	#[default]
+	Call,
+	/// A read-only call.
+	StaticCall,
+	/// A delegate call.
+	DelegateCall,
+	/// A create call.
+	Create,
+	/// A create2 call.
+	Create2,
+}
+
+/// The action of a flat call trace.
+///
+/// The fields are set according to the type of the trace:
+/// - calls set `from`, `call_type`, `gas`, `input`, `to` and `value`,
+/// - contract creations set `from`, `creation_method`, `gas`, `init` and `value`,
+/// - selfdestructs set `address`, `refund_address` and `balance`.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallAction {
+	/// Address of the sender.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub from: Option<H160>,
+	/// Type of call.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub call_type: Option<FlatCallType>,
+	/// Type of contract creation.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub creation_method: Option<FlatCallType>,
+	/// Amount of gas provided for the call.
+	#[serde(default, skip_serializing_if = "Option::is_none", with = "super::hex_serde::option")]
+	pub gas: Option<u64>,
+	/// Call input data.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub input: Option<Bytes>,
+	/// Contract creation code.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub init: Option<Bytes>,
+	/// Address of the receiver.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub to: Option<H160>,
+	/// Amount of value transferred.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub value: Option<U256>,
+	/// Address of the destructed contract.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub address: Option<H160>,
+	/// Address receiving the balance of the destructed contract.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub refund_address: Option<H160>,
+	/// Balance of the destructed contract.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+}
+
+/// The result of a successful flat call trace.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallResult {
+	/// Address of the created contract.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub address: Option<H160>,
+	/// Code of the created contract.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// Amount of gas used.
+	#[serde(with = "super::hex_serde")]
+	pub gas_used: u64,
+	/// Return data of the call.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub output: Option<Bytes>,
+}
+
+/// A call trace in the Parity `trace_*` format.
+///
+/// The call tree of a transaction is flattened in depth-first order, each trace locating its call
+/// in the tree with its `trace_address`.
+///
+/// ## References
+///
+/// - <https://openethereum.github.io/JSONRPC-trace-module>
+/// - <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers#flat-call-tracer>
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallTrace {
+	/// The action of the trace.
+	pub action: FlatCallAction,
+	/// The hash of the block including the transaction.
+	#[serde(default)]
+	pub block_hash: Option<H256>,
+	/// The number of the block including the transaction.
+	#[serde(default)]
+	pub block_number: Option<u64>,
+	/// The error message if the call failed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The result of the call, if it succeeded.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub result: Option<FlatCallResult>,
+	/// The number of direct sub-calls.
+	pub subtraces: u32,
+	/// The path from the top-level call to this call in the call tree.
+	pub trace_address: Vec<u32>,
+	/// The hash of the transaction.
+	#[serde(default)]
+	pub transaction_hash: Option<H256>,
+	/// The index of the transaction in the block.
+	#[serde(default)]
+	pub transaction_position: Option<u32>,
+	/// The type of the trace.
+	#[serde(rename = "type")]
+	pub trace_type: FlatCallTraceType,
+}
+
+/// The filter of a `trace_filter` request.
+///
+/// Traces match the filter if their sender is in `from_address` and their receiver is in
+/// `to_address`. An empty or absent list matches any address.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+	/// The first block to trace, defaults to the latest block.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub from_block: Option<BlockNumberOrTag>,
+	/// The last block to trace, defaults to the latest block.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub to_block: Option<BlockNumberOrTag>,
+	/// The senders to match.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub from_address: Option<Vec<H160>>,
+	/// The receivers to match.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub to_address: Option<Vec<H160>>,
+	/// The number of matching traces to skip.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub after: Option<u32>,
+	/// The maximum number of traces to return.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub count: Option<u32>,
+}
+
+impl TraceFilter {
+	/// Returns whether the given trace matches the addresses of the filter.
+	pub fn matches(&self, trace: &FlatCallTrace) -> bool {
+		let FlatCallAction { from, to, address, refund_address, .. } = &trace.action;
+		let sender = from.or(*address);
+		let receiver = to
+			.or(*refund_address)
+			.or_else(|| trace.result.as_ref().and_then(|result| result.address));
+
+		let matches = |addresses: &Option<Vec<H160>>, address: Option<H160>| match addresses {
+			Some(addresses) if !addresses.is_empty() => {
+				address.is_some_and(|address| addresses.contains(&address))
+			},
+			_ => true,
+		};
+		matches(&self.from_address, sender) && matches(&self.to_address, receiver)
+	}
+}
+
 /// A transaction trace
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types returned by the `eth_getProof` JSON-RPC method.
+
+use super::Bytes;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The proof of an account and some of its storage slots, returned by `eth_getProof`.
+///
+/// The response has the shape defined by EIP-1186, but holds Substrate trie proofs instead of
+/// Ethereum Merkle-Patricia proofs:
+///
+/// - `account_proof` holds the nodes of the main state trie, rooted at the block state root, needed
+///   to read the `OriginalAccount` and `AccountInfoOf` entries of the address, the
+///   `System::Account` entry of the mapped account id and, for contracts, the root of the contract
+///   child trie.
+/// - `storage_hash` is the root of the contract child trie, or zero if the account is not a
+///   contract.
+/// - `storage_proof` holds, for each requested slot, the nodes of the contract child trie rooted at
+///   `storage_hash` needed to read the slot. Slots are keyed by the `blake2_256` hash of the slot.
+///
+/// The `pallet-revive-proof-verifier` crate verifies such a proof offline, against the state root
+/// of the block header.
+///
+/// ## References
+///
+/// - <https://eips.ethereum.org/EIPS/eip-1186>
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+	/// The address of the account.
+	pub address: H160,
+	/// The nodes of the main state trie proving the account.
+	pub account_proof: Vec<Bytes>,
+	/// The balance of the account.
+	pub balance: U256,
+	/// The code hash of the account.
+	///
+	/// `keccak256("")` for an existing account without code, and zero for a non-existing account.
+	pub code_hash: H256,
+	/// The nonce of the account.
+	pub nonce: U256,
+	/// The root of the contract child trie, zero if the account is not a contract.
+	pub storage_hash: H256,
+	/// The proofs of the requested storage slots.
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// The proof of a contract storage slot.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+	/// The storage slot.
+	pub key: U256,
+	/// The value of the storage slot.
+	pub value: U256,
+	/// The nodes of the contract child trie proving the storage slot.
+	pub proof: Vec<Bytes>,
+}
+
+#[test]
+fn account_proof_serde_works() {
+	let proof = AccountProof {
+		address: H160::repeat_byte(0x42),
+		account_proof: vec![Bytes(vec![1, 2, 3])],
+		nonce: U256::from(1),
+		storage_proof: vec![StorageProof {
+			key: U256::from(2),
+			value: U256::from(3),
+			proof: vec![Bytes(vec![4])],
+		}],
+		..Default::default()
+	};
+
+	let json = serde_json::to_value(&proof).unwrap();
+	assert_eq!(json["accountProof"], serde_json::json!(["0x010203"]));
+	assert_eq!(json["storageHash"], serde_json::to_value(H256::zero()).unwrap());
+	assert_eq!(json["storageProof"][0]["proof"], serde_json::json!(["0x04"]));
+	assert_eq!(serde_json::from_value::<AccountProof>(json).unwrap(), proof);
+}
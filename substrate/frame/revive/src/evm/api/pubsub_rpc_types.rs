@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `eth_subscribe` pubsub JSON-RPC methods.
+
+use super::{Block, Log};
+use alloc::boxed::Box;
+use derive_more::From;
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+/// The kind of subscription requested with `eth_subscribe`.
+///
+/// ## References
+///
+/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// Fires a notification each time a new header is appended to the chain.
+	NewHeads,
+	/// Returns logs that are included in new imported blocks and match the given filter.
+	Logs,
+	/// Returns the hash of every transaction that is added to the pending state.
+	NewPendingTransactions,
+}
+
+/// A notification sent to an `eth_subscribe` subscriber.
+#[derive(Debug, Clone, Serialize, Deserialize, From, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// A new block header, sent to `newHeads` subscribers.
+	Header(Box<Block>),
+	/// A log, sent to `logs` subscribers.
+	Log(Log),
+	/// A transaction hash, sent to `newPendingTransactions` subscribers.
+	TransactionHash(H256),
+}
+
+#[test]
+fn subscription_kind_serde_works() {
+	let cases = [
+		(SubscriptionKind::NewHeads, r#""newHeads""#),
+		(SubscriptionKind::Logs, r#""logs""#),
+		(SubscriptionKind::NewPendingTransactions, r#""newPendingTransactions""#),
+	];
+
+	for (kind, json) in cases {
+		assert_eq!(serde_json::to_string(&kind).unwrap(), json);
+		assert_eq!(serde_json::from_str::<SubscriptionKind>(json).unwrap(), kind);
+	}
+}
+
+#[test]
+fn subscription_item_serializes_untagged() {
+	let hash = H256::repeat_byte(0x42);
+	let item = SubscriptionItem::from(hash);
+	assert_eq!(serde_json::to_value(&item).unwrap(), serde_json::to_value(hash).unwrap());
+
+	let log = Log { transaction_hash: hash, ..Default::default() };
+	let item = SubscriptionItem::from(log.clone());
+	assert_eq!(serde_json::to_value(&item).unwrap(), serde_json::to_value(log).unwrap());
+}
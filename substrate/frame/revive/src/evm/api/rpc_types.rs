@@ -27,16 +27,21 @@ use sp_core::{H160, U256};
 pub struct DryRunConfig<Moment> {
 	/// Optional timestamp override for dry-run in pending block.
 	pub timestamp_override: Option<Moment>,
-	/// Used for future extensions without breaking encoding.
-	pub reserved: Option<()>,
+	/// Optional block number override for dry-run in a simulated block.
+	///
+	/// This replaces a reserved `Option<()>` field, and is encoded identically when `None`.
+	pub block_number_override: Option<U256>,
 }
 impl<Moment> DryRunConfig<Moment> {
 	/// Create a new `DryRunConfig` with an optional timestamp override.
 	pub fn new(timestamp_override: Option<Moment>) -> Self {
-		Self {
-			timestamp_override,
-			reserved: None, // default value
-		}
+		Self { timestamp_override, block_number_override: None }
+	}
+
+	/// Run the dry-run in a block with the given number.
+	pub fn with_block_number_override(mut self, block_number: U256) -> Self {
+		self.block_number_override = Some(block_number);
+		self
 	}
 }
 
@@ -0,0 +1,212 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `eth_simulateV1` and `eth_createAccessList` JSON-RPC methods.
+
+use super::{AccessList, Bytes, GenericTransaction, Log};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The payload of an `eth_simulateV1` request.
+///
+/// ## References
+///
+/// - <https://github.com/ethereum/execution-apis/blob/main/src/eth/execute.yaml>
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+	/// The blocks to simulate, each one built on top of the previous one.
+	pub block_state_calls: Vec<SimulateBlock>,
+	/// Whether to report native transfers as logs. This is not supported.
+	#[serde(default)]
+	pub trace_transfers: bool,
+	/// Whether to check the gas price of the calls against the base fee.
+	///
+	/// When `false`, the gas price of the calls is ignored, and the calls are charged the base
+	/// fee.
+	#[serde(default)]
+	pub validation: bool,
+}
+
+/// A block of calls to simulate with `eth_simulateV1`.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+	/// The overrides of the block environment.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub block_overrides: Option<BlockOverrides>,
+	/// The overrides of the accounts state, applied before the calls are executed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state_overrides: Option<StateOverrides>,
+	/// The calls to execute, in order.
+	#[serde(default)]
+	pub calls: Vec<GenericTransaction>,
+}
+
+/// The overrides of the block environment of a simulated block.
+///
+/// Only the block number and timestamp can be overridden, other fields are rejected.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlockOverrides {
+	/// The block number, defaults to the number of the previous block plus one.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub number: Option<U256>,
+	/// The block timestamp in seconds, defaults to the timestamp of the previous block plus one
+	/// second.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub time: Option<U256>,
+}
+
+/// The overrides of the accounts state, keyed by address.
+pub type StateOverrides = BTreeMap<H160, AccountOverride>;
+
+/// The overrides of the state of an account.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccountOverride {
+	/// Overrides the balance of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// Overrides the nonce of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<U256>,
+	/// Overrides the code of the account, turning it into a contract.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// Replaces the whole storage of the contract.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Overrides individual storage slots of the contract.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// A block simulated with `eth_simulateV1`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+	/// The block hash.
+	///
+	/// Simulated blocks are not part of the chain, so the hash is derived from the hash of the
+	/// parent block, the number, the timestamp and the transaction hashes of the block.
+	pub hash: H256,
+	/// The block number.
+	pub number: U256,
+	/// The block timestamp, in seconds.
+	pub timestamp: U256,
+	/// The block gas limit.
+	pub gas_limit: U256,
+	/// The gas used by the calls of the block.
+	pub gas_used: U256,
+	/// The base fee per gas.
+	pub base_fee_per_gas: U256,
+	/// The results of the calls, in order.
+	pub calls: Vec<SimulateCallResult>,
+}
+
+/// The result of a call simulated with `eth_simulateV1`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallResult {
+	/// The data returned by the call.
+	pub return_data: Bytes,
+	/// The logs emitted by the call.
+	pub logs: Vec<Log>,
+	/// The gas used by the call.
+	pub gas_used: U256,
+	/// `1` if the call succeeded, `0` otherwise.
+	pub status: U256,
+	/// The error of the call, if it failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<SimulateCallError>,
+}
+
+/// The error of a call simulated with `eth_simulateV1`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallError {
+	/// The error code.
+	pub code: i32,
+	/// The error message.
+	pub message: String,
+	/// The revert data, if the call reverted.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub data: Option<Bytes>,
+}
+
+/// The result of an `eth_createAccessList` request.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// The accounts and storage slots accessed by the transaction.
+	pub access_list: AccessList,
+	/// The gas used by the transaction, with the access list applied.
+	pub gas_used: U256,
+	/// The error of the transaction, if it failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+#[test]
+fn simulate_payload_deserialization_works() {
+	let json = r#"{
+		"blockStateCalls": [{
+			"blockOverrides": { "number": "0x10", "time": "0x20" },
+			"stateOverrides": {
+				"0x4242424242424242424242424242424242424242": {
+					"balance": "0x1",
+					"stateDiff": {
+						"0x0000000000000000000000000000000000000000000000000000000000000001":
+						"0x0000000000000000000000000000000000000000000000000000000000000002"
+					}
+				}
+			},
+			"calls": [{ "to": "0x4242424242424242424242424242424242424242" }]
+		}],
+		"validation": true
+	}"#;
+
+	let payload: SimulatePayload = serde_json::from_str(json).unwrap();
+	assert!(payload.validation);
+	assert!(!payload.trace_transfers);
+
+	let block = &payload.block_state_calls[0];
+	assert_eq!(
+		block.block_overrides,
+		Some(BlockOverrides { number: Some(U256::from(0x10)), time: Some(U256::from(0x20)) })
+	);
+	let account = &block.state_overrides.as_ref().unwrap()[&H160::repeat_byte(0x42)];
+	assert_eq!(account.balance, Some(U256::one()));
+	assert_eq!(
+		account.state_diff,
+		Some([(H256::from_low_u64_be(1), H256::from_low_u64_be(2))].into_iter().collect())
+	);
+	assert_eq!(block.calls[0].to, Some(H160::repeat_byte(0x42)));
+
+	// Unsupported block overrides are rejected.
+	let json = r#"{ "blockStateCalls": [{ "blockOverrides": { "baseFeePerGas": "0x1" } }] }"#;
+	assert!(serde_json::from_str::<SimulatePayload>(json).is_err());
+}
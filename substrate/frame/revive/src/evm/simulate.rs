@@ -0,0 +1,301 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Simulation of a sequence of blocks of calls, backing the `eth_simulateV1` RPC.
+
+use crate::{
+	AccountInfo, AddressMapper, BalanceOf, CallOf, Config, ContractBlob, ContractInfo,
+	DryRunConfig, Error, EthTransactError, ExecConfig, Executable, MomentOf, Pallet,
+	SimulatedBlockInfo, SimulatedCall, TransactionLimits, TransactionMeter,
+	evm::{
+		AccountOverride, CallLog, CallTrace, CallTracer, CallTracerConfig, GenericTransaction,
+		SimulateBlock, runtime::SetWeightLimit,
+	},
+	tracing::trace,
+	vm::CodeInfo,
+};
+use alloc::{format, vec::Vec};
+use frame_support::{
+	storage::child,
+	traits::{
+		Get, Time,
+		fungible::{Inspect, Mutate},
+	},
+};
+use frame_system::{Pallet as System, pallet_prelude::BlockNumberFor};
+use sp_core::{H160, U256};
+use sp_runtime::{
+	DispatchError, SaturatedConversion,
+	traits::{Bounded, Saturating},
+};
+
+/// The maximum number of blocks that can be simulated in a single `eth_simulateV1` request.
+pub const MAX_SIMULATED_BLOCKS: usize = 256;
+
+/// Collect the logs emitted by `trace` and its successful sub calls, in execution order.
+///
+/// The logs of the reverted or failed frames are discarded, as they are not part of the state.
+fn collect_logs(trace: CallTrace, logs: &mut Vec<CallLog>) {
+	if trace.error.is_some() {
+		return;
+	}
+
+	let mut calls = trace.calls.into_iter();
+	let mut position = 0;
+	for log in trace.logs {
+		// The logs emitted by the sub calls that precede this log.
+		while position < log.position {
+			if let Some(call) = calls.next() {
+				collect_logs(call, logs);
+			}
+			position += 1;
+		}
+		logs.push(log);
+	}
+
+	for call in calls {
+		collect_logs(call, logs);
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Simulate a sequence of blocks of calls, each block building on the state left by the
+	/// previous one.
+	///
+	/// The simulation starts on top of the current block. Unless overridden, each simulated
+	/// block is numbered after the previous one, and is one second later. The state overrides of
+	/// a block are applied before its calls are executed.
+	///
+	/// When `validation` is `false`, the gas price of the calls is ignored and they are charged
+	/// the base fee.
+	///
+	/// # Note
+	///
+	/// This function changes the state and is meant to be called from a runtime API, whose
+	/// changes are discarded.
+	pub fn dry_run_eth_simulate(
+		blocks: Vec<SimulateBlock>,
+		validation: bool,
+	) -> Result<Vec<SimulatedBlockInfo>, EthTransactError>
+	where
+		T::Nonce: Into<U256>,
+		CallOf<T>: SetWeightLimit,
+	{
+		log::debug!(target: crate::LOG_TARGET, "dry_run_eth_simulate: {} blocks", blocks.len());
+
+		if blocks.len() > MAX_SIMULATED_BLOCKS {
+			return Err(EthTransactError::Message(format!(
+				"Too many blocks to simulate: {} > {MAX_SIMULATED_BLOCKS}",
+				blocks.len()
+			)));
+		}
+
+		let mut number: U256 = System::<T>::block_number().into();
+		// The timestamp of the previous block, in milliseconds.
+		let mut timestamp: U256 = T::Time::now().into();
+
+		let mut simulated = Vec::with_capacity(blocks.len());
+		for SimulateBlock { block_overrides, state_overrides, calls } in blocks {
+			let block_overrides = block_overrides.unwrap_or_default();
+			let next_number = block_overrides.number.unwrap_or(number.saturating_add(1.into()));
+			let next_timestamp = block_overrides
+				.time
+				.map(|time| time.saturating_mul(1000.into()))
+				.unwrap_or(timestamp.saturating_add(1000.into()));
+
+			if next_number <= number {
+				return Err(EthTransactError::Message(format!(
+					"Block number {next_number} is not greater than {number}"
+				)));
+			}
+			if next_timestamp <= timestamp {
+				return Err(EthTransactError::Message(format!(
+					"Block timestamp {} is not greater than {}",
+					next_timestamp / 1000,
+					timestamp / 1000
+				)));
+			}
+			(number, timestamp) = (next_number, next_timestamp);
+
+			for (address, account) in state_overrides.unwrap_or_default() {
+				Self::apply_account_override(&address, account)?;
+			}
+
+			let moment: MomentOf<T> = u128::try_from(timestamp)
+				.map_err(|_| EthTransactError::Message(format!("Invalid timestamp {timestamp}")))?
+				.saturated_into();
+			BlockNumberFor::<T>::try_from(number)
+				.map_err(|_| EthTransactError::Message(format!("Invalid block number {number}")))?;
+			let config = DryRunConfig::new(Some(moment)).with_block_number_override(number);
+
+			let calls = calls
+				.into_iter()
+				.map(|tx| Self::simulate_call(tx, config.clone(), validation))
+				.collect();
+			simulated.push(SimulatedBlockInfo { number, timestamp: timestamp / 1000, calls });
+		}
+
+		Ok(simulated)
+	}
+
+	/// Execute a single call of a simulated block, and collect its logs.
+	fn simulate_call(
+		mut tx: GenericTransaction,
+		config: DryRunConfig<MomentOf<T>>,
+		validation: bool,
+	) -> SimulatedCall
+	where
+		T::Nonce: Into<U256>,
+		CallOf<T>: SetWeightLimit,
+	{
+		if !validation {
+			tx.gas_price = None;
+			tx.max_fee_per_gas = None;
+			tx.max_priority_fee_per_gas = None;
+		}
+
+		let mut tracer =
+			CallTracer::new(CallTracerConfig { with_logs: true, only_top_call: false });
+		let result = trace(&mut tracer, || Self::dry_run_eth_transact(tx, config));
+		let call_trace = tracer.collect_trace();
+
+		match result {
+			Ok(info) => {
+				let mut logs = Vec::new();
+				if let Some(call_trace) = call_trace {
+					collect_logs(call_trace, &mut logs);
+				}
+				SimulatedCall { result: Ok(info.data), logs, gas_used: info.eth_gas }
+			},
+			Err(err) => SimulatedCall {
+				result: Err(err),
+				logs: Vec::new(),
+				gas_used: call_trace.map(|call| call.gas_used.into()).unwrap_or_default(),
+			},
+		}
+	}
+
+	/// Apply the state overrides of a simulated block to the account at `address`.
+	///
+	/// The code is overridden first, so that the storage of an account turned into a contract
+	/// can be overridden as well.
+	fn apply_account_override(
+		address: &H160,
+		account: AccountOverride,
+	) -> Result<(), EthTransactError> {
+		let AccountOverride { balance, nonce, code, state, state_diff } = account;
+		let account_id = T::AddressMapper::to_account_id(address);
+
+		if let Some(code) = code {
+			Self::override_code(address, code.0)?;
+		}
+
+		if let Some(balance) = balance {
+			Self::set_evm_balance(address, balance).map_err(|err| {
+				EthTransactError::Message(format!("Failed to override balance: {err:?}"))
+			})?;
+		}
+
+		if let Some(nonce) = nonce {
+			let nonce = u64::try_from(nonce)
+				.map_err(|_| EthTransactError::Message(format!("Invalid nonce {nonce}")))?;
+			frame_system::Account::<T>::mutate(&account_id, |info| {
+				info.nonce = nonce.saturated_into()
+			});
+		}
+
+		let storage = match (state, state_diff) {
+			(Some(_), Some(_)) => {
+				return Err(EthTransactError::Message(format!(
+					"Both state and stateDiff are overridden for {address:?}"
+				)));
+			},
+			(Some(state), None) => {
+				let contract_info = AccountInfo::<T>::load_contract(address).ok_or_else(|| {
+					EthTransactError::Message(format!("Cannot override the state of {address:?}"))
+				})?;
+				let _ = child::clear_storage(&contract_info.child_trie_info(), None, None);
+				state
+			},
+			(None, Some(state_diff)) => state_diff,
+			(None, None) => return Ok(()),
+		};
+
+		for (key, value) in storage {
+			// Writing a zero value clears the slot, as `SSTORE` does.
+			let value = (!value.is_zero()).then(|| value.0.to_vec());
+			Self::set_storage(*address, key.0, value).map_err(|err| {
+				EthTransactError::Message(format!(
+					"Failed to override the storage of {address:?}: {err:?}"
+				))
+			})?;
+		}
+
+		Ok(())
+	}
+
+	/// Replace the code of the account at `address`, turning it into a contract if needed.
+	///
+	/// The code is uploaded on behalf of the account, which is funded with the code deposit.
+	/// Overriding the code of a contract keeps its storage.
+	fn override_code(address: &H160, code: Vec<u8>) -> Result<(), EthTransactError> {
+		let to_message = |err: DispatchError| {
+			EthTransactError::Message(format!("Failed to override code: {err:?}"))
+		};
+		let owner = T::AddressMapper::to_account_id(address);
+
+		let mut module = if code.starts_with(&polkavm_common::program::BLOB_MAGIC) {
+			ContractBlob::<T>::from_pvm_code(code, owner.clone())
+		} else if T::AllowEVMBytecode::get() {
+			ContractBlob::<T>::from_evm_runtime_code(code, owner.clone())
+		} else {
+			Err(Error::<T>::CodeRejected.into())
+		}
+		.map_err(to_message)?;
+
+		let deposit = module.code_info().deposit();
+		T::Currency::mint_into(&owner, deposit.saturating_add(T::Currency::minimum_balance()))
+			.map_err(to_message)?;
+		let mut meter = TransactionMeter::<T>::new(TransactionLimits::WeightAndDeposit {
+			weight_limit: Default::default(),
+			deposit_limit: BalanceOf::<T>::max_value(),
+		})
+		.map_err(to_message)?;
+		module
+			.store_code(&ExecConfig::new_substrate_tx(), &mut meter)
+			.map_err(to_message)?;
+
+		let code_hash = *module.code_hash();
+		let contract_info = match AccountInfo::<T>::load_contract(address) {
+			Some(contract_info) if contract_info.code_hash == code_hash => return Ok(()),
+			Some(mut contract_info) => {
+				// Nothing is refunded for removed code, and the simulated state is discarded
+				// anyway, so whether the replaced code was removed doesn't matter here.
+				let _code_removed = CodeInfo::<T>::decrement_refcount(contract_info.code_hash)
+					.map_err(to_message)?;
+				contract_info.code_hash = code_hash;
+				contract_info
+			},
+			None => ContractInfo::<T>::new(address, System::<T>::account_nonce(&owner), code_hash)
+				.map_err(to_message)?,
+		};
+		CodeInfo::<T>::increment_refcount(code_hash).map_err(to_message)?;
+		AccountInfo::<T>::insert_contract(address, contract_info);
+
+		Ok(())
+	}
+}
@@ -16,9 +16,10 @@
 // limitations under the License.
 use crate::{
 	Config,
-	evm::{CallTrace, ExecutionTrace, Trace},
+	evm::{CallTrace, ExecutionTrace, FlatCallTrace, FourByteTrace, Trace},
 	tracing::Tracing,
 };
+use alloc::vec::Vec;
 
 mod call_tracing;
 pub use call_tracing::*;
@@ -29,6 +30,12 @@ pub use prestate_tracing::*;
 mod execution_tracing;
 pub use execution_tracing::*;
 
+mod four_byte_tracing;
+pub use four_byte_tracing::*;
+
+mod flat_call_tracing;
+pub use flat_call_tracing::*;
+
 /// A composite tracer.
 #[derive(derive_more::From, Debug)]
 pub enum Tracer<T> {
@@ -38,6 +45,10 @@ pub enum Tracer<T> {
 	PrestateTracer(PrestateTracer<T>),
 	/// A tracer that traces opcodes and syscalls.
 	ExecutionTracer(ExecutionTracer),
+	/// A tracer that counts the function selectors of the calls.
+	FourByteTracer(FourByteTracer<T>),
+	/// A tracer that traces calls as a flat list.
+	FlatCallTracer(FlatCallTracer<T>),
 }
 
 impl<T: Config> Tracer<T>
@@ -50,6 +61,8 @@ where
 			Tracer::CallTracer(_) => CallTrace::default().into(),
 			Tracer::PrestateTracer(tracer) => tracer.empty_trace().into(),
 			Tracer::ExecutionTracer(_) => ExecutionTrace::default().into(),
+			Tracer::FourByteTracer(_) => FourByteTrace::default().into(),
+			Tracer::FlatCallTracer(_) => Vec::<FlatCallTrace>::new().into(),
 		}
 	}
 
@@ -59,6 +72,8 @@ where
 			Tracer::CallTracer(inner) => inner as &mut dyn Tracing,
 			Tracer::PrestateTracer(inner) => inner as &mut dyn Tracing,
 			Tracer::ExecutionTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::FourByteTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::FlatCallTracer(inner) => inner as &mut dyn Tracing,
 		}
 	}
 
@@ -68,6 +83,8 @@ where
 			Tracer::CallTracer(inner) => inner.collect_trace().map(Trace::Call),
 			Tracer::PrestateTracer(inner) => Some(inner.collect_trace().into()),
 			Tracer::ExecutionTracer(inner) => Some(inner.collect_trace().into()),
+			Tracer::FourByteTracer(inner) => Some(inner.collect_trace().into()),
+			Tracer::FlatCallTracer(inner) => inner.collect_trace().map(Trace::Flat),
 		}
 	}
 
@@ -0,0 +1,236 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	Code, Config, ContractBlob, DispatchError, Weight,
+	evm::{
+		CallTrace, CallTracer, CallTracerConfig, CallType, FlatCallAction, FlatCallResult,
+		FlatCallTrace, FlatCallTraceType, FlatCallTracerConfig, FlatCallType,
+	},
+	exec::is_precompile,
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+};
+use alloc::{string::String, vec::Vec};
+use sp_core::{H160, H256, U256};
+
+/// A tracer that reports the calls of a transaction as a flat list, in the Parity `trace_*`
+/// format.
+///
+/// The calls are traced with a [`CallTracer`], and the call tree is flattened when the trace is
+/// collected. The block and transaction fields of the traces are left empty, they are filled by
+/// the RPC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatCallTracer<T> {
+	/// The tracer of the call tree.
+	call_tracer: CallTracer,
+
+	/// The tracer configuration.
+	config: FlatCallTracerConfig,
+
+	_phantom: core::marker::PhantomData<T>,
+}
+
+impl<T: Config> FlatCallTracer<T> {
+	/// Create a new [`FlatCallTracer`] instance.
+	pub fn new(config: FlatCallTracerConfig) -> Self {
+		let call_tracer =
+			CallTracer::new(CallTracerConfig { with_logs: false, only_top_call: false });
+		Self { call_tracer, config, _phantom: Default::default() }
+	}
+
+	/// Collect the traces and return them.
+	pub fn collect_trace(self) -> Option<Vec<FlatCallTrace>> {
+		let call = self.call_tracer.collect_trace()?;
+		let mut traces = Vec::new();
+		flatten(
+			call,
+			Vec::new(),
+			&self.config,
+			&|address| is_precompile::<T, ContractBlob<T>>(address),
+			&mut traces,
+		);
+		Some(traces)
+	}
+}
+
+/// Push the flat traces of `call` and of its sub-calls to `traces`, in depth-first order.
+fn flatten(
+	call: CallTrace,
+	trace_address: Vec<u32>,
+	config: &FlatCallTracerConfig,
+	is_precompile: &impl Fn(&H160) -> bool,
+	traces: &mut Vec<FlatCallTrace>,
+) {
+	let CallTrace {
+		from, gas, gas_used, to, input, output, error, calls, value, call_type, ..
+	} = call;
+
+	let calls = calls
+		.into_iter()
+		.filter(|call| {
+			config.include_precompiles ||
+				!matches!(
+					call.call_type,
+					CallType::Call | CallType::StaticCall | CallType::DelegateCall
+				) || !is_precompile(&call.to)
+		})
+		.collect::<Vec<_>>();
+
+	let value = value.unwrap_or_default();
+	let (trace_type, action, result) = match call_type {
+		CallType::Create | CallType::Create2 => (
+			FlatCallTraceType::Create,
+			FlatCallAction {
+				from: Some(from),
+				creation_method: Some(if call_type == CallType::Create {
+					FlatCallType::Create
+				} else {
+					FlatCallType::Create2
+				}),
+				gas: Some(gas),
+				init: Some(input),
+				value: Some(value),
+				..Default::default()
+			},
+			Some(FlatCallResult {
+				address: Some(to),
+				code: Some(output),
+				gas_used,
+				..Default::default()
+			}),
+		),
+		CallType::Selfdestruct => (
+			FlatCallTraceType::Suicide,
+			FlatCallAction {
+				address: Some(from),
+				refund_address: Some(to),
+				balance: Some(value),
+				..Default::default()
+			},
+			None,
+		),
+		CallType::Call | CallType::StaticCall | CallType::DelegateCall => (
+			FlatCallTraceType::Call,
+			FlatCallAction {
+				from: Some(from),
+				call_type: Some(match call_type {
+					CallType::StaticCall => FlatCallType::StaticCall,
+					CallType::DelegateCall => FlatCallType::DelegateCall,
+					_ => FlatCallType::Call,
+				}),
+				gas: Some(gas),
+				input: Some(input),
+				to: Some(to),
+				value: Some(value),
+				..Default::default()
+			},
+			Some(FlatCallResult { gas_used, output: Some(output), ..Default::default() }),
+		),
+	};
+
+	let error = if config.convert_parity_errors { error.map(to_parity_error) } else { error };
+	traces.push(FlatCallTrace {
+		action,
+		result: if error.is_some() { None } else { result },
+		error,
+		subtraces: calls.len() as u32,
+		trace_address: trace_address.clone(),
+		trace_type,
+		..Default::default()
+	});
+
+	for (index, call) in calls.into_iter().enumerate() {
+		let mut trace_address = trace_address.clone();
+		trace_address.push(index as u32);
+		flatten(call, trace_address, config, is_precompile, traces);
+	}
+}
+
+/// Convert an error message of the call tracer to the one used by Parity.
+fn to_parity_error(error: String) -> String {
+	let parity_error = match error.as_str() {
+		"execution reverted" => "Reverted",
+		"OutOfGas" => "Out of gas",
+		"InvalidJump" => "Bad jump destination",
+		"InvalidInstruction" => "Bad instruction",
+		"OutOfBounds" => "Out of bounds",
+		"StackUnderflow" => "Stack underflow",
+		"StackOverflow" | "MaxCallDepthReached" => "Out of stack",
+		_ => return error,
+	};
+	parity_error.into()
+}
+
+impl<T: Config> Tracing for FlatCallTracer<T> {
+	fn instantiate_code(&mut self, code: &Code, salt: Option<&[u8; 32]>) {
+		self.call_tracer.instantiate_code(code, salt)
+	}
+
+	fn terminate(
+		&mut self,
+		contract_address: H160,
+		beneficiary_address: H160,
+		gas_left: u64,
+		value: U256,
+	) {
+		self.call_tracer
+			.terminate(contract_address, beneficiary_address, gas_left, value)
+	}
+
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		delegate_call: Option<H160>,
+		is_read_only: bool,
+		value: U256,
+		input: &[u8],
+		gas_limit: u64,
+	) {
+		self.call_tracer.enter_child_span(
+			from,
+			to,
+			delegate_call,
+			is_read_only,
+			value,
+			input,
+			gas_limit,
+		)
+	}
+
+	fn log_event(&mut self, address: H160, topics: &[H256], data: &[u8]) {
+		self.call_tracer.log_event(address, topics, data)
+	}
+
+	fn exit_child_span(
+		&mut self,
+		output: &ExecReturnValue,
+		gas_used: u64,
+		weight_consumed: Weight,
+	) {
+		self.call_tracer.exit_child_span(output, gas_used, weight_consumed)
+	}
+
+	fn exit_child_span_with_error(
+		&mut self,
+		error: DispatchError,
+		gas_used: u64,
+		weight_consumed: Weight,
+	) {
+		self.call_tracer.exit_child_span_with_error(error, gas_used, weight_consumed)
+	}
+}
@@ -0,0 +1,73 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	Code, Config, ContractBlob, evm::FourByteTrace, exec::is_precompile, tracing::Tracing,
+};
+use alloc::format;
+use sp_core::{H160, U256};
+
+/// A tracer that counts the function selectors and input sizes of the calls.
+///
+/// Contract creations and calls to precompiles are not counted, nor are the calls with an input
+/// shorter than a selector.
+#[derive(frame_support::DefaultNoBound, Debug, Clone, PartialEq)]
+pub struct FourByteTracer<T> {
+	/// The number of calls per selector and input size.
+	trace: FourByteTrace,
+
+	/// Whether the next call is a contract creation.
+	is_create: bool,
+
+	_phantom: core::marker::PhantomData<T>,
+}
+
+impl<T: Config> FourByteTracer<T> {
+	/// Create a new [`FourByteTracer`] instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Collect the trace and return it.
+	pub fn collect_trace(self) -> FourByteTrace {
+		self.trace
+	}
+}
+
+impl<T: Config> Tracing for FourByteTracer<T> {
+	fn instantiate_code(&mut self, _code: &Code, _salt: Option<&[u8; 32]>) {
+		self.is_create = true;
+	}
+
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		to: H160,
+		_delegate_call: Option<H160>,
+		_is_read_only: bool,
+		_value: U256,
+		input: &[u8],
+		_gas_limit: u64,
+	) {
+		if core::mem::take(&mut self.is_create) || is_precompile::<T, ContractBlob<T>>(&to) {
+			return;
+		}
+
+		let Some((selector, data)) = input.split_first_chunk::<4>() else { return };
+		let key = format!("{:#010x}-{}", u32::from_be_bytes(*selector), data.len());
+		*self.trace.0.entry(key).or_default() += 1;
+	}
+}
@@ -59,7 +59,7 @@ use sp_core::{
 };
 use sp_io::{crypto::secp256k1_ecdsa_recover_compressed, hashing::blake2_256};
 use sp_runtime::{
-	DispatchError, SaturatedConversion,
+	ArithmeticError, DispatchError, SaturatedConversion,
 	traits::{BadOrigin, Saturating, TrailingZeroInput},
 };
 
@@ -1010,6 +1010,13 @@ where
 			let delta = 1000u32.into();
 			timestamp = cmp::max(timestamp.saturating_add(delta), timestamp_override);
 		}
+		// if dry run with block number override is provided we simulate the run in that block
+		if let Some(number) =
+			exec_config.is_dry_run.as_ref().and_then(|cfg| cfg.block_number_override)
+		{
+			block_number =
+				BlockNumberFor::<T>::try_from(number).map_err(|_| ArithmeticError::Overflow)?;
+		}
 
 		let stack = Self {
 			origin,
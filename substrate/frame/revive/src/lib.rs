@@ -49,9 +49,10 @@ pub mod weights;
 
 use crate::{
 	evm::{
-		CallTracer, CreateCallMode, ExecutionTracer, GenericTransaction, PrestateTracer,
-		TYPE_EIP1559, Trace, Tracer, TracerType, block_hash::EthereumBlockBuilderIR, block_storage,
-		fees::InfoT as FeeInfo, runtime::SetWeightLimit,
+		CallTracer, CreateCallMode, ExecutionTracer, FlatCallTracer, FourByteTracer,
+		GenericTransaction, PrestateTracer, SimulateBlock, TYPE_EIP1559, Trace, Tracer, TracerType,
+		block_hash::EthereumBlockBuilderIR, block_storage, fees::InfoT as FeeInfo,
+		runtime::SetWeightLimit,
 	},
 	exec::{AccountIdOf, ExecError, ReentrancyProtection, Stack as ExecStack},
 	storage::{AccountType, DeletionQueueManager},
@@ -2137,6 +2138,10 @@ impl<T: Config> Pallet<T> {
 			TracerType::ExecutionTracer(config) => {
 				ExecutionTracer::new(config.unwrap_or_default()).into()
 			},
+			TracerType::FourByteTracer => FourByteTracer::new().into(),
+			TracerType::FlatCallTracer(config) => {
+				FlatCallTracer::new(config.unwrap_or_default()).into()
+			},
 		}
 	}
 
@@ -2186,6 +2191,36 @@ impl<T: Config> Pallet<T> {
 		Ok(maybe_value)
 	}
 
+	/// Get the storage keys needed to prove the account at `address` and the given storage slots.
+	///
+	/// See [`AccountProofKeys`].
+	pub fn account_proof_keys(address: H160, keys: Vec<[u8; 32]>) -> AccountProofKeys {
+		let account_id = T::AddressMapper::to_account_id(&address);
+		let account_keys = vec![
+			OriginalAccount::<T>::hashed_key_for(&address),
+			frame_system::Account::<T>::hashed_key_for(&account_id),
+			AccountInfoOf::<T>::hashed_key_for(&address),
+		];
+
+		let Some(contract_info) = AccountInfo::<T>::load_contract(&address) else {
+			let code_hash = if System::<T>::account_exists(&account_id) {
+				exec::EMPTY_CODE_HASH
+			} else {
+				H256::zero()
+			};
+			return AccountProofKeys { account_keys, code_hash, ..Default::default() };
+		};
+
+		AccountProofKeys {
+			account_keys,
+			child_trie_key: Some(
+				contract_info.child_trie_info().prefixed_storage_key().into_inner(),
+			),
+			storage_keys: keys.into_iter().map(|key| Key::from_fixed(key).hash()).collect(),
+			code_hash: contract_info.code_hash,
+		}
+	}
+
 	/// Get the immutable data of a specified contract.
 	///
 	/// Returns `None` if the contract does not exist or has no immutable data.
@@ -2614,6 +2649,14 @@ sp_api::decl_runtime_apis! {
 			config: DryRunConfig<Moment>,
 		) -> Result<EthTransactInfo<Balance>, EthTransactError>;
 
+		/// Simulate a sequence of blocks of Ethereum calls.
+		///
+		/// See [`crate::Pallet::dry_run_eth_simulate`]
+		fn eth_simulate(
+			blocks: Vec<SimulateBlock>,
+			validation: bool,
+		) -> Result<Vec<SimulatedBlockInfo>, EthTransactError>;
+
 		/// Upload new code without instantiating a contract from it.
 		///
 		/// See [`crate::Pallet::bare_upload_code`].
@@ -2643,6 +2686,12 @@ sp_api::decl_runtime_apis! {
 			key: Vec<u8>,
 		) -> GetStorageResult;
 
+		/// Get the storage keys needed to prove the account at `address` and the given storage
+		/// slots.
+		///
+		/// See eth-rpc `eth_getProof` for usage.
+		fn account_proof_keys(address: H160, keys: Vec<[u8; 32]>) -> AccountProofKeys;
+
 		/// Traces the execution of an entire block and returns call traces.
 		///
 		/// This is intended to be called through `state_call` to replay the block from the
@@ -2795,6 +2844,13 @@ macro_rules! impl_runtime_apis_plus_revive_traits {
 					$crate::Pallet::<Self>::dry_run_eth_transact(tx, config)
 				}
 
+				fn eth_simulate(
+					blocks: Vec<$crate::evm::SimulateBlock>,
+					validation: bool,
+				) -> Result<Vec<$crate::SimulatedBlockInfo>, $crate::EthTransactError> {
+					$crate::Pallet::<Self>::dry_run_eth_simulate(blocks, validation)
+				}
+
 				fn call(
 					origin: AccountId,
 					dest: $crate::H160,
@@ -2874,6 +2930,13 @@ macro_rules! impl_runtime_apis_plus_revive_traits {
 					$crate::Pallet::<Self>::get_storage(address, key)
 				}
 
+				fn account_proof_keys(
+					address: $crate::H160,
+					keys: Vec<[u8; 32]>,
+				) -> $crate::AccountProofKeys {
+					$crate::Pallet::<Self>::account_proof_keys(address, keys)
+				}
+
 				fn trace_block(
 					block: Block,
 					tracer_type: $crate::evm::TracerType,
@@ -16,6 +16,7 @@
 // limitations under the License.
 
 mod blake2f;
+mod bls12_381;
 mod bn128;
 mod ecrecover;
 mod identity;
@@ -60,6 +61,13 @@ type Production<T> = (
 	bn128::Bn128Pairing<T>,
 	blake2f::Blake2F<T>,
 	point_eval::PointEval<T>,
+	bls12_381::Bls12G1Add<T>,
+	bls12_381::Bls12G1Msm<T>,
+	bls12_381::Bls12G2Add<T>,
+	bls12_381::Bls12G2Msm<T>,
+	bls12_381::Bls12Pairing<T>,
+	bls12_381::Bls12MapFpToG1<T>,
+	bls12_381::Bls12MapFp2ToG2<T>,
 	p256_verify::P256Verify<T>,
 	system::System<T>,
 	storage::Storage<T>,
@@ -0,0 +1,398 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EIP-2537 BLS12-381 Precompiles
+//!
+//! This module implements the [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537) precompiles for
+//! operations on the BLS12-381 curve: point addition and multi-scalar multiplication on G1 and G2,
+//! pairing checks and mapping of field elements to G1 and G2.
+//!
+//! The arithmetic is delegated to `revm`. The input length and the encoding of the base field
+//! elements are checked here beforehand, so that the errors match the Ethereum test vectors.
+
+use crate::{
+	Config,
+	precompiles::{BuiltinAddressMatcher, Error, Ext, PrimitivePrecompile},
+	vm::RuntimeCosts,
+};
+use alloc::vec::Vec;
+use core::{marker::PhantomData, num::NonZero};
+use revm::precompile::{PrecompileWithAddress, bls12_381};
+use sp_runtime::DispatchError;
+
+/// The length of an encoded base field element, padded to 64 bytes.
+const FP_LENGTH: usize = 64;
+
+/// The number of leading zero bytes padding an encoded base field element.
+const FP_PADDING: usize = 16;
+
+/// The length of an encoded scalar.
+const SCALAR_LENGTH: usize = 32;
+
+/// The length of an encoded G1 point.
+const G1_LENGTH: usize = 2 * FP_LENGTH;
+
+/// The length of an encoded G2 point.
+const G2_LENGTH: usize = 4 * FP_LENGTH;
+
+/// The big-endian encoding of the base field modulus.
+const MODULUS: [u8; FP_LENGTH - FP_PADDING] = hex_literal::hex!(
+	"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"
+);
+
+/// The error returned when a point is not on the curve.
+const NOT_ON_CURVE: &str = "invalid point: not on curve";
+
+/// The error returned when a point is not on the curve or not in the correct subgroup.
+const NOT_IN_SUBGROUP: &str = "invalid point: not on curve or not in correct subgroup";
+
+/// Check that `input` holds exactly `len` bytes.
+fn check_length(input: &[u8], len: usize) -> Result<(), DispatchError> {
+	if input.len() != len {
+		Err(DispatchError::from("invalid input length"))?;
+	}
+	Ok(())
+}
+
+/// Check that `input` holds a non-zero number of `item_len` long items, and return their number.
+fn check_items(input: &[u8], item_len: usize) -> Result<usize, DispatchError> {
+	if input.is_empty() || !input.len().is_multiple_of(item_len) {
+		Err(DispatchError::from("invalid input length"))?;
+	}
+	Ok(input.len() / item_len)
+}
+
+/// Check the encoding of the base field elements of `input`.
+///
+/// `input` is made of `item_len` long items, each one starting with `fps` base field elements.
+fn check_field_elements(input: &[u8], item_len: usize, fps: usize) -> Result<(), DispatchError> {
+	for item in input.chunks(item_len) {
+		for fp in item[..fps * FP_LENGTH].chunks(FP_LENGTH) {
+			let (padding, element) = fp.split_at(FP_PADDING);
+			if padding.iter().any(|byte| *byte != 0) {
+				Err(DispatchError::from("invalid field element top bytes"))?;
+			}
+			if element >= &MODULUS[..] {
+				Err(DispatchError::from("invalid fp.Element encoding"))?;
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Run the given `revm` precompile on a checked input.
+fn run(
+	precompile: PrecompileWithAddress,
+	input: &[u8],
+	error: &'static str,
+) -> Result<Vec<u8>, Error> {
+	// The weight is charged by the caller, `revm` gas is not metered.
+	let output = (precompile.1)(input, u64::MAX).map_err(|_| DispatchError::from(error))?;
+	Ok(output.bytes.to_vec())
+}
+
+pub struct Bls12G1Add<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12G1Add<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0b).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	/// Add two G1 points. The points are not checked to be in the correct subgroup.
+	///
+	/// | point a | point b |
+	/// | :-----: | :-----: |
+	/// |   128   |   128   |
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::Bls12G1Add)?;
+
+		check_length(&input, 2 * G1_LENGTH)?;
+		check_field_elements(&input, G1_LENGTH, 2)?;
+		run(bls12_381::g1_add::PRECOMPILE, &input, NOT_ON_CURVE)
+	}
+}
+
+pub struct Bls12G1Msm<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12G1Msm<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0c).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	/// Compute the multi-scalar multiplication of a non-empty list of G1 points.
+	///
+	/// | point 1 | scalar 1 | ... | point k | scalar k |
+	/// | :-----: | :------: | :-: | :-----: | :------: |
+	/// |   128   |    32    | ... |   128   |    32    |
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		let k = check_items(&input, G1_LENGTH + SCALAR_LENGTH)?;
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::Bls12G1Msm(k as u32))?;
+
+		check_field_elements(&input, G1_LENGTH + SCALAR_LENGTH, 2)?;
+		run(bls12_381::g1_msm::PRECOMPILE, &input, NOT_IN_SUBGROUP)
+	}
+}
+
+pub struct Bls12G2Add<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12G2Add<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0d).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	/// Add two G2 points. The points are not checked to be in the correct subgroup.
+	///
+	/// | point a | point b |
+	/// | :-----: | :-----: |
+	/// |   256   |   256   |
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::Bls12G2Add)?;
+
+		check_length(&input, 2 * G2_LENGTH)?;
+		check_field_elements(&input, G2_LENGTH, 4)?;
+		run(bls12_381::g2_add::PRECOMPILE, &input, NOT_ON_CURVE)
+	}
+}
+
+pub struct Bls12G2Msm<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12G2Msm<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0e).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	/// Compute the multi-scalar multiplication of a non-empty list of G2 points.
+	///
+	/// | point 1 | scalar 1 | ... | point k | scalar k |
+	/// | :-----: | :------: | :-: | :-----: | :------: |
+	/// |   256   |    32    | ... |   256   |    32    |
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		let k = check_items(&input, G2_LENGTH + SCALAR_LENGTH)?;
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::Bls12G2Msm(k as u32))?;
+
+		check_field_elements(&input, G2_LENGTH + SCALAR_LENGTH, 4)?;
+		run(bls12_381::g2_msm::PRECOMPILE, &input, NOT_IN_SUBGROUP)
+	}
+}
+
+pub struct Bls12Pairing<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12Pairing<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0f).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	/// Check that the product of the pairings of a non-empty list of pairs is one.
+	///
+	/// Returns `1` as a 32 bytes word if the check succeeds, `0` otherwise.
+	///
+	/// | G1 point 1 | G2 point 1 | ... | G1 point k | G2 point k |
+	/// | :--------: | :--------: | :-: | :--------: | :--------: |
+	/// |    128     |    256     | ... |    128     |    256     |
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		let k = check_items(&input, G1_LENGTH + G2_LENGTH)?;
+		env.frame_meter_mut()
+			.charge_weight_token(RuntimeCosts::Bls12Pairing(k as u32))?;
+
+		check_field_elements(&input, G1_LENGTH + G2_LENGTH, 6)?;
+		run(bls12_381::pairing::PRECOMPILE, &input, NOT_IN_SUBGROUP)
+	}
+}
+
+pub struct Bls12MapFpToG1<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12MapFpToG1<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x10).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	/// Map a base field element to a G1 point.
+	///
+	/// | field element |
+	/// | :-----------: |
+	/// |      64       |
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::Bls12MapFpToG1)?;
+
+		check_length(&input, FP_LENGTH)?;
+		check_field_elements(&input, FP_LENGTH, 1)?;
+		run(bls12_381::map_fp_to_g1::PRECOMPILE, &input, "invalid fp.Element encoding")
+	}
+}
+
+pub struct Bls12MapFp2ToG2<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12MapFp2ToG2<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x11).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	/// Map an element of the quadratic extension of the base field to a G2 point.
+	///
+	/// | field element c0 | field element c1 |
+	/// | :--------------: | :--------------: |
+	/// |        64        |        64        |
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::Bls12MapFp2ToG2)?;
+
+		check_length(&input, 2 * FP_LENGTH)?;
+		check_field_elements(&input, 2 * FP_LENGTH, 2)?;
+		run(bls12_381::map_fp2_to_g2::PRECOMPILE, &input, "invalid fp.Element encoding")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		precompiles::tests::{run_failure_test_vectors, run_test_vectors},
+		tests::Test,
+	};
+
+	// https://github.com/ethereum/go-ethereum/tree/master/core/vm/testdata/precompiles
+	#[test]
+	fn test_bls12_g1add() {
+		run_test_vectors::<Bls12G1Add<Test>>(include_str!("./testdata/11-bls12_g1add.json"));
+		run_failure_test_vectors::<Bls12G1Add<Test>>(include_str!(
+			"./testdata/11-bls12_g1add-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12_g1msm() {
+		run_test_vectors::<Bls12G1Msm<Test>>(include_str!("./testdata/12-bls12_g1msm.json"));
+		run_failure_test_vectors::<Bls12G1Msm<Test>>(include_str!(
+			"./testdata/12-bls12_g1msm-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12_g2add() {
+		run_test_vectors::<Bls12G2Add<Test>>(include_str!("./testdata/13-bls12_g2add.json"));
+		run_failure_test_vectors::<Bls12G2Add<Test>>(include_str!(
+			"./testdata/13-bls12_g2add-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12_g2msm() {
+		run_test_vectors::<Bls12G2Msm<Test>>(include_str!("./testdata/14-bls12_g2msm.json"));
+		run_failure_test_vectors::<Bls12G2Msm<Test>>(include_str!(
+			"./testdata/14-bls12_g2msm-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12_pairing() {
+		run_test_vectors::<Bls12Pairing<Test>>(include_str!("./testdata/15-bls12_pairing.json"));
+		run_failure_test_vectors::<Bls12Pairing<Test>>(include_str!(
+			"./testdata/15-bls12_pairing-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12_map_fp_to_g1() {
+		run_test_vectors::<Bls12MapFpToG1<Test>>(include_str!(
+			"./testdata/16-bls12_map_fp_to_g1.json"
+		));
+		run_failure_test_vectors::<Bls12MapFpToG1<Test>>(include_str!(
+			"./testdata/16-bls12_map_fp_to_g1-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12_map_fp2_to_g2() {
+		run_test_vectors::<Bls12MapFp2ToG2<Test>>(include_str!(
+			"./testdata/17-bls12_map_fp2_to_g2.json"
+		));
+		run_failure_test_vectors::<Bls12MapFp2ToG2<Test>>(include_str!(
+			"./testdata/17-bls12_map_fp2_to_g2-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12_address_match() {
+		let base_address = |address: u8| {
+			let mut expected = [0u8; 20];
+			expected[19] = address;
+			expected
+		};
+		assert_eq!(
+			<Bls12G1Add<Test> as PrimitivePrecompile>::MATCHER.base_address(),
+			base_address(0x0b)
+		);
+		assert_eq!(
+			<Bls12G1Msm<Test> as PrimitivePrecompile>::MATCHER.base_address(),
+			base_address(0x0c)
+		);
+		assert_eq!(
+			<Bls12G2Add<Test> as PrimitivePrecompile>::MATCHER.base_address(),
+			base_address(0x0d)
+		);
+		assert_eq!(
+			<Bls12G2Msm<Test> as PrimitivePrecompile>::MATCHER.base_address(),
+			base_address(0x0e)
+		);
+		assert_eq!(
+			<Bls12Pairing<Test> as PrimitivePrecompile>::MATCHER.base_address(),
+			base_address(0x0f)
+		);
+		assert_eq!(
+			<Bls12MapFpToG1<Test> as PrimitivePrecompile>::MATCHER.base_address(),
+			base_address(0x10)
+		);
+		assert_eq!(
+			<Bls12MapFp2ToG2<Test> as PrimitivePrecompile>::MATCHER.base_address(),
+			base_address(0x11)
+		);
+	}
+}
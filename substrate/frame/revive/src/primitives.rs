@@ -18,8 +18,11 @@
 //! A crate that hosts a common definitions that are relevant for the pallet-revive.
 
 use crate::{
-	BalanceOf, Config, H160, Time, U256, evm::DryRunConfig, mock::MockHandler,
-	storage::WriteOutcome, transient_storage::TransientStorage,
+	BalanceOf, Config, H160, H256, Time, U256,
+	evm::{CallLog, DryRunConfig},
+	mock::MockHandler,
+	storage::WriteOutcome,
+	transient_storage::TransientStorage,
 };
 use alloc::{boxed::Box, fmt::Debug, string::String, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -110,6 +113,28 @@ pub enum EthTransactError {
 	Message(String),
 }
 
+/// The result of a call simulated with `ReviveApi::eth_simulate`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct SimulatedCall {
+	/// The data returned by the call, or the error if the call failed.
+	pub result: Result<Vec<u8>, EthTransactError>,
+	/// The logs emitted by the call, in order. Empty if the call failed.
+	pub logs: Vec<CallLog>,
+	/// The gas used by the call.
+	pub gas_used: U256,
+}
+
+/// A block simulated with `ReviveApi::eth_simulate`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo, Default)]
+pub struct SimulatedBlockInfo {
+	/// The number of the simulated block.
+	pub number: U256,
+	/// The timestamp of the simulated block, in seconds.
+	pub timestamp: U256,
+	/// The results of the calls of the block, in order.
+	pub calls: Vec<SimulatedCall>,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
 /// Error encountered while creating a BalanceWithDust from a U256 balance.
 pub enum BalanceConversionError {
@@ -185,6 +210,24 @@ pub type GetStorageResult = Result<Option<Vec<u8>>, ContractAccessError>;
 /// Result type of a `set_storage` call.
 pub type SetStorageResult = Result<WriteOutcome, ContractAccessError>;
 
+/// The storage keys needed to prove an account and some of its storage slots.
+///
+/// This is returned by the `ReviveApi::account_proof_keys` runtime API, and used by the eth-rpc
+/// `eth_getProof` method to request the matching storage proofs from the node.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo, Default)]
+pub struct AccountProofKeys {
+	/// The keys of the main state trie holding the account data, in this order:
+	/// `OriginalAccount`, `frame_system::Account` and `AccountInfoOf`.
+	pub account_keys: Vec<Vec<u8>>,
+	/// The prefixed storage key of the contract child trie, `None` if the account is not a
+	/// contract.
+	pub child_trie_key: Option<Vec<u8>>,
+	/// The hashed keys of the requested storage slots in the contract child trie.
+	pub storage_keys: Vec<Vec<u8>>,
+	/// The code hash of the account.
+	pub code_hash: H256,
+}
+
 /// The possible errors that can happen querying the storage of a contract.
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, MaxEncodedLen, Debug, TypeInfo)]
 pub enum ContractAccessError {
@@ -19,6 +19,7 @@ mod block_hash;
 mod pallet_dummy;
 mod precompiles;
 mod pvm;
+mod simulate;
 mod sol;
 mod stipends;
 
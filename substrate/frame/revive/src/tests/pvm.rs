@@ -5120,6 +5120,43 @@ fn get_set_storage_var_key_works() {
 	});
 }
 
+#[test]
+fn account_proof_keys_works() {
+	let (code, code_hash) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let slot = [1; 32];
+		Pallet::<Test>::set_storage(addr, slot, Some(vec![1, 2, 3])).unwrap();
+
+		// Keys of a contract include its child trie and the hashed storage slots.
+		let keys = Pallet::<Test>::account_proof_keys(addr, vec![slot]);
+		let contract_info = get_contract(&addr);
+		assert_eq!(keys.account_keys.len(), 3);
+		assert_eq!(keys.account_keys[2], AccountInfoOf::<Test>::hashed_key_for(&addr));
+		assert_eq!(
+			keys.child_trie_key,
+			Some(contract_info.child_trie_info().prefixed_storage_key().into_inner())
+		);
+		assert_eq!(keys.storage_keys, vec![blake2_256(&slot).to_vec()]);
+		assert_eq!(
+			child::get_raw(&contract_info.child_trie_info(), &keys.storage_keys[0]),
+			Some(vec![1, 2, 3])
+		);
+		assert_eq!(keys.code_hash, code_hash);
+
+		// Keys of an externally owned account have no child trie.
+		let keys = Pallet::<Test>::account_proof_keys(ALICE_ADDR, vec![slot]);
+		assert_eq!(keys.child_trie_key, None);
+		assert!(keys.storage_keys.is_empty());
+		assert_eq!(keys.code_hash, crate::exec::EMPTY_CODE_HASH);
+	});
+}
+
 #[test]
 fn get_set_immutables_works() {
 	let (code, _code_hash) = compile_module("immutable_data").unwrap();
@@ -5398,6 +5435,8 @@ fn self_destruct_by_syscall_tracing_works() {
 				crate::evm::Trace::Call(ct) => Trace::Call(ct),
 				crate::evm::Trace::Prestate(pt) => Trace::Prestate(pt),
 				crate::evm::Trace::Execution(_) => panic!("Execution trace not expected"),
+				crate::evm::Trace::FourByte(_) => panic!("4byte trace not expected"),
+				crate::evm::Trace::Flat(_) => panic!("Flat call trace not expected"),
 			};
 
 			assert_eq!(trace_wrapped, expected_trace, "Trace mismatch for: {}", description);
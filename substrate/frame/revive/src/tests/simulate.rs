@@ -0,0 +1,256 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests of the simulation of blocks of calls backing `eth_simulateV1`.
+
+use crate::{
+	Code, Config, EthTransactError, Pallet, SimulatedBlockInfo,
+	evm::{AccountOverride, BlockOverrides, CallLog, GenericTransaction, SimulateBlock},
+	test_utils::{ALICE, ALICE_ADDR, CHARLIE_ADDR, DJANGO_ADDR, builder::Contract},
+	tests::{ExtBuilder, System, Test, builder},
+};
+use alloy_core::sol_types::{SolCall, SolInterface};
+use codec::Encode;
+use frame_support::traits::fungible::Mutate;
+use pallet_revive_fixtures::{Counter, FixtureType, compile_module, compile_module_with_type};
+use pretty_assertions::assert_eq;
+use sp_core::{H160, H256, U256};
+
+fn call(to: H160, input: Vec<u8>) -> GenericTransaction {
+	GenericTransaction {
+		from: Some(ALICE_ADDR),
+		to: Some(to),
+		input: input.into(),
+		..Default::default()
+	}
+}
+
+fn counter_call(to: H160, call: Counter::CounterCalls) -> GenericTransaction {
+	self::call(to, call.abi_encode())
+}
+
+fn number() -> Counter::CounterCalls {
+	Counter::CounterCalls::number(Counter::numberCall {})
+}
+
+fn increment() -> Counter::CounterCalls {
+	Counter::CounterCalls::increment(Counter::incrementCall {})
+}
+
+fn simulate(blocks: Vec<SimulateBlock>) -> Result<Vec<SimulatedBlockInfo>, EthTransactError> {
+	Pallet::<Test>::dry_run_eth_simulate(blocks, false)
+}
+
+/// The values returned by the calls of the simulated blocks.
+fn returned(blocks: &[SimulatedBlockInfo]) -> Vec<Vec<Result<Vec<u8>, EthTransactError>>> {
+	blocks
+		.iter()
+		.map(|block| block.calls.iter().map(|call| call.result.clone()).collect())
+		.collect()
+}
+
+fn encoded_number(number: u64) -> Vec<u8> {
+	Counter::numberCall::abi_encode_returns(&number)
+}
+
+#[test]
+fn calls_are_chained_within_and_across_blocks() {
+	let (code, _) = compile_module_with_type("Counter", FixtureType::Solc).unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+		let current = System::block_number();
+
+		let blocks = simulate(vec![
+			SimulateBlock {
+				calls: vec![
+					counter_call(addr, increment()),
+					counter_call(addr, increment()),
+					counter_call(addr, number()),
+				],
+				..Default::default()
+			},
+			SimulateBlock {
+				block_overrides: Some(BlockOverrides {
+					number: Some((current + 10).into()),
+					..Default::default()
+				}),
+				calls: vec![counter_call(addr, increment()), counter_call(addr, number())],
+				..Default::default()
+			},
+		])
+		.unwrap();
+
+		assert_eq!(
+			blocks.iter().map(|block| block.number).collect::<Vec<_>>(),
+			vec![U256::from(current + 1), U256::from(current + 10)]
+		);
+		assert!(blocks[0].timestamp < blocks[1].timestamp);
+		assert_eq!(
+			returned(&blocks),
+			vec![
+				vec![Ok(vec![]), Ok(vec![]), Ok(encoded_number(5))],
+				vec![Ok(vec![]), Ok(encoded_number(6))],
+			]
+		);
+		assert!(
+			blocks
+				.iter()
+				.flat_map(|block| &block.calls)
+				.all(|call| !call.gas_used.is_zero())
+		);
+	});
+}
+
+#[test]
+fn state_and_code_overrides_are_applied_before_the_calls() {
+	let (code, _) = compile_module_with_type("Counter", FixtureType::Resolc).unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000_000_000);
+
+		let account = |override_: AccountOverride| [(CHARLIE_ADDR, override_)].into();
+		let blocks = simulate(vec![
+			// The code is deployed without running the constructor, the storage is empty.
+			SimulateBlock {
+				state_overrides: Some(account(AccountOverride {
+					code: Some(code.into()),
+					..Default::default()
+				})),
+				calls: vec![counter_call(CHARLIE_ADDR, number())],
+				..Default::default()
+			},
+			SimulateBlock {
+				state_overrides: Some(
+					[
+						(
+							CHARLIE_ADDR,
+							AccountOverride {
+								state_diff: Some(
+									[(H256::zero(), H256::from_low_u64_be(42))].into(),
+								),
+								..Default::default()
+							},
+						),
+						(
+							DJANGO_ADDR,
+							AccountOverride {
+								balance: Some(U256::from(1_000_000_000_000_000u64)),
+								nonce: Some(U256::from(7)),
+								..Default::default()
+							},
+						),
+					]
+					.into(),
+				),
+				calls: vec![
+					counter_call(CHARLIE_ADDR, increment()),
+					counter_call(CHARLIE_ADDR, number()),
+				],
+				..Default::default()
+			},
+			// Replacing the whole state clears the slots written by the previous block.
+			SimulateBlock {
+				state_overrides: Some(account(AccountOverride {
+					state: Some(Default::default()),
+					..Default::default()
+				})),
+				calls: vec![counter_call(CHARLIE_ADDR, number())],
+				..Default::default()
+			},
+		])
+		.unwrap();
+
+		assert_eq!(
+			returned(&blocks),
+			vec![
+				vec![Ok(encoded_number(0))],
+				vec![Ok(vec![]), Ok(encoded_number(43))],
+				vec![Ok(encoded_number(0))],
+			]
+		);
+		assert_eq!(Pallet::<Test>::evm_balance(&DJANGO_ADDR), U256::from(1_000_000_000_000_000u64));
+		assert_eq!(Pallet::<Test>::evm_nonce(&DJANGO_ADDR), 7);
+	});
+}
+
+#[test]
+fn logs_of_the_calls_are_collected() {
+	let (code, _) = compile_module("tracing").unwrap();
+	let (callee_code, _) = compile_module("tracing_callee").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000_000_000);
+		let Contract { addr: callee_addr, .. } =
+			builder::bare_instantiate(Code::Upload(callee_code)).build_and_unwrap_contract();
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
+			.evm_value(10_000_000.into())
+			.build_and_unwrap_contract();
+
+		// The contract logs before and after calling the callee, then calls itself again with one
+		// call less. The callee traps on the first call and doesn't log anything.
+		let blocks = simulate(vec![SimulateBlock {
+			calls: vec![
+				call(addr, (2u32, callee_addr).encode()),
+				call(addr, (0u32, callee_addr).encode()),
+			],
+			..Default::default()
+		}])
+		.unwrap();
+
+		let log = |data: &[u8]| CallLog {
+			address: addr,
+			data: data.to_vec().into(),
+			..Default::default()
+		};
+		let logs = blocks[0]
+			.calls
+			.iter()
+			.map(|call| {
+				call.logs
+					.iter()
+					.map(|log| CallLog { position: 0, ..log.clone() })
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			logs,
+			vec![vec![log(b"before"), log(b"after"), log(b"before"), log(b"after")], vec![]]
+		);
+	});
+}
+
+#[test]
+fn invalid_blocks_are_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		let current = System::block_number();
+		let block = |number: U256| SimulateBlock {
+			block_overrides: Some(BlockOverrides { number: Some(number), ..Default::default() }),
+			..Default::default()
+		};
+
+		assert_eq!(
+			simulate(vec![block(U256::from(current))]),
+			Err(EthTransactError::Message(format!(
+				"Block number {current} is not greater than {current}"
+			)))
+		);
+		assert_eq!(
+			simulate(vec![block(U256::MAX)]),
+			Err(EthTransactError::Message(format!("Invalid block number {}", U256::MAX)))
+		);
+	});
+}
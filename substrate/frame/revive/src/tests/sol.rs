@@ -173,6 +173,84 @@ fn basic_evm_flow_tracing_works() {
 	});
 }
 
+#[test]
+fn four_byte_and_flat_call_tracing_works() {
+	use crate::evm::{
+		FlatCallAction, FlatCallResult, FlatCallTrace, FlatCallTraceType, FlatCallTracer,
+		FlatCallType, FourByteTrace, FourByteTracer,
+	};
+	let (code, _) = compile_module_with_type("Fibonacci", FixtureType::Solc).unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let input = Fibonacci::FibonacciCalls::fib(Fibonacci::fibCall { n: 10u64 }).abi_encode();
+
+		let mut tracer = FlatCallTracer::<Test>::new(Default::default());
+		let Contract { addr, .. } = trace(&mut tracer, || {
+			builder::bare_instantiate(Code::Upload(code.clone()))
+				.salt(None)
+				.build_and_unwrap_contract()
+		});
+		let traces = tracer.collect_trace().unwrap();
+		let runtime_code = PristineCode::<Test>::get(get_contract(&addr).code_hash).unwrap();
+		assert_eq!(
+			traces,
+			vec![FlatCallTrace {
+				action: FlatCallAction {
+					from: Some(ALICE_ADDR),
+					creation_method: Some(FlatCallType::Create),
+					gas: traces[0].action.gas,
+					init: Some(code.into()),
+					value: Some(crate::U256::zero()),
+					..Default::default()
+				},
+				result: Some(FlatCallResult {
+					address: Some(addr),
+					code: Some(runtime_code.into()),
+					gas_used: traces[0].result.as_ref().unwrap().gas_used,
+					..Default::default()
+				}),
+				trace_type: FlatCallTraceType::Create,
+				..Default::default()
+			}]
+		);
+
+		let mut tracer = FlatCallTracer::<Test>::new(Default::default());
+		let result = trace(&mut tracer, || {
+			builder::bare_call(addr).data(input.clone()).build_and_unwrap_result()
+		});
+		let traces = tracer.collect_trace().unwrap();
+		assert_eq!(
+			traces,
+			vec![FlatCallTrace {
+				action: FlatCallAction {
+					from: Some(ALICE_ADDR),
+					call_type: Some(FlatCallType::Call),
+					gas: traces[0].action.gas,
+					input: Some(input.clone().into()),
+					to: Some(addr),
+					value: Some(crate::U256::zero()),
+					..Default::default()
+				},
+				result: Some(FlatCallResult {
+					gas_used: traces[0].result.as_ref().unwrap().gas_used,
+					output: Some(result.data.into()),
+					..Default::default()
+				}),
+				trace_type: FlatCallTraceType::Call,
+				..Default::default()
+			}]
+		);
+
+		let mut tracer = FourByteTracer::<Test>::new();
+		trace(&mut tracer, || {
+			builder::bare_call(addr).data(input.clone()).build_and_unwrap_result()
+		});
+		let key = format!("0x{}-{}", alloy_core::hex::encode(&input[..4]), input.len() - 4);
+		assert_eq!(tracer.collect_trace(), FourByteTrace([(key, 1)].into_iter().collect()));
+	});
+}
+
 #[test]
 fn eth_contract_too_large() {
 	// Create EVM init code that is one byte larger than the EIP-3860 limit.
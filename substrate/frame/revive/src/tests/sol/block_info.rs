@@ -25,10 +25,11 @@ use crate::{
 };
 
 use alloy_core::sol_types::{SolCall, SolInterface};
-use frame_support::traits::fungible::Mutate;
+use frame_support::{assert_err, traits::fungible::Mutate};
 use pallet_revive_fixtures::{BlockInfo, FixtureType, compile_module_with_type};
 use pretty_assertions::assert_eq;
-use sp_core::H160;
+use sp_core::{H160, U256};
+use sp_runtime::ArithmeticError;
 use test_case::test_case;
 
 /// Tests that the blocknumber opcode works as expected.
@@ -78,6 +79,55 @@ fn block_number_dry_run_works(fixture_type: FixtureType) {
 	});
 }
 
+#[test_case(FixtureType::Solc)]
+#[test_case(FixtureType::Resolc)]
+fn block_number_dry_run_override_works(fixture_type: FixtureType) {
+	let (code, _) = compile_module_with_type("BlockInfo", fixture_type).unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		System::set_block_number(42);
+
+		let result = builder::bare_call(addr)
+			.data(
+				BlockInfo::BlockInfoCalls::blockNumber(BlockInfo::blockNumberCall {}).abi_encode(),
+			)
+			.exec_config(
+				ExecConfig::new_substrate_tx().with_dry_run(
+					DryRunConfig::new(None).with_block_number_override(100u64.into()),
+				),
+			)
+			.build_and_unwrap_result();
+		let decoded = BlockInfo::blockNumberCall::abi_decode_returns(&result.data).unwrap();
+		assert_eq!(100u64, decoded);
+	});
+}
+
+#[test_case(FixtureType::Solc)]
+#[test_case(FixtureType::Resolc)]
+fn block_number_dry_run_override_overflow_fails(fixture_type: FixtureType) {
+	let (code, _) = compile_module_with_type("BlockInfo", fixture_type).unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let result = builder::bare_call(addr)
+			.data(
+				BlockInfo::BlockInfoCalls::blockNumber(BlockInfo::blockNumberCall {}).abi_encode(),
+			)
+			.exec_config(
+				ExecConfig::new_substrate_tx()
+					.with_dry_run(DryRunConfig::new(None).with_block_number_override(U256::MAX)),
+			)
+			.build()
+			.result;
+		assert_err!(result, ArithmeticError::Overflow);
+	});
+}
+
 /// Tests that the blockauthor opcode works as expected.
 #[test_case(FixtureType::Solc)]
 #[test_case(FixtureType::Resolc)]
@@ -178,6 +178,20 @@ pub enum RuntimeCosts {
 	Blake2F(u32),
 	/// Weight of calling `Modexp` precompile
 	Modexp(u64),
+	/// Weight of calling `Bls12G1Add` precompile.
+	Bls12G1Add,
+	/// Weight of calling `Bls12G1Msm` precompile for the given number of input pairs.
+	Bls12G1Msm(u32),
+	/// Weight of calling `Bls12G2Add` precompile.
+	Bls12G2Add,
+	/// Weight of calling `Bls12G2Msm` precompile for the given number of input pairs.
+	Bls12G2Msm(u32),
+	/// Weight of calling `Bls12Pairing` precompile for the given number of input pairs.
+	Bls12Pairing(u32),
+	/// Weight of calling `Bls12MapFpToG1` precompile.
+	Bls12MapFpToG1,
+	/// Weight of calling `Bls12MapFp2ToG2` precompile.
+	Bls12MapFp2ToG2,
 }
 
 /// For functions that modify storage, benchmarks are performed with one item in the
@@ -340,6 +354,13 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			Identity(len) => T::WeightInfo::identity(len),
 			Blake2F(rounds) => T::WeightInfo::blake2f(rounds),
 			Modexp(gas) => Weight::from_parts(gas.saturating_mul(WEIGHT_PER_GAS), 0),
+			Bls12G1Add => T::WeightInfo::bls12_g1_add(),
+			Bls12G1Msm(len) => T::WeightInfo::bls12_g1_msm(len),
+			Bls12G2Add => T::WeightInfo::bls12_g2_add(),
+			Bls12G2Msm(len) => T::WeightInfo::bls12_g2_msm(len),
+			Bls12Pairing(len) => T::WeightInfo::bls12_pairing(len),
+			Bls12MapFpToG1 => T::WeightInfo::bls12_map_fp_to_g1(),
+			Bls12MapFp2ToG2 => T::WeightInfo::bls12_map_fp2_to_g2(),
 		}
 	}
 }
@@ -160,6 +160,13 @@ pub trait WeightInfo {
 	fn bn128_mul() -> Weight;
 	fn bn128_pairing(n: u32, ) -> Weight;
 	fn blake2f(n: u32, ) -> Weight;
+	fn bls12_g1_add() -> Weight;
+	fn bls12_g1_msm(n: u32, ) -> Weight;
+	fn bls12_g2_add() -> Weight;
+	fn bls12_g2_msm(n: u32, ) -> Weight;
+	fn bls12_pairing(n: u32, ) -> Weight;
+	fn bls12_map_fp_to_g1() -> Weight;
+	fn bls12_map_fp2_to_g2() -> Weight;
 	fn seal_ecdsa_to_eth_address() -> Weight;
 	fn evm_opcode(r: u32, ) -> Weight;
 	fn instr(r: u32, ) -> Weight;
@@ -1288,6 +1295,64 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 2
 			.saturating_add(Weight::from_parts(29_543, 0).saturating_mul(n.into()))
 	}
+	fn bls12_g1_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 28_764_000 picoseconds.
+		Weight::from_parts(65_450_000, 0)
+	}
+	/// The range of component `n` is `[1, 64]`.
+	fn bls12_g1_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_879_853_000 picoseconds.
+		Weight::from_parts(4_095_108_176, 0)
+			// Standard Error: 5_956_539
+			.saturating_add(Weight::from_parts(1_076_308_542, 0).saturating_mul(n.into()))
+	}
+	fn bls12_g2_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 45_842_000 picoseconds.
+		Weight::from_parts(102_830_000, 0)
+	}
+	/// The range of component `n` is `[1, 64]`.
+	fn bls12_g2_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_075_436_000 picoseconds.
+		Weight::from_parts(4_207_148_230, 0)
+			// Standard Error: 19_573_447
+			.saturating_add(Weight::from_parts(1_979_161_773, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_pairing(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_056_223_000 picoseconds.
+		Weight::from_parts(6_484_951_461, 0)
+			// Standard Error: 87_055_902
+			.saturating_add(Weight::from_parts(4_065_069_383, 0).saturating_mul(n.into()))
+	}
+	fn bls12_map_fp_to_g1() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 764_698_000 picoseconds.
+		Weight::from_parts(1_281_300_000, 0)
+	}
+	fn bls12_map_fp2_to_g2() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_517_960_000 picoseconds.
+		Weight::from_parts(4_135_935_000, 0)
+	}
 	fn seal_ecdsa_to_eth_address() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -2583,6 +2648,64 @@ impl WeightInfo for () {
 			// Standard Error: 2
 			.saturating_add(Weight::from_parts(29_543, 0).saturating_mul(n.into()))
 	}
+	fn bls12_g1_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 28_764_000 picoseconds.
+		Weight::from_parts(65_450_000, 0)
+	}
+	/// The range of component `n` is `[1, 64]`.
+	fn bls12_g1_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_879_853_000 picoseconds.
+		Weight::from_parts(4_095_108_176, 0)
+			// Standard Error: 5_956_539
+			.saturating_add(Weight::from_parts(1_076_308_542, 0).saturating_mul(n.into()))
+	}
+	fn bls12_g2_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 45_842_000 picoseconds.
+		Weight::from_parts(102_830_000, 0)
+	}
+	/// The range of component `n` is `[1, 64]`.
+	fn bls12_g2_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_075_436_000 picoseconds.
+		Weight::from_parts(4_207_148_230, 0)
+			// Standard Error: 19_573_447
+			.saturating_add(Weight::from_parts(1_979_161_773, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_pairing(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_056_223_000 picoseconds.
+		Weight::from_parts(6_484_951_461, 0)
+			// Standard Error: 87_055_902
+			.saturating_add(Weight::from_parts(4_065_069_383, 0).saturating_mul(n.into()))
+	}
+	fn bls12_map_fp_to_g1() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 764_698_000 picoseconds.
+		Weight::from_parts(1_281_300_000, 0)
+	}
+	fn bls12_map_fp2_to_g2() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_517_960_000 picoseconds.
+		Weight::from_parts(4_135_935_000, 0)
+	}
 	fn seal_ecdsa_to_eth_address() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -143,6 +143,12 @@ impl<H: Clone + AsRef<[u8]>> Database<H> for RocksDbAdapter {
 	fn optimize_db_col(&self, col: ColumnId) -> error::Result<()> {
 		self.0.force_compact(col).map_err(|e| error::DatabaseError(Box::new(e)))
 	}
+
+	fn try_catch_up_with_primary(&self) -> error::Result<()> {
+		self.0
+			.try_catch_up_with_primary()
+			.map_err(|e| error::DatabaseError(Box::new(e)))
+	}
 }
 
 /// Wrap RocksDB database into a trait object with `optimize_db` support.
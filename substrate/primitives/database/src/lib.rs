@@ -124,6 +124,13 @@ pub trait Database<H: Clone + AsRef<[u8]>>: Send + Sync {
 	fn optimize_db_col(&self, _col: ColumnId) -> error::Result<()> {
 		Ok(())
 	}
+
+	/// Catch up with the changes made by the primary instance of the database.
+	///
+	/// Only meaningful for databases opened as a read-only secondary instance, a noop otherwise.
+	fn try_catch_up_with_primary(&self) -> error::Result<()> {
+		Ok(())
+	}
 }
 
 impl<H> std::fmt::Debug for dyn Database<H> {